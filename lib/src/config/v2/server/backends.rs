@@ -1,6 +1,8 @@
 use bytesize::ByteSize;
 use serde::{Deserialize, Serialize};
 
+use crate::track::MediaTypesSimple;
+
 /// Settings specific to a backend
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Default)]
 #[serde(default)] // allow missing fields and fill them with the `..Self::default()` in this struct
@@ -44,6 +46,8 @@ pub struct RustyBackendSettings {
     ///
     /// If the given value is less than the default, the default will be used instead.
     pub decoded_buffer_size: ByteSize,
+    /// Shorten long silent stretches in the decoded audio ("smart speed")
+    pub silence_skip: SilenceSkipSettings,
 }
 
 impl Default for RustyBackendSettings {
@@ -52,6 +56,47 @@ impl Default for RustyBackendSettings {
             soundtouch: true,
             file_buffer_size: ByteSize::b(FILEBUF_SIZE_DEFAULT),
             decoded_buffer_size: ByteSize::b(DECODEDBUF_SIZE_DEFAULT),
+            silence_skip: SilenceSkipSettings::default(),
+        }
+    }
+}
+
+/// Settings for skipping silence in the `rusty` backend
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)] // allow missing fields and fill them with the `..Self::default()` in this struct
+pub struct SilenceSkipSettings {
+    /// Enable skipping silence for music tracks
+    pub music: bool,
+    /// Enable skipping silence for podcast episodes
+    pub podcast: bool,
+    /// Audio quieter than this level (in dBFS) is considered silence
+    pub threshold_db: i16,
+    /// Silent stretches longer than this (in milliseconds) get shortened to this length
+    pub max_silence_ms: u32,
+}
+
+impl SilenceSkipSettings {
+    /// Get if skipping silence is enabled for the given [`MediaTypesSimple`]
+    ///
+    /// Live radio is never enabled, as it cannot be played faster than it is received.
+    #[allow(clippy::needless_pass_by_value)] // "MediaTypesSimple" is a 1-byte copy
+    #[must_use]
+    pub fn is_enabled_for(&self, media_type: MediaTypesSimple) -> bool {
+        match media_type {
+            MediaTypesSimple::Music => self.music,
+            MediaTypesSimple::Podcast => self.podcast,
+            MediaTypesSimple::LiveRadio => false,
+        }
+    }
+}
+
+impl Default for SilenceSkipSettings {
+    fn default() -> Self {
+        Self {
+            music: false,
+            podcast: true,
+            threshold_db: -45,
+            max_silence_ms: 300,
         }
     }
 }
@@ -258,6 +258,10 @@ pub struct PlayerSettings {
     /// speed / 10 = actual speed (float but not floats)
     // the number should never be 0, because that would effectively be paused forever
     pub speed: i32,
+    /// Pitch shift (transpose) in cents, independent of the speed
+    ///
+    /// 100 cents = 1 semitone, only has a effect with the rusty backend with soundtouch enabled
+    pub pitch: i32,
     /// Enable gapless decoding & prefetching the next track
     pub gapless: bool,
     /// How much to seek on a seek event
@@ -295,6 +299,7 @@ impl Default for PlayerSettings {
             // rather use a lower value than a high so that ears dont get blown off
             volume: 30,
             speed: 10,
+            pitch: 0,
             gapless: true,
             seek_step: SeekStep::default(),
 
@@ -349,6 +354,15 @@ pub enum LoopMode {
     Playlist = 1,
     /// Select a random track on each next track
     Random = 2,
+    /// Select a random track that has not been played yet in the current cycle,
+    /// a new cycle starts once all tracks have been played
+    #[serde(rename = "shuffle_no_repeat")]
+    ShuffleNoRepeat = 3,
+    /// Play the albums in a random order, but keep the track order inside each album
+    #[serde(rename = "shuffle_albums")]
+    ShuffleAlbums = 4,
+    /// Select a random track, favouring highly rated or rarely played tracks
+    Weighted = 5,
 }
 
 impl LoopMode {
@@ -359,16 +373,39 @@ impl LoopMode {
                 Self::Single => "🔂",
                 Self::Playlist => "🔁",
                 Self::Random => "🔀",
+                Self::ShuffleNoRepeat => "🃏",
+                Self::ShuffleAlbums => "💿",
+                Self::Weighted => "🎯",
             }
         } else {
             match self {
                 Self::Single => "single",
                 Self::Playlist => "playlist",
                 Self::Random => "random",
+                Self::ShuffleNoRepeat => "no-repeat",
+                Self::ShuffleAlbums => "albums",
+                Self::Weighted => "weighted",
             }
         }
     }
 
+    /// Get the mode that comes after this one when cycling through the modes.
+    ///
+    /// order:
+    /// [Single](Self::Single) -> [Random](Self::Random) -> [`ShuffleNoRepeat`](Self::ShuffleNoRepeat)
+    /// -> [`ShuffleAlbums`](Self::ShuffleAlbums) -> [Weighted](Self::Weighted) -> [Playlist](Self::Playlist) -> [Single](Self::Single)
+    #[must_use]
+    pub fn cycle(self) -> Self {
+        match self {
+            Self::Single => Self::Random,
+            Self::Random => Self::ShuffleNoRepeat,
+            Self::ShuffleNoRepeat => Self::ShuffleAlbums,
+            Self::ShuffleAlbums => Self::Weighted,
+            Self::Weighted => Self::Playlist,
+            Self::Playlist => Self::Single,
+        }
+    }
+
     /// Convert the current enum variant into its number representation
     #[must_use]
     pub fn discriminant(&self) -> u8 {
@@ -382,6 +419,9 @@ impl LoopMode {
             0 => Self::Single,
             1 => Self::Playlist,
             2 => Self::Random,
+            3 => Self::ShuffleNoRepeat,
+            4 => Self::ShuffleAlbums,
+            5 => Self::Weighted,
             _ => return None,
         })
     }
@@ -575,6 +615,7 @@ mod v1_interop {
                 loop_mode: value.player_loop_mode.into(),
                 volume: value.player_volume,
                 speed: value.player_speed,
+                pitch: 0,
                 gapless: value.player_gapless,
                 seek_step: value.player_seek_step.into(),
 
@@ -670,6 +711,7 @@ mod v1_interop {
                     loop_mode: LoopMode::Random,
                     volume: 70,
                     speed: 10,
+                    pitch: 0,
                     gapless: true,
                     seek_step: SeekStep::Depends {
                         short_tracks: NonZeroU32::new(5).unwrap(),
@@ -80,6 +80,8 @@ pub struct Keys {
     pub move_cover_art_keys: KeysMoveCoverArt,
     #[serde(rename = "config")]
     pub config_keys: KeysConfigEditor,
+    #[serde(rename = "tag_editor")]
+    pub tag_editor_keys: KeysTagEditor,
 }
 
 impl Keys {
@@ -108,6 +110,7 @@ impl Default for Keys {
             podcast_keys: KeysPodcast::default(),
             move_cover_art_keys: KeysMoveCoverArt::default(),
             config_keys: KeysConfigEditor::default(),
+            tag_editor_keys: KeysTagEditor::default(),
         }
     }
 }
@@ -161,11 +164,11 @@ impl CheckConflict for Keys {
             conflicts.extend(new);
         }
         key_path.pop();
-        // key_path.push("tag_editor");
-        // if let Err(new) = self.tag_editor.check_conflict(key_path, global_keys) {
-        //     conflicts.extend(new);
-        // }
-        // key_path.pop();
+        key_path.push("tag_editor");
+        if let Err(new) = self.tag_editor_keys.check_conflict(key_path, global_keys) {
+            conflicts.extend(new);
+        }
+        key_path.pop();
 
         assert_eq!(global_keys.len(), init_len); // sanity check, the above should not have added global_keys
 
@@ -356,11 +359,39 @@ pub struct KeysPlayer {
     ///
     /// Will only apply in specific widgets (like the Playlist, but not in Config)
     pub speed_down: KeyBinding,
+    /// Key to transpose the pitch up (by one semitone)
+    ///
+    /// Will only apply in specific widgets (like the Playlist, but not in Config)
+    pub pitch_up: KeyBinding,
+    /// Key to transpose the pitch down (by one semitone)
+    ///
+    /// Will only apply in specific widgets (like the Playlist, but not in Config)
+    pub pitch_down: KeyBinding,
+    /// Key to fine-tune the pitch up (by a few cents)
+    ///
+    /// Will only apply in specific widgets (like the Playlist, but not in Config)
+    pub pitch_fine_up: KeyBinding,
+    /// Key to fine-tune the pitch down (by a few cents)
+    ///
+    /// Will only apply in specific widgets (like the Playlist, but not in Config)
+    pub pitch_fine_down: KeyBinding,
     /// Key to toggle if track-prefetching should be enabled
     ///
     /// Will only apply in specific widgets (like the Playlist, but not in Config)
     // TODO: always enable "gapless" in rusty backend and rename option to "prefetch"
     pub toggle_prefetch: KeyBinding,
+    /// Key to mark the current position as the start ("A") of a A-B loop
+    ///
+    /// Will only apply in specific widgets (like the Playlist, but not in Config)
+    pub ab_loop_mark_a: KeyBinding,
+    /// Key to mark the current position as the end ("B") of a A-B loop and start looping
+    ///
+    /// Will only apply in specific widgets (like the Playlist, but not in Config)
+    pub ab_loop_mark_b: KeyBinding,
+    /// Key to clear the current A-B loop
+    ///
+    /// Will only apply in specific widgets (like the Playlist, but not in Config)
+    pub ab_loop_clear: KeyBinding,
 
     /// Key to save the current playlist as a "m3u" playlist
     pub save_playlist: KeyBinding,
@@ -390,11 +421,18 @@ impl Default for KeysPlayer {
                 tuievents::KeyModifiers::CONTROL,
             )
             .into(),
+            pitch_up: tuievents::Key::Char('>').into(),
+            pitch_down: tuievents::Key::Char('<').into(),
+            pitch_fine_up: tuievents::Key::Char(')').into(),
+            pitch_fine_down: tuievents::Key::Char('(').into(),
             toggle_prefetch: tuievents::KeyEvent::new(
                 tuievents::Key::Char('g'),
                 tuievents::KeyModifiers::CONTROL,
             )
             .into(),
+            ab_loop_mark_a: tuievents::Key::Char('[').into(),
+            ab_loop_mark_b: tuievents::Key::Char(']').into(),
+            ab_loop_clear: tuievents::Key::Char('\\').into(),
             save_playlist: tuievents::KeyEvent::new(
                 tuievents::Key::Char('s'),
                 tuievents::KeyModifiers::CONTROL,
@@ -416,7 +454,14 @@ impl CheckConflict for KeysPlayer {
             (&self.seek_backward, "seek_backward"),
             (&self.speed_up, "speed_up"),
             (&self.speed_down, "speed_down"),
+            (&self.pitch_up, "pitch_up"),
+            (&self.pitch_down, "pitch_down"),
+            (&self.pitch_fine_up, "pitch_fine_up"),
+            (&self.pitch_fine_down, "pitch_fine_down"),
             (&self.toggle_prefetch, "toggle_prefetch"),
+            (&self.ab_loop_mark_a, "ab_loop_mark_a"),
+            (&self.ab_loop_mark_b, "ab_loop_mark_b"),
+            (&self.ab_loop_clear, "ab_loop_clear"),
 
             (&self.save_playlist, "save_playlist"),
         }
@@ -478,6 +523,10 @@ pub struct KeysLyric {
     ///
     /// Will only apply in specific widgets (like the Playlist, but not in Config)
     pub cycle_frames: KeyBinding,
+    /// Key to save the adjusted lyric offset back to the lyric frame or sidecar file it was read from
+    ///
+    /// Will only apply in specific widgets (like the Playlist, but not in Config)
+    pub save_offset: KeyBinding,
 }
 
 impl Default for KeysLyric {
@@ -498,6 +547,11 @@ impl Default for KeysLyric {
                 tuievents::KeyModifiers::SHIFT,
             )
             .into(),
+            save_offset: tuievents::KeyEvent::new(
+                tuievents::Key::Char('W'),
+                tuievents::KeyModifiers::SHIFT,
+            )
+            .into(),
         }
     }
 }
@@ -508,6 +562,7 @@ impl CheckConflict for KeysLyric {
             (&self.adjust_offset_forwards, "adjust_offset_forwards"),
             (&self.adjust_offset_backwards, "adjust_offset_backwards"),
             (&self.cycle_frames, "cycle_frames"),
+            (&self.save_offset, "save_offset"),
         }
     }
 
@@ -665,6 +720,8 @@ pub struct KeysLibrary {
     pub youtube_search: KeyBinding,
     /// Key to open the tag editor on that node (only works for files)
     pub open_tag_editor: KeyBinding,
+    /// Key to add the currently selected node to the "play next" queue (for directories, all tracks directly in it)
+    pub queue_next: KeyBinding,
 }
 
 impl Default for KeysLibrary {
@@ -689,6 +746,7 @@ impl Default for KeysLibrary {
             search: tuievents::Key::Char('/').into(),
             youtube_search: tuievents::Key::Char('s').into(),
             open_tag_editor: tuievents::Key::Char('t').into(),
+            queue_next: tuievents::Key::Char('e').into(),
         }
     }
 }
@@ -708,6 +766,7 @@ impl CheckConflict for KeysLibrary {
             (&self.search, "search"),
             (&self.youtube_search, "youtube_search"),
             (&self.open_tag_editor, "open_tag_editor"),
+            (&self.queue_next, "queue_next"),
         }
     }
 
@@ -779,6 +838,33 @@ pub struct KeysPlaylist {
     /// previously known as `cmus_lqueue`
     // NOTE: currently this can be somewhat broken sometimes, cause unknown
     pub add_random_album: KeyBinding,
+
+    /// Key to open the list of named playlists stored on the server (to load, save, rename or delete them)
+    pub named_playlists: KeyBinding,
+
+    /// Key to add the currently selected track to the "play next" queue
+    pub queue_next: KeyBinding,
+    /// Key to open the "play next" queue (to remove tracks from it or clear it)
+    pub show_queue: KeyBinding,
+
+    /// Key to undo the last edit to the playlist (add, remove, swap, shuffle or clear)
+    pub undo: KeyBinding,
+    /// Key to redo the last undone edit to the playlist
+    pub redo: KeyBinding,
+
+    /// Key to open the popup to sort the playlist by some metadata or remove duplicate tracks
+    pub sort: KeyBinding,
+
+    /// Key to mark / unmark the currently selected track, marked tracks are deleted, moved and saved together
+    pub mark: KeyBinding,
+    /// Key to start / stop marking all tracks between where it was started and the currently selected track
+    pub mark_range: KeyBinding,
+    /// Key to move the marked tracks (or the currently selected track) to the top of the playlist
+    pub move_top: KeyBinding,
+    /// Key to move the marked tracks (or the currently selected track) to the bottom of the playlist
+    pub move_bottom: KeyBinding,
+    /// Key to save the marked tracks as a new named playlist
+    pub save_marked: KeyBinding,
 }
 
 impl Default for KeysPlaylist {
@@ -810,6 +896,33 @@ impl Default for KeysPlaylist {
                 tuievents::KeyModifiers::SHIFT,
             )
             .into(),
+            named_playlists: tuievents::KeyEvent::new(
+                tuievents::Key::Char('P'),
+                tuievents::KeyModifiers::SHIFT,
+            )
+            .into(),
+            queue_next: tuievents::Key::Char('e').into(),
+            show_queue: tuievents::KeyEvent::new(
+                tuievents::Key::Char('E'),
+                tuievents::KeyModifiers::SHIFT,
+            )
+            .into(),
+            undo: tuievents::Key::Char('u').into(),
+            redo: tuievents::KeyEvent::new(
+                tuievents::Key::Char('U'),
+                tuievents::KeyModifiers::SHIFT,
+            )
+            .into(),
+            sort: tuievents::KeyEvent::new(
+                tuievents::Key::Char('O'),
+                tuievents::KeyModifiers::SHIFT,
+            )
+            .into(),
+            mark: tuievents::Key::Char('x').into(),
+            mark_range: tuievents::Key::Char('v').into(),
+            move_top: tuievents::Key::Char('{').into(),
+            move_bottom: tuievents::Key::Char('}').into(),
+            save_marked: tuievents::Key::Char('w').into(),
         }
     }
 }
@@ -828,6 +941,22 @@ impl CheckConflict for KeysPlaylist {
 
             (&self.add_random_songs, "add_random_songs"),
             (&self.add_random_album, "add_random_album"),
+
+            (&self.named_playlists, "named_playlists"),
+
+            (&self.queue_next, "queue_next"),
+            (&self.show_queue, "show_queue"),
+
+            (&self.undo, "undo"),
+            (&self.redo, "redo"),
+
+            (&self.sort, "sort"),
+
+            (&self.mark, "mark"),
+            (&self.mark_range, "mark_range"),
+            (&self.move_top, "move_top"),
+            (&self.move_bottom, "move_bottom"),
+            (&self.save_marked, "save_marked"),
         }
     }
 
@@ -1164,6 +1293,128 @@ impl CheckConflict for KeysConfigEditor {
     }
 }
 
+/// Keys for the tag editor actions, which apply in all of the tag editor's widgets
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default)] // allow missing fields and fill them with the `..Self::default()` in this struct
+pub struct KeysTagEditor {
+    /// Analyze the loudness of the album of the current track and write the `ReplayGain` tags
+    pub analyze_loudness: KeyBinding,
+    /// Add the sidecar lyric file of the current track as a new lyric frame
+    pub embed_sidecar: KeyBinding,
+    /// Export the selected lyric frame of the current track to a sidecar lyric file
+    pub export_sidecar: KeyBinding,
+    /// Start the tap-to-sync lyric editor with the selected lyric frame
+    pub sync_lyrics: KeyBinding,
+    /// Look up the album of the current track on `MusicBrainz`
+    pub musicbrainz_lookup: KeyBinding,
+    /// Preview moving the edited files to the path of `file_patterns.rename`
+    pub rename_by_pattern: KeyBinding,
+    /// Preview reading tags from the paths of the edited files with `file_patterns.parse`
+    pub tags_from_filename: KeyBinding,
+    /// Show the cover art actions, like embedding an image file as cover
+    pub cover_art: KeyBinding,
+}
+
+impl Default for KeysTagEditor {
+    fn default() -> Self {
+        Self {
+            analyze_loudness: tuievents::KeyEvent::new(
+                tuievents::Key::Char('r'),
+                tuievents::KeyModifiers::CONTROL,
+            )
+            .into(),
+            embed_sidecar: tuievents::KeyEvent::new(
+                tuievents::Key::Char('l'),
+                tuievents::KeyModifiers::CONTROL,
+            )
+            .into(),
+            export_sidecar: tuievents::KeyEvent::new(
+                tuievents::Key::Char('x'),
+                tuievents::KeyModifiers::CONTROL,
+            )
+            .into(),
+            sync_lyrics: tuievents::KeyEvent::new(
+                tuievents::Key::Char('t'),
+                tuievents::KeyModifiers::CONTROL,
+            )
+            .into(),
+            musicbrainz_lookup: tuievents::KeyEvent::new(
+                tuievents::Key::Char('b'),
+                tuievents::KeyModifiers::CONTROL,
+            )
+            .into(),
+            rename_by_pattern: tuievents::KeyEvent::new(
+                tuievents::Key::Char('n'),
+                tuievents::KeyModifiers::CONTROL,
+            )
+            .into(),
+            tags_from_filename: tuievents::KeyEvent::new(
+                tuievents::Key::Char('f'),
+                tuievents::KeyModifiers::CONTROL,
+            )
+            .into(),
+            cover_art: tuievents::KeyEvent::new(
+                tuievents::Key::Char('p'),
+                tuievents::KeyModifiers::CONTROL,
+            )
+            .into(),
+        }
+    }
+}
+
+impl CheckConflict for KeysTagEditor {
+    fn iter(&self) -> impl Iterator<Item = (&KeyBinding, &'static str)> {
+        once_chain! {
+            (&self.analyze_loudness, "analyze_loudness"),
+            (&self.embed_sidecar, "embed_sidecar"),
+            (&self.export_sidecar, "export_sidecar"),
+            (&self.sync_lyrics, "sync_lyrics"),
+            (&self.musicbrainz_lookup, "musicbrainz_lookup"),
+            (&self.rename_by_pattern, "rename_by_pattern"),
+            (&self.tags_from_filename, "tags_from_filename"),
+            (&self.cover_art, "cover_art"),
+        }
+    }
+
+    fn check_conflict(
+        &self,
+        key_path: &mut KeyPath,
+        global_keys: &mut KeyHashMapOwned,
+    ) -> Result<(), Vec<KeyConflictError>> {
+        let mut conflicts: Vec<KeyConflictError> = Vec::new();
+        let mut current_keys = KeyHashMap::new();
+
+        for (key, path) in self.iter() {
+            // check global first
+            if let Some(existing_path) = global_keys.get(key) {
+                conflicts.push(KeyConflictError {
+                    key_path_first: existing_path.to_string(),
+                    key_path_second: key_path.join_with_field(path),
+                    key: key.clone(),
+                });
+                continue;
+            }
+
+            if let Some(existing_path) = current_keys.get(key) {
+                conflicts.push(KeyConflictError {
+                    key_path_first: key_path.join_with_field(existing_path),
+                    key_path_second: key_path.join_with_field(path),
+                    key: key.clone(),
+                });
+                continue;
+            }
+
+            current_keys.insert(key, path);
+        }
+
+        if !conflicts.is_empty() {
+            return Err(conflicts);
+        }
+
+        Ok(())
+    }
+}
+
 /// Keys for the database view
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 #[serde(default)] // allow missing fields and fill them with the `..Self::default()` in this struct
@@ -1810,6 +2061,7 @@ mod v1_interop {
     use super::{
         tuievents, KeyBinding, Keys, KeysConfigEditor, KeysDatabase, KeysLibrary, KeysLyric,
         KeysMoveCoverArt, KeysNavigation, KeysPlayer, KeysPlaylist, KeysPodcast, KeysSelectView,
+        KeysTagEditor,
     };
     use crate::config::v1;
 
@@ -1913,13 +2165,24 @@ mod v1_interop {
                     seek_backward: value.global_player_seek_backward.into(),
                     speed_up: value.global_player_speed_up.into(),
                     speed_down: value.global_player_speed_down.into(),
+                    // not available in v1
+                    pitch_up: KeysPlayer::default().pitch_up,
+                    pitch_down: KeysPlayer::default().pitch_down,
+                    pitch_fine_up: KeysPlayer::default().pitch_fine_up,
+                    pitch_fine_down: KeysPlayer::default().pitch_fine_down,
                     toggle_prefetch: value.global_player_toggle_gapless.into(),
+                    // not available in v1
+                    ab_loop_mark_a: KeysPlayer::default().ab_loop_mark_a,
+                    ab_loop_mark_b: KeysPlayer::default().ab_loop_mark_b,
+                    ab_loop_clear: KeysPlayer::default().ab_loop_clear,
                     save_playlist: value.global_save_playlist.into(),
                 },
                 lyric_keys: KeysLyric {
                     adjust_offset_forwards: value.global_lyric_adjust_forward.into(),
                     adjust_offset_backwards: value.global_lyric_adjust_backward.into(),
                     cycle_frames: value.global_lyric_cycle.into(),
+                    // not available in v1
+                    save_offset: KeysLyric::default().save_offset,
                 },
                 library_keys: KeysLibrary {
                     // this is weird, but the previous implementation used "global_right" as the loading key to not conflict
@@ -1934,6 +2197,8 @@ mod v1_interop {
                     search: value.library_search.into(),
                     youtube_search: value.library_search_youtube.into(),
                     open_tag_editor: value.library_tag_editor_open.into(),
+                    // not available in v1
+                    queue_next: KeysLibrary::default().queue_next,
                 },
                 playlist_keys: KeysPlaylist {
                     delete: value.playlist_delete.into(),
@@ -1946,6 +2211,18 @@ mod v1_interop {
                     swap_down: value.playlist_swap_down.into(),
                     add_random_songs: value.playlist_add_random_tracks.into(),
                     add_random_album: value.playlist_add_random_album.into(),
+                    // not available in v1
+                    named_playlists: KeysPlaylist::default().named_playlists,
+                    queue_next: KeysPlaylist::default().queue_next,
+                    show_queue: KeysPlaylist::default().show_queue,
+                    undo: KeysPlaylist::default().undo,
+                    redo: KeysPlaylist::default().redo,
+                    sort: KeysPlaylist::default().sort,
+                    mark: KeysPlaylist::default().mark,
+                    mark_range: KeysPlaylist::default().mark_range,
+                    move_top: KeysPlaylist::default().move_top,
+                    move_bottom: KeysPlaylist::default().move_bottom,
+                    save_marked: KeysPlaylist::default().save_marked,
                 },
                 database_keys: KeysDatabase {
                     // this is weird, but the previous implementation used "global_right" as the loading key to not conflict
@@ -1975,6 +2252,8 @@ mod v1_interop {
                 config_keys: KeysConfigEditor {
                     save: value.config_save.into(),
                 },
+                // not available in v1
+                tag_editor_keys: KeysTagEditor::default(),
             }
         }
     }
@@ -2053,11 +2332,18 @@ mod v1_interop {
                     tuievents::KeyModifiers::CONTROL,
                 )
                 .into(),
+                pitch_up: tuievents::Key::Char('>').into(),
+                pitch_down: tuievents::Key::Char('<').into(),
+                pitch_fine_up: tuievents::Key::Char(')').into(),
+                pitch_fine_down: tuievents::Key::Char('(').into(),
                 toggle_prefetch: tuievents::KeyEvent::new(
                     tuievents::Key::Char('g'),
                     tuievents::KeyModifiers::CONTROL,
                 )
                 .into(),
+                ab_loop_mark_a: tuievents::Key::Char('[').into(),
+                ab_loop_mark_b: tuievents::Key::Char(']').into(),
+                ab_loop_clear: tuievents::Key::Char('\\').into(),
                 save_playlist: tuievents::KeyEvent::new(
                     tuievents::Key::Char('s'),
                     tuievents::KeyModifiers::CONTROL,
@@ -2082,6 +2368,11 @@ mod v1_interop {
                     tuievents::KeyModifiers::SHIFT,
                 )
                 .into(),
+                save_offset: tuievents::KeyEvent::new(
+                    tuievents::Key::Char('W'),
+                    tuievents::KeyModifiers::SHIFT,
+                )
+                .into(),
             };
             assert_eq!(converted.lyric_keys, expected_lyric_keys);
 
@@ -2105,6 +2396,7 @@ mod v1_interop {
                 search: tuievents::Key::Char('/').into(),
                 youtube_search: tuievents::Key::Char('s').into(),
                 open_tag_editor: tuievents::Key::Char('t').into(),
+                queue_next: tuievents::Key::Char('e').into(),
             };
             assert_eq!(converted.library_keys, expected_library_keys);
 
@@ -2135,6 +2427,33 @@ mod v1_interop {
                     tuievents::KeyModifiers::SHIFT,
                 )
                 .into(),
+                named_playlists: tuievents::KeyEvent::new(
+                    tuievents::Key::Char('P'),
+                    tuievents::KeyModifiers::SHIFT,
+                )
+                .into(),
+                queue_next: tuievents::Key::Char('e').into(),
+                show_queue: tuievents::KeyEvent::new(
+                    tuievents::Key::Char('E'),
+                    tuievents::KeyModifiers::SHIFT,
+                )
+                .into(),
+                undo: tuievents::Key::Char('u').into(),
+                redo: tuievents::KeyEvent::new(
+                    tuievents::Key::Char('U'),
+                    tuievents::KeyModifiers::SHIFT,
+                )
+                .into(),
+                sort: tuievents::KeyEvent::new(
+                    tuievents::Key::Char('O'),
+                    tuievents::KeyModifiers::SHIFT,
+                )
+                .into(),
+                mark: tuievents::Key::Char('x').into(),
+                mark_range: tuievents::Key::Char('v').into(),
+                move_top: tuievents::Key::Char('{').into(),
+                move_bottom: tuievents::Key::Char('}').into(),
+                save_marked: tuievents::Key::Char('w').into(),
             };
             assert_eq!(converted.playlist_keys, expected_playlist_keys);
 
@@ -2225,6 +2544,50 @@ mod v1_interop {
             };
             assert_eq!(converted.config_keys, expected_config_editor_keys);
 
+            let expected_tag_editor_keys = KeysTagEditor {
+                analyze_loudness: tuievents::KeyEvent::new(
+                    tuievents::Key::Char('r'),
+                    tuievents::KeyModifiers::CONTROL,
+                )
+                .into(),
+                embed_sidecar: tuievents::KeyEvent::new(
+                    tuievents::Key::Char('l'),
+                    tuievents::KeyModifiers::CONTROL,
+                )
+                .into(),
+                export_sidecar: tuievents::KeyEvent::new(
+                    tuievents::Key::Char('x'),
+                    tuievents::KeyModifiers::CONTROL,
+                )
+                .into(),
+                sync_lyrics: tuievents::KeyEvent::new(
+                    tuievents::Key::Char('t'),
+                    tuievents::KeyModifiers::CONTROL,
+                )
+                .into(),
+                musicbrainz_lookup: tuievents::KeyEvent::new(
+                    tuievents::Key::Char('b'),
+                    tuievents::KeyModifiers::CONTROL,
+                )
+                .into(),
+                rename_by_pattern: tuievents::KeyEvent::new(
+                    tuievents::Key::Char('n'),
+                    tuievents::KeyModifiers::CONTROL,
+                )
+                .into(),
+                tags_from_filename: tuievents::KeyEvent::new(
+                    tuievents::Key::Char('f'),
+                    tuievents::KeyModifiers::CONTROL,
+                )
+                .into(),
+                cover_art: tuievents::KeyEvent::new(
+                    tuievents::Key::Char('p'),
+                    tuievents::KeyModifiers::CONTROL,
+                )
+                .into(),
+            };
+            assert_eq!(converted.tag_editor_keys, expected_tag_editor_keys);
+
             let expected_keys = Keys {
                 escape: tuievents::Key::Esc.into(),
                 quit: tuievents::Key::Char('q').into(),
@@ -2238,6 +2601,7 @@ mod v1_interop {
                 podcast_keys: expected_podcast_keys,
                 move_cover_art_keys: expected_move_cover_art_keys,
                 config_keys: expected_config_editor_keys,
+                tag_editor_keys: expected_tag_editor_keys,
             };
 
             assert_eq!(converted, expected_keys);
@@ -2290,11 +2654,18 @@ mod v1_interop {
                     tuievents::KeyModifiers::CONTROL,
                 )
                 .into(),
+                pitch_up: tuievents::Key::Char('>').into(),
+                pitch_down: tuievents::Key::Char('<').into(),
+                pitch_fine_up: tuievents::Key::Char(')').into(),
+                pitch_fine_down: tuievents::Key::Char('(').into(),
                 toggle_prefetch: tuievents::KeyEvent::new(
                     tuievents::Key::Char('g'),
                     tuievents::KeyModifiers::CONTROL,
                 )
                 .into(),
+                ab_loop_mark_a: tuievents::Key::Char('[').into(),
+                ab_loop_mark_b: tuievents::Key::Char(']').into(),
+                ab_loop_clear: tuievents::Key::Char('\\').into(),
                 save_playlist: tuievents::KeyEvent::new(
                     tuievents::Key::Char('s'),
                     tuievents::KeyModifiers::CONTROL,
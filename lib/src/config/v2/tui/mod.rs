@@ -1,4 +1,4 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
@@ -19,6 +19,10 @@ pub struct TuiSettings {
     pub com_resolved: Option<ComSettings>,
     pub behavior: BehaviorSettings,
     pub coverart: CoverArtPosition,
+    pub spectrum: SpectrumPosition,
+    pub lyrics: LyricSettings,
+    pub musicbrainz: MusicBrainzSettings,
+    pub file_patterns: FilePatternSettings,
     #[serde(flatten)]
     pub theme: theme::ThemeWrap,
     pub keys: keys::Keys,
@@ -102,6 +106,154 @@ pub struct CoverArtPosition {
     pub hidden: bool,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)] // allow missing fields and fill them with the `..Self::default()` in this struct
+pub struct SpectrumPosition {
+    /// Alignment of the Spectrum visualizer in the tui
+    pub align: Alignment,
+    /// Size of the visualizer, in percent of the terminal width and height
+    pub size_scale: u8,
+    /// Whether to show or hide the spectrum visualizer
+    pub hidden: bool,
+}
+
+impl Default for SpectrumPosition {
+    fn default() -> Self {
+        Self {
+            align: Alignment::TopRight,
+            size_scale: 20,
+            hidden: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)] // allow missing fields and fill them with the `..Self::default()` in this struct
+pub struct LyricSettings {
+    /// Directories to search for sidecar lyric files (`<basename>.lrc` or `Artist - Title.lrc`, also `.txt`), in order
+    ///
+    /// Relative paths are relative to the directory of the track.
+    /// Sidecar lyrics take precedence over lyrics embedded in the track.
+    pub sidecar_dirs: Vec<PathBuf>,
+    /// Online services to search lyrics and tags with in the tag editor
+    ///
+    /// The results of all enabled providers are merged by `priority` (lowest first), with duplicates removed.
+    /// A provider can be listed multiple times, like for a self-hosted instance.
+    pub providers: Vec<LyricProvider>,
+}
+
+impl Default for LyricSettings {
+    fn default() -> Self {
+        Self {
+            sidecar_dirs: vec![PathBuf::from(".")],
+            providers: vec![
+                LyricProvider::new(ServiceProvider::Netease, 0),
+                LyricProvider::new(ServiceProvider::Migu, 1),
+                LyricProvider::new(ServiceProvider::Kugou, 2),
+                LyricProvider::new(ServiceProvider::Lrclib, 3),
+            ],
+        }
+    }
+}
+
+/// A online service to search lyrics and tags with
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ServiceProvider {
+    Netease,
+    Kugou,
+    Migu,
+    Lrclib,
+}
+
+impl std::fmt::Display for ServiceProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let service_provider = match self {
+            Self::Netease => "Netease",
+            Self::Kugou => "Kugou",
+            Self::Migu => "Migu",
+            Self::Lrclib => "LRCLIB",
+        };
+        write!(f, "{service_provider}")
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)] // allow missing fields and fill them with the `..Self::default()` in this struct
+pub struct LyricProvider {
+    /// The service to use
+    pub provider: ServiceProvider,
+    /// Whether to search this provider
+    pub enabled: bool,
+    /// Order of the results, lower comes first
+    pub priority: u16,
+    /// Base url of the service, like of a mirror or self-hosted instance
+    ///
+    /// Only supported by `netease` and `lrclib`, if unset the public instance is used.
+    pub base_url: Option<String>,
+}
+
+impl LyricProvider {
+    #[must_use]
+    pub const fn new(provider: ServiceProvider, priority: u16) -> Self {
+        Self {
+            provider,
+            enabled: true,
+            priority,
+            base_url: None,
+        }
+    }
+}
+
+impl Default for LyricProvider {
+    /// A enabled `lrclib` entry, so that only a `base_url` is necessary to add a self-hosted instance
+    fn default() -> Self {
+        Self::new(ServiceProvider::Lrclib, 0)
+    }
+}
+
+/// The public `MusicBrainz` instance
+pub const URL_BASE_MUSICBRAINZ: &str = "https://musicbrainz.org";
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)] // allow missing fields and fill them with the `..Self::default()` in this struct
+pub struct MusicBrainzSettings {
+    /// Base url of the `MusicBrainz` compatible api to look up releases with in the tag editor
+    pub base_url: String,
+}
+
+impl Default for MusicBrainzSettings {
+    fn default() -> Self {
+        Self {
+            base_url: URL_BASE_MUSICBRAINZ.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)] // allow missing fields and fill them with the `..Self::default()` in this struct
+pub struct FilePatternSettings {
+    /// Pattern to move files to in the tag editor, relative to the music directory the file is in.
+    ///
+    /// Available fields: `{artist}`, `{albumartist}`, `{album}`, `{title}`, `{genre}`, `{year}`, `{track}`, `{disc}`,
+    /// numbers can be zero-padded like `{track:02}`
+    pub rename: String,
+    /// Pattern to read tags from the file path with in the tag editor, using the same fields as `rename`
+    pub parse: String,
+    /// Remove the directories that are left empty after moving files with `rename`
+    pub remove_empty_dirs: bool,
+}
+
+impl Default for FilePatternSettings {
+    fn default() -> Self {
+        Self {
+            rename: "{albumartist}/{year} - {album}/{disc}{track:02} {title}".to_string(),
+            parse: "{artist} - {title}".to_string(),
+            remove_empty_dirs: false,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Deserialize, Serialize, Default, PartialEq, Eq)]
 pub enum Alignment {
     #[serde(rename = "top right")]
@@ -124,7 +276,8 @@ pub struct Ytdlp {
 
 mod v1_interop {
     use super::{
-        Alignment, BehaviorSettings, CoverArtPosition, MaybeComSettings, TuiSettings, Ytdlp,
+        Alignment, BehaviorSettings, CoverArtPosition, FilePatternSettings, LyricSettings,
+        MaybeComSettings, MusicBrainzSettings, SpectrumPosition, TuiSettings, Ytdlp,
     };
     use crate::config::v1;
 
@@ -163,6 +316,10 @@ mod v1_interop {
                     confirm_quit: value.enable_exit_confirmation,
                 },
                 coverart: value.album_photo_xywh.into(),
+                spectrum: SpectrumPosition::default(),
+                lyrics: LyricSettings::default(),
+                musicbrainz: MusicBrainzSettings::default(),
+                file_patterns: FilePatternSettings::default(),
                 theme,
                 keys: value.keys.into(),
                 ytdlp: Ytdlp::default(),
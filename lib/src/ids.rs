@@ -19,31 +19,45 @@ pub enum Id {
     Library,
     Lyric,
     MessagePopup,
+    NamedPlaylistsPopup,
+    NamedPlaylistInputPopup,
+    NamedPlaylistDeleteConfirm,
     Playlist,
+    PlaylistSortPopup,
     Podcast,
     PodcastAddPopup,
     PodcastSearchTablePopup,
     FeedDeleteConfirmRadioPopup,
     FeedDeleteConfirmInputPopup,
     Progress,
+    QueuePopup,
     QuitPopup,
     SavePlaylistPopup,
     SavePlaylistLabel,
     SavePlaylistConfirm,
+    Spectrum,
     TagEditor(IdTagEditor),
     YoutubeSearchInputPopup,
     YoutubeSearchTablePopup,
     DatabaseAddConfirmPopup,
+    LyricOffsetConfirmPopup,
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Copy, Hash)]
 pub enum IdTagEditor {
+    BatchFiles,
+    BatchPreview,
     CounterDelete,
+    CoverArt,
+    CoverFile,
     LabelHint,
     InputArtist,
     InputTitle,
     InputAlbum,
     InputGenre,
+    MusicBrainzCandidates,
+    MusicBrainzChanges,
+    PatternPreview,
     SelectLyric,
     TableLyricOptions,
     TextareaLyric,
@@ -129,6 +143,7 @@ pub enum IdKey {
     GlobalLyricAdjustForward,
     GlobalLyricAdjustBackward,
     GlobalLyricCycle,
+    GlobalLyricSaveOffset,
     GlobalPlayerToggleGapless,
     GlobalPlayerTogglePause,
     GlobalPlayerNext,
@@ -160,6 +175,18 @@ pub enum IdKey {
     PlaylistSwapUp,
     PlaylistAddRandomAlbum,
     PlaylistAddRandomTracks,
+    PlaylistNamedPlaylists,
+    PlaylistQueueNext,
+    PlaylistShowQueue,
+    LibraryQueueNext,
+    PlaylistUndo,
+    PlaylistRedo,
+    PlaylistSort,
+    PlaylistMark,
+    PlaylistMarkRange,
+    PlaylistMoveTop,
+    PlaylistMoveBottom,
+    PlaylistSaveMarked,
     LibrarySwitchRoot,
     LibraryAddRoot,
     LibraryRemoveRoot,
@@ -171,6 +198,13 @@ pub enum IdKey {
     GlobalXywhZoomIn,
     GlobalXywhZoomOut,
     GlobalXywhHide,
+    GlobalPlayerABLoopMarkA,
+    GlobalPlayerABLoopMarkB,
+    GlobalPlayerABLoopClear,
+    GlobalPlayerPitchUp,
+    GlobalPlayerPitchDown,
+    GlobalPlayerPitchFineUp,
+    GlobalPlayerPitchFineDown,
     PodcastMarkPlayed,
     PodcastMarkAllPlayed,
     PodcastEpDownload,
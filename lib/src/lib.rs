@@ -10,6 +10,7 @@ pub mod config;
 pub mod ids;
 pub mod invidious;
 pub mod library_db;
+pub mod loudness;
 pub mod player;
 pub mod playlist;
 pub mod podcast;
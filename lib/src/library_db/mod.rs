@@ -356,6 +356,30 @@ impl DataBase {
         Ok(())
     }
 
+    /// Update the paths of moved tracks, given as `(from, to)`, keeping all other values like `last_position`.
+    ///
+    /// Records which already existed for a target path are replaced.
+    pub fn move_records(&mut self, moves: &[(PathBuf, PathBuf)]) -> Result<()> {
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction()?;
+
+        for (from, to) in moves {
+            let file = to.to_string_lossy();
+            let name = to.file_stem().unwrap_or_default().to_string_lossy();
+            let ext = to.extension().unwrap_or_default().to_string_lossy();
+            let directory = to.parent().unwrap_or(Path::new("")).to_string_lossy();
+
+            tx.execute("DELETE FROM tracks WHERE file = ?", params![file])?;
+            tx.execute(
+                "UPDATE tracks SET file = ?1, name = ?2, ext = ?3, directory = ?4 WHERE file = ?5",
+                params![file, name, ext, directory, from.to_string_lossy()],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
     /// Get a Track by the given full file path
     pub fn get_record_by_path(&mut self, file_path: &str) -> Result<TrackDB> {
         let search_str = "SELECT * FROM tracks WHERE file = ?";
@@ -384,3 +408,46 @@ mod test_utils {
         Connection::open_in_memory().expect("open db failed")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    use parking_lot::Mutex;
+    use pretty_assertions::assert_eq;
+
+    use super::test_utils::gen_database;
+    use super::{migration, DataBase};
+    use crate::config::v2::server::ScanDepth;
+
+    #[test]
+    fn should_move_records() {
+        let conn = gen_database();
+        migration::migrate(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO tracks (artist, title, album, genre, file, duration, name, ext, directory, last_modified, last_position)
+            VALUES ('Artist', 'Title', 'Album', '', '/music/old.mp3', 60, 'old', 'mp3', '/music', '0', 42);",
+            [],
+        )
+        .unwrap();
+        let mut db = DataBase {
+            conn: Arc::new(Mutex::new(conn)),
+            max_depth: ScanDepth::Limited(10),
+        };
+
+        db.move_records(&[(
+            PathBuf::from("/music/old.mp3"),
+            PathBuf::from("/music/Artist/Album/01 Title.mp3"),
+        )])
+        .unwrap();
+
+        assert!(db.get_record_by_path("/music/old.mp3").is_err());
+        let record = db
+            .get_record_by_path("/music/Artist/Album/01 Title.mp3")
+            .unwrap();
+        assert_eq!(record.name, "01 Title");
+        assert_eq!(record.directory, "/music/Artist/Album");
+        assert_eq!(record.last_position.as_secs(), 42);
+    }
+}
@@ -0,0 +1,338 @@
+//! Loudness and true-peak measurement as per ITU-R BS.1770-4 / EBU R128.
+
+use std::f64::consts::PI;
+
+/// Absolute gating threshold in LUFS.
+const ABSOLUTE_GATE: f64 = -70.0;
+/// Relative gating threshold in LU, below the absolute-gated loudness.
+const RELATIVE_GATE: f64 = -10.0;
+/// Amount of 100ms steps that make up one 400ms gating block (75% overlap).
+const STEPS_PER_BLOCK: usize = 4;
+/// Amount of taps per polyphase of the true-peak interpolation filter.
+const TAPS_PER_PHASE: usize = 12;
+
+/// Convert a (weighted) mean square to loudness in LUFS.
+fn energy_to_loudness(energy: f64) -> f64 {
+    -0.691 + 10.0 * energy.log10()
+}
+
+/// Calculate the gated loudness (in LUFS) from the given gating block energies.
+///
+/// Returns `None` if there are no blocks above the absolute gate, like for silence.
+#[allow(clippy::cast_precision_loss)]
+pub(super) fn gated_loudness<'a, I>(blocks: &I) -> Option<f64>
+where
+    I: Iterator<Item = &'a f64> + Clone,
+{
+    let mean_above = |threshold: f64| {
+        let (sum, count) = blocks
+            .clone()
+            .filter(|v| energy_to_loudness(**v) > threshold)
+            .fold((0.0, 0usize), |(sum, count), v| (sum + v, count + 1));
+
+        (count > 0).then(|| sum / count as f64)
+    };
+
+    let absolute = mean_above(ABSOLUTE_GATE)?;
+    let relative_threshold = energy_to_loudness(absolute) + RELATIVE_GATE;
+
+    mean_above(relative_threshold.max(ABSOLUTE_GATE)).map(energy_to_loudness)
+}
+
+/// A simple biquad filter in "Direct Form I".
+#[derive(Debug, Clone, Default)]
+struct Biquad {
+    b: [f64; 3],
+    a: [f64; 2],
+    x: [f64; 2],
+    y: [f64; 2],
+}
+
+impl Biquad {
+    fn process(&mut self, input: f64) -> f64 {
+        let output = self.b[0] * input + self.b[1] * self.x[0] + self.b[2] * self.x[1]
+            - self.a[0] * self.y[0]
+            - self.a[1] * self.y[1];
+
+        self.x = [input, self.x[0]];
+        self.y = [output, self.y[0]];
+
+        output
+    }
+}
+
+/// The BS.1770 "K" frequency weighting, a high-shelf followed by a high-pass.
+///
+/// The coefficients are derived for the given sample rate instead of using the fixed 48kHz values of the specification.
+#[derive(Debug, Clone)]
+struct KWeighting {
+    shelf: Biquad,
+    highpass: Biquad,
+}
+
+impl KWeighting {
+    fn new(sample_rate: u32) -> Self {
+        let rate = f64::from(sample_rate);
+
+        let f0 = 1_681.974_450_955_533;
+        let gain = 3.999_843_853_973_347;
+        let q = 0.707_175_236_955_419_6;
+        let k = (PI * f0 / rate).tan();
+        let vh = 10f64.powf(gain / 20.0);
+        let vb = vh.powf(0.499_666_774_154_541_6);
+        let a0 = 1.0 + k / q + k * k;
+        let shelf = Biquad {
+            b: [
+                (vh + vb * k / q + k * k) / a0,
+                2.0 * (k * k - vh) / a0,
+                (vh - vb * k / q + k * k) / a0,
+            ],
+            a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+            ..Default::default()
+        };
+
+        let f0 = 38.135_470_876_024_44;
+        let q = 0.500_327_037_323_877_3;
+        let k = (PI * f0 / rate).tan();
+        let a0 = 1.0 + k / q + k * k;
+        let highpass = Biquad {
+            b: [1.0, -2.0, 1.0],
+            a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+            ..Default::default()
+        };
+
+        Self { shelf, highpass }
+    }
+
+    fn process(&mut self, input: f64) -> f64 {
+        self.highpass.process(self.shelf.process(input))
+    }
+}
+
+/// Estimate the true peak of a channel by oversampling with a windowed-sinc interpolation filter.
+#[derive(Debug, Clone)]
+struct TruePeak {
+    /// The filter, split into `factor` phases of [`TAPS_PER_PHASE`] taps each.
+    phases: Vec<[f64; TAPS_PER_PHASE]>,
+    /// The last input samples, newest first.
+    history: [f64; TAPS_PER_PHASE],
+    peak: f64,
+}
+
+impl TruePeak {
+    #[allow(clippy::cast_precision_loss)]
+    fn new(sample_rate: u32) -> Self {
+        // the higher the sample rate, the less oversampling is necessary
+        let factor: usize = match sample_rate {
+            0..96_000 => 4,
+            96_000..192_000 => 2,
+            _ => 1,
+        };
+
+        let len = factor * TAPS_PER_PHASE;
+        let center = (len - 1) as f64 / 2.0;
+        let coefficient = |n: usize| {
+            let x = (n as f64 - center) / factor as f64;
+            let sinc = if x == 0.0 {
+                1.0
+            } else {
+                (PI * x).sin() / (PI * x)
+            };
+            // hann window
+            let window = 0.5 - 0.5 * (2.0 * PI * (n as f64 + 0.5) / len as f64).cos();
+
+            sinc * window
+        };
+
+        let phases = (0..factor)
+            .map(|phase| std::array::from_fn(|tap| coefficient(phase + tap * factor)))
+            .collect();
+
+        Self {
+            phases,
+            history: [0.0; TAPS_PER_PHASE],
+            peak: 0.0,
+        }
+    }
+
+    fn push(&mut self, sample: f64) {
+        self.history.rotate_right(1);
+        self.history[0] = sample;
+
+        self.peak = self.peak.max(sample.abs());
+
+        for phase in &self.phases {
+            let value: f64 = phase.iter().zip(&self.history).map(|(h, x)| h * x).sum();
+            self.peak = self.peak.max(value.abs());
+        }
+    }
+}
+
+/// Measure integrated loudness and true peak of interleaved samples.
+#[derive(Debug, Clone)]
+pub struct LoudnessMeter {
+    channels: usize,
+    /// Channel weights as per BS.1770, `0` for channels that should be ignored (LFE).
+    weights: Vec<f64>,
+    filters: Vec<KWeighting>,
+    peaks: Vec<TruePeak>,
+
+    /// Amount of frames in a 100ms step.
+    step_len: usize,
+    /// Amount of frames already in the current step.
+    step_frames: usize,
+    /// The weighted sum of squares of the current step.
+    step_energy: f64,
+    /// The energy of the last [`STEPS_PER_BLOCK`] steps.
+    steps: [f64; STEPS_PER_BLOCK],
+    /// The amount of steps done in total.
+    steps_done: usize,
+
+    /// The mean square of all finished gating blocks.
+    blocks: Vec<f64>,
+}
+
+impl LoudnessMeter {
+    /// Create a new meter for the given channel count and sample rate.
+    #[must_use]
+    pub fn new(channels: u16, sample_rate: u32) -> Self {
+        let channels = usize::from(channels.max(1));
+
+        // assume the common layouts for 5.0 (L R C Ls Rs) and 5.1 (L R C LFE Ls Rs)
+        let weights = (0..channels)
+            .map(|channel| match (channels, channel) {
+                (6, 3) => 0.0,
+                (5, 3 | 4) | (6, 4 | 5) => 1.41,
+                _ => 1.0,
+            })
+            .collect();
+
+        Self {
+            channels,
+            weights,
+            filters: vec![KWeighting::new(sample_rate); channels],
+            peaks: vec![TruePeak::new(sample_rate); channels],
+            step_len: usize::try_from(sample_rate / 10)
+                .unwrap_or(usize::MAX)
+                .max(1),
+            step_frames: 0,
+            step_energy: 0.0,
+            steps: [0.0; STEPS_PER_BLOCK],
+            steps_done: 0,
+            blocks: Vec::new(),
+        }
+    }
+
+    /// Add interleaved samples to the measurement.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn push(&mut self, samples: &[f32]) {
+        for frame in samples.chunks_exact(self.channels) {
+            for (channel, sample) in frame.iter().enumerate() {
+                let sample = f64::from(*sample);
+                self.peaks[channel].push(sample);
+
+                let filtered = self.filters[channel].process(sample);
+                self.step_energy += self.weights[channel] * filtered * filtered;
+            }
+
+            self.step_frames += 1;
+            if self.step_frames < self.step_len {
+                continue;
+            }
+
+            self.steps[self.steps_done % STEPS_PER_BLOCK] = self.step_energy;
+            self.steps_done += 1;
+            self.step_frames = 0;
+            self.step_energy = 0.0;
+
+            if self.steps_done >= STEPS_PER_BLOCK {
+                let sum: f64 = self.steps.iter().sum();
+                self.blocks
+                    .push(sum / (STEPS_PER_BLOCK * self.step_len) as f64);
+            }
+        }
+    }
+
+    /// Get the integrated loudness in LUFS, or `None` if nothing was above the gate (like silence).
+    #[must_use]
+    pub fn integrated_loudness(&self) -> Option<f64> {
+        gated_loudness(&self.blocks.iter())
+    }
+
+    /// Get the maximal true peak across all channels, as a linear amplitude.
+    #[must_use]
+    pub fn true_peak(&self) -> f64 {
+        self.peaks.iter().map(|v| v.peak).fold(0.0, f64::max)
+    }
+
+    /// Get the energies of all gating blocks, to allow calculating the loudness across multiple meters.
+    #[must_use]
+    pub(super) fn blocks(&self) -> &[f64] {
+        &self.blocks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::LoudnessMeter;
+
+    /// Generate `secs` seconds of interleaved stereo sine with the given frequency, amplitude and phase.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    fn stereo_sine(rate: u32, freq: f64, amplitude: f64, phase: f64, secs: u32) -> Vec<f32> {
+        (0..rate * secs)
+            .flat_map(|i| {
+                let v = (amplitude
+                    * (2.0 * PI * freq * f64::from(i) / f64::from(rate) + phase).sin())
+                    as f32;
+                [v, v]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn should_measure_reference_sine() {
+        // EBU Tech 3341, test case 1: stereo 1kHz sine at -23 dBFS should be -23 LUFS
+        let amplitude = 10f64.powf(-23.0 / 20.0);
+        for rate in [44_100, 48_000] {
+            let mut meter = LoudnessMeter::new(2, rate);
+            meter.push(&stereo_sine(rate, 1000.0, amplitude, 0.0, 5));
+
+            let loudness = meter.integrated_loudness().unwrap();
+            assert!((loudness - -23.0).abs() < 0.1, "{rate}: {loudness}");
+        }
+    }
+
+    #[test]
+    fn should_gate_silence() {
+        let mut meter = LoudnessMeter::new(2, 48_000);
+        meter.push(&vec![0.0; 48_000 * 2 * 5]);
+
+        assert_eq!(meter.integrated_loudness(), None);
+        assert!(meter.true_peak().abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn should_ignore_silence_between_loud_parts() {
+        let amplitude = 10f64.powf(-23.0 / 20.0);
+        let mut meter = LoudnessMeter::new(2, 48_000);
+        meter.push(&stereo_sine(48_000, 1000.0, amplitude, 0.0, 3));
+        meter.push(&vec![0.0; 48_000 * 2 * 3]);
+        meter.push(&stereo_sine(48_000, 1000.0, amplitude, 0.0, 3));
+
+        // the blocks overlapping the transitions are only partially loud, but still pass the gates
+        let loudness = meter.integrated_loudness().unwrap();
+        assert!((loudness - -23.0).abs() < 0.25, "{loudness}");
+    }
+
+    #[test]
+    fn should_find_inter_sample_peak() {
+        // a sine at a quarter of the sample rate with a 45 degree phase never has a sample on its peak
+        let mut meter = LoudnessMeter::new(2, 48_000);
+        meter.push(&stereo_sine(48_000, 12_000.0, 0.5, PI / 4.0, 1));
+
+        let peak = meter.true_peak();
+        assert!((peak - 0.5).abs() < 0.025, "{peak}");
+    }
+}
@@ -0,0 +1,395 @@
+//! Loudness analysis (EBU R128) of tracks and albums, and writing of the resulting `ReplayGain` / R128 tags.
+
+use std::collections::BTreeMap;
+use std::ffi::OsStr;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use lofty::file::FileType;
+use lofty::tag::ItemKey;
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{DecoderOptions, CODEC_TYPE_NULL};
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::{MediaSourceStream, MediaSourceStreamOptions};
+use symphonia::core::meta::MetadataOptions as SymphoniaMetadataOptions;
+use symphonia::core::probe::Hint;
+
+use crate::config::v2::server::ScanDepth;
+use crate::songtag::edit::{edit_tag, TagEdit};
+use crate::track::{parse_metadata_from_file, MetadataOptions};
+use crate::utils::filetype_supported;
+
+pub use meter::LoudnessMeter;
+
+mod meter;
+
+/// The reference loudness of `ReplayGain` 2.0 in LUFS.
+pub const REPLAYGAIN_REFERENCE: f64 = -18.0;
+/// The reference loudness of R128 tags (as used by Opus) in LUFS.
+pub const R128_REFERENCE: f64 = -23.0;
+
+/// The result of a loudness measurement.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Loudness {
+    /// The integrated loudness in LUFS.
+    pub integrated: f64,
+    /// The maximal true peak, as a linear amplitude.
+    pub true_peak: f64,
+}
+
+impl Loudness {
+    /// Get the gain in dB to reach the `ReplayGain` 2.0 reference loudness.
+    #[must_use]
+    pub fn replaygain_gain(&self) -> f64 {
+        REPLAYGAIN_REFERENCE - self.integrated
+    }
+
+    /// Get the gain in the Q7.8 format as used by `R128_*_GAIN` tags.
+    #[allow(clippy::cast_possible_truncation)]
+    #[must_use]
+    pub fn r128_gain(&self) -> i16 {
+        ((R128_REFERENCE - self.integrated) * 256.0)
+            .round()
+            .clamp(f64::from(i16::MIN), f64::from(i16::MAX)) as i16
+    }
+}
+
+/// A group of tracks considered to be one album.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlbumGroup {
+    /// The album name, `None` if the tracks do not have a album tag, in which case they are treated as singles.
+    pub album: Option<String>,
+    pub files: Vec<PathBuf>,
+}
+
+/// Progress of a album analysis, reported before each track is started.
+#[derive(Debug, Clone, Copy)]
+pub struct AnalyzeProgress<'a> {
+    /// The (0-based) index of the track about to be analyzed.
+    pub current: usize,
+    pub total: usize,
+    pub path: &'a Path,
+}
+
+/// The result of analyzing a [`AlbumGroup`].
+#[derive(Debug)]
+pub struct AlbumAnalysis {
+    /// The result for each track, in the order given.
+    pub tracks: Vec<(PathBuf, Result<Loudness>)>,
+    /// The loudness across all successfully analyzed tracks.
+    ///
+    /// Only set if the group is a actual album and there was any audible audio.
+    pub album: Option<Loudness>,
+}
+
+impl AlbumAnalysis {
+    /// Write the tags for all successfully analyzed tracks.
+    ///
+    /// Returns the paths and errors for the tracks that failed to be written.
+    #[must_use]
+    pub fn write_tags(&self) -> Vec<(PathBuf, anyhow::Error)> {
+        self.tracks
+            .iter()
+            .filter_map(|(path, res)| res.as_ref().ok().map(|v| (path, v)))
+            .filter_map(|(path, track)| {
+                write_tags(path, track, self.album.as_ref())
+                    .err()
+                    .map(|err| (path.clone(), err))
+            })
+            .collect()
+    }
+}
+
+/// Decode the file at `path` with symphonia and measure its loudness.
+fn measure_file(path: &Path) -> Result<LoudnessMeter> {
+    let file = File::open(path).context("open file")?;
+    let mss = MediaSourceStream::new(Box::new(file), MediaSourceStreamOptions::default());
+
+    let mut hint = Hint::new();
+    if let Some(ext) = path.extension().and_then(OsStr::to_str) {
+        hint.with_extension(ext);
+    }
+
+    let probed = symphonia::default::get_probe()
+        .format(
+            &hint,
+            mss,
+            &FormatOptions::default(),
+            &SymphoniaMetadataOptions::default(),
+        )
+        .context("probe format")?;
+    let mut format = probed.format;
+
+    let track = format
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+        .context("no supported audio track")?;
+    let track_id = track.id;
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .context("create decoder")?;
+
+    let mut meter: Option<LoudnessMeter> = None;
+    let mut buffer: Option<SampleBuffer<f32>> = None;
+
+    loop {
+        let packet = match format.next_packet() {
+            Ok(v) => v,
+            Err(SymphoniaError::IoError(err))
+                if err.kind() == std::io::ErrorKind::UnexpectedEof =>
+            {
+                break;
+            }
+            // chained streams are not supported, treat it as the end
+            Err(SymphoniaError::ResetRequired) => break,
+            Err(err) => return Err(err).context("read packet"),
+        };
+
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        let audio_buf = match decoder.decode(&packet) {
+            Ok(v) => v,
+            // a single broken packet should not make the whole file fail
+            Err(SymphoniaError::DecodeError(err)) => {
+                warn!("Decode error in {}: {err}", path.display());
+                continue;
+            }
+            Err(err) => return Err(err).context("decode packet"),
+        };
+
+        let spec = *audio_buf.spec();
+        let capacity = audio_buf.capacity();
+        let buffer = match &mut buffer {
+            Some(buffer) if buffer.capacity() >= capacity => buffer,
+            _ => buffer.insert(SampleBuffer::new(capacity as u64, spec)),
+        };
+        buffer.copy_interleaved_ref(audio_buf);
+
+        let channels = u16::try_from(spec.channels.count()).unwrap_or(u16::MAX);
+        meter
+            .get_or_insert_with(|| LoudnessMeter::new(channels, spec.rate))
+            .push(buffer.samples());
+    }
+
+    meter.context("no audio decoded")
+}
+
+/// Get the [`Loudness`] of a finished measurement.
+///
+/// Returns a error if there was no audible audio.
+fn meter_loudness(meter: &LoudnessMeter) -> Result<Loudness> {
+    Ok(Loudness {
+        integrated: meter.integrated_loudness().context("no audible audio")?,
+        true_peak: meter.true_peak(),
+    })
+}
+
+/// Analyze a single track.
+///
+/// Returns a error if the track could not be decoded or there was no audible audio.
+pub fn analyze_track(path: &Path) -> Result<Loudness> {
+    meter_loudness(&measure_file(path)?)
+}
+
+/// Analyze all tracks in the given group, and the album as a whole if it is a album.
+///
+/// `progress` is called before each track is analyzed.
+pub fn analyze_album<F>(group: &AlbumGroup, mut progress: F) -> AlbumAnalysis
+where
+    F: FnMut(AnalyzeProgress<'_>),
+{
+    let total = group.files.len();
+    let mut tracks = Vec::with_capacity(total);
+    let mut meters = Vec::with_capacity(total);
+
+    for (current, path) in group.files.iter().enumerate() {
+        progress(AnalyzeProgress {
+            current,
+            total,
+            path,
+        });
+
+        let res = measure_file(path).and_then(|meter| {
+            let loudness = meter_loudness(&meter)?;
+            meters.push(meter);
+
+            Ok(loudness)
+        });
+
+        tracks.push((path.clone(), res));
+    }
+
+    let album = if group.album.is_some() {
+        meter::gated_loudness(&meters.iter().flat_map(LoudnessMeter::blocks)).map(|integrated| {
+            Loudness {
+                integrated,
+                true_peak: meters
+                    .iter()
+                    .map(LoudnessMeter::true_peak)
+                    .fold(0.0, f64::max),
+            }
+        })
+    } else {
+        None
+    };
+
+    AlbumAnalysis { tracks, album }
+}
+
+/// Write the loudness results as tags to the file at `path`.
+///
+/// Opus files get `R128_TRACK_GAIN` / `R128_ALBUM_GAIN` as per RFC 7845, all other files get the `ReplayGain` 2.0 tags.
+/// Existing album values are left as-is if `album` is `None`, all other tags of the file are kept.
+pub fn write_tags(path: &Path, track: &Loudness, album: Option<&Loudness>) -> Result<()> {
+    let is_opus = FileType::from_path(path) == Some(FileType::Opus);
+
+    edit_tag(path, |tag| {
+        insert_loudness_items(tag, is_opus, track, album);
+        Ok(true)
+    })?;
+
+    Ok(())
+}
+
+/// Insert the items for the given results into `tag`, see [`write_tags`].
+fn insert_loudness_items(
+    tag: &mut dyn TagEdit,
+    is_opus: bool,
+    track: &Loudness,
+    album: Option<&Loudness>,
+) {
+    if is_opus {
+        tag.insert_text(
+            ItemKey::Unknown("R128_TRACK_GAIN".to_string()),
+            track.r128_gain().to_string(),
+        );
+        if let Some(album) = album {
+            tag.insert_text(
+                ItemKey::Unknown("R128_ALBUM_GAIN".to_string()),
+                album.r128_gain().to_string(),
+            );
+        }
+    } else {
+        tag.insert_text(
+            ItemKey::ReplayGainTrackGain,
+            format!("{:.2} dB", track.replaygain_gain()),
+        );
+        tag.insert_text(
+            ItemKey::ReplayGainTrackPeak,
+            format!("{:.6}", track.true_peak),
+        );
+        if let Some(album) = album {
+            tag.insert_text(
+                ItemKey::ReplayGainAlbumGain,
+                format!("{:.2} dB", album.replaygain_gain()),
+            );
+            tag.insert_text(
+                ItemKey::ReplayGainAlbumPeak,
+                format!("{:.6}", album.true_peak),
+            );
+        }
+    }
+}
+
+/// Find all supported tracks below `root` and group them as albums.
+///
+/// Tracks are considered one album if they are in the same directory and have the same album tag.
+/// Tracks without a album tag are returned as one group with `album: None`, per directory.
+#[must_use]
+pub fn find_albums(root: &Path, max_depth: ScanDepth) -> Vec<AlbumGroup> {
+    let mut walker = walkdir::WalkDir::new(root).follow_links(true);
+    if let ScanDepth::Limited(limit) = max_depth {
+        walker = walker.max_depth(usize::try_from(limit).unwrap_or(usize::MAX));
+    }
+
+    let mut groups: BTreeMap<(PathBuf, Option<String>), Vec<PathBuf>> = BTreeMap::new();
+
+    for entry in walker
+        .into_iter()
+        .filter_map(std::result::Result::ok)
+        .filter(|f| f.file_type().is_file())
+        .filter(|f| filetype_supported(f.path()))
+    {
+        let album = parse_metadata_from_file(
+            entry.path(),
+            MetadataOptions {
+                album: true,
+                ..Default::default()
+            },
+        )
+        .ok()
+        .and_then(|v| v.album)
+        .filter(|v| !v.is_empty());
+        let dir = entry
+            .path()
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+
+        groups
+            .entry((dir, album))
+            .or_default()
+            .push(entry.into_path());
+    }
+
+    groups
+        .into_iter()
+        .map(|((_, album), mut files)| {
+            files.sort();
+            AlbumGroup { album, files }
+        })
+        .collect()
+}
+
+/// Find the album the track at `path` belongs to, see [`find_albums`].
+#[must_use]
+pub fn find_album_of(path: &Path) -> Option<AlbumGroup> {
+    let dir = path.parent()?;
+
+    find_albums(dir, ScanDepth::Limited(1))
+        .into_iter()
+        .find(|v| v.files.iter().any(|file| file == path))
+}
+
+#[cfg(test)]
+mod tests {
+    use lofty::id3::v2::Id3v2Tag;
+
+    use super::{insert_loudness_items, Loudness};
+
+    #[test]
+    fn should_calculate_gains() {
+        let loudness = Loudness {
+            integrated: -23.0,
+            true_peak: 0.5,
+        };
+        assert!((loudness.replaygain_gain() - 5.0).abs() < f64::EPSILON);
+        assert_eq!(loudness.r128_gain(), 0);
+
+        let loudness = Loudness {
+            integrated: -13.0,
+            true_peak: 1.0,
+        };
+        assert!((loudness.replaygain_gain() - -5.0).abs() < f64::EPSILON);
+        assert_eq!(loudness.r128_gain(), -2560);
+    }
+
+    #[test]
+    fn should_insert_replaygain_frames_into_id3v2() {
+        let track = Loudness {
+            integrated: -20.0,
+            true_peak: 0.25,
+        };
+        let mut tag = Id3v2Tag::new();
+        insert_loudness_items(&mut tag, false, &track, None);
+
+        assert_eq!(tag.get_user_text("REPLAYGAIN_TRACK_GAIN"), Some("2.00 dB"));
+        assert_eq!(tag.get_user_text("REPLAYGAIN_TRACK_PEAK"), Some("0.250000"));
+        assert_eq!(tag.get_user_text("REPLAYGAIN_ALBUM_GAIN"), None);
+    }
+}
@@ -104,6 +104,70 @@ pub struct TrackChangedInfo {
     pub title: Option<String>,
     /// Current progress of the track
     pub progress: Option<PlayerProgress>,
+    /// The current track, if it was taken from the "play next" queue.
+    /// In that case `current_track_index` still refers to the last played playlist track.
+    pub queued_track: Option<playlist_helpers::PlaylistTrackSource>,
+}
+
+/// A single frame of spectrum data
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SpectrumData {
+    /// Magnitude of each band, from lowest to highest frequency, normalized to `0.0..=1.0`
+    pub bands: Vec<f32>,
+}
+
+impl From<SpectrumData> for protobuf::SpectrumUpdate {
+    fn from(value: SpectrumData) -> Self {
+        Self { bands: value.bands }
+    }
+}
+
+impl From<protobuf::SpectrumUpdate> for SpectrumData {
+    fn from(value: protobuf::SpectrumUpdate) -> Self {
+        Self {
+            bands: value.bands.into_iter().map(|v| v.clamp(0.0, 1.0)).collect(),
+        }
+    }
+}
+
+/// A A-B loop region of the current track, playback will go back to `start` once `end` has been reached
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ABLoopRange {
+    pub start: PlayerTimeUnit,
+    pub end: PlayerTimeUnit,
+}
+
+impl ABLoopRange {
+    /// Create a new loop region, returns `None` if `start` is not before `end`
+    #[must_use]
+    pub fn new(start: PlayerTimeUnit, end: PlayerTimeUnit) -> Option<Self> {
+        if start >= end {
+            return None;
+        }
+
+        Some(Self { start, end })
+    }
+}
+
+impl From<ABLoopRange> for protobuf::AbLoopRange {
+    fn from(value: ABLoopRange) -> Self {
+        Self {
+            start: Some(value.start.into()),
+            end: Some(value.end.into()),
+        }
+    }
+}
+
+impl TryFrom<protobuf::AbLoopRange> for ABLoopRange {
+    type Error = anyhow::Error;
+
+    fn try_from(value: protobuf::AbLoopRange) -> Result<Self, Self::Error> {
+        let start = unwrap_msg(value.start, "ABLoopRange.start")?.into();
+        let end = unwrap_msg(value.end, "ABLoopRange.end")?.into();
+
+        Self::new(start, end)
+            .ok_or_else(|| anyhow!("\"ABLoopRange.start\" is not before \"ABLoopRange.end\"!"))
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -115,6 +179,8 @@ pub enum UpdateEvents {
     TrackChanged(TrackChangedInfo),
     GaplessChanged { gapless: bool },
     PlaylistChanged(UpdatePlaylistEvents),
+    ABLoopChanged { range: Option<ABLoopRange> },
+    PitchChanged { pitch: i32 },
 }
 
 type StreamTypes = protobuf::stream_updates::Type;
@@ -148,6 +214,7 @@ impl From<UpdateEvents> for protobuf::StreamUpdates {
                     .title
                     .map(protobuf::update_track_changed::OptionalTitle::Title),
                 progress: info.progress.map(Into::into),
+                queued_track: info.queued_track.map(Into::into),
             }),
             UpdateEvents::GaplessChanged { gapless } => {
                 StreamTypes::GaplessChanged(UpdateGaplessChanged {
@@ -155,6 +222,14 @@ impl From<UpdateEvents> for protobuf::StreamUpdates {
                 })
             }
             UpdateEvents::PlaylistChanged(ev) => StreamTypes::PlaylistChanged(ev.into()),
+            UpdateEvents::PitchChanged { pitch } => StreamTypes::PitchChanged(UpdatePitchChanged {
+                msg: Some(PitchState { cents: pitch }),
+            }),
+            UpdateEvents::ABLoopChanged { range } => {
+                StreamTypes::AbLoopChanged(UpdateAbLoopChanged {
+                    range: range.map(Into::into),
+                })
+            }
         };
 
         Self { r#type: Some(val) }
@@ -189,6 +264,11 @@ impl TryFrom<protobuf::StreamUpdates> for UpdateEvents {
                     v
                 }),
                 progress: ev.progress.map(Into::into),
+                queued_track: ev
+                    .queued_track
+                    .map(playlist_helpers::PlaylistTrackSource::try_from)
+                    .transpose()
+                    .context("In \"StreamUpdates.types.track_changed.queued_track\"")?,
             }),
             StreamTypes::GaplessChanged(ev) => Self::GaplessChanged {
                 gapless: unwrap_msg(ev.msg, "StreamUpdates.types.gapless_changed.msg")?.gapless,
@@ -197,6 +277,16 @@ impl TryFrom<protobuf::StreamUpdates> for UpdateEvents {
                 ev.try_into()
                     .context("In \"StreamUpdates.types.playlist_changed\"")?,
             ),
+            StreamTypes::PitchChanged(ev) => Self::PitchChanged {
+                pitch: unwrap_msg(ev.msg, "StreamUpdates.types.pitch_changed.msg")?.cents,
+            },
+            StreamTypes::AbLoopChanged(ev) => Self::ABLoopChanged {
+                range: ev
+                    .range
+                    .map(ABLoopRange::try_from)
+                    .transpose()
+                    .context("In \"StreamUpdates.types.ab_loop_changed\"")?,
+            },
         };
 
         Ok(res)
@@ -236,17 +326,62 @@ impl From<LoopMode> for PlaylistLoopModeInfo {
     }
 }
 
+/// The one-shot playback controls for the current track
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PlaybackControls {
+    /// Stop playback once the current track has finished
+    pub stop_after_current: bool,
+    /// How many more times the current track will be repeated
+    pub repeat_count: u32,
+}
+
+impl From<PlaybackControls> for PlaylistPlaybackControls {
+    fn from(value: PlaybackControls) -> Self {
+        Self {
+            stop_after_current: value.stop_after_current,
+            repeat_count: value.repeat_count,
+        }
+    }
+}
+
+impl From<PlaylistPlaybackControls> for PlaybackControls {
+    fn from(value: PlaylistPlaybackControls) -> Self {
+        Self {
+            stop_after_current: value.stop_after_current,
+            repeat_count: value.repeat_count,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct PlaylistSwapInfo {
     pub index_a: u64,
     pub index_b: u64,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlaylistMoveInfo {
+    pub from_index: u64,
+    pub count: u64,
+    pub to_index: u64,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct PlaylistShuffledInfo {
     pub tracks: PlaylistTracks,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlaylistLoadedInfo {
+    pub tracks: PlaylistTracks,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlaylistQueueChangedInfo {
+    /// The whole new "play next" queue
+    pub queue: playlist_helpers::PlaylistQueueTracks,
+}
+
 /// Separate nested enum to handle all playlist related events
 #[derive(Debug, Clone, PartialEq)]
 pub enum UpdatePlaylistEvents {
@@ -256,6 +391,10 @@ pub enum UpdatePlaylistEvents {
     PlaylistLoopMode(PlaylistLoopModeInfo),
     PlaylistSwapTracks(PlaylistSwapInfo),
     PlaylistShuffled(PlaylistShuffledInfo),
+    PlaylistLoaded(PlaylistLoadedInfo),
+    PlaylistQueueChanged(PlaylistQueueChangedInfo),
+    PlaylistPlaybackControls(PlaybackControls),
+    PlaylistMoveTracks(PlaylistMoveInfo),
 }
 
 type PPlaylistTypes = protobuf::update_playlist::Type;
@@ -295,6 +434,26 @@ impl From<UpdatePlaylistEvents> for protobuf::UpdatePlaylist {
                     shuffled: Some(vals.tracks),
                 })
             }
+            UpdatePlaylistEvents::PlaylistLoaded(vals) => {
+                PPlaylistTypes::Loaded(protobuf::PlaylistLoaded {
+                    loaded: Some(vals.tracks),
+                })
+            }
+            UpdatePlaylistEvents::PlaylistQueueChanged(vals) => {
+                PPlaylistTypes::QueueChanged(protobuf::PlaylistQueueChanged {
+                    queue: Some(vals.queue.into()),
+                })
+            }
+            UpdatePlaylistEvents::PlaylistPlaybackControls(vals) => {
+                PPlaylistTypes::PlaybackControls(vals.into())
+            }
+            UpdatePlaylistEvents::PlaylistMoveTracks(vals) => {
+                PPlaylistTypes::MoveTracks(protobuf::PlaylistMoveTracks {
+                    from_index: vals.from_index,
+                    count: vals.count,
+                    to_index: vals.to_index,
+                })
+            }
         };
 
         Self { r#type: Some(val) }
@@ -338,10 +497,28 @@ impl TryFrom<protobuf::UpdatePlaylist> for UpdatePlaylistEvents {
                 index_a: ev.index_a,
                 index_b: ev.index_b,
             }),
+            PPlaylistTypes::MoveTracks(ev) => Self::PlaylistMoveTracks(PlaylistMoveInfo {
+                from_index: ev.from_index,
+                count: ev.count,
+                to_index: ev.to_index,
+            }),
             PPlaylistTypes::Shuffled(ev) => {
                 let shuffled = unwrap_msg(ev.shuffled, "UpdatePlaylist.type.shuffled.shuffled")?;
                 Self::PlaylistShuffled(PlaylistShuffledInfo { tracks: shuffled })
             }
+            PPlaylistTypes::Loaded(ev) => {
+                let loaded = unwrap_msg(ev.loaded, "UpdatePlaylist.type.loaded.loaded")?;
+                Self::PlaylistLoaded(PlaylistLoadedInfo { tracks: loaded })
+            }
+            PPlaylistTypes::QueueChanged(ev) => {
+                let queue = unwrap_msg(ev.queue, "UpdatePlaylist.type.queue_changed.queue")?;
+                Self::PlaylistQueueChanged(PlaylistQueueChangedInfo {
+                    queue: queue
+                        .try_into()
+                        .context("UpdatePlaylist.type.queue_changed.queue")?,
+                })
+            }
+            PPlaylistTypes::PlaybackControls(ev) => Self::PlaylistPlaybackControls(ev.into()),
         };
 
         Ok(res)
@@ -362,12 +539,12 @@ fn clamp_u16(val: u32) -> u16 {
 }
 
 pub mod playlist_helpers {
-    use anyhow::Context;
+    use anyhow::{bail, Context, Result};
 
     use super::{protobuf, unwrap_msg, PlaylistTracksToRemoveClear};
 
     /// A Id / Source for a given Track
-    #[derive(Debug, Clone, PartialEq)]
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
     pub enum PlaylistTrackSource {
         Path(String),
         Url(String),
@@ -572,6 +749,298 @@ pub mod playlist_helpers {
         }
     }
 
+    /// Data for requesting some tracks next to each other to be moved in the server
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct PlaylistMoveTrack {
+        pub from_index: u64,
+        pub count: u64,
+        pub to_index: u64,
+    }
+
+    impl From<PlaylistMoveTrack> for protobuf::PlaylistMoveTracks {
+        fn from(value: PlaylistMoveTrack) -> Self {
+            Self {
+                from_index: value.from_index,
+                count: value.count,
+                to_index: value.to_index,
+            }
+        }
+    }
+
+    impl TryFrom<protobuf::PlaylistMoveTracks> for PlaylistMoveTrack {
+        type Error = anyhow::Error;
+
+        fn try_from(value: protobuf::PlaylistMoveTracks) -> Result<Self, Self::Error> {
+            Ok(Self {
+                from_index: value.from_index,
+                count: value.count,
+                to_index: value.to_index,
+            })
+        }
+    }
+
+    /// Get the index the track at `index` is at after `count` tracks starting at `from_index` have been moved so that the first is at `to_index`.
+    #[must_use]
+    pub fn moved_index(index: usize, from_index: usize, count: usize, to_index: usize) -> usize {
+        if (from_index..from_index + count).contains(&index) {
+            index - from_index + to_index
+        } else if (to_index..from_index).contains(&index) {
+            // moved up, the tracks in-between are shifted down
+            index + count
+        } else if (from_index + count..to_index + count).contains(&index) {
+            // moved down, the tracks in-between are shifted up
+            index - count
+        } else {
+            index
+        }
+    }
+
+    /// Move `count` items starting at `from_index` as one block, so that the first of them is at `to_index` afterwards.
+    ///
+    /// Use [`moved_index`] to update indexes into `items`.
+    ///
+    /// # Errors
+    ///
+    /// - if the items to move or the target are out-of-bounds
+    pub fn move_range<T>(
+        items: &mut [T],
+        from_index: usize,
+        count: usize,
+        to_index: usize,
+    ) -> Result<()> {
+        let len = items.len();
+        if from_index.checked_add(count).is_none_or(|end| end > len) {
+            bail!("Range {from_index}+{count} not within tracks bounds {len}");
+        }
+        if to_index.checked_add(count).is_none_or(|end| end > len) {
+            bail!("Target {to_index}+{count} not within tracks bounds {len}");
+        }
+
+        if to_index < from_index {
+            items[to_index..from_index + count].rotate_right(count);
+        } else {
+            items[from_index..to_index + count].rotate_left(count);
+        }
+
+        Ok(())
+    }
+
+    /// A track selected for a batched edit, with the index it is expected at
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct PlaylistSelectedTrack {
+        pub index: u64,
+        pub id: PlaylistTrackSource,
+    }
+
+    impl From<PlaylistSelectedTrack> for protobuf::PlaylistSelectedTrack {
+        fn from(value: PlaylistSelectedTrack) -> Self {
+            Self {
+                index: value.index,
+                id: Some(value.id.into()),
+            }
+        }
+    }
+
+    impl TryFrom<protobuf::PlaylistSelectedTrack> for PlaylistSelectedTrack {
+        type Error = anyhow::Error;
+
+        fn try_from(value: protobuf::PlaylistSelectedTrack) -> Result<Self, Self::Error> {
+            Ok(Self {
+                index: value.index,
+                id: unwrap_msg(value.id, "PlaylistSelectedTrack.id").and_then(|v| {
+                    PlaylistTrackSource::try_from(v).context("PlaylistSelectedTrack.id")
+                })?,
+            })
+        }
+    }
+
+    /// Some tracks of the playlist, which do not need to be next to each other
+    #[derive(Debug, Clone, PartialEq, Default)]
+    pub struct PlaylistSelection {
+        pub tracks: Vec<PlaylistSelectedTrack>,
+    }
+
+    impl From<PlaylistSelection> for protobuf::PlaylistSelection {
+        fn from(value: PlaylistSelection) -> Self {
+            Self {
+                tracks: value.tracks.into_iter().map(Into::into).collect(),
+            }
+        }
+    }
+
+    impl TryFrom<protobuf::PlaylistSelection> for PlaylistSelection {
+        type Error = anyhow::Error;
+
+        fn try_from(value: protobuf::PlaylistSelection) -> Result<Self, Self::Error> {
+            let tracks = value
+                .tracks
+                .into_iter()
+                .map(|v| PlaylistSelectedTrack::try_from(v).context("PlaylistSelection.tracks"))
+                .collect::<Result<Vec<_>, anyhow::Error>>()?;
+
+            Ok(Self { tracks })
+        }
+    }
+
+    /// Data for requesting the selected tracks to be moved as one block
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct PlaylistMoveSelection {
+        pub selection: PlaylistSelection,
+        /// The index the first selected track will be at after the move
+        pub to_index: u64,
+    }
+
+    impl From<PlaylistMoveSelection> for protobuf::PlaylistMoveSelection {
+        fn from(value: PlaylistMoveSelection) -> Self {
+            Self {
+                selection: Some(value.selection.into()),
+                to_index: value.to_index,
+            }
+        }
+    }
+
+    impl TryFrom<protobuf::PlaylistMoveSelection> for PlaylistMoveSelection {
+        type Error = anyhow::Error;
+
+        fn try_from(value: protobuf::PlaylistMoveSelection) -> Result<Self, Self::Error> {
+            Ok(Self {
+                selection: unwrap_msg(value.selection, "PlaylistMoveSelection.selection")
+                    .and_then(PlaylistSelection::try_from)?,
+                to_index: value.to_index,
+            })
+        }
+    }
+
+    /// The metadata to sort the playlist by
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[repr(u8)]
+    pub enum PlaylistSortKey {
+        /// Sort by artist, then album, then disc and track number
+        Artist = 0,
+        /// Sort by album, then disc and track number
+        Album = 1,
+        /// Sort by disc number, then track number
+        DiscTrack = 2,
+        Title = 3,
+        Duration = 4,
+        /// Sort by the file path or URL
+        Path = 5,
+        /// Sort by the time the file was created (or modified, if not available)
+        DateAdded = 6,
+    }
+
+    impl PlaylistSortKey {
+        /// Convert the current enum variant into its number representation
+        #[must_use]
+        pub fn discriminant(&self) -> u8 {
+            (*self) as u8
+        }
+
+        /// Try to convert the input number representation to a variant
+        #[must_use]
+        pub fn tryfrom_discriminant(num: u8) -> Option<Self> {
+            Some(match num {
+                0 => Self::Artist,
+                1 => Self::Album,
+                2 => Self::DiscTrack,
+                3 => Self::Title,
+                4 => Self::Duration,
+                5 => Self::Path,
+                6 => Self::DateAdded,
+                _ => return None,
+            })
+        }
+    }
+
+    /// Sort the playlist by `key`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct PlaylistSort {
+        pub key: PlaylistSortKey,
+        pub descending: bool,
+    }
+
+    impl From<PlaylistSort> for protobuf::PlaylistSort {
+        fn from(value: PlaylistSort) -> Self {
+            Self {
+                key: u32::from(value.key.discriminant()),
+                descending: value.descending,
+            }
+        }
+    }
+
+    impl TryFrom<protobuf::PlaylistSort> for PlaylistSort {
+        type Error = anyhow::Error;
+
+        fn try_from(value: protobuf::PlaylistSort) -> Result<Self, Self::Error> {
+            let key = u8::try_from(value.key)
+                .ok()
+                .and_then(PlaylistSortKey::tryfrom_discriminant)
+                .with_context(|| format!("Unknown sort key \"{}\"", value.key))?;
+
+            Ok(Self {
+                key,
+                descending: value.descending,
+            })
+        }
+    }
+
+    /// The tracks of the "play next" queue, or tracks to add to it
+    #[derive(Debug, Clone, PartialEq, Default)]
+    pub struct PlaylistQueueTracks {
+        pub tracks: Vec<PlaylistTrackSource>,
+    }
+
+    impl From<PlaylistQueueTracks> for protobuf::QueueTracks {
+        fn from(value: PlaylistQueueTracks) -> Self {
+            Self {
+                tracks: value.tracks.into_iter().map(Into::into).collect(),
+            }
+        }
+    }
+
+    impl TryFrom<protobuf::QueueTracks> for PlaylistQueueTracks {
+        type Error = anyhow::Error;
+
+        fn try_from(value: protobuf::QueueTracks) -> Result<Self, Self::Error> {
+            let tracks = value
+                .tracks
+                .into_iter()
+                .map(|v| PlaylistTrackSource::try_from(v).context("QueueTracks.tracks"))
+                .collect::<Result<Vec<_>, anyhow::Error>>()?;
+
+            Ok(Self { tracks })
+        }
+    }
+
+    /// Data for requesting a track to be removed from the "play next" queue
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct PlaylistQueueRemoveTrack {
+        pub at_index: u64,
+        pub id: PlaylistTrackSource,
+    }
+
+    impl From<PlaylistQueueRemoveTrack> for protobuf::QueueRemoveTrack {
+        fn from(value: PlaylistQueueRemoveTrack) -> Self {
+            Self {
+                at_index: value.at_index,
+                id: Some(value.id.into()),
+            }
+        }
+    }
+
+    impl TryFrom<protobuf::QueueRemoveTrack> for PlaylistQueueRemoveTrack {
+        type Error = anyhow::Error;
+
+        fn try_from(value: protobuf::QueueRemoveTrack) -> Result<Self, Self::Error> {
+            Ok(Self {
+                at_index: value.at_index,
+                id: unwrap_msg(value.id, "QueueRemoveTrack.id").and_then(|v| {
+                    PlaylistTrackSource::try_from(v).context("QueueRemoveTrack.id")
+                })?,
+            })
+        }
+    }
+
     /// Data for requesting to skip / play a specific track
     #[derive(Debug, Clone, PartialEq)]
     pub struct PlaylistPlaySpecific {
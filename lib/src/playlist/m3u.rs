@@ -1,29 +1,66 @@
-//! Extract urls from M3U playlist files
+//! Extract urls from M3U playlist files, and write extended M3U playlist files
 
 // TODO: resolve relative paths
 
-use super::PlaylistValue;
+use std::{fmt::Write as _, time::Duration};
+
+use super::{single_line, PlaylistEntry, PlaylistValue};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct M3UItem {
     pub url: PlaylistValue,
+    /// The title from a preceding `#EXTINF` line, if any
+    pub title: Option<String>,
+    /// The duration from a preceding `#EXTINF` line, if any and not unknown (`-1`)
+    pub duration: Option<Duration>,
+}
+
+/// The values of a `#EXTINF` line, as `(duration, title)`
+type ExtInf = (Option<Duration>, Option<String>);
+
+/// Parse the remainder of a `#EXTINF:` line, like `123,Artist - Title`.
+fn parse_extinf(remainder: &str) -> ExtInf {
+    let (duration, title) = remainder.split_once(',').unwrap_or((remainder, ""));
+
+    // the duration may be followed by attributes separated by spaces, like `tvg-id="..."`
+    let duration = duration
+        .split_whitespace()
+        .next()
+        .and_then(|v| v.parse::<f64>().ok())
+        .filter(|v| v.is_finite() && *v >= 0.0)
+        .map(Duration::from_secs_f64);
+    let title = Some(title.trim())
+        .filter(|v| !v.is_empty())
+        .map(ToString::to_string);
+
+    (duration, title)
 }
 
 /// M3U(8) is a de-facto standart (meaning there is no formal standard), where each line that does not start with `#` is a entry, separated by newlines
 ///
+/// Extended M3U `#EXTINF` lines are applied to the entry following them.
+///
 /// <https://en.wikipedia.org/wiki/M3U#File_format>
 pub fn decode(content: &str) -> Vec<M3UItem> {
     let lines = content.lines();
     let mut list = vec![];
+    let mut extinf: Option<ExtInf> = None;
     for line in lines {
         if line.is_empty() {
             continue;
         }
 
+        if let Some(remainder) = line.strip_prefix("#EXTINF:") {
+            extinf = Some(parse_extinf(remainder));
+            continue;
+        }
+
         if line.starts_with('#') {
             continue;
         }
 
+        let (duration, title) = extinf.take().unwrap_or_default();
+
         let mut p_value = match PlaylistValue::try_from_str(line) {
             Ok(v) => v,
             Err(err) => {
@@ -36,11 +73,35 @@ pub fn decode(content: &str) -> Vec<M3UItem> {
             continue;
         }
 
-        list.push(M3UItem { url: p_value });
+        list.push(M3UItem {
+            url: p_value,
+            title,
+            duration,
+        });
     }
     list
 }
 
+/// Encode the entries as a extended M3U playlist.
+///
+/// Entries without any title, artist or duration are written without a `#EXTINF` line.
+pub fn encode(entries: &[PlaylistEntry]) -> String {
+    let mut m3u = String::from("#EXTM3U\n");
+    for entry in entries {
+        let title = entry.display_title();
+        if title.is_some() || entry.duration.is_some() {
+            let duration = entry
+                .duration
+                .map_or(-1, |v| i64::try_from(v.as_secs()).unwrap_or(i64::MAX));
+            let _ = writeln!(m3u, "#EXTINF:{duration},{}", title.unwrap_or_default());
+        }
+
+        let _ = writeln!(m3u, "{}", single_line(&entry.location.to_string()));
+    }
+
+    m3u
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -67,4 +128,61 @@ https://somewhere.url/path";
             PlaylistValue::Url(Url::parse("https://somewhere.url/path").unwrap())
         );
     }
+
+    #[test]
+    fn should_parse_extinf() {
+        let playlist = r"#EXTM3U
+#EXTINF:123,Artist - Title
+/some/path.mp3
+#EXTINF:-1,
+/some/other.mp3
+/no/extinf.mp3";
+
+        let results = decode(playlist);
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].title.as_deref(), Some("Artist - Title"));
+        assert_eq!(results[0].duration, Some(Duration::from_secs(123)));
+        assert_eq!(results[1].title, None);
+        assert_eq!(results[1].duration, None);
+        assert_eq!(results[2].url, PlaylistValue::Path("/no/extinf.mp3".into()));
+        assert_eq!(results[2].title, None);
+    }
+
+    #[test]
+    fn should_round_trip() {
+        let entries = [
+            PlaylistEntry {
+                location: PlaylistValue::Path("relative/path.mp3".into()),
+                title: Some("Title".to_string()),
+                artist: Some("Artist".to_string()),
+                duration: Some(Duration::from_secs(200)),
+            },
+            PlaylistEntry {
+                location: PlaylistValue::Url(Url::parse("https://somewhere.url/radio").unwrap()),
+                title: Some("Multi\nLine".to_string()),
+                artist: None,
+                duration: None,
+            },
+            PlaylistEntry {
+                location: PlaylistValue::Path("/absolute/path.flac".into()),
+                title: None,
+                artist: None,
+                duration: None,
+            },
+        ];
+
+        let content = encode(&entries);
+        assert!(content.starts_with("#EXTM3U\n"));
+
+        let results = decode(&content);
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].url, entries[0].location);
+        assert_eq!(results[0].title.as_deref(), Some("Artist - Title"));
+        assert_eq!(results[0].duration, Some(Duration::from_secs(200)));
+        assert_eq!(results[1].url, entries[1].location);
+        assert_eq!(results[1].title.as_deref(), Some("Multi Line"));
+        assert_eq!(results[1].duration, None);
+        assert_eq!(results[2].url, entries[2].location);
+        assert_eq!(results[2].title, None);
+    }
 }
@@ -1,10 +1,13 @@
 //! This is a very simple url extractor for different kinds of playlist formats: M3U, PLS, ASX, XSPF
 //!
 //! It is not optimized yet and does create a lot of strings on the way.
+//!
+//! Writing is supported for M3U (extended), PLS and XSPF, see [`encode`].
 
 mod asx;
 mod m3u;
 mod pls;
+pub mod state;
 mod xspf;
 
 use std::{
@@ -12,9 +15,10 @@ use std::{
     fmt::Display,
     path::{Path, PathBuf},
     str::FromStr,
+    time::Duration,
 };
 
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use reqwest::Url;
 
 use crate::utils;
@@ -96,6 +100,125 @@ impl PlaylistValue {
     }
 }
 
+/// The playlist formats that can be written, see [`encode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::module_name_repetitions)]
+pub enum PlaylistFormat {
+    /// Extended M3U, with `#EXTINF` lines
+    M3U,
+    PLS,
+    XSPF,
+}
+
+impl PlaylistFormat {
+    /// Get the format from the extension of the given path (case-insensitive).
+    ///
+    /// Returns `None` if the path has no extension, or it is not a writable format.
+    #[must_use]
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+
+        match ext.as_str() {
+            "m3u" | "m3u8" => Some(Self::M3U),
+            "pls" => Some(Self::PLS),
+            "xspf" => Some(Self::XSPF),
+            _ => None,
+        }
+    }
+
+    /// Get the default extension for this format, without a leading `.`.
+    #[must_use]
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::M3U => "m3u",
+            Self::PLS => "pls",
+            Self::XSPF => "xspf",
+        }
+    }
+}
+
+/// Resolve the path a playlist should be exported to, it has to be a file in one of the `music_dirs`.
+///
+/// The extension of [`PlaylistFormat::M3U`] is appended, if `path` does not have the extension of a writable format.
+///
+/// # Errors
+///
+/// - if `path` does not end with a file name, or the file name is hidden
+/// - if the directory of `path` cannot be resolved, for example because it does not exist
+/// - if the directory of `path` is not in one of the `music_dirs`
+pub fn resolve_export_path(path: &Path, music_dirs: &[PathBuf]) -> Result<PathBuf> {
+    let (Some(dir), Some(name)) = (path.parent(), path.file_name()) else {
+        bail!("\"{}\" is not a file path", path.display());
+    };
+    if name.to_string_lossy().starts_with('.') {
+        bail!("Playlist file name cannot start with a \".\"");
+    }
+
+    let dir = dir
+        .canonicalize()
+        .with_context(|| format!("resolve \"{}\"", dir.display()))?;
+    let in_music_dir = music_dirs.iter().any(|music_dir| {
+        shellexpand::path::tilde(music_dir)
+            .canonicalize()
+            .is_ok_and(|music_dir| dir.starts_with(music_dir))
+    });
+    if !in_music_dir {
+        bail!("\"{}\" is not in a music directory", dir.display());
+    }
+
+    let mut name = name.to_os_string();
+    if PlaylistFormat::from_path(Path::new(&name)).is_none() {
+        name.push(".");
+        name.push(PlaylistFormat::M3U.extension());
+    }
+
+    Ok(dir.join(name))
+}
+
+/// A single entry to write into a playlist, see [`encode`].
+#[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::module_name_repetitions)]
+pub struct PlaylistEntry {
+    pub location: PlaylistValue,
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub duration: Option<Duration>,
+}
+
+impl PlaylistEntry {
+    /// Get a single-line display title in the common `Artist - Title` form, if there is any of both.
+    fn display_title(&self) -> Option<String> {
+        let title = match (self.artist.as_deref(), self.title.as_deref()) {
+            (Some(artist), Some(title)) => format!("{artist} - {title}"),
+            (Some(v), None) | (None, Some(v)) => v.to_string(),
+            (None, None) => return None,
+        };
+
+        Some(single_line(&title).into_owned())
+    }
+}
+
+/// Replace all line breaks in `val` with spaces, as most formats are line-based.
+fn single_line(val: &str) -> Cow<'_, str> {
+    if val.contains(['\n', '\r']) {
+        return Cow::Owned(val.replace(['\n', '\r'], " "));
+    }
+
+    Cow::Borrowed(val)
+}
+
+/// Encode the given entries as a playlist of the given format.
+///
+/// Locations are written as-is, so paths should already be relative to the playlist's directory where wanted.
+#[must_use]
+pub fn encode(format: PlaylistFormat, entries: &[PlaylistEntry]) -> String {
+    match format {
+        PlaylistFormat::M3U => m3u::encode(entries),
+        PlaylistFormat::PLS => pls::encode(entries),
+        PlaylistFormat::XSPF => xspf::encode(entries),
+    }
+}
+
 /// Decode playlist content string. It checks for M3U, PLS, XSPF and ASX content in the string.
 ///
 /// Returns the parsed entries from the playlist, in playlist order.
@@ -213,6 +336,59 @@ Title1=mytitle
         );
     }
 
+    #[test]
+    fn should_get_format_from_path() {
+        assert_eq!(
+            PlaylistFormat::from_path(Path::new("/a/list.m3u")),
+            Some(PlaylistFormat::M3U)
+        );
+        assert_eq!(
+            PlaylistFormat::from_path(Path::new("/a/list.M3U8")),
+            Some(PlaylistFormat::M3U)
+        );
+        assert_eq!(
+            PlaylistFormat::from_path(Path::new("list.pls")),
+            Some(PlaylistFormat::PLS)
+        );
+        assert_eq!(
+            PlaylistFormat::from_path(Path::new("list.xspf")),
+            Some(PlaylistFormat::XSPF)
+        );
+        assert_eq!(PlaylistFormat::from_path(Path::new("list.asx")), None);
+        assert_eq!(PlaylistFormat::from_path(Path::new("list")), None);
+    }
+
+    #[test]
+    fn should_resolve_export_path() {
+        let music_dir =
+            std::env::temp_dir().join(format!("termusic-export-path-{}", std::process::id()));
+        let album = music_dir.join("album");
+        std::fs::create_dir_all(&album).unwrap();
+        let music_dirs = [music_dir.clone()];
+        let album = album.canonicalize().unwrap();
+
+        assert_eq!(
+            resolve_export_path(&music_dir.join("album/list.xspf"), &music_dirs).unwrap(),
+            album.join("list.xspf")
+        );
+        assert_eq!(
+            resolve_export_path(&music_dir.join("album/list"), &music_dirs).unwrap(),
+            album.join("list.m3u")
+        );
+
+        // outside of the music directories
+        assert!(resolve_export_path(&music_dir.join("../list.m3u"), &music_dirs).is_err());
+        assert!(resolve_export_path(&music_dir.join("album/../../list.m3u"), &music_dirs).is_err());
+        assert!(resolve_export_path(Path::new("/list.m3u"), &music_dirs).is_err());
+        // not a file name
+        assert!(resolve_export_path(&music_dir.join("album/.."), &music_dirs).is_err());
+        assert!(resolve_export_path(&music_dir.join("album/.list.m3u"), &music_dirs).is_err());
+        // directory does not exist
+        assert!(resolve_export_path(&music_dir.join("other/list.m3u"), &music_dirs).is_err());
+
+        std::fs::remove_dir_all(&music_dir).unwrap();
+    }
+
     #[test]
     fn should_parse_m3u() {
         let playlist = "/some/absolute/unix/path.mp3";
@@ -1,8 +1,11 @@
-//! Decode File and Title parts from simple playlist PLS files
+//! Decode File and Title parts from simple playlist PLS files, and write PLS files
 
-use std::collections::{hash_map::Entry, HashMap};
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    fmt::Write as _,
+};
 
-use super::PlaylistValue;
+use super::{single_line, PlaylistEntry, PlaylistValue};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct PLSItem {
@@ -155,6 +158,31 @@ pub fn decode(content: &str) -> Vec<PLSItem> {
     list.into_iter().map(|v| v.1).collect()
 }
 
+/// Encode the entries as a PLS (version 2) playlist.
+///
+/// The `Length` of entries without a duration is written as `-1` (unknown).
+pub fn encode(entries: &[PlaylistEntry]) -> String {
+    let mut pls = String::from("[playlist]\n");
+    for (num, entry) in (1..).zip(entries) {
+        let _ = writeln!(
+            pls,
+            "File{num}={}",
+            single_line(&entry.location.to_string())
+        );
+        if let Some(title) = entry.display_title() {
+            let _ = writeln!(pls, "Title{num}={title}");
+        }
+        let length = entry
+            .duration
+            .map_or(-1, |v| i64::try_from(v.as_secs()).unwrap_or(i64::MAX));
+        let _ = writeln!(pls, "Length{num}={length}");
+    }
+    let _ = writeln!(pls, "NumberOfEntries={}", entries.len());
+    pls.push_str("Version=2\n");
+
+    pls
+}
+
 /// Parse a Entry id from the start of the value until the first `=`.
 ///
 /// Returns the parsed number and the remainder after the first `=`.
@@ -205,6 +233,8 @@ fn is_comment(val: &str) -> bool {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use super::*;
     use pretty_assertions::assert_eq;
     use reqwest::Url;
@@ -242,6 +272,36 @@ File3=http://c.mp3",
         );
     }
 
+    #[test]
+    fn should_round_trip() {
+        let entries = [
+            PlaylistEntry {
+                location: PlaylistValue::Path("relative/path.mp3".into()),
+                title: Some("Title".to_string()),
+                artist: Some("Artist".to_string()),
+                duration: Some(Duration::from_secs(200)),
+            },
+            PlaylistEntry {
+                location: PlaylistValue::Url(Url::parse("https://somewhere.url/radio").unwrap()),
+                title: None,
+                artist: None,
+                duration: None,
+            },
+        ];
+
+        let content = encode(&entries);
+        assert!(content.contains("Length1=200\n"));
+        assert!(content.contains("Length2=-1\n"));
+        assert!(content.contains("NumberOfEntries=2\n"));
+
+        let items = decode(&content);
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].url, entries[0].location);
+        assert_eq!(items[0].title.as_deref(), Some("Artist - Title"));
+        assert_eq!(items[1].url, entries[1].location);
+        assert_eq!(items[1].title, None);
+    }
+
     #[test]
     fn anycase_playlist_header() {
         let items = decode(
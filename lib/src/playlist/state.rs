@@ -0,0 +1,324 @@
+//! The versioned state file for the current playlist and named playlists.
+//!
+//! Unlike the old line-based format (`playlist.log`), each entry stores its source type explicitly,
+//! a id that is stable across saves and the metadata of local files, so that tags do not need to be re-read on every load.
+
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, Result};
+use lofty::file::FileType;
+use serde::{Deserialize, Serialize};
+
+use crate::player::playlist_helpers::PlaylistTrackSource;
+use crate::track::{MediaTypes, Track, TrackMetadata};
+
+/// The extension of playlist state files
+pub const FILE_EXTENSION: &str = "toml";
+/// The extension of the old line-based playlist files, which are migrated on load
+pub const LEGACY_FILE_EXTENSION: &str = "log";
+
+/// The type used by the application / the latest state version
+type ApplicationType = PlaylistState;
+
+/// Enum that contains all versions of the playlist state file
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "version")]
+pub enum PlaylistStateVersioned<'a> {
+    // Cow data so that we can use a reference for saving instead of cloning
+    #[serde(rename = "1")]
+    V1(Cow<'a, PlaylistState>),
+}
+
+impl PlaylistStateVersioned<'_> {
+    /// Convert Into the type used by the application, instead of what is parsed
+    ///
+    /// Will convert any version into the latest
+    #[must_use]
+    pub fn into_state(self) -> ApplicationType {
+        match self {
+            PlaylistStateVersioned::V1(v) => v.into_owned(),
+        }
+    }
+}
+
+/// The state of a playlist, as stored in the file
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct PlaylistState {
+    /// Index into `entries` of the current track
+    #[serde(default)]
+    pub current_track_index: usize,
+    /// The name of the named playlist the tracks were loaded from, only set for the current playlist
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The position in the current track in seconds, only set for named playlists
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<f64>,
+    /// The id the next new entry will get, ids are never re-used in the same file
+    #[serde(default)]
+    pub next_id: u64,
+    #[serde(default, rename = "entry")]
+    pub entries: Vec<PlaylistStateEntry>,
+}
+
+/// A single track in the playlist state
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct PlaylistStateEntry {
+    /// The id of this entry, stable across saves and unique in the file
+    pub id: u64,
+    #[serde(flatten)]
+    pub source: PlaylistStateSource,
+    /// The metadata of a local file at the time it was saved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<CachedMetadata>,
+}
+
+/// The explicit source of a entry
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PlaylistStateSource {
+    Path { path: PathBuf },
+    Radio { url: String },
+    Podcast { url: String },
+}
+
+impl From<PlaylistTrackSource> for PlaylistStateSource {
+    fn from(value: PlaylistTrackSource) -> Self {
+        match value {
+            PlaylistTrackSource::Path(path) => Self::Path { path: path.into() },
+            PlaylistTrackSource::Url(url) => Self::Radio { url },
+            PlaylistTrackSource::PodcastUrl(url) => Self::Podcast { url },
+        }
+    }
+}
+
+impl From<PlaylistStateSource> for PlaylistTrackSource {
+    fn from(value: PlaylistStateSource) -> Self {
+        match value {
+            PlaylistStateSource::Path { path } => Self::Path(path.to_string_lossy().to_string()),
+            PlaylistStateSource::Radio { url } => Self::Url(url),
+            PlaylistStateSource::Podcast { url } => Self::PodcastUrl(url),
+        }
+    }
+}
+
+/// The metadata of a local file, to not have to read the tags again if the file did not change
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct CachedMetadata {
+    /// The modification time of the file in milliseconds since the unix epoch, used to detect changes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub artist: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub album: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub album_artist: Option<String>,
+    /// Duration in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub track_number: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disc_number: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rating: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub play_count: Option<u64>,
+}
+
+impl CachedMetadata {
+    /// Get the metadata to cache from a local file track, `None` for other types.
+    #[must_use]
+    pub fn from_track(track: &Track) -> Option<Self> {
+        let MediaTypes::Track(track_data) = track.inner() else {
+            return None;
+        };
+
+        Some(Self {
+            modified: modified_millis(track_data.path()),
+            title: track.title().map(ToString::to_string),
+            artist: track.artist().map(ToString::to_string),
+            album: track_data.album().map(ToString::to_string),
+            album_artist: track_data.album_artist().map(ToString::to_string),
+            duration: track.duration().map(|v| v.as_secs_f64()),
+            track_number: track_data.track_number(),
+            disc_number: track_data.disc_number(),
+            rating: track_data.rating(),
+            play_count: track_data.play_count(),
+        })
+    }
+
+    /// Check whether the cached metadata can still be used for the file at `path`.
+    ///
+    /// Files whose modification time cannot be read (like ones on a currently unmounted drive) are considered unchanged:
+    /// they are kept in the playlist either way, and reading their tags would fail too, so the cached metadata is the best there is.
+    #[must_use]
+    pub fn is_current(&self, path: &Path) -> bool {
+        match modified_millis(path) {
+            Some(modified) => self.modified == Some(modified),
+            None => true,
+        }
+    }
+
+    /// Create a [`Track`] for `path` from the cached metadata, without reading the file.
+    #[must_use]
+    pub fn into_track(self, path: PathBuf) -> Track {
+        let metadata = TrackMetadata {
+            title: self.title,
+            artist: self.artist,
+            album: self.album,
+            album_artist: self.album_artist,
+            duration: self
+                .duration
+                .and_then(|v| Duration::try_from_secs_f64(v).ok()),
+            track_number: self.track_number,
+            disc_number: self.disc_number,
+            rating: self.rating,
+            play_count: self.play_count,
+            file_type: FileType::from_path(&path),
+            ..Default::default()
+        };
+
+        Track::from_path_with_metadata(path, metadata)
+    }
+}
+
+/// Get the modification time of `path` in milliseconds since the unix epoch, if available.
+fn modified_millis(path: &Path) -> Option<u64> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    let millis = modified
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .ok()?
+        .as_millis();
+
+    u64::try_from(millis).ok()
+}
+
+/// Parse a playlist state file's content.
+///
+/// # Errors
+///
+/// If the content is not a valid state file of any version
+pub fn decode(content: &str) -> Result<PlaylistState> {
+    let data: PlaylistStateVersioned<'_> =
+        toml::from_str(content).context("parse playlist state")?;
+
+    Ok(data.into_state())
+}
+
+/// Encode a playlist state into the latest version of the file format.
+///
+/// # Errors
+///
+/// If serializing fails
+pub fn encode(state: &PlaylistState) -> Result<String> {
+    // wrap the data in the latest version for saving
+    let data = PlaylistStateVersioned::V1(Cow::Borrowed(state));
+
+    Ok(toml::to_string(&data)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_round_trip() {
+        let state = PlaylistState {
+            current_track_index: 1,
+            name: Some("Some Name".to_string()),
+            position: Some(12.5),
+            next_id: 5,
+            entries: vec![
+                PlaylistStateEntry {
+                    id: 0,
+                    source: PlaylistStateSource::Path {
+                        path: "/some/file.mp3".into(),
+                    },
+                    metadata: Some(CachedMetadata {
+                        modified: Some(1_700_000_000_000),
+                        title: Some("Title".to_string()),
+                        duration: Some(200.0),
+                        track_number: Some(3),
+                        ..Default::default()
+                    }),
+                },
+                PlaylistStateEntry {
+                    id: 4,
+                    source: PlaylistStateSource::Radio {
+                        url: "https://somewhere.url/radio".to_string(),
+                    },
+                    metadata: None,
+                },
+                PlaylistStateEntry {
+                    id: 2,
+                    source: PlaylistStateSource::Podcast {
+                        url: "https://somewhere.url/episode.mp3".to_string(),
+                    },
+                    metadata: None,
+                },
+            ],
+        };
+
+        let content = encode(&state).unwrap();
+        assert!(content.starts_with("version = \"1\"\n"));
+        assert!(content.contains("[[entry]]"));
+        assert_eq!(decode(&content).unwrap(), state);
+    }
+
+    #[test]
+    fn should_decode_minimal() {
+        let content = r#"version = "1"
+
+[[entry]]
+id = 0
+type = "radio"
+url = "https://somewhere.url/radio"
+"#;
+
+        let state = decode(content).unwrap();
+        assert_eq!(state.current_track_index, 0);
+        assert_eq!(state.entries.len(), 1);
+        assert_eq!(
+            PlaylistTrackSource::from(state.entries[0].source.clone()),
+            PlaylistTrackSource::Url("https://somewhere.url/radio".to_string())
+        );
+    }
+
+    #[test]
+    fn should_error_on_unknown_version() {
+        assert!(decode("version = \"999\"\n").is_err());
+    }
+
+    #[test]
+    fn should_check_cached_metadata_is_current() {
+        let dir = std::env::temp_dir().join(format!("termusic-state-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("track.mp3");
+        std::fs::write(&path, "").unwrap();
+
+        let current = CachedMetadata {
+            modified: modified_millis(&path),
+            ..Default::default()
+        };
+        assert!(current.modified.is_some());
+        assert!(current.is_current(&path));
+
+        let stale = CachedMetadata {
+            modified: current.modified.map(|v| v - 1000),
+            ..Default::default()
+        };
+        assert!(!stale.is_current(&path));
+        let unknown = CachedMetadata::default();
+        assert!(!unknown.is_current(&path));
+
+        // missing files keep their metadata
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(stale.is_current(&path));
+    }
+}
@@ -1,9 +1,13 @@
+use std::borrow::Cow;
+use std::fmt::Write as _;
+
 use anyhow::Result;
-use quick_xml::escape::unescape;
+use quick_xml::escape::{escape, unescape};
 use quick_xml::events::Event;
 use quick_xml::Reader;
+use reqwest::Url;
 
-use super::PlaylistValue;
+use super::{PlaylistEntry, PlaylistValue};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct XSPFItem {
@@ -107,11 +111,53 @@ pub fn decode(content: &str) -> Result<Vec<XSPFItem>> {
     Ok(list)
 }
 
+/// Get the XSPF `location` for the given value.
+///
+/// The spec requires a URI, so absolute paths are converted to `file://` urls, relative paths are kept as-is.
+fn location_uri(value: &PlaylistValue) -> Cow<'_, str> {
+    match value {
+        PlaylistValue::Url(url) => Cow::Borrowed(url.as_str()),
+        PlaylistValue::Path(path) => Url::from_file_path(path).map_or_else(
+            |()| path.to_string_lossy(),
+            |url| Cow::Owned(url.to_string()),
+        ),
+    }
+}
+
+/// Encode the entries as a XSPF playlist.
+pub fn encode(entries: &[PlaylistEntry]) -> String {
+    let mut xspf = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<playlist version=\"1\" xmlns=\"http://xspf.org/ns/0/\">\n  <trackList>\n",
+    );
+    for entry in entries {
+        xspf.push_str("    <track>\n");
+        let _ = writeln!(
+            xspf,
+            "      <location>{}</location>",
+            escape(location_uri(&entry.location))
+        );
+        if let Some(title) = &entry.title {
+            let _ = writeln!(xspf, "      <title>{}</title>", escape(title));
+        }
+        if let Some(artist) = &entry.artist {
+            let _ = writeln!(xspf, "      <creator>{}</creator>", escape(artist));
+        }
+        if let Some(duration) = entry.duration {
+            let _ = writeln!(xspf, "      <duration>{}</duration>", duration.as_millis());
+        }
+        xspf.push_str("    </track>\n");
+    }
+    xspf.push_str("  </trackList>\n</playlist>\n");
+
+    xspf
+}
+
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use super::*;
     use pretty_assertions::assert_eq;
-    use reqwest::Url;
 
     #[test]
     fn xspf() {
@@ -172,4 +218,34 @@ mod tests {
         let items = decode(s).unwrap();
         assert_eq!(items.len(), 2);
     }
+    #[test]
+    fn should_round_trip() {
+        let entries = [
+            PlaylistEntry {
+                location: PlaylistValue::Path("/absolute/some path & more.mp3".into()),
+                title: Some("<Title> & \"more\"".to_string()),
+                artist: Some("Artist".to_string()),
+                duration: Some(Duration::from_millis(200_500)),
+            },
+            PlaylistEntry {
+                location: PlaylistValue::Url(
+                    Url::parse("https://somewhere.url/radio?a=1&b=2").unwrap(),
+                ),
+                title: None,
+                artist: None,
+                duration: None,
+            },
+        ];
+
+        let content = encode(&entries);
+        assert!(content.contains("<creator>Artist</creator>"));
+        assert!(content.contains("<duration>200500</duration>"));
+
+        let items = decode(&content).unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].location, entries[0].location);
+        assert_eq!(items[0].title, entries[0].title);
+        assert_eq!(items[1].location, entries[1].location);
+        assert_eq!(items[1].title, None);
+    }
 }
@@ -0,0 +1,332 @@
+//! Editing the common tags of many files at once.
+//!
+//! Fields which differ between the files are shown as [`KEEP`], which leaves them unchanged on every file.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use lofty::config::ParseOptions;
+use lofty::file::TaggedFileExt;
+use lofty::prelude::Accessor;
+use lofty::probe::Probe;
+use lofty::tag::ItemKey;
+
+use super::edit::{edit_tag, TagEdit};
+
+/// The value shown for a field which differs between the files, keeping the value of every file
+pub const KEEP: &str = "<keep>";
+
+/// The fields of a file which can be changed in batches
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchField {
+    Artist,
+    AlbumArtist,
+    Title,
+    Album,
+    Genre,
+    Year,
+    Track,
+    Disc,
+}
+
+impl BatchField {
+    /// The fields which can be edited for all files at once in the tag editor
+    pub const ALL: [Self; 4] = [Self::Artist, Self::Title, Self::Album, Self::Genre];
+
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Artist => "Artist",
+            Self::AlbumArtist => "Album Artist",
+            Self::Title => "Title",
+            Self::Album => "Album",
+            Self::Genre => "Genre",
+            Self::Year => "Year",
+            Self::Track => "Track",
+            Self::Disc => "Disc",
+        }
+    }
+
+    /// Whether the field only holds a number
+    #[must_use]
+    pub fn is_number(self) -> bool {
+        matches!(self, Self::Year | Self::Track | Self::Disc)
+    }
+
+    /// Set `value` on `tag`, or remove the field if it is [`None`].
+    ///
+    /// Values of number fields which are not a number are ignored.
+    fn apply_to(self, tag: &mut dyn TagEdit, value: Option<&str>) {
+        let number = value.and_then(|v| v.parse::<u32>().ok());
+        match (self, value) {
+            (Self::Artist, Some(v)) => tag.set_artist(v.to_string()),
+            (Self::Artist, None) => tag.remove_artist(),
+            (Self::AlbumArtist, Some(v)) => tag.insert_text(ItemKey::AlbumArtist, v.to_string()),
+            (Self::AlbumArtist, None) => tag.remove_text(&ItemKey::AlbumArtist),
+            (Self::Title, Some(v)) => tag.set_title(v.to_string()),
+            (Self::Title, None) => tag.remove_title(),
+            (Self::Album, Some(v)) => tag.set_album(v.to_string()),
+            (Self::Album, None) => tag.remove_album(),
+            (Self::Genre, Some(v)) => tag.set_genre(v.to_string()),
+            (Self::Genre, None) => tag.remove_genre(),
+            (Self::Year, None) => tag.remove_year(),
+            (Self::Track, None) => tag.remove_track(),
+            (Self::Disc, None) => tag.remove_disk(),
+            (Self::Year, Some(_)) => {
+                if let Some(v) = number {
+                    tag.set_year(v);
+                }
+            }
+            (Self::Track, Some(_)) => {
+                if let Some(v) = number {
+                    tag.set_track(v);
+                }
+            }
+            (Self::Disc, Some(_)) => {
+                if let Some(v) = number {
+                    tag.set_disk(v);
+                }
+            }
+        }
+    }
+}
+
+/// The current values of the fields of one file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BatchFile {
+    pub path: PathBuf,
+    pub artist: Option<String>,
+    pub album_artist: Option<String>,
+    pub title: Option<String>,
+    pub album: Option<String>,
+    pub genre: Option<String>,
+    pub year: Option<String>,
+    pub track: Option<String>,
+    pub disc: Option<String>,
+}
+
+impl BatchFile {
+    /// Read the current values of the file at `path`
+    ///
+    /// # Errors
+    ///
+    /// If the file cannot be read as a audio file
+    pub fn read(path: &Path) -> Result<Self> {
+        let tagged_file = Probe::open(path)
+            .and_then(|v| v.options(ParseOptions::new().read_cover_art(false)).read())
+            .context("read tags")?;
+
+        let mut file = Self {
+            path: path.to_path_buf(),
+            ..Default::default()
+        };
+        if let Some(tag) = tagged_file
+            .primary_tag()
+            .or_else(|| tagged_file.first_tag())
+        {
+            file.artist = tag.artist().map(|v| v.to_string());
+            file.album_artist = tag.get_string(&ItemKey::AlbumArtist).map(ToOwned::to_owned);
+            file.title = tag.title().map(|v| v.to_string());
+            file.album = tag.album().map(|v| v.to_string());
+            file.genre = tag.genre().map(|v| v.to_string());
+            file.year = tag.year().map(|v| v.to_string());
+            file.track = tag.track().map(|v| v.to_string());
+            file.disc = tag.disk().map(|v| v.to_string());
+        }
+
+        Ok(file)
+    }
+
+    #[must_use]
+    pub fn get(&self, field: BatchField) -> Option<&str> {
+        match field {
+            BatchField::Artist => self.artist.as_deref(),
+            BatchField::AlbumArtist => self.album_artist.as_deref(),
+            BatchField::Title => self.title.as_deref(),
+            BatchField::Album => self.album.as_deref(),
+            BatchField::Genre => self.genre.as_deref(),
+            BatchField::Year => self.year.as_deref(),
+            BatchField::Track => self.track.as_deref(),
+            BatchField::Disc => self.disc.as_deref(),
+        }
+    }
+}
+
+/// Get the value of `field` if it is the same for all `files`, otherwise [`KEEP`]
+#[must_use]
+pub fn common_value(files: &[BatchFile], field: BatchField) -> String {
+    let mut values = files.iter().map(|v| v.get(field).unwrap_or_default());
+    let Some(first) = values.next() else {
+        return String::new();
+    };
+
+    if values.all(|v| v == first) {
+        first.to_string()
+    } else {
+        KEEP.to_string()
+    }
+}
+
+/// What to do with a field on all files
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldEdit {
+    /// Leave the field as it is on every file
+    Keep,
+    /// Set the field to the value on every file
+    Set(String),
+    /// Remove the field from every file
+    Remove,
+}
+
+impl FieldEdit {
+    /// Get the edit from the value of a input field, which is [`KEEP`] or the value for all files, empty to remove it
+    #[must_use]
+    pub fn from_input(input: &str) -> Self {
+        let input = input.trim();
+        if input == KEEP {
+            Self::Keep
+        } else if input.is_empty() {
+            Self::Remove
+        } else {
+            Self::Set(input.to_string())
+        }
+    }
+}
+
+/// A field of a file which would change
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchChange {
+    pub path: PathBuf,
+    pub field: BatchField,
+    pub current: Option<String>,
+    /// The new value, or [`None`] to remove the field
+    pub new: Option<String>,
+}
+
+/// Get all changes `edits` would make to `files`, fields which already have the value are skipped
+#[must_use]
+pub fn preview(files: &[BatchFile], edits: &[(BatchField, FieldEdit)]) -> Vec<BatchChange> {
+    let mut changes = Vec::new();
+    for file in files {
+        for (field, edit) in edits {
+            let new = match edit {
+                FieldEdit::Keep => continue,
+                FieldEdit::Set(v) => Some(v.as_str()),
+                FieldEdit::Remove => None,
+            };
+            let current = file.get(*field);
+            if current == new {
+                continue;
+            }
+
+            changes.push(BatchChange {
+                path: file.path.clone(),
+                field: *field,
+                current: current.map(ToOwned::to_owned),
+                new: new.map(ToOwned::to_owned),
+            });
+        }
+    }
+
+    changes
+}
+
+/// Write `changes` of a single file to `path`
+fn write_file(path: &Path, changes: &[&BatchChange]) -> Result<()> {
+    edit_tag(path, |tag| {
+        for change in changes {
+            change.field.apply_to(tag, change.new.as_deref());
+        }
+        Ok(true)
+    })?;
+
+    Ok(())
+}
+
+/// Write all `changes`, each file once, returning the files that failed
+#[must_use]
+pub fn write_changes(changes: &[BatchChange]) -> Vec<(PathBuf, anyhow::Error)> {
+    let mut paths: Vec<&Path> = Vec::new();
+    for change in changes {
+        if !paths.contains(&change.path.as_path()) {
+            paths.push(&change.path);
+        }
+    }
+
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let file_changes: Vec<&BatchChange> =
+                changes.iter().filter(|v| v.path == path).collect();
+            write_file(path, &file_changes)
+                .err()
+                .map(|err| (path.to_path_buf(), err))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{common_value, preview, BatchField, BatchFile, FieldEdit, KEEP};
+
+    fn file(name: &str, artist: &str, album: Option<&str>) -> BatchFile {
+        BatchFile {
+            path: PathBuf::from(name),
+            artist: Some(artist.to_string()),
+            title: Some(name.to_string()),
+            album: album.map(ToOwned::to_owned),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn should_get_common_values() {
+        let files = [
+            file("a", "Artist", Some("Album")),
+            file("b", "Artist", None),
+        ];
+
+        assert_eq!(common_value(&files, BatchField::Artist), "Artist");
+        assert_eq!(common_value(&files, BatchField::Title), KEEP);
+        assert_eq!(common_value(&files, BatchField::Album), KEEP);
+        assert_eq!(common_value(&files, BatchField::Genre), "");
+        assert_eq!(common_value(&[], BatchField::Genre), "");
+    }
+
+    #[test]
+    fn should_parse_input() {
+        assert_eq!(FieldEdit::from_input(KEEP), FieldEdit::Keep);
+        assert_eq!(FieldEdit::from_input(" <keep> "), FieldEdit::Keep);
+        assert_eq!(FieldEdit::from_input(" "), FieldEdit::Remove);
+        assert_eq!(
+            FieldEdit::from_input("Rock "),
+            FieldEdit::Set("Rock".to_string())
+        );
+    }
+
+    #[test]
+    fn should_only_preview_changes() {
+        let files = [file("a", "Artist", Some("Album")), file("b", "Other", None)];
+        let edits = [
+            (BatchField::Artist, FieldEdit::Set("Artist".to_string())),
+            (BatchField::Title, FieldEdit::Keep),
+            (BatchField::Album, FieldEdit::Remove),
+        ];
+
+        let changes = preview(&files, &edits);
+        let changes: Vec<(&str, BatchField, Option<&str>)> = changes
+            .iter()
+            .map(|v| (v.path.to_str().unwrap(), v.field, v.new.as_deref()))
+            .collect();
+
+        assert_eq!(
+            changes,
+            [
+                ("a", BatchField::Album, None),
+                ("b", BatchField::Artist, Some("Artist")),
+            ]
+        );
+    }
+}
@@ -0,0 +1,434 @@
+//! SPDX-License-Identifier: MIT
+//!
+//! Embedded cover art: embed image files as front or back cover, extract, remove and shrink oversized pictures.
+
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::{ImageFormat, ImageReader};
+use lofty::config::ParseOptions;
+use lofty::file::TaggedFileExt;
+use lofty::picture::{MimeType, Picture, PictureType};
+use lofty::probe::Probe;
+
+use super::edit::{edit_tag, TagEdit};
+
+/// Largest width or height of a embedded picture, larger ones are scaled down
+pub const MAX_DIMENSION: u32 = 1200;
+/// Pictures larger than this many bytes are re-compressed, even if they are not too large in dimension
+pub const MAX_BYTES: usize = 1024 * 1024;
+/// Quality of re-compressed pictures
+const JPEG_QUALITY: u8 = 90;
+
+/// Image files to consider in a track's folder
+const IMAGE_EXTENSIONS: [&str; 3] = ["jpg", "jpeg", "png"];
+
+/// The covers which can be managed in the tag editor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoverKind {
+    Front,
+    Back,
+}
+
+impl CoverKind {
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Front => "front",
+            Self::Back => "back",
+        }
+    }
+
+    #[must_use]
+    pub fn pic_type(self) -> PictureType {
+        match self {
+            Self::Front => PictureType::CoverFront,
+            Self::Back => PictureType::CoverBack,
+        }
+    }
+
+    /// Whether a picture of `pic_type` is this cover.
+    ///
+    /// Pictures without a type ([`PictureType::Other`]), like the ones fetched online, are taken as front cover.
+    #[must_use]
+    pub fn matches(self, pic_type: PictureType) -> bool {
+        match self {
+            Self::Front => matches!(pic_type, PictureType::CoverFront | PictureType::Other),
+            Self::Back => pic_type == PictureType::CoverBack,
+        }
+    }
+
+    /// File stems of images in the folder which are this cover, in order of preference
+    fn folder_names(self) -> &'static [&'static str] {
+        match self {
+            Self::Front => &["cover", "folder", "front", "album"],
+            Self::Back => &["back"],
+        }
+    }
+}
+
+/// Find a image file for the cover `kind` in the folder of `track_path`.
+///
+/// Files are chosen by name like `cover.jpg` or `back.png`; for the front cover any other image is taken if none is named,
+/// except `artist` and `back` images.
+///
+/// # Errors
+///
+/// - if there is no parent in the given path
+/// - if reading the directory fails
+pub fn folder_image(track_path: &Path, kind: CoverKind) -> Result<Option<PathBuf>> {
+    let Some(parent) = track_path.parent() else {
+        bail!("Track does not have a parent directory");
+    };
+
+    let mut images: Vec<(String, PathBuf)> = std::fs::read_dir(parent)
+        .with_context(|| parent.display().to_string())?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|ext| IMAGE_EXTENSIONS.iter().any(|v| ext.eq_ignore_ascii_case(v)))
+        })
+        .filter_map(|path| {
+            let stem = path.file_stem()?.to_string_lossy().to_lowercase();
+            Some((stem, path))
+        })
+        .collect();
+    images.sort();
+
+    for name in kind.folder_names() {
+        if let Some((_, path)) = images.iter().find(|(stem, _)| stem == name) {
+            return Ok(Some(path.clone()));
+        }
+    }
+
+    if kind == CoverKind::Front {
+        // "artist" images may exist for standalone tracks which are in the same directory as the artist info,
+        // for example when using jellyfin
+        return Ok(images
+            .into_iter()
+            .find(|(stem, _)| {
+                stem != "artist" && !CoverKind::Back.folder_names().contains(&stem.as_str())
+            })
+            .map(|(_, path)| path));
+    }
+
+    Ok(None)
+}
+
+/// Read all pictures embedded in the file at `path`
+///
+/// # Errors
+///
+/// - if reading or parsing the file fails
+pub fn read_pictures(path: &Path) -> Result<Vec<Picture>> {
+    let tagged_file = Probe::open(path)
+        .context("open file")?
+        .options(ParseOptions::new().read_properties(false))
+        .read()
+        .context("read tags")?;
+
+    Ok(tagged_file
+        .primary_tag()
+        .or_else(|| tagged_file.first_tag())
+        .map(|tag| tag.pictures().to_vec())
+        .unwrap_or_default())
+}
+
+/// Find the picture for the cover `kind`, preferring the one with the exact type
+#[must_use]
+pub fn find(pictures: &[Picture], kind: CoverKind) -> Option<&Picture> {
+    pictures
+        .iter()
+        .find(|v| v.pic_type() == kind.pic_type())
+        .or_else(|| pictures.iter().find(|v| kind.matches(v.pic_type())))
+}
+
+/// Get the width and height of the image in `data`
+fn dimensions(data: &[u8]) -> Result<(u32, u32)> {
+    ImageReader::new(Cursor::new(data))
+        .with_guessed_format()
+        .context("guess image format")?
+        .into_dimensions()
+        .context("read image dimensions")
+}
+
+/// A short description of `picture`, like `600x600 JPEG, 80.0 KiB`
+#[must_use]
+pub fn describe(picture: &Picture) -> String {
+    let format = match picture.mime_type() {
+        Some(MimeType::Jpeg) => "JPEG",
+        Some(MimeType::Png) => "PNG",
+        Some(MimeType::Gif) => "GIF",
+        Some(MimeType::Bmp) => "BMP",
+        Some(MimeType::Tiff) => "TIFF",
+        _ => "image",
+    };
+    let size = bytesize::ByteSize(picture.data().len() as u64);
+
+    match dimensions(picture.data()) {
+        Ok((width, height)) => format!("{width}x{height} {format}, {size}"),
+        Err(_) => format!("{format}, {size}"),
+    }
+}
+
+/// Whether the image in `data` is larger than [`MAX_DIMENSION`] or [`MAX_BYTES`]
+fn is_oversized(data: &[u8]) -> Result<bool> {
+    let (width, height) = dimensions(data)?;
+    Ok(width > MAX_DIMENSION || height > MAX_DIMENSION || data.len() > MAX_BYTES)
+}
+
+/// Create a picture of `pic_type` from the image in `data`.
+///
+/// Oversized images are scaled down to [`MAX_DIMENSION`] and re-compressed as JPEG,
+/// formats other than JPEG and PNG are converted to JPEG, as not all players support them.
+///
+/// # Errors
+///
+/// - if the data is not a supported image
+pub fn prepare_image(data: Vec<u8>, pic_type: PictureType) -> Result<Picture> {
+    let format = image::guess_format(&data).context("guess image format")?;
+    let mime = match format {
+        ImageFormat::Jpeg => Some(MimeType::Jpeg),
+        ImageFormat::Png => Some(MimeType::Png),
+        _ => None,
+    };
+
+    let (mime, data) = match mime {
+        Some(mime) if !is_oversized(&data)? => (mime, data),
+        _ => {
+            let mut image =
+                image::load_from_memory_with_format(&data, format).context("decode image")?;
+            if image.width() > MAX_DIMENSION || image.height() > MAX_DIMENSION {
+                image = image.resize(MAX_DIMENSION, MAX_DIMENSION, FilterType::Lanczos3);
+            }
+
+            let mut encoded = Vec::new();
+            JpegEncoder::new_with_quality(&mut encoded, JPEG_QUALITY)
+                .encode_image(&image.to_rgb8())
+                .context("encode image")?;
+            (MimeType::Jpeg, encoded)
+        }
+    };
+
+    Ok(Picture::new_unchecked(pic_type, Some(mime), None, data))
+}
+
+/// Read the image file at `path` as cover `kind`, see [`prepare_image`]
+///
+/// # Errors
+///
+/// - if reading the file fails
+/// - if the file is not a supported image
+pub fn read_image(path: &Path, kind: CoverKind) -> Result<Picture> {
+    let data = std::fs::read(path).with_context(|| path.display().to_string())?;
+    prepare_image(data, kind.pic_type()).with_context(|| path.display().to_string())
+}
+
+/// Remove all pictures matching `kind` from `tag`, returning how many were removed
+fn remove_kind(tag: &mut dyn TagEdit, kind: CoverKind) -> usize {
+    let count = tag
+        .pictures()
+        .iter()
+        .filter(|v| kind.matches(v.pic_type()))
+        .count();
+    for pic_type in [
+        PictureType::Other,
+        PictureType::CoverFront,
+        PictureType::CoverBack,
+    ] {
+        if kind.matches(pic_type) {
+            tag.remove_pictures(pic_type);
+        }
+    }
+
+    count
+}
+
+/// Embed `picture` as cover `kind` in the file at `path`, replacing the current one
+///
+/// # Errors
+///
+/// - if reading or saving the tags fails
+pub fn embed(path: &Path, kind: CoverKind, picture: &Picture) -> Result<()> {
+    edit_tag(path, |tag| {
+        remove_kind(tag, kind);
+        tag.add_picture(picture.clone());
+        Ok(true)
+    })?;
+
+    Ok(())
+}
+
+/// Remove the cover `kind` from the file at `path`, or all pictures if [`None`].
+///
+/// Returns the number of removed pictures.
+///
+/// # Errors
+///
+/// - if reading or saving the tags fails
+pub fn remove(path: &Path, kind: Option<CoverKind>) -> Result<usize> {
+    let mut removed = 0;
+    edit_tag(path, |tag| {
+        removed = if let Some(kind) = kind {
+            remove_kind(tag, kind)
+        } else {
+            let pictures = tag.pictures();
+            for picture in &pictures {
+                tag.remove_pictures(picture.pic_type());
+            }
+            pictures.len()
+        };
+        Ok(removed > 0)
+    })?;
+
+    Ok(removed)
+}
+
+/// Scale down and re-compress all oversized pictures of the file at `path`, see [`prepare_image`].
+///
+/// Returns the number of changed pictures.
+///
+/// # Errors
+///
+/// - if reading or saving the tags fails
+/// - if a picture cannot be decoded
+pub fn shrink(path: &Path) -> Result<usize> {
+    let mut shrunk = 0;
+    edit_tag(path, |tag| {
+        let pictures = tag.pictures();
+        let mut changed = Vec::with_capacity(pictures.len());
+        for picture in &pictures {
+            if is_oversized(picture.data())? {
+                changed.push(prepare_image(picture.data().to_vec(), picture.pic_type())?);
+                shrunk += 1;
+            } else {
+                changed.push(picture.clone());
+            }
+        }
+        if shrunk == 0 {
+            return Ok(false);
+        }
+
+        for picture in &pictures {
+            tag.remove_pictures(picture.pic_type());
+        }
+        for picture in changed {
+            tag.add_picture(picture);
+        }
+        Ok(true)
+    })?;
+
+    Ok(shrunk)
+}
+
+/// Write the cover `kind` of the file at `path` to a image file like `cover.jpg` next to it.
+///
+/// Returns the path of the written file.
+///
+/// # Errors
+///
+/// - if the file has no such cover
+/// - if the image file already exists
+/// - if reading the tags or writing the image file fails
+pub fn extract(path: &Path, kind: CoverKind) -> Result<PathBuf> {
+    let pictures = read_pictures(path)?;
+    let Some(picture) = find(&pictures, kind) else {
+        bail!("There is no {} cover", kind.name());
+    };
+
+    let ext = match picture.mime_type() {
+        Some(MimeType::Png) => "png",
+        Some(MimeType::Gif) => "gif",
+        Some(MimeType::Bmp) => "bmp",
+        Some(MimeType::Tiff) => "tiff",
+        _ => "jpg",
+    };
+    let name = match kind {
+        CoverKind::Front => "cover",
+        CoverKind::Back => "back",
+    };
+    let target = path.with_file_name(format!("{name}.{ext}"));
+    if target.exists() {
+        bail!("\"{}\" already exists", target.display());
+    }
+
+    std::fs::write(&target, picture.data()).with_context(|| target.display().to_string())?;
+
+    Ok(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use std::path::Path;
+
+    use image::{ImageFormat, RgbImage};
+    use lofty::picture::{MimeType, PictureType};
+    use pretty_assertions::assert_eq;
+
+    use super::{dimensions, folder_image, prepare_image, CoverKind, MAX_DIMENSION};
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut data = Vec::new();
+        RgbImage::new(width, height)
+            .write_to(&mut Cursor::new(&mut data), ImageFormat::Png)
+            .unwrap();
+        data
+    }
+
+    #[test]
+    fn should_match_kinds() {
+        assert!(CoverKind::Front.matches(PictureType::CoverFront));
+        assert!(CoverKind::Front.matches(PictureType::Other));
+        assert!(!CoverKind::Front.matches(PictureType::CoverBack));
+        assert!(CoverKind::Back.matches(PictureType::CoverBack));
+        assert!(!CoverKind::Back.matches(PictureType::Other));
+    }
+
+    #[test]
+    fn should_keep_small_images() {
+        let data = png(300, 300);
+        let picture = prepare_image(data.clone(), PictureType::CoverFront).unwrap();
+
+        assert_eq!(picture.mime_type(), Some(&MimeType::Png));
+        assert_eq!(picture.data(), data.as_slice());
+        assert_eq!(picture.pic_type(), PictureType::CoverFront);
+    }
+
+    #[test]
+    fn should_shrink_large_images() {
+        let picture = prepare_image(png(MAX_DIMENSION * 2, 2), PictureType::CoverBack).unwrap();
+
+        assert_eq!(picture.mime_type(), Some(&MimeType::Jpeg));
+        assert_eq!(dimensions(picture.data()).unwrap(), (MAX_DIMENSION, 1));
+        assert_eq!(picture.pic_type(), PictureType::CoverBack);
+    }
+
+    #[test]
+    fn should_find_folder_images() {
+        let dir = std::env::temp_dir().join(format!("termusic-cover-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let track = dir.join("track.mp3");
+        for name in ["artist.jpg", "back.png", "scan.jpg", "notes.txt"] {
+            std::fs::write(dir.join(name), "").unwrap();
+        }
+
+        let name = |kind| {
+            folder_image(&track, kind)
+                .unwrap()
+                .map(|v| v.file_name().unwrap().to_string_lossy().to_string())
+        };
+        assert_eq!(name(CoverKind::Front).as_deref(), Some("scan.jpg"));
+        assert_eq!(name(CoverKind::Back).as_deref(), Some("back.png"));
+
+        std::fs::write(dir.join("Cover.JPG"), "").unwrap();
+        assert_eq!(name(CoverKind::Front).as_deref(), Some("Cover.JPG"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(folder_image(Path::new("/"), CoverKind::Front).is_err());
+    }
+}
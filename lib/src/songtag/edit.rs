@@ -0,0 +1,414 @@
+//! SPDX-License-Identifier: MIT
+//!
+//! Editing the existing tag of a file in place, keeping everything which is not changed.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use id3::frame::Lyrics;
+use lofty::config::{ParseOptions, WriteOptions};
+use lofty::file::{AudioFile, FileType, TaggedFileExt};
+use lofty::id3::v2::{Frame, FrameId, Id3v2Tag};
+use lofty::mpeg::MpegFile;
+use lofty::picture::{Picture, PictureType};
+use lofty::prelude::{Accessor, TagExt};
+use lofty::tag::{ItemKey, ItemValue, Tag, TagItem, TagType};
+
+use super::sylt;
+use crate::track::UNSYNCED_LYRICS_KEY;
+
+/// The editing functions of a tag which are not part of [`Accessor`], as they differ per tag type
+pub trait TagEdit: Accessor {
+    /// Set the text item `key` to `value`, replacing the current one
+    fn insert_text(&mut self, key: ItemKey, value: String);
+    /// Remove the item `key`
+    fn remove_text(&mut self, key: &ItemKey);
+    /// Get all embedded pictures
+    fn pictures(&self) -> Vec<Picture>;
+    /// Remove all pictures of `pic_type`
+    fn remove_pictures(&mut self, pic_type: PictureType);
+    /// Add `picture`, replacing a picture of the same type on tags which only allow one per type
+    fn add_picture(&mut self, picture: Picture);
+    /// Replace the text of the lyric frame (or item) read as `lyrics` with `text`, keeping its language and description
+    ///
+    /// Returns `false` if there is no such lyric frame.
+    fn replace_lyrics(&mut self, lyrics: &Lyrics, text: String) -> bool;
+}
+
+impl TagEdit for Tag {
+    fn insert_text(&mut self, key: ItemKey, value: String) {
+        Tag::insert_text(self, key, value);
+    }
+
+    fn remove_text(&mut self, key: &ItemKey) {
+        self.remove_key(key);
+    }
+
+    fn pictures(&self) -> Vec<Picture> {
+        Tag::pictures(self).to_vec()
+    }
+
+    fn remove_pictures(&mut self, pic_type: PictureType) {
+        self.remove_picture_type(pic_type);
+    }
+
+    fn add_picture(&mut self, picture: Picture) {
+        self.push_picture(picture);
+    }
+
+    fn replace_lyrics(&mut self, lyrics: &Lyrics, text: String) -> bool {
+        let is_lyrics = |item: &TagItem| {
+            item.lang().escape_ascii().to_string() == lyrics.lang
+                && item.description() == lyrics.description
+                && item.value().text() == Some(lyrics.text.as_str())
+        };
+
+        let mut replaced = false;
+        // some taggers write the same text to both keys, which is read as one lyric
+        for key in [
+            ItemKey::Lyrics,
+            ItemKey::Unknown(UNSYNCED_LYRICS_KEY.to_string()),
+        ] {
+            let items: Vec<TagItem> = self.take_filter(&key, is_lyrics).collect();
+            for item in items {
+                let mut new_item = TagItem::new(key.clone(), ItemValue::Text(text.clone()));
+                new_item.set_lang(*item.lang());
+                new_item.set_description(item.description().to_string());
+                self.push_unchecked(new_item);
+                replaced = true;
+            }
+        }
+
+        replaced
+    }
+}
+
+impl TagEdit for Id3v2Tag {
+    fn insert_text(&mut self, key: ItemKey, value: String) {
+        // let lofty pick the frame for the item, like `TPE2` or a `TXXX` with the right description
+        let mut tag = Tag::new(TagType::Id3v2);
+        tag.insert_text(key, value);
+        for frame in Id3v2Tag::from(tag) {
+            self.insert(frame);
+        }
+    }
+
+    fn remove_text(&mut self, key: &ItemKey) {
+        let Some(id) = key.map_key(TagType::Id3v2, false) else {
+            return;
+        };
+
+        // keys which are not a frame id are stored as `TXXX` with the key as description
+        if let Ok(id) = FrameId::new(id) {
+            let _ = self.remove(&id);
+        } else {
+            let _ = self.remove_user_text(id);
+        }
+    }
+
+    fn pictures(&self) -> Vec<Picture> {
+        self.into_iter()
+            .filter_map(|frame| match frame {
+                Frame::Picture(frame) => Some(frame.picture.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn remove_pictures(&mut self, pic_type: PictureType) {
+        self.remove_picture_type(pic_type);
+    }
+
+    fn add_picture(&mut self, picture: Picture) {
+        self.insert_picture(picture);
+    }
+
+    fn replace_lyrics(&mut self, lyrics: &Lyrics, text: String) -> bool {
+        let tag: &Self = self;
+        let uslt = tag.into_iter().find_map(|frame| match frame {
+            Frame::UnsynchronizedText(frame)
+                if frame.language.escape_ascii().to_string() == lyrics.lang
+                    && frame.description == lyrics.description =>
+            {
+                Some(frame.clone())
+            }
+            _ => None,
+        });
+        let has_sylt = tag
+            .into_iter()
+            .any(|frame| sylt::is_sylt_frame_of(frame, lyrics));
+        if uslt.is_none() && !has_sylt {
+            return false;
+        }
+
+        // binary frames are only equal with the same data, so the old one would not be replaced on insert
+        self.retain(|frame| !sylt::is_sylt_frame_of(frame, lyrics));
+
+        let lyrics = Lyrics {
+            text,
+            ..lyrics.clone()
+        };
+        if let Some(mut uslt) = uslt {
+            uslt.content.clone_from(&lyrics.text);
+            self.insert(Frame::UnsynchronizedText(uslt));
+        }
+        // like on saving in the tag editor, a synced lyric is also written as `SYLT`
+        if let Some(frame) = sylt::sylt_frame(&lyrics) {
+            self.insert(frame);
+        }
+
+        true
+    }
+}
+
+/// Change the tag of the file at `path` with `edit`, saving only if it returns `true`.
+///
+/// Only the existing tag is changed, so all other frames and items are kept.
+/// MPEG files only have their ID3v2 tag touched, as converting it to a generic tag loses the language and description of lyric frames.
+///
+/// # Errors
+///
+/// - if reading or saving the tags fails
+/// - if `edit` fails
+pub fn edit_tag<F>(path: &Path, edit: F) -> Result<bool>
+where
+    F: FnOnce(&mut dyn TagEdit) -> Result<bool>,
+{
+    if FileType::from_path(path) == Some(FileType::Mpeg) {
+        let mut reader = std::fs::File::open(path).context("open file")?;
+        let file = MpegFile::read_from(&mut reader, ParseOptions::new().read_properties(false))
+            .context("read tags")?;
+        drop(reader);
+
+        let mut tag = file.id3v2().cloned().unwrap_or_default();
+        if !edit(&mut tag)? {
+            return Ok(false);
+        }
+        tag.save_to_path(path, WriteOptions::new())
+            .context("save tags")?;
+
+        return Ok(true);
+    }
+
+    let tagged_file = lofty::read_from_path(path).context("read tags")?;
+    let mut tag = tagged_file
+        .primary_tag()
+        .cloned()
+        .unwrap_or_else(|| Tag::new(tagged_file.primary_tag_type()));
+    if !edit(&mut tag)? {
+        return Ok(false);
+    }
+    tag.save_to_path(path, WriteOptions::new())
+        .context("save tags")?;
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+    use std::path::{Path, PathBuf};
+
+    use id3::frame::Lyrics;
+    use lofty::config::{ParseOptions, WriteOptions};
+    use lofty::file::AudioFile;
+    use lofty::id3::v2::{Frame, FrameId, Id3v2Tag, UnsynchronizedTextFrame};
+    use lofty::mpeg::MpegFile;
+    use lofty::prelude::{Accessor, TagExt};
+    use lofty::tag::{ItemKey, ItemValue, Tag, TagItem, TagType};
+    use lofty::TextEncoding;
+
+    use super::{edit_tag, TagEdit};
+    use crate::songtag::sylt;
+
+    /// Write a MPEG file of silent frames with `tag` to a new temporary directory
+    fn mpeg_file(name: &str, tag: &Id3v2Tag) -> (PathBuf, PathBuf) {
+        let dir = std::env::temp_dir().join(format!("termusic-edit-{name}-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("track.mp3");
+
+        // MPEG-1 Layer 3, 128 kbit/s, 44.1 kHz: 417 bytes per frame
+        let mut frame = vec![0xFF, 0xFB, 0x90, 0x64];
+        frame.resize(417, 0);
+        std::fs::write(&path, frame.repeat(10)).unwrap();
+        tag.save_to_path(&path, WriteOptions::new()).unwrap();
+
+        (dir, path)
+    }
+
+    fn read_id3v2(path: &Path) -> Id3v2Tag {
+        let mut reader = std::fs::File::open(path).unwrap();
+        MpegFile::read_from(&mut reader, ParseOptions::new().read_properties(false))
+            .unwrap()
+            .id3v2()
+            .cloned()
+            .unwrap()
+    }
+
+    #[test]
+    fn should_keep_lyric_frames_of_mpeg_files() {
+        let mut tag = Id3v2Tag::new();
+        tag.set_title("Title".to_string());
+        tag.insert(Frame::UnsynchronizedText(UnsynchronizedTextFrame::new(
+            TextEncoding::UTF8,
+            *b"deu",
+            "Strophe".to_string(),
+            "Text".to_string(),
+        )));
+        let (dir, path) = mpeg_file("lyrics", &tag);
+
+        let changed = edit_tag(&path, |tag| {
+            tag.insert_text(ItemKey::AlbumArtist, "Album Artist".to_string());
+            Ok(true)
+        })
+        .unwrap();
+        assert!(changed);
+
+        let tag = read_id3v2(&path);
+        std::fs::remove_dir_all(dir).unwrap();
+
+        assert_eq!(tag.title().as_deref(), Some("Title"));
+        assert!(tag
+            .get_text(&FrameId::Valid(Cow::Borrowed("TPE2")))
+            .is_some_and(|v| v == "Album Artist"));
+        let lyrics: Vec<_> = tag.unsync_text().collect();
+        assert_eq!(lyrics.len(), 1);
+        assert_eq!(&lyrics[0].language, b"deu");
+        assert_eq!(lyrics[0].description, "Strophe");
+        assert_eq!(lyrics[0].content, "Text");
+    }
+
+    #[test]
+    fn should_insert_and_remove_id3v2_items() {
+        let mut tag = Id3v2Tag::new();
+
+        tag.insert_text(ItemKey::AlbumArtist, "First".to_string());
+        tag.insert_text(ItemKey::AlbumArtist, "Second".to_string());
+        tag.insert_text(ItemKey::ReplayGainTrackGain, "-1.00 dB".to_string());
+        assert!(tag
+            .get_text(&FrameId::Valid(Cow::Borrowed("TPE2")))
+            .is_some_and(|v| v == "Second"));
+        assert_eq!(tag.get_user_text("REPLAYGAIN_TRACK_GAIN"), Some("-1.00 dB"));
+        assert_eq!(tag.len(), 2);
+
+        tag.remove_text(&ItemKey::AlbumArtist);
+        tag.remove_text(&ItemKey::ReplayGainTrackGain);
+        assert!(tag.is_empty());
+    }
+
+    fn uslt(lang: [u8; 3], description: &str, text: &str) -> Frame<'static> {
+        Frame::UnsynchronizedText(UnsynchronizedTextFrame::new(
+            TextEncoding::UTF8,
+            lang,
+            description.to_string(),
+            text.to_string(),
+        ))
+    }
+
+    #[test]
+    fn should_replace_only_the_matching_lyric_frame() {
+        let mut tag = Id3v2Tag::new();
+        tag.insert_text(ItemKey::ReplayGainTrackGain, "-1.00 dB".to_string());
+        tag.insert(uslt(*b"eng", "first", "[00:01.00]One"));
+        tag.insert(uslt(*b"deu", "second", "[00:01.00]Eins"));
+        let (dir, path) = mpeg_file("replace-lyrics", &tag);
+
+        let lyrics = Lyrics {
+            lang: "eng".to_string(),
+            description: "first".to_string(),
+            text: "[00:01.00]One".to_string(),
+        };
+        let changed = edit_tag(&path, |tag| {
+            Ok(tag.replace_lyrics(&lyrics, "[00:01.50]One".to_string()))
+        })
+        .unwrap();
+        assert!(changed);
+
+        let tag = read_id3v2(&path);
+        std::fs::remove_dir_all(dir).unwrap();
+
+        assert_eq!(tag.get_user_text("REPLAYGAIN_TRACK_GAIN"), Some("-1.00 dB"));
+        let lyrics: Vec<_> = tag
+            .unsync_text()
+            .map(|v| (v.description.as_str(), v.content.as_str()))
+            .collect();
+        assert_eq!(lyrics.len(), 2);
+        assert!(lyrics.contains(&("first", "[00:01.50]One")));
+        assert!(lyrics.contains(&("second", "[00:01.00]Eins")));
+        // the synced lyric is also written as "SYLT"
+        let sylt: Vec<_> = tag
+            .into_iter()
+            .filter(|frame| frame.id_str() == "SYLT")
+            .collect();
+        assert_eq!(sylt.len(), 1);
+        assert!(sylt::is_sylt_frame_of(&sylt[0], &lyrics_of("eng", "first")));
+    }
+
+    fn lyrics_of(lang: &str, description: &str) -> Lyrics {
+        Lyrics {
+            lang: lang.to_string(),
+            description: description.to_string(),
+            text: String::new(),
+        }
+    }
+
+    #[test]
+    fn should_not_replace_missing_lyrics() {
+        let mut tag = Id3v2Tag::new();
+        tag.insert(uslt(*b"eng", "first", "One"));
+        assert!(!TagEdit::replace_lyrics(
+            &mut tag,
+            &lyrics_of("eng", "other"),
+            "Two".to_string()
+        ));
+
+        let mut tag = Tag::new(TagType::VorbisComments);
+        tag.push(TagItem::new(
+            ItemKey::Lyrics,
+            ItemValue::Text("One".to_string()),
+        ));
+        assert!(!TagEdit::replace_lyrics(
+            &mut tag,
+            &lyrics_of("XXX", ""),
+            "Two".to_string()
+        ));
+    }
+
+    #[test]
+    fn should_replace_lyric_items_with_the_same_text() {
+        let mut tag = Tag::new(TagType::VorbisComments);
+        tag.insert_text(ItemKey::TrackArtist, "Artist".to_string());
+        tag.push(TagItem::new(
+            ItemKey::Lyrics,
+            ItemValue::Text("One".to_string()),
+        ));
+        tag.push_unchecked(TagItem::new(
+            ItemKey::Unknown("UNSYNCEDLYRICS".to_string()),
+            ItemValue::Text("One".to_string()),
+        ));
+        tag.push(TagItem::new(
+            ItemKey::Lyrics,
+            ItemValue::Text("Other".to_string()),
+        ));
+
+        let lyrics = Lyrics {
+            lang: "XXX".to_string(),
+            description: String::new(),
+            text: "One".to_string(),
+        };
+        assert!(TagEdit::replace_lyrics(
+            &mut tag,
+            &lyrics,
+            "Two".to_string()
+        ));
+
+        assert_eq!(tag.get_string(&ItemKey::TrackArtist), Some("Artist"));
+        let mut lyrics: Vec<_> = tag.get_strings(&ItemKey::Lyrics).collect();
+        lyrics.sort_unstable();
+        assert_eq!(lyrics, ["Other", "Two"]);
+        assert_eq!(
+            tag.get_string(&ItemKey::Unknown("UNSYNCEDLYRICS".to_string())),
+            Some("Two")
+        );
+    }
+}
@@ -219,6 +219,7 @@ fn parse_song_info(v: &Value) -> Option<SongTag> {
         lyric_id: Some(v.get("hash")?.as_str()?.to_owned()),
         url: Some(urltype),
         album_id: Some(v.get("album_id")?.as_str()?.to_owned()),
+        base_url: None,
     })
 }
 
@@ -367,7 +368,8 @@ mod tests {
                 lyric_id: Some("11111111111111111111111111111111".to_owned()),
                 url: Some(UrlTypes::Protected),
                 pic_id: Some("11111111111111111111111111111111".to_owned()),
-                album_id: Some("88888888".to_owned())
+                album_id: Some("88888888".to_owned()),
+                base_url: None,
             }
         );
     }
@@ -36,6 +36,8 @@
 // [al:Hits Of The 60's - Vol. 2 – Oldies]
 // [00:12.00]Lyrics beginning ...
 // [00:15.30]Some more lyrics ...
+// Enhanced LRC (A2 extension) additionally has timestamps for each word:
+// [00:18.00]<00:18.00>Some <00:18.40>timed <00:19.10>words
 use anyhow::Result;
 use std::fmt::{Error as FmtError, Write};
 use std::str::FromStr;
@@ -59,8 +61,31 @@ pub struct Lyric {
 pub struct Caption {
     /// Timestamp in milliseconds
     timestamp: i64,
-    /// The text of the current caption, trimmed
+    /// The text of the current caption, trimmed, without word timestamps
     text: String,
+    /// The timed words of the caption, empty if the caption has no word timestamps
+    ///
+    /// The text of all words together is the same as `text`.
+    words: Vec<CaptionWord>,
+}
+
+/// A word (or syllable) of a [`Caption`] with its own timestamp, from enhanced LRC
+#[derive(Clone, Debug, PartialEq)]
+pub struct CaptionWord {
+    /// Timestamp in milliseconds
+    timestamp: i64,
+    /// The text of the word, including whitespace up to the next word
+    text: String,
+}
+
+/// The caption at some time with the captions around it, see [`Lyric::get_context`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CaptionContext<'a> {
+    pub previous: Option<&'a Caption>,
+    pub current: &'a Caption,
+    pub next: Option<&'a Caption>,
+    /// Index into the words of `current` that is currently sung, if it has word timestamps and the first word has started
+    pub word: Option<usize>,
 }
 
 impl Lyric {
@@ -89,15 +114,52 @@ impl Lyric {
 
         time = adjusted_time;
 
-        let mut text = &self.captions.first()?.text;
-        for caption in &self.captions {
-            if time >= caption.timestamp {
-                text = &caption.text;
-            } else {
-                break;
-            }
+        Some(&self.captions[self.caption_index_at(time)].text)
+    }
+
+    /// Get the caption at `time` or next lowest (like [`get_text`](Self::get_text)), with the previous and next caption
+    /// and the word currently sung.
+    ///
+    /// The caption is picked with the same 2 second lead as [`get_text`](Self::get_text), the word is picked at `time` exactly.
+    /// This function takes `self.offset` into account.
+    ///
+    /// # Panics
+    ///
+    /// if `time` cannot be represented as a [`i64`]
+    #[must_use]
+    pub fn get_context(&self, time: Duration) -> Option<CaptionContext<'_>> {
+        if self.captions.is_empty() {
+            return None;
         }
-        Some(text)
+
+        let time = i64::try_from(time.as_millis()).expect("Cannot represent input time as i64");
+        let time = time + self.offset;
+
+        // use a 2 second offset because of client progress delay, like "get_text"
+        let index = self.caption_index_at((time + 2000).max(0));
+        let current = &self.captions[index];
+        let word = current
+            .words
+            .iter()
+            .take_while(|word| time >= word.timestamp)
+            .count()
+            .checked_sub(1);
+
+        Some(CaptionContext {
+            previous: index.checked_sub(1).and_then(|i| self.captions.get(i)),
+            current,
+            next: self.captions.get(index + 1),
+            word,
+        })
+    }
+
+    /// Get the index of the caption at `time` (in milliseconds, already adjusted) or next lowest, or the first caption.
+    fn caption_index_at(&self, time: i64) -> usize {
+        self.captions
+            .iter()
+            .take_while(|caption| time >= caption.timestamp)
+            .count()
+            .saturating_sub(1)
     }
 
     /// Get a index for the next lowest caption from `time` (in milliseconds)
@@ -138,19 +200,30 @@ impl Lyric {
                 self.offset += offset;
             } else {
                 // fine tuning each line after 10 seconds
-                let caption = &mut self.captions[index];
-                let adjusted_time_stamp = caption.timestamp + offset;
-                caption.timestamp = if adjusted_time_stamp > 0 {
-                    adjusted_time_stamp
-                } else {
-                    0
-                };
+                self.adjust_caption(index, offset);
+                return;
             }
         }
         // we sort the captions by time_stamp. This is to fix some lyrics downloaded are not sorted
         self.captions.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
     }
 
+    /// Move the caption at `index` (with its words) by `offset`(milliseconds) and sort captions based on new timestamps
+    ///
+    /// Unlike [`adjust_offset`](Self::adjust_offset), this never changes `self.offset`.
+    pub fn adjust_caption(&mut self, index: usize, offset: i64) {
+        if let Some(caption) = self.captions.get_mut(index) {
+            let old_time_stamp = caption.timestamp;
+            caption.timestamp = (caption.timestamp + offset).max(0);
+            // the words move with their caption
+            let shift = caption.timestamp - old_time_stamp;
+            for word in &mut caption.words {
+                word.timestamp = (word.timestamp + shift).max(0);
+            }
+        }
+        self.captions.sort_by_key(|v| v.timestamp);
+    }
+
     /// Format current [`Lyric`] as a LRC file
     #[must_use]
     pub fn as_lrc_text(&self) -> String {
@@ -167,6 +240,96 @@ impl Lyric {
         result
     }
 
+    /// Create a [`Lyric`] from synchronized text segments, like the content of a ID3v2 `SYLT` frame.
+    ///
+    /// `content` is a list of timestamps (in milliseconds) and text.
+    /// If any segment starts with a newline, the segments are words (or syllables) and each newline starts a new caption,
+    /// otherwise each segment is a caption.
+    #[must_use]
+    pub fn from_synced(content: &[(u32, String)]) -> Self {
+        let is_newline = |text: &str| text.starts_with(['\n', '\r']);
+        let has_words = content.iter().any(|(_, text)| is_newline(text));
+
+        let mut captions: Vec<Caption> = Vec::new();
+        for (timestamp, text) in content {
+            let timestamp = i64::from(*timestamp);
+
+            if !has_words {
+                captions.push(Caption {
+                    timestamp,
+                    text: text.trim().to_string(),
+                    words: Vec::new(),
+                });
+                continue;
+            }
+
+            let word = CaptionWord {
+                timestamp,
+                text: text.trim_start_matches(['\n', '\r']).to_string(),
+            };
+            match captions.last_mut() {
+                Some(caption) if !is_newline(text) => caption.words.push(word),
+                _ => captions.push(Caption {
+                    timestamp,
+                    text: String::new(),
+                    words: vec![word],
+                }),
+            }
+        }
+
+        for caption in captions.iter_mut().filter(|v| !v.words.is_empty()) {
+            if let Some(first) = caption.words.first_mut() {
+                first.text = first.text.trim_start().to_string();
+            }
+            if let Some(last) = caption.words.last_mut() {
+                last.text.truncate(last.text.trim_end().len());
+            }
+            caption.text = caption.words.iter().map(|v| v.text.as_str()).collect();
+
+            // a single word is the same as a caption without word timestamps
+            if caption.words.len() == 1 {
+                caption.words.clear();
+            }
+        }
+
+        captions.sort_by_key(|v| v.timestamp);
+
+        Self {
+            offset: 0,
+            captions,
+        }
+    }
+
+    /// Format current [`Lyric`] as synchronized text segments (timestamps in milliseconds), like the content of a ID3v2 `SYLT` frame.
+    ///
+    /// `self.offset` is applied to the timestamps.
+    /// If any caption has word timestamps, all captions are split into words, with the first word of each caption starting with a newline.
+    #[must_use]
+    pub fn as_synced(&self) -> Vec<(u32, String)> {
+        let has_words = self.captions.iter().any(|v| !v.words.is_empty());
+        let time =
+            |timestamp: i64| u32::try_from((timestamp - self.offset).max(0)).unwrap_or(u32::MAX);
+
+        let mut content = Vec::new();
+        for caption in &self.captions {
+            if !has_words {
+                content.push((time(caption.timestamp), caption.text.clone()));
+                continue;
+            }
+
+            for (i, word) in caption.timed_words().into_iter().enumerate() {
+                let text = if i == 0 {
+                    format!("\n{}", word.text)
+                } else {
+                    word.text
+                };
+                content.push((time(word.timestamp), text));
+            }
+        }
+
+        content
+    }
+
     /// Merge captions that are less than 2 seconds apart
     pub fn merge_adjacent(&mut self) {
         let mut merged_captions = self.captions.clone();
@@ -174,6 +337,14 @@ impl Lyric {
         for (i, old_caption) in self.captions.iter().enumerate().skip(1) {
             if let Some(item) = merged_captions.get_mut(i - offset) {
                 if old_caption.timestamp - item.timestamp < 2000 {
+                    if !item.words.is_empty() || !old_caption.words.is_empty() {
+                        let mut words = item.timed_words();
+                        if let Some(last) = words.last_mut() {
+                            last.text += "  ";
+                        }
+                        words.extend(old_caption.timed_words());
+                        item.words = words;
+                    }
                     item.text += "  ";
                     item.text += old_caption.text.as_ref();
                     merged_captions.remove(i - offset + 1);
@@ -187,6 +358,46 @@ impl Lyric {
 }
 
 impl Caption {
+    /// Create a new caption at `timestamp` (in milliseconds) without word timestamps.
+    #[must_use]
+    pub fn new<T: Into<String>>(timestamp: i64, text: T) -> Self {
+        Self {
+            timestamp,
+            text: text.into(),
+            words: Vec::new(),
+        }
+    }
+
+    /// Get the timestamp of the caption in milliseconds.
+    #[must_use]
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    /// Get the text of the caption, without word timestamps.
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Get the timed words of the caption, empty if it has no word timestamps.
+    #[must_use]
+    pub fn words(&self) -> &[CaptionWord] {
+        &self.words
+    }
+
+    /// Get the words of the caption, or the whole text as one word starting with the caption if there are no word timestamps.
+    fn timed_words(&self) -> Vec<CaptionWord> {
+        if self.words.is_empty() {
+            vec![CaptionWord {
+                timestamp: self.timestamp,
+                text: self.text.clone(),
+            }]
+        } else {
+            self.words.clone()
+        }
+    }
+
     /// Try to parse a single [`Caption`]
     fn parse_line(line: &str) -> Option<Self> {
         //[00:12.00]Line 1 lyrics
@@ -200,15 +411,66 @@ impl Caption {
         // exclude the end character
         let text_start = timestamp_end + 1;
 
-        let time_stamp = Self::parse_time(&line[timestamp_start..timestamp_end])?;
-        let text = line[text_start..].to_string();
+        let timestamp = Self::parse_time(&line[timestamp_start..timestamp_end])?
+            .try_into()
+            .unwrap_or(0);
+        let (text, words) = Self::parse_words(timestamp, &line[text_start..]);
 
         Some(Self {
-            timestamp: time_stamp.try_into().unwrap_or(0),
+            timestamp,
             text,
+            words,
         })
     }
 
+    /// Parse enhanced LRC word timestamps (`<mm:ss.xx>`) out of the text of a caption starting at `timestamp`.
+    ///
+    /// Returns the text without the word timestamps and the timed words, which are empty if there were no word timestamps.
+    /// Text before the first word timestamp is a word starting with the caption.
+    fn parse_words(timestamp: i64, text: &str) -> (String, Vec<CaptionWord>) {
+        let mut words = Vec::new();
+        let mut has_timestamps = false;
+        let mut current = CaptionWord {
+            timestamp,
+            text: String::new(),
+        };
+        let mut rest = text;
+
+        while let Some(start) = rest.find('<') {
+            let Some(end) = rest[start..].find('>').map(|v| v + start) else {
+                break;
+            };
+            let Some(word_time) = Self::parse_time(&rest[start + 1..end]) else {
+                // not a timestamp, keep it as text
+                current.text.push_str(&rest[..=start]);
+                rest = &rest[start + 1..];
+                continue;
+            };
+
+            current.text.push_str(&rest[..start]);
+            // dont keep a empty word before the first timestamp
+            if has_timestamps || !current.text.is_empty() {
+                words.push(current);
+            }
+            has_timestamps = true;
+            current = CaptionWord {
+                timestamp: word_time.try_into().unwrap_or(0),
+                text: String::new(),
+            };
+            rest = &rest[end + 1..];
+        }
+        current.text.push_str(rest);
+
+        if !has_timestamps {
+            return (current.text, Vec::new());
+        }
+
+        words.push(current);
+        let text = words.iter().map(|word| word.text.as_str()).collect();
+
+        (text, words)
+    }
+
     /// Parse the time from a caption, the input needs to have the "[]" already removed
     ///
     /// LRC time is `mm:ss.xx` where `m` is minutes, `s` is seconds and `x` hundreths of a second (centis)
@@ -233,11 +495,38 @@ impl Caption {
         Some(sum_millis)
     }
 
-    /// Format the current [`Caption`] as a LRC line
+    /// Format the current [`Caption`] as a LRC line, with enhanced LRC word timestamps if there are timed words
+    fn as_lrc(&self, w: &mut impl Write) -> Result<(), FmtError> {
+        write!(w, "[{}]", time_lrc(self.timestamp.try_into().unwrap_or(0)))?;
+        if self.words.is_empty() {
+            return writeln!(w, "{}", self.text);
+        }
+
+        for word in &self.words {
+            word.as_lrc(w)?;
+        }
+        writeln!(w)
+    }
+}
+
+impl CaptionWord {
+    /// Get the timestamp of the word in milliseconds.
+    #[must_use]
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    /// Get the text of the word, including whitespace up to the next word.
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Format the current [`CaptionWord`] as a enhanced LRC word
     fn as_lrc(&self, w: &mut impl Write) -> Result<(), FmtError> {
-        writeln!(
+        write!(
             w,
-            "[{}]{}",
+            "<{}>{}",
             time_lrc(self.timestamp.try_into().unwrap_or(0)),
             self.text
         )
@@ -332,15 +621,18 @@ mod tests {
             &[
                 Caption {
                     timestamp: 12 * 1000,
-                    text: "Lyrics beginning ...".into()
+                    text: "Lyrics beginning ...".into(),
+                    words: Vec::new()
                 },
                 Caption {
                     timestamp: (15 * 1000) + 300,
-                    text: "Some more lyrics ...".into()
+                    text: "Some more lyrics ...".into(),
+                    words: Vec::new()
                 },
                 Caption {
                     timestamp: (10 * 60 * 1000) + (11 * 1000) + 120,
-                    text: "Extra Lyrics".into()
+                    text: "Extra Lyrics".into(),
+                    words: Vec::new()
                 },
             ]
         );
@@ -358,7 +650,8 @@ mod tests {
             lyrics.captions.as_slice(),
             &[Caption {
                 timestamp: 12 * 1000,
-                text: "Lyrics beginning ...".into()
+                text: "Lyrics beginning ...".into(),
+                words: Vec::new()
             },]
         );
     }
@@ -375,11 +668,164 @@ mod tests {
             lyrics.captions.as_slice(),
             &[Caption {
                 timestamp: 12 * 1000 + 305,
-                text: "Lyrics beginning ...".into()
+                text: "Lyrics beginning ...".into(),
+                words: Vec::new()
             },]
         );
     }
 
+    #[test]
+    fn should_parse_word_timestamps() {
+        let txt = r"[00:12.00]<00:12.00>Some <00:12.50>timed <00:13.05>words<00:14.00>
+[00:18.00]Untimed start <00:18.40>then <not a time>timed";
+
+        let lyrics = Lyric::from_str(txt).unwrap();
+
+        assert_eq!(
+            lyrics.captions.as_slice(),
+            &[
+                Caption {
+                    timestamp: 12 * 1000,
+                    text: "Some timed words".into(),
+                    words: vec![
+                        CaptionWord {
+                            timestamp: 12 * 1000,
+                            text: "Some ".into()
+                        },
+                        CaptionWord {
+                            timestamp: 12 * 1000 + 500,
+                            text: "timed ".into()
+                        },
+                        CaptionWord {
+                            timestamp: 13 * 1000 + 50,
+                            text: "words".into()
+                        },
+                        CaptionWord {
+                            timestamp: 14 * 1000,
+                            text: String::new()
+                        },
+                    ]
+                },
+                Caption {
+                    timestamp: 18 * 1000,
+                    text: "Untimed start then <not a time>timed".into(),
+                    words: vec![
+                        CaptionWord {
+                            timestamp: 18 * 1000,
+                            text: "Untimed start ".into()
+                        },
+                        CaptionWord {
+                            timestamp: 18 * 1000 + 400,
+                            text: "then <not a time>timed".into()
+                        },
+                    ]
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn should_format_word_timestamps_as_lrc() {
+        let txt = "[00:12.00]<00:12.00>Some <00:12.50>timed <00:13.05>words<00:14.00>
+[00:18.00]Untimed line
+";
+
+        let lyrics = Lyric::from_str(txt).unwrap();
+
+        assert_eq!(lyrics.as_lrc_text(), txt);
+    }
+
+    #[test]
+    fn should_get_context() {
+        let txt = r"[00:10.00]first
+[00:20.00]<00:20.00>second <00:21.00>line
+[00:30.00]third";
+
+        let lyrics = Lyric::from_str(txt).unwrap();
+
+        // the line has a 2 second lead, words do not
+        let context = lyrics.get_context(Duration::from_secs(19)).unwrap();
+        assert_eq!(context.previous.unwrap().text(), "first");
+        assert_eq!(context.current.text(), "second line");
+        assert_eq!(context.next.unwrap().text(), "third");
+        assert_eq!(context.word, None);
+
+        let context = lyrics
+            .get_context(Duration::from_millis(21 * 1000 + 500))
+            .unwrap();
+        assert_eq!(context.word, Some(1));
+        assert_eq!(context.current.words()[1].text(), "line");
+
+        let context = lyrics.get_context(Duration::from_secs(0)).unwrap();
+        assert_eq!(context.previous, None);
+        assert_eq!(context.current.text(), "first");
+
+        let context = lyrics.get_context(Duration::from_secs(40)).unwrap();
+        assert_eq!(context.current.text(), "third");
+        assert_eq!(context.next, None);
+    }
+
+    #[test]
+    fn should_convert_synced() {
+        let content = vec![
+            (1000, "First line".to_string()),
+            (5000, "Second line".to_string()),
+        ];
+
+        let mut lyrics = Lyric::from_synced(&content);
+
+        assert_eq!(
+            lyrics,
+            Lyric {
+                offset: 0,
+                captions: vec![
+                    Caption {
+                        timestamp: 1000,
+                        text: "First line".into(),
+                        words: Vec::new()
+                    },
+                    Caption {
+                        timestamp: 5000,
+                        text: "Second line".into(),
+                        words: Vec::new()
+                    },
+                ]
+            }
+        );
+        assert_eq!(lyrics.as_synced(), content);
+
+        // the offset is applied to the timestamps
+        lyrics.offset = 500;
+        assert_eq!(
+            lyrics.as_synced(),
+            vec![
+                (500, "First line".to_string()),
+                (4500, "Second line".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_convert_synced_words() {
+        let lyrics = Lyric::from_str(
+            "[00:01.00]<00:01.00>Some <00:01.50>timed\n[00:05.00]<00:05.00>words <00:05.50>here",
+        )
+        .unwrap();
+
+        let content = lyrics.as_synced();
+
+        assert_eq!(
+            content,
+            vec![
+                (1000, "\nSome ".to_string()),
+                (1500, "timed".to_string()),
+                (5000, "\nwords ".to_string()),
+                (5500, "here".to_string()),
+            ]
+        );
+        assert_eq!(Lyric::from_synced(&content), lyrics);
+    }
+
     #[test]
     fn should_handle_empty() {
         let txt = "";
@@ -397,14 +843,17 @@ mod tests {
                 Caption {
                     timestamp: 12 * 1000,
                     text: "Lyrics beginning ...".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: (15 * 1000) + 300,
                     text: "Some more lyrics ...".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: (10 * 60 * 1000) + (11 * 1000) + 120,
                     text: "Extra Lyrics".into(),
+                    words: Vec::new(),
                 },
             ],
         };
@@ -427,18 +876,22 @@ mod tests {
                 Caption {
                     timestamp: 1000,
                     text: "unmerged1".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: 3 * 1000,
                     text: "merged1".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: 4 * 1000,
                     text: "merged2".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: 5 * 1000,
                     text: "unmerged2".into(),
+                    words: Vec::new(),
                 },
             ],
         };
@@ -450,15 +903,18 @@ mod tests {
             &[
                 Caption {
                     timestamp: 1000,
-                    text: "unmerged1".into()
+                    text: "unmerged1".into(),
+                    words: Vec::new()
                 },
                 Caption {
                     timestamp: 3 * 1000,
-                    text: "merged1  merged2".into()
+                    text: "merged1  merged2".into(),
+                    words: Vec::new()
                 },
                 Caption {
                     timestamp: 5 * 1000,
-                    text: "unmerged2".into()
+                    text: "unmerged2".into(),
+                    words: Vec::new()
                 },
             ]
         );
@@ -472,26 +928,32 @@ mod tests {
                 Caption {
                     timestamp: 5 * 1000,
                     text: "changed offset".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: 11 * 1000,
                     text: "unchanged1".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: 13 * 1000,
                     text: "changed1".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: 15 * 1000,
                     text: "changed2".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: 16 * 1000,
                     text: "unchanged2".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: 17 * 1000,
                     text: "unchanged3".into(),
+                    words: Vec::new(),
                 },
             ],
         };
@@ -514,26 +976,32 @@ mod tests {
                 Caption {
                     timestamp: 5 * 1000,
                     text: "changed offset".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: 11 * 1000,
                     text: "unchanged1".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: 15 * 1000,
                     text: "changed1".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: 16 * 1000,
                     text: "changed2".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: 16 * 1000,
                     text: "unchanged2".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: 17 * 1000,
                     text: "unchanged3".into(),
+                    words: Vec::new(),
                 },
             ]
         );
@@ -547,18 +1015,22 @@ mod tests {
                 Caption {
                     timestamp: 1000,
                     text: "text1".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: 3 * 1000,
                     text: "text2".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: 4 * 1000,
                     text: "text3".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: 5 * 1000,
                     text: "text4".into(),
+                    words: Vec::new(),
                 },
             ],
         };
@@ -0,0 +1,177 @@
+use std::time::Duration;
+
+use anyhow::anyhow;
+use lofty::picture::Picture;
+use model::{to_lyric, to_song_info};
+use reqwest::{Client, ClientBuilder};
+
+use super::{
+    service::{SongTagService, SongTagServiceError, SongTagServiceErrorWhere},
+    ServiceProvider, SongTag,
+};
+
+mod model;
+
+const URL_BASE_LRCLIB: &str = "https://lrclib.net";
+const PATH_SEARCH_LRCLIB: &str = "/api/search";
+const PATH_GET_LRCLIB: &str = "/api/get/";
+
+/// lrclib asks clients to identify themselves
+const USER_AGENT: &str = concat!(
+    "termusic v",
+    env!("CARGO_PKG_VERSION"),
+    " (https://github.com/tramhao/termusic)"
+);
+
+pub struct Api {
+    client: Client,
+    base_url: String,
+}
+
+impl Api {
+    pub fn new() -> Self {
+        Self::with_base_url(URL_BASE_LRCLIB)
+    }
+
+    /// Create a instance for the api at `base_url` instead of the public one, like a self-hosted instance
+    pub fn with_base_url(base_url: &str) -> Self {
+        let client = ClientBuilder::new()
+            .timeout(Duration::from_secs(10))
+            .user_agent(USER_AGENT)
+            .build()
+            .expect("failed to build reqwest client.");
+
+        Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+impl SongTagService for Api {
+    type Error = anyhow::Error;
+
+    fn display_name() -> &'static str
+    where
+        Self: Sized,
+    {
+        "lrclib"
+    }
+
+    async fn search_recording(
+        &self,
+        keywords: &str,
+        offset: u32,
+        limit: u32,
+    ) -> Result<Vec<SongTag>, SongTagServiceError<Self::Error>> {
+        let result = self
+            .client
+            .get(format!("{}{PATH_SEARCH_LRCLIB}", self.base_url))
+            .query(&[("q", keywords)])
+            .send()
+            .await
+            .map_err(anyhow::Error::from)?
+            .error_for_status()
+            .map_err(anyhow::Error::from)?
+            .text()
+            .await
+            .map_err(anyhow::Error::from)?;
+
+        let list = to_song_info(&result).map_err(|err| {
+            SongTagServiceError::Other(anyhow!(err).context("Parse result into SongTag Array"))
+        })?;
+
+        // the api does not support paging
+        Ok(list
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect())
+    }
+
+    async fn get_lyrics(&self, song: &SongTag) -> Result<String, SongTagServiceError<Self::Error>> {
+        if song.service_provider() != ServiceProvider::Lrclib {
+            return Err(SongTagServiceError::IncorrectService(
+                song.service_provider().to_string(),
+                Self::display_name(),
+            ));
+        }
+
+        let Some(lyric_id) = &song.lyric_id else {
+            return Err(SongTagServiceError::Other(anyhow!(
+                "Provided songtag does not have a lyric_id!"
+            )));
+        };
+
+        let result = self
+            .client
+            .get(format!("{}{PATH_GET_LRCLIB}{lyric_id}", self.base_url))
+            .send()
+            .await
+            .map_err(anyhow::Error::from)?
+            .error_for_status()
+            .map_err(anyhow::Error::from)?
+            .text()
+            .await
+            .map_err(anyhow::Error::from)?;
+
+        to_lyric(&result).map_err(|err| {
+            SongTagServiceError::Other(anyhow!(err).context("Extract Lyric text from result"))
+        })
+    }
+
+    async fn get_picture(
+        &self,
+        _song: &SongTag,
+    ) -> Result<Picture, SongTagServiceError<Self::Error>> {
+        Err(SongTagServiceError::NotSupported(
+            SongTagServiceErrorWhere::GetPicture,
+            Self::display_name(),
+        ))
+    }
+
+    async fn download_recording(
+        &self,
+        _song: &SongTag,
+    ) -> Result<String, SongTagServiceError<Self::Error>> {
+        Err(SongTagServiceError::NotSupported(
+            SongTagServiceErrorWhere::DownloadRecording,
+            Self::display_name(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Api;
+    use crate::songtag::service::SongTagService;
+    use crate::songtag::test_utils::mock_server;
+
+    const SEARCH_RESULT: &str = r#"[
+        {"id": 1, "trackName": "Track A", "artistName": "Artist", "albumName": "Album", "syncedLyrics": "[00:01.00] A"},
+        {"id": 2, "trackName": "Track B", "artistName": "Artist", "albumName": "Album", "syncedLyrics": null}
+    ]"#;
+
+    #[tokio::test]
+    async fn should_search_and_get_lyrics() {
+        let base_url = mock_server(vec![
+            ("/api/search?q=Artist", SEARCH_RESULT.to_string()),
+            (
+                "/api/get/2",
+                r#"{"id": 2, "plainLyrics": "B", "syncedLyrics": null}"#.to_string(),
+            ),
+        ]);
+        let api = Api::with_base_url(&format!("{base_url}/"));
+
+        let res = api.search_recording("Artist", 0, 30).await.unwrap();
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[1].title(), Some("Track B"));
+
+        let res_offset = api.search_recording("Artist", 1, 30).await.unwrap();
+        assert_eq!(res_offset, &res[1..]);
+
+        assert_eq!(api.get_lyrics(&res[1]).await.unwrap(), "B");
+        // not served by the mock
+        assert!(api.get_lyrics(&res[0]).await.is_err());
+    }
+}
@@ -0,0 +1,132 @@
+use super::super::{ServiceProvider, SongTag};
+use serde_json::{from_str, Value};
+
+#[derive(Debug, thiserror::Error)]
+pub enum LrclibParseError {
+    #[error("Expected a Array as result")]
+    NotAnArray,
+
+    #[error("Expected property \"{0}\" to exist")]
+    MissingProperty(&'static str),
+
+    #[error("Track is instrumental and has no lyrics")]
+    Instrumental,
+
+    #[error(transparent)]
+    ParseError(#[from] serde_json::Error),
+}
+
+type Result<T> = std::result::Result<T, LrclibParseError>;
+
+/// Try to get the lyric content from the given result, synced lyrics are preferred
+pub fn to_lyric(json: &str) -> Result<String> {
+    let value = from_str::<Value>(json)?;
+
+    let non_empty = |key: &str| {
+        value
+            .get(key)
+            .and_then(Value::as_str)
+            .filter(|v| !v.trim().is_empty())
+    };
+
+    if let Some(lyric) = non_empty("syncedLyrics").or_else(|| non_empty("plainLyrics")) {
+        return Ok(lyric.to_owned());
+    }
+
+    if value.get("instrumental").and_then(Value::as_bool) == Some(true) {
+        return Err(LrclibParseError::Instrumental);
+    }
+
+    Err(LrclibParseError::MissingProperty("syncedLyrics"))
+}
+
+/// Try to get individual [`SongTag`]s from the json response
+pub fn to_song_info(json: &str) -> Result<Vec<SongTag>> {
+    let value = from_str::<Value>(json)?;
+
+    let array = value.as_array().ok_or(LrclibParseError::NotAnArray)?;
+
+    Ok(array.iter().filter_map(parse_song_info).collect())
+}
+
+/// Try to parse a single [`SongTag`] from a given lrclib value
+fn parse_song_info(v: &Value) -> Option<SongTag> {
+    let song_id = v.get("id").and_then(Value::as_u64).map(|v| v.to_string())?;
+
+    let get_str = |key: &str| v.get(key).and_then(Value::as_str).map(ToOwned::to_owned);
+
+    Some(SongTag {
+        lyric_id: Some(song_id.clone()),
+        song_id,
+        title: get_str("trackName"),
+        artist: get_str("artistName"),
+        album: get_str("albumName"),
+        pic_id: None,
+        lang_ext: Some("lrclib".to_string()),
+        service_provider: ServiceProvider::Lrclib,
+        // lrclib only has lyrics
+        url: None,
+        album_id: None,
+        base_url: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_parse_songinfo() {
+        let sample_data = r#"[
+            {
+                "id": 1234,
+                "trackName": "Track A",
+                "artistName": "Some Artist",
+                "albumName": "Some Album",
+                "duration": 201.0,
+                "instrumental": false,
+                "plainLyrics": "Line 1\nLine 2",
+                "syncedLyrics": "[00:01.00] Line 1\n[00:02.00] Line 2"
+            },
+            {
+                "trackName": "Missing id"
+            }
+        ]"#;
+
+        let res = to_song_info(sample_data).unwrap();
+
+        assert_eq!(
+            res,
+            vec![SongTag {
+                song_id: "1234".to_string(),
+                artist: Some("Some Artist".to_string()),
+                title: Some("Track A".to_string()),
+                album: Some("Some Album".to_string()),
+                lang_ext: Some("lrclib".to_string()),
+                lyric_id: Some("1234".to_string()),
+                url: None,
+                pic_id: None,
+                album_id: None,
+                service_provider: ServiceProvider::Lrclib,
+                base_url: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn should_prefer_synced_lyrics() {
+        let synced = r#"{"id": 1, "plainLyrics": "Line 1", "syncedLyrics": "[00:01.00] Line 1"}"#;
+        assert_eq!(to_lyric(synced).unwrap(), "[00:01.00] Line 1");
+
+        let plain = r#"{"id": 1, "plainLyrics": "Line 1", "syncedLyrics": null}"#;
+        assert_eq!(to_lyric(plain).unwrap(), "Line 1");
+
+        let instrumental =
+            r#"{"id": 1, "instrumental": true, "plainLyrics": null, "syncedLyrics": null}"#;
+        assert!(matches!(
+            to_lyric(instrumental),
+            Err(LrclibParseError::Instrumental)
+        ));
+    }
+}
@@ -197,6 +197,7 @@ fn parse_song_info(v: &Value) -> Option<SongTag> {
         lyric_id,
         url: Some(url),
         album_id,
+        base_url: None,
     })
 }
 
@@ -284,7 +285,8 @@ mod tests {
                 lyric_id: Some("0000000AAAA".to_owned()),
                 url: Some(UrlTypes::FreeDownloadable("https://freetyst.nf.migu.cn/SomeLongPercentFilename.mp3?Key=AAAAAAAAAAAAAAAA&Tim=1111111111111&channelid=01&msisdn=AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA".to_owned())),
                 pic_id: Some("https://mcontent.migu.cn/newlv2/new/album/20230810/0000000000/someRandomCode.jpg".to_owned()),
-                album_id: Some("0000000000".to_owned())
+                album_id: Some("0000000000".to_owned()),
+                base_url: None,
             }
         );
 
@@ -300,7 +302,8 @@ mod tests {
                 lyric_id: Some("1111111BBBB".to_owned()),
                 url: Some(UrlTypes::FreeDownloadable("https://freetyst.nf.migu.cn/SomeOtherLongPercentFilename.mp3?Key=AAAAAAAAAAAAAAAA&Tim=1111111111111&channelid=01&msisdn=AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA".to_owned())),
                 pic_id: Some("https://tyqk.migu.cn/files/resize/album/2023-12-19/someOtherRandomCode.jpg?200x200".to_owned()),
-                album_id: Some("1111111111".to_owned())
+                album_id: Some("1111111111".to_owned()),
+                base_url: None,
             }
         );
     }
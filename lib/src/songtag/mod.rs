@@ -5,25 +5,37 @@ use std::sync::Arc;
 use std::thread::{self, sleep};
 use std::time::Duration;
 
-use anyhow::{anyhow, bail, Result};
+use anyhow::{bail, Result};
+use id3::frame::Lyrics;
 use lofty::config::WriteOptions;
 use lofty::id3::v2::{Frame, Id3v2Tag, UnsynchronizedTextFrame};
 use lofty::picture::Picture;
 use lofty::prelude::{Accessor, TagExt};
 use lofty::TextEncoding;
-use service::SongTagService;
+use provider::{ProviderApi, ProviderRegistry};
 use tokio::sync::mpsc::UnboundedSender;
 use ytd_rs::{Arg, YoutubeDL};
 
+use crate::config::v2::tui::LyricProvider;
+pub use crate::config::v2::tui::ServiceProvider;
 use crate::library_db::const_unknown::{UNKNOWN_ARTIST, UNKNOWN_TITLE};
 use crate::types::{DLMsg, Msg, SongTagRecordingResult, TEMsg};
 use crate::utils::get_parent_folder;
 
+pub mod batch;
+pub mod cover;
+pub mod edit;
 mod kugou;
 pub mod lrc;
+mod lrclib;
 mod migu;
+pub mod musicbrainz;
 mod netease_v2;
+pub mod pattern;
+mod provider;
 mod service;
+pub mod sidecar;
+pub mod sylt;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct SongTag {
@@ -38,6 +50,8 @@ pub struct SongTag {
     pic_id: Option<String>,
     album_id: Option<String>,
     // genre: Option<String>,
+    /// The base url of the provider instance this song was found with, if not the default
+    base_url: Option<String>,
 }
 
 /// Indicate in which way the song can be downloaded, if at all.
@@ -51,60 +65,10 @@ pub enum UrlTypes {
     FreeDownloadable(String),
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-pub enum ServiceProvider {
-    Netease,
-    Kugou,
-    Migu,
-}
-
-impl std::fmt::Display for ServiceProvider {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let service_provider = match self {
-            Self::Netease => "Netease",
-            Self::Kugou => "Kugou",
-            Self::Migu => "Migu",
-        };
-        write!(f, "{service_provider}")
-    }
-}
-
-// Search function of 3 servers. Run in parallel to get results faster.
-pub async fn search(search_str: &str, tx_done: UnboundedSender<Msg>) {
-    let mut results: Vec<SongTag> = Vec::new();
-
-    let handle_netease = async {
-        let neteasev2_api = netease_v2::Api::new();
-        neteasev2_api.search_recording(search_str, 0, 30).await
-    };
-
-    let handle_migu = async {
-        let migu_api = migu::Api::new();
-        migu_api.search_recording(search_str, 0, 30).await
-    };
-
-    let handle_kugou = async {
-        let kugou_api = kugou::Api::new();
-        kugou_api.search_recording(search_str, 0, 30).await
-    };
-
-    let (netease_res, migu_res, kugou_res) =
-        futures_util::join!(handle_netease, handle_migu, handle_kugou);
-
-    match netease_res {
-        Ok(vec) => results.extend(vec),
-        Err(err) => error!("Netease Error: {err:#}"),
-    }
-
-    match migu_res {
-        Ok(vec) => results.extend(vec),
-        Err(err) => error!("Migu Error: {err:#}"),
-    }
-
-    match kugou_res {
-        Ok(vec) => results.extend(vec),
-        Err(err) => error!("Kogou Error: {err:#}"),
-    }
+/// Search all enabled `providers` in parallel and send the merged results.
+pub async fn search(search_str: &str, providers: &[LyricProvider], tx_done: UnboundedSender<Msg>) {
+    let registry = ProviderRegistry::from_settings(providers);
+    let results = registry.search(search_str, 30).await;
 
     let _ = tx_done.send(Msg::TagEditor(TEMsg::TESearchLyricResult(
         SongTagRecordingResult::Finish(results),
@@ -149,25 +113,14 @@ impl SongTag {
         &self.song_id
     }
 
+    /// Get the api of the provider (instance) this song was found with
+    fn provider_api(&self) -> ProviderApi {
+        ProviderApi::new(self.service_provider, self.base_url.as_deref())
+    }
+
     // get lyric by lyric_id
     pub async fn fetch_lyric(&self) -> Result<Option<String>> {
-        let lyric_string = match self.service_provider {
-            ServiceProvider::Kugou => {
-                let kugou_api = kugou::Api::new();
-                kugou_api.get_lyrics(self).await.map_err(|v| anyhow!(v))?
-            }
-            ServiceProvider::Netease => {
-                let neteasev2_api = netease_v2::Api::new();
-                neteasev2_api
-                    .get_lyrics(self)
-                    .await
-                    .map_err(|v| anyhow!(v))?
-            }
-            ServiceProvider::Migu => {
-                let migu_api = migu::Api::new();
-                migu_api.get_lyrics(self).await.map_err(|v| anyhow!(v))?
-            }
-        };
+        let lyric_string = self.provider_api().get_lyrics(self).await?;
 
         Ok(Some(lyric_string))
     }
@@ -175,23 +128,7 @@ impl SongTag {
     /// Fetch a picture for the current song
     /// For kugou & netease `pic_id()` or for migu `song_id` is used
     pub async fn fetch_photo(&self) -> Result<Picture> {
-        match self.service_provider {
-            ServiceProvider::Kugou => {
-                let kugou_api = kugou::Api::new();
-                Ok(kugou_api.get_picture(self).await.map_err(|v| anyhow!(v))?)
-            }
-            ServiceProvider::Netease => {
-                let neteasev2_api = netease_v2::Api::new();
-                Ok(neteasev2_api
-                    .get_picture(self)
-                    .await
-                    .map_err(|v| anyhow!(v))?)
-            }
-            ServiceProvider::Migu => {
-                let migu_api = migu::Api::new();
-                Ok(migu_api.get_picture(self).await.map_err(|v| anyhow!(v))?)
-            }
-        }
+        self.provider_api().get_picture(self).await
     }
 
     #[allow(clippy::too_many_lines)]
@@ -235,22 +172,9 @@ impl SongTag {
             String::new()
         };
 
-        match self.service_provider {
-            ServiceProvider::Netease => {
-                let neteasev2_api = netease_v2::Api::new();
-                url = neteasev2_api
-                    .download_recording(self)
-                    .await
-                    .map_err(|v| anyhow!(v))?;
-            }
-            ServiceProvider::Migu => {}
-            ServiceProvider::Kugou => {
-                let kugou_api = kugou::Api::new();
-                url = kugou_api
-                    .download_recording(self)
-                    .await
-                    .map_err(|v| anyhow!(v))?;
-            }
+        // migu urls are already available from the search
+        if self.service_provider != ServiceProvider::Migu {
+            url = self.provider_api().download_recording(self).await?;
         }
 
         if url.is_empty() {
@@ -297,13 +221,24 @@ impl SongTag {
             tag.set_album(album);
 
             if let Ok(Some(l)) = lyric {
+                let lyrics = Lyrics {
+                    lang: String::from("eng"),
+                    description: String::from("saved by termusic"),
+                    text: l,
+                };
+                let sylt_frame = sylt::sylt_frame(&lyrics);
+
                 let frame = Frame::UnsynchronizedText(UnsynchronizedTextFrame::new(
                     TextEncoding::UTF8,
                     *b"eng",
-                    String::from("saved by termusic"),
-                    l,
+                    lyrics.description,
+                    lyrics.text,
                 ));
                 tag.insert(frame);
+
+                if let Some(frame) = sylt_frame {
+                    tag.insert(frame);
+                }
             }
 
             if let Ok(picture) = photo {
@@ -333,3 +268,50 @@ impl SongTag {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test_utils {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    /// Start a local http server which responds with the json of the first route whose path is a prefix of the request path, or `404`.
+    ///
+    /// Returns the base url of the server, which runs until the tests end.
+    pub fn mock_server(routes: Vec<(&'static str, String)>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock server");
+        let addr = listener.local_addr().unwrap();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else {
+                    continue;
+                };
+                let mut reader = BufReader::new(&stream);
+
+                let mut request_line = String::new();
+                if reader.read_line(&mut request_line).is_err() {
+                    continue;
+                }
+                // skip the headers, requests are expected to not have a body
+                let mut line = String::new();
+                while reader.read_line(&mut line).is_ok_and(|read| read > 2) {
+                    line.clear();
+                }
+
+                let path = request_line.split_whitespace().nth(1).unwrap_or("/");
+                let (status, body) = routes
+                    .iter()
+                    .find(|(route, _)| path.starts_with(route))
+                    .map_or(("404 Not Found", ""), |(_, body)| ("200 OK", body.as_str()));
+
+                let _ = write!(
+                    &stream,
+                    "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+            }
+        });
+
+        format!("http://{addr}")
+    }
+}
@@ -0,0 +1,195 @@
+//! Lookup of releases on a `MusicBrainz` compatible api, to tag whole albums.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use reqwest::{Client, ClientBuilder};
+
+pub use model::{Release, ReleaseCandidate, ReleaseTrack};
+pub use tags::{match_release, write_all, FieldDiff, FileChange, ReleaseTags};
+
+mod model;
+mod tags;
+
+const PATH_RELEASE: &str = "/ws/2/release";
+
+/// `MusicBrainz` requires clients to identify themselves
+const USER_AGENT: &str = concat!(
+    "termusic/",
+    env!("CARGO_PKG_VERSION"),
+    " ( https://github.com/tramhao/termusic )"
+);
+
+/// What to search releases by, empty values are not searched for
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReleaseQuery {
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub track_count: Option<usize>,
+}
+
+impl ReleaseQuery {
+    /// Build the lucene search query
+    fn to_query(&self) -> String {
+        // inside a phrase only quotes and backslashes need to be escaped
+        let phrase = |v: &str| format!("\"{}\"", v.replace('\\', "\\\\").replace('"', "\\\""));
+
+        let mut parts = Vec::new();
+        if let Some(artist) = self.artist.as_deref().filter(|v| !v.trim().is_empty()) {
+            parts.push(format!("artist:{}", phrase(artist)));
+        }
+        if let Some(album) = self.album.as_deref().filter(|v| !v.trim().is_empty()) {
+            parts.push(format!("release:{}", phrase(album)));
+        }
+        if let Some(count) = self.track_count {
+            parts.push(format!("tracks:{count}"));
+        }
+
+        parts.join(" AND ")
+    }
+}
+
+pub struct Api {
+    client: Client,
+    base_url: String,
+}
+
+impl Api {
+    /// Create a instance for the api at `base_url`, like [`URL_BASE_MUSICBRAINZ`](crate::config::v2::tui::URL_BASE_MUSICBRAINZ) or a mirror
+    ///
+    /// # Panics
+    ///
+    /// If the http client cannot be built
+    #[must_use]
+    pub fn new(base_url: &str) -> Self {
+        let client = ClientBuilder::new()
+            .timeout(Duration::from_secs(10))
+            .user_agent(USER_AGENT)
+            .build()
+            .expect("failed to build reqwest client.");
+
+        Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    async fn get(&self, url: String, query: &[(&str, &str)]) -> Result<String> {
+        let text = self
+            .client
+            .get(url)
+            .query(query)
+            .query(&[("fmt", "json")])
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+
+        Ok(text)
+    }
+
+    /// Search for releases matching `query`, best matches first
+    ///
+    /// # Errors
+    ///
+    /// - if the query is empty
+    /// - if the request fails or the response cannot be parsed
+    pub async fn search_releases(
+        &self,
+        query: &ReleaseQuery,
+        limit: u32,
+    ) -> Result<Vec<ReleaseCandidate>> {
+        let query = query.to_query();
+        if query.is_empty() {
+            anyhow::bail!("Nothing to search for, set artist or album first");
+        }
+
+        let result = self
+            .get(
+                format!("{}{PATH_RELEASE}", self.base_url),
+                &[("query", &query), ("limit", &limit.to_string())],
+            )
+            .await?;
+
+        model::to_candidates(&result).context("Parse result into releases")
+    }
+
+    /// Get the release with the MBID `id`, including all tracks
+    ///
+    /// # Errors
+    ///
+    /// If the request fails or the response cannot be parsed
+    pub async fn get_release(&self, id: &str) -> Result<Release> {
+        let result = self
+            .get(
+                format!("{}{PATH_RELEASE}/{id}", self.base_url),
+                &[("inc", "recordings artist-credits release-groups")],
+            )
+            .await?;
+
+        model::to_release(&result).context("Parse result into release")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Api, ReleaseQuery};
+    use crate::songtag::test_utils::mock_server;
+
+    #[test]
+    fn should_build_query() {
+        let query = ReleaseQuery {
+            artist: Some("The \"Artist\"".to_string()),
+            album: Some(" ".to_string()),
+            track_count: Some(12),
+        };
+
+        assert_eq!(query.to_query(), r#"artist:"The \"Artist\"" AND tracks:12"#);
+        assert_eq!(ReleaseQuery::default().to_query(), "");
+    }
+
+    #[tokio::test]
+    async fn should_search_and_get_release() {
+        let base_url = mock_server(vec![
+            (
+                "/ws/2/release/rel-1?",
+                r#"{
+                    "id": "rel-1",
+                    "title": "Album",
+                    "artist-credit": [{"name": "Artist", "artist": {"id": "art-1"}}],
+                    "media": [{"position": 1, "tracks": [
+                        {"id": "tr-1", "position": 1, "title": "One", "recording": {"id": "rec-1"}}
+                    ]}]
+                }"#
+                .to_string(),
+            ),
+            (
+                "/ws/2/release?query=artist",
+                r#"{"releases": [{"id": "rel-1", "title": "Album", "score": 100, "track-count": 1}]}"#
+                    .to_string(),
+            ),
+        ]);
+        let api = Api::new(&format!("{base_url}/"));
+
+        let query = ReleaseQuery {
+            artist: Some("Artist".to_string()),
+            album: Some("Album".to_string()),
+            track_count: Some(1),
+        };
+        let candidates = api.search_releases(&query, 10).await.unwrap();
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].id, "rel-1");
+
+        let release = api.get_release(&candidates[0].id).await.unwrap();
+        assert_eq!(release.tracks.len(), 1);
+        assert_eq!(release.tracks[0].recording_id, "rec-1");
+
+        // not served by the mock
+        assert!(api.get_release("rel-2").await.is_err());
+        assert!(api
+            .search_releases(&ReleaseQuery::default(), 10)
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,290 @@
+use serde::Deserialize;
+use serde_json::from_str;
+
+/// A release found by a search, to choose from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReleaseCandidate {
+    pub id: String,
+    pub title: String,
+    pub artist: String,
+    pub date: Option<String>,
+    pub country: Option<String>,
+    pub track_count: u32,
+    /// How well the release matched the search, `0..=100`
+    pub score: u8,
+}
+
+/// A release with all its tracks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Release {
+    pub id: String,
+    pub title: String,
+    pub artist: String,
+    pub artist_id: Option<String>,
+    pub date: Option<String>,
+    pub release_group_id: Option<String>,
+    pub tracks: Vec<ReleaseTrack>,
+}
+
+impl Release {
+    /// Get the year of the release date, if there is one
+    #[must_use]
+    pub fn year(&self) -> Option<u32> {
+        self.date.as_deref()?.get(..4)?.parse().ok()
+    }
+
+    /// Get the number of discs (media) of the release
+    #[must_use]
+    pub fn disc_total(&self) -> u32 {
+        self.tracks.iter().map(|v| v.disc).max().unwrap_or(1)
+    }
+}
+
+/// A track on a specific disc of a [`Release`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReleaseTrack {
+    /// The position of the disc on the release, starting at 1
+    pub disc: u32,
+    /// The position of the track on the disc, starting at 1
+    pub position: u32,
+    /// The number of tracks on the disc
+    pub disc_track_count: u32,
+    pub title: String,
+    /// The MBID of the track on this release
+    pub track_id: String,
+    /// The MBID of the recording, shared by all releases with the same recording
+    pub recording_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    #[serde(default)]
+    releases: Vec<ReleaseJson>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct ReleaseJson {
+    id: String,
+    title: String,
+    #[serde(default)]
+    artist_credit: Vec<ArtistCreditJson>,
+    date: Option<String>,
+    country: Option<String>,
+    #[serde(default)]
+    track_count: u32,
+    #[serde(default)]
+    score: u8,
+    release_group: Option<IdJson>,
+    #[serde(default)]
+    media: Vec<MediumJson>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ArtistCreditJson {
+    name: String,
+    #[serde(default)]
+    joinphrase: String,
+    artist: Option<IdJson>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IdJson {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct MediumJson {
+    position: Option<u32>,
+    #[serde(default)]
+    track_count: u32,
+    #[serde(default)]
+    tracks: Vec<TrackJson>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TrackJson {
+    id: String,
+    position: u32,
+    title: String,
+    recording: IdJson,
+}
+
+/// Join the credited artists like they are displayed, for example `A feat. B`
+fn artist_name(credits: &[ArtistCreditJson]) -> String {
+    credits
+        .iter()
+        .flat_map(|v| [v.name.as_str(), v.joinphrase.as_str()])
+        .collect()
+}
+
+/// Parse the candidates from a release search response
+pub fn to_candidates(json: &str) -> serde_json::Result<Vec<ReleaseCandidate>> {
+    let res: SearchResponse = from_str(json)?;
+
+    Ok(res
+        .releases
+        .into_iter()
+        .map(|v| {
+            // older servers do not include "track-count" on the release itself
+            let track_count = if v.track_count == 0 {
+                v.media.iter().map(|v| v.track_count).sum()
+            } else {
+                v.track_count
+            };
+
+            ReleaseCandidate {
+                artist: artist_name(&v.artist_credit),
+                id: v.id,
+                title: v.title,
+                date: v.date.filter(|v| !v.is_empty()),
+                country: v.country,
+                track_count,
+                score: v.score,
+            }
+        })
+        .collect())
+}
+
+/// Parse a release lookup response, which has to include `recordings` and `artist-credits`
+pub fn to_release(json: &str) -> serde_json::Result<Release> {
+    let res: ReleaseJson = from_str(json)?;
+
+    let mut tracks = Vec::new();
+    for (idx, medium) in res.media.into_iter().enumerate() {
+        let disc = medium
+            .position
+            .unwrap_or_else(|| u32::try_from(idx + 1).unwrap_or(u32::MAX));
+        let disc_track_count = u32::try_from(medium.tracks.len())
+            .unwrap_or(u32::MAX)
+            .max(medium.track_count);
+
+        tracks.extend(medium.tracks.into_iter().map(|v| ReleaseTrack {
+            disc,
+            position: v.position,
+            disc_track_count,
+            title: v.title,
+            track_id: v.id,
+            recording_id: v.recording.id,
+        }));
+    }
+
+    Ok(Release {
+        artist: artist_name(&res.artist_credit),
+        // the first credited artist is considered the main one
+        artist_id: res
+            .artist_credit
+            .into_iter()
+            .find_map(|v| v.artist)
+            .map(|v| v.id),
+        id: res.id,
+        title: res.title,
+        date: res.date.filter(|v| !v.is_empty()),
+        release_group_id: res.release_group.map(|v| v.id),
+        tracks,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_parse_candidates() {
+        let sample_data = r#"{
+            "created": "2024-01-01T00:00:00.000Z",
+            "count": 2,
+            "offset": 0,
+            "releases": [
+                {
+                    "id": "rel-1",
+                    "score": 100,
+                    "title": "Some Album",
+                    "status": "Official",
+                    "artist-credit": [
+                        {"name": "Artist A", "joinphrase": " feat. ", "artist": {"id": "art-a", "name": "Artist A"}},
+                        {"name": "Artist B", "artist": {"id": "art-b", "name": "Artist B"}}
+                    ],
+                    "date": "1997-05-21",
+                    "country": "GB",
+                    "track-count": 12,
+                    "media": [{"format": "CD", "track-count": 12}]
+                },
+                {
+                    "id": "rel-2",
+                    "score": 80,
+                    "title": "Some Album",
+                    "artist-credit": [{"name": "Artist A", "artist": {"id": "art-a"}}],
+                    "date": "",
+                    "media": [{"track-count": 6}, {"track-count": 7}]
+                }
+            ]
+        }"#;
+
+        let res = to_candidates(sample_data).unwrap();
+
+        assert_eq!(
+            res,
+            vec![
+                ReleaseCandidate {
+                    id: "rel-1".to_string(),
+                    title: "Some Album".to_string(),
+                    artist: "Artist A feat. Artist B".to_string(),
+                    date: Some("1997-05-21".to_string()),
+                    country: Some("GB".to_string()),
+                    track_count: 12,
+                    score: 100,
+                },
+                ReleaseCandidate {
+                    id: "rel-2".to_string(),
+                    title: "Some Album".to_string(),
+                    artist: "Artist A".to_string(),
+                    date: None,
+                    country: None,
+                    track_count: 13,
+                    score: 80,
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn should_parse_release() {
+        let sample_data = r#"{
+            "id": "rel-1",
+            "title": "Some Album",
+            "date": "1997",
+            "artist-credit": [{"name": "Artist A", "joinphrase": "", "artist": {"id": "art-a"}}],
+            "release-group": {"id": "rg-1", "primary-type": "Album"},
+            "media": [
+                {"position": 1, "track-count": 1, "tracks": [
+                    {"id": "tr-1", "number": "1", "position": 1, "title": "One", "recording": {"id": "rec-1", "title": "One"}}
+                ]},
+                {"position": 2, "track-count": 1, "tracks": [
+                    {"id": "tr-2", "number": "1", "position": 1, "title": "Two", "recording": {"id": "rec-2", "title": "Two"}}
+                ]}
+            ]
+        }"#;
+
+        let res = to_release(sample_data).unwrap();
+
+        assert_eq!(res.artist, "Artist A");
+        assert_eq!(res.artist_id.as_deref(), Some("art-a"));
+        assert_eq!(res.release_group_id.as_deref(), Some("rg-1"));
+        assert_eq!(res.year(), Some(1997));
+        assert_eq!(res.disc_total(), 2);
+        assert_eq!(
+            res.tracks[1],
+            ReleaseTrack {
+                disc: 2,
+                position: 1,
+                disc_track_count: 1,
+                title: "Two".to_string(),
+                track_id: "tr-2".to_string(),
+                recording_id: "rec-2".to_string(),
+            }
+        );
+    }
+}
@@ -0,0 +1,387 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use lofty::file::TaggedFileExt;
+use lofty::prelude::Accessor;
+use lofty::tag::{ItemKey, Tag};
+
+use super::{Release, ReleaseTrack};
+use crate::songtag::edit::{edit_tag, TagEdit};
+
+/// The tags of a file which are set from a [`Release`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReleaseTags {
+    pub album: Option<String>,
+    pub album_artist: Option<String>,
+    pub track: Option<u32>,
+    pub track_total: Option<u32>,
+    pub disc: Option<u32>,
+    pub disc_total: Option<u32>,
+    pub year: Option<u32>,
+    pub release_id: Option<String>,
+    pub release_group_id: Option<String>,
+    pub release_artist_id: Option<String>,
+    pub recording_id: Option<String>,
+    pub track_id: Option<String>,
+}
+
+/// The text items of [`ReleaseTags`], which are read and written the same way
+const TEXT_ITEMS: [(ItemKey, &str); 5] = [
+    (ItemKey::AlbumArtist, "Album Artist"),
+    (ItemKey::MusicBrainzReleaseId, "Release MBID"),
+    (ItemKey::MusicBrainzReleaseGroupId, "Release Group MBID"),
+    (ItemKey::MusicBrainzReleaseArtistId, "Album Artist MBID"),
+    (ItemKey::MusicBrainzRecordingId, "Recording MBID"),
+];
+
+impl ReleaseTags {
+    /// Get the tags `track` would have on `release`
+    #[must_use]
+    pub fn new(release: &Release, track: &ReleaseTrack) -> Self {
+        Self {
+            album: Some(release.title.clone()),
+            album_artist: Some(release.artist.clone()),
+            track: Some(track.position),
+            track_total: Some(track.disc_track_count),
+            disc: Some(track.disc),
+            disc_total: Some(release.disc_total()),
+            year: release.year(),
+            release_id: Some(release.id.clone()),
+            release_group_id: release.release_group_id.clone(),
+            release_artist_id: release.artist_id.clone(),
+            recording_id: Some(track.recording_id.clone()),
+            track_id: Some(track.track_id.clone()),
+        }
+    }
+
+    fn from_tag(tag: &Tag) -> Self {
+        let get = |key: &ItemKey| tag.get_string(key).map(ToOwned::to_owned);
+
+        Self {
+            album: tag.album().map(|v| v.to_string()),
+            album_artist: get(&ItemKey::AlbumArtist),
+            track: tag.track(),
+            track_total: tag.track_total(),
+            disc: tag.disk(),
+            disc_total: tag.disk_total(),
+            year: tag.year(),
+            release_id: get(&ItemKey::MusicBrainzReleaseId),
+            release_group_id: get(&ItemKey::MusicBrainzReleaseGroupId),
+            release_artist_id: get(&ItemKey::MusicBrainzReleaseArtistId),
+            recording_id: get(&ItemKey::MusicBrainzRecordingId),
+            track_id: get(&ItemKey::MusicBrainzTrackId),
+        }
+    }
+
+    /// Get the text items in the order of [`TEXT_ITEMS`]
+    fn text_items(&self) -> [&Option<String>; 5] {
+        [
+            &self.album_artist,
+            &self.release_id,
+            &self.release_group_id,
+            &self.release_artist_id,
+            &self.recording_id,
+        ]
+    }
+
+    /// Set all values which are [`Some`] on `tag`, other values are left as-is
+    fn apply_to(&self, tag: &mut dyn TagEdit) {
+        if let Some(album) = &self.album {
+            tag.set_album(album.clone());
+        }
+        if let Some(track) = self.track {
+            tag.set_track(track);
+        }
+        if let Some(track_total) = self.track_total {
+            tag.set_track_total(track_total);
+        }
+        if let Some(disc) = self.disc {
+            tag.set_disk(disc);
+        }
+        if let Some(disc_total) = self.disc_total {
+            tag.set_disk_total(disc_total);
+        }
+        if let Some(year) = self.year {
+            tag.set_year(year);
+        }
+        for ((key, _), value) in TEXT_ITEMS.iter().zip(self.text_items()) {
+            if let Some(value) = value {
+                tag.insert_text(key.clone(), value.clone());
+            }
+        }
+        if let Some(track_id) = &self.track_id {
+            tag.insert_text(ItemKey::MusicBrainzTrackId, track_id.clone());
+        }
+    }
+}
+
+/// A field which would change, see [`FileChange::diff`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDiff {
+    pub field: &'static str,
+    pub current: Option<String>,
+    pub new: String,
+}
+
+/// The planned change of one file of the album
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileChange {
+    pub path: PathBuf,
+    /// The title of the file, only used for matching
+    pub title: Option<String>,
+    pub current: ReleaseTags,
+    /// The new tags, or [`None`] if the file did not match any track of the release
+    pub new: Option<ReleaseTags>,
+}
+
+impl FileChange {
+    /// Read the current tags of the file at `path`
+    ///
+    /// # Errors
+    ///
+    /// If the file cannot be read as a audio file
+    pub fn read(path: &Path) -> Result<Self> {
+        let tagged_file = lofty::read_from_path(path).context("read tags")?;
+        let (title, current) = tagged_file
+            .primary_tag()
+            .or_else(|| tagged_file.first_tag())
+            .map(|tag| {
+                (
+                    tag.title().map(|v| v.to_string()),
+                    ReleaseTags::from_tag(tag),
+                )
+            })
+            .unwrap_or_default();
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            title,
+            current,
+            new: None,
+        })
+    }
+
+    /// Get all fields which would be changed by the new tags
+    #[must_use]
+    pub fn diff(&self) -> Vec<FieldDiff> {
+        let Some(new) = &self.new else {
+            return Vec::new();
+        };
+        let current = &self.current;
+
+        let mut diff = Vec::new();
+        let mut push = |field, current: Option<String>, new: Option<String>| {
+            if let Some(new) = new {
+                if current.as_ref() != Some(&new) {
+                    diff.push(FieldDiff {
+                        field,
+                        current,
+                        new,
+                    });
+                }
+            }
+        };
+        let num = |v: Option<u32>| v.map(|v| v.to_string());
+
+        push("Album", current.album.clone(), new.album.clone());
+        push("Track", num(current.track), num(new.track));
+        push(
+            "Track Total",
+            num(current.track_total),
+            num(new.track_total),
+        );
+        push("Disc", num(current.disc), num(new.disc));
+        push("Disc Total", num(current.disc_total), num(new.disc_total));
+        push("Year", num(current.year), num(new.year));
+        for ((_, field), (current, new)) in TEXT_ITEMS
+            .iter()
+            .zip(current.text_items().into_iter().zip(new.text_items()))
+        {
+            push(field, current.clone(), new.clone());
+        }
+        push("Track MBID", current.track_id.clone(), new.track_id.clone());
+
+        diff
+    }
+
+    /// Write the new tags to the file, if there are any, keeping all other tags of the file
+    ///
+    /// # Errors
+    ///
+    /// If reading or saving the file fails
+    pub fn write(&self) -> Result<()> {
+        let Some(new) = &self.new else {
+            return Ok(());
+        };
+
+        edit_tag(&self.path, |tag| {
+            new.apply_to(tag);
+            Ok(true)
+        })?;
+
+        Ok(())
+    }
+}
+
+/// Normalize a title for comparison
+fn normalize(title: &str) -> String {
+    title.trim().to_lowercase()
+}
+
+/// Match `files` to the tracks of `release` and set their new tags.
+///
+/// Files are matched by disc and track number first, then by title. Every track is matched at most once.
+pub fn match_release(release: &Release, files: &mut [FileChange]) {
+    let mut used = HashSet::new();
+
+    for file in files.iter_mut() {
+        let Some(number) = file.current.track else {
+            continue;
+        };
+        let disc = file.current.disc.unwrap_or(1);
+
+        if let Some((idx, track)) = release
+            .tracks
+            .iter()
+            .enumerate()
+            .find(|(idx, v)| v.disc == disc && v.position == number && !used.contains(idx))
+        {
+            used.insert(idx);
+            file.new = Some(ReleaseTags::new(release, track));
+        }
+    }
+
+    for file in files.iter_mut().filter(|v| v.new.is_none()) {
+        let Some(title) = file.title.as_deref().map(normalize) else {
+            continue;
+        };
+
+        if let Some((idx, track)) = release
+            .tracks
+            .iter()
+            .enumerate()
+            .find(|(idx, v)| normalize(&v.title) == title && !used.contains(idx))
+        {
+            used.insert(idx);
+            file.new = Some(ReleaseTags::new(release, track));
+        }
+    }
+}
+
+/// Write the new tags of all `files`, returning the files that failed
+#[must_use]
+pub fn write_all(files: &[FileChange]) -> Vec<(PathBuf, anyhow::Error)> {
+    files
+        .iter()
+        .filter_map(|file| file.write().err().map(|err| (file.path.clone(), err)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+    use std::path::PathBuf;
+
+    use lofty::id3::v2::{FrameId, Id3v2Tag};
+    use lofty::prelude::Accessor;
+
+    use super::{match_release, FileChange, ReleaseTags};
+    use crate::songtag::musicbrainz::{Release, ReleaseTrack};
+
+    fn release() -> Release {
+        let track = |position: u32, title: &str| ReleaseTrack {
+            disc: 1,
+            position,
+            disc_track_count: 3,
+            title: title.to_string(),
+            track_id: format!("tr-{position}"),
+            recording_id: format!("rec-{position}"),
+        };
+
+        Release {
+            id: "rel-1".to_string(),
+            title: "Some Album".to_string(),
+            artist: "Artist".to_string(),
+            artist_id: Some("art-1".to_string()),
+            date: Some("2001-02-03".to_string()),
+            release_group_id: Some("rg-1".to_string()),
+            tracks: vec![track(1, "One"), track(2, "Two"), track(3, "Three")],
+        }
+    }
+
+    fn file(name: &str, title: Option<&str>, track: Option<u32>) -> FileChange {
+        FileChange {
+            path: PathBuf::from(name),
+            title: title.map(ToOwned::to_owned),
+            current: ReleaseTags {
+                track,
+                ..Default::default()
+            },
+            new: None,
+        }
+    }
+
+    #[test]
+    fn should_match_by_number_then_title() {
+        let release = release();
+        let mut files = vec![
+            file("a", Some("Whatever"), Some(2)),
+            file("b", Some(" three"), None),
+            // track 2 is already taken
+            file("c", Some("Two"), Some(7)),
+            file("d", None, None),
+        ];
+
+        match_release(&release, &mut files);
+
+        let matched: Vec<Option<&str>> = files
+            .iter()
+            .map(|v| v.new.as_ref().and_then(|v| v.track_id.as_deref()))
+            .collect();
+        assert_eq!(matched, [Some("tr-2"), Some("tr-3"), None, None]);
+
+        let new = files[0].new.as_ref().unwrap();
+        assert_eq!(new.album.as_deref(), Some("Some Album"));
+        assert_eq!(new.year, Some(2001));
+        assert_eq!(new.track_total, Some(3));
+        assert_eq!(new.release_artist_id.as_deref(), Some("art-1"));
+    }
+
+    #[test]
+    fn should_only_diff_changed_fields() {
+        let release = release();
+        let mut change = file("a", Some("One"), Some(1));
+        change.current.album = Some("Some Album".to_string());
+        match_release(&release, std::slice::from_mut(&mut change));
+
+        let diff = change.diff();
+        let fields: Vec<&str> = diff.iter().map(|v| v.field).collect();
+        assert!(!fields.contains(&"Album"));
+        assert!(!fields.contains(&"Track"));
+        assert!(fields.contains(&"Year"));
+        assert!(fields.contains(&"Recording MBID"));
+
+        assert!(file("b", None, None).diff().is_empty());
+    }
+
+    #[test]
+    fn should_apply_release_tags_to_id3v2() {
+        let release = release();
+        let tags = ReleaseTags::new(&release, &release.tracks[1]);
+
+        let mut tag = Id3v2Tag::new();
+        tag.set_title("Two".to_string());
+        tags.apply_to(&mut tag);
+
+        assert_eq!(tag.title().as_deref(), Some("Two"));
+        assert_eq!(tag.album().as_deref(), Some("Some Album"));
+        assert_eq!(tag.track(), Some(2));
+        assert_eq!(tag.track_total(), Some(3));
+        assert_eq!(
+            tag.get_text(&FrameId::Valid(Cow::Borrowed("TPE2"))),
+            Some("Artist")
+        );
+        assert_eq!(tag.get_user_text("MusicBrainz Album Id"), Some("rel-1"));
+    }
+}
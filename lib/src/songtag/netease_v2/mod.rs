@@ -16,9 +16,10 @@ use super::service::{SongTagService, SongTagServiceError};
 
 mod model;
 
-const URL_SEARCH_NETEASE: &str = "https://music.163.com/weapi/search/get";
-const URL_LYRIC_NETEASE: &str = "https://music.163.com/weapi/song/lyric";
-const URL_DOWNLOAD_NETEASE: &str = "https://music.163.com/weapi/song/enhance/player/url/v1";
+const URL_BASE_NETEASE: &str = "https://music.163.com";
+const PATH_SEARCH_NETEASE: &str = "/weapi/search/get";
+const PATH_LYRIC_NETEASE: &str = "/weapi/song/lyric";
+const PATH_DOWNLOAD_NETEASE: &str = "/weapi/song/enhance/player/url/v1";
 const URL_PICTURE_SERVICE: &str = "https://p3.music.126.net/";
 
 const REFERER: &str = "https://music.163.com";
@@ -26,16 +27,30 @@ const HOST: &str = "music.163.com";
 
 pub struct Api {
     client: Client,
+    base_url: String,
 }
 
 impl Api {
     pub fn new() -> Self {
+        Self::with_base_url(URL_BASE_NETEASE)
+    }
+
+    /// Create a instance for the api at `base_url` instead of the public one
+    pub fn with_base_url(base_url: &str) -> Self {
         let client = ClientBuilder::new()
             .timeout(Duration::from_secs(10))
             .build()
             .expect("failed to build reqwest client.");
 
-        Self { client }
+        Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Get the full url for the api `path`
+    fn url(&self, path: &str) -> String {
+        format!("{}{path}", self.base_url)
     }
 
     fn common_request(builder: RequestBuilder, params: &[(&str, &str)]) -> RequestBuilder {
@@ -166,10 +181,13 @@ impl SongTagService for Api {
             ("limit", &limit),
         ];
 
-        let response = Self::common_request(self.client.post(URL_SEARCH_NETEASE), query_params)
-            .send()
-            .await
-            .map_err(anyhow::Error::from)?;
+        let response = Self::common_request(
+            self.client.post(self.url(PATH_SEARCH_NETEASE)),
+            query_params,
+        )
+        .send()
+        .await
+        .map_err(anyhow::Error::from)?;
 
         let result = response.text().await.map_err(anyhow::Error::from)?;
 
@@ -202,10 +220,11 @@ impl SongTagService for Api {
             ("tv", "-1"),
         ];
 
-        let response = Self::common_request(self.client.post(URL_LYRIC_NETEASE), query_params)
-            .send()
-            .await
-            .map_err(anyhow::Error::from)?;
+        let response =
+            Self::common_request(self.client.post(self.url(PATH_LYRIC_NETEASE)), query_params)
+                .send()
+                .await
+                .map_err(anyhow::Error::from)?;
 
         let result = response.text().await.map_err(anyhow::Error::from)?;
 
@@ -268,10 +287,13 @@ impl SongTagService for Api {
             ("encodeType", "aac"),
         ];
 
-        let response = Self::common_request(self.client.post(URL_DOWNLOAD_NETEASE), query_params)
-            .send()
-            .await
-            .map_err(anyhow::Error::from)?;
+        let response = Self::common_request(
+            self.client.post(self.url(PATH_DOWNLOAD_NETEASE)),
+            query_params,
+        )
+        .send()
+        .await
+        .map_err(anyhow::Error::from)?;
 
         let result = response.text().await.map_err(anyhow::Error::from)?;
 
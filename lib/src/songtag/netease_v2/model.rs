@@ -158,6 +158,7 @@ fn parse_song_info(v: &Value) -> Option<SongTag> {
         lyric_id,
         url: Some(urltype),
         album_id,
+        base_url: None,
     })
 }
 
@@ -297,7 +298,8 @@ mod tests {
                 lyric_id: Some("1000000000".to_owned()),
                 url: Some(UrlTypes::Protected),
                 pic_id: Some("444444444444444444".to_owned()),
-                album_id: Some("444444444444444444".to_owned())
+                album_id: Some("444444444444444444".to_owned()),
+                base_url: None,
             }
         );
 
@@ -313,7 +315,8 @@ mod tests {
                 lyric_id: Some("1111111111".to_owned()),
                 url: Some(UrlTypes::Protected),
                 pic_id: Some("555555555555555555".to_owned()),
-                album_id: Some("555555555555555555".to_owned())
+                album_id: Some("555555555555555555".to_owned()),
+                base_url: None,
             }
         );
     }
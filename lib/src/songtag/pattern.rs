@@ -0,0 +1,555 @@
+//! SPDX-License-Identifier: MIT
+//!
+//! File name patterns like `{albumartist}/{year} - {album}/{disc}{track:02} {title}`,
+//! to build file paths from tags or read tags from file paths.
+
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use super::batch::{BatchField, BatchFile};
+use super::sidecar::SIDECAR_EXTENSIONS;
+
+/// Characters which are not allowed in a path component on at least one common platform
+const INVALID_CHARS: [char; 9] = ['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Text(String),
+    /// A field with a optional zero-padded width
+    Field(BatchField, Option<usize>),
+}
+
+/// A parsed file name pattern
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    tokens: Vec<Token>,
+}
+
+fn field_from_key(key: &str) -> Option<BatchField> {
+    Some(match key {
+        "artist" => BatchField::Artist,
+        "albumartist" => BatchField::AlbumArtist,
+        "title" => BatchField::Title,
+        "album" => BatchField::Album,
+        "genre" => BatchField::Genre,
+        "year" => BatchField::Year,
+        "track" => BatchField::Track,
+        "disc" => BatchField::Disc,
+        _ => return None,
+    })
+}
+
+/// Make `value` usable as (part of) a single path component
+fn sanitize(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| {
+            if INVALID_CHARS.contains(&c) || c.is_control() {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+impl Pattern {
+    /// Parse a pattern, placeholders are a field name in braces, numeric fields may have a width like `{track:02}`.
+    ///
+    /// # Errors
+    ///
+    /// - if the pattern is empty
+    /// - if a placeholder is not closed or names a unknown field
+    /// - if a width is given for a non-numeric field
+    pub fn parse(pattern: &str) -> Result<Self> {
+        let pattern = pattern.trim().trim_matches('/');
+        if pattern.is_empty() {
+            bail!("Pattern is empty");
+        }
+
+        let mut tokens = Vec::new();
+        let mut rest = pattern;
+        while !rest.is_empty() {
+            let Some(start) = rest.find('{') else {
+                tokens.push(Token::Text(rest.to_string()));
+                break;
+            };
+            if start > 0 {
+                tokens.push(Token::Text(rest[..start].to_string()));
+            }
+            let end = rest[start..]
+                .find('}')
+                .map(|v| v + start)
+                .ok_or_else(|| anyhow!("Placeholder at {start} is not closed"))?;
+
+            let placeholder = &rest[start + 1..end];
+            let (key, width) = match placeholder.split_once(':') {
+                Some((key, width)) => (
+                    key,
+                    Some(
+                        width
+                            .parse::<usize>()
+                            .with_context(|| format!("Invalid width \"{width}\""))?,
+                    ),
+                ),
+                None => (placeholder, None),
+            };
+            let field = field_from_key(key.trim())
+                .ok_or_else(|| anyhow!("Unknown placeholder \"{{{placeholder}}}\""))?;
+            if width.is_some() && !field.is_number() {
+                bail!("Only numeric fields can have a width, in \"{{{placeholder}}}\"");
+            }
+
+            tokens.push(Token::Field(field, width));
+            rest = &rest[end + 1..];
+        }
+
+        Ok(Self { tokens })
+    }
+
+    /// Get the value of `field` to use in a path, with fallbacks for missing values
+    fn value(file: &BatchFile, field: BatchField, width: Option<usize>) -> String {
+        let value = file.get(field).map(str::trim).filter(|v| !v.is_empty());
+        let value = match field {
+            BatchField::AlbumArtist => value
+                .or_else(|| file.get(BatchField::Artist))
+                .unwrap_or("Unknown Artist")
+                .to_string(),
+            BatchField::Artist => value.unwrap_or("Unknown Artist").to_string(),
+            BatchField::Album => value.unwrap_or("Unknown Album").to_string(),
+            BatchField::Genre => value.unwrap_or("Unknown Genre").to_string(),
+            BatchField::Title => value
+                .map(ToOwned::to_owned)
+                .or_else(|| {
+                    file.path
+                        .file_stem()
+                        .map(|v| v.to_string_lossy().to_string())
+                })
+                .unwrap_or_else(|| "Unknown Title".to_string()),
+            BatchField::Year | BatchField::Track | BatchField::Disc => {
+                match (value.and_then(|v| v.parse::<u32>().ok()), width) {
+                    (Some(number), Some(width)) => format!("{number:0width$}"),
+                    (Some(number), None) => number.to_string(),
+                    (None, _) => String::new(),
+                }
+            }
+        };
+
+        sanitize(&value)
+    }
+
+    /// Get the path `file` would have under `base`, keeping the current extension.
+    ///
+    /// Values are sanitized, so that every `/` in the pattern is the only thing creating a new directory.
+    #[must_use]
+    pub fn target(&self, base: &Path, file: &BatchFile) -> PathBuf {
+        let mut formatted = String::new();
+        for token in &self.tokens {
+            match token {
+                Token::Text(text) => formatted.push_str(text),
+                Token::Field(field, width) => {
+                    formatted.push_str(&Self::value(file, *field, *width));
+                }
+            }
+        }
+
+        let mut components: Vec<String> = formatted
+            .split('/')
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(|v| match v {
+                "." | ".." => "_".to_string(),
+                v => v.to_string(),
+            })
+            .collect();
+        if components.is_empty() {
+            components.push("Unknown".to_string());
+        }
+
+        // not using "with_extension", as that would replace anything after a dot in the title
+        if let (Some(last), Some(ext)) = (components.last_mut(), file.path.extension()) {
+            last.push('.');
+            last.push_str(&ext.to_string_lossy());
+        }
+
+        let mut target = base.to_path_buf();
+        target.extend(components);
+        target
+    }
+
+    /// Read the field values from `path`, matched against the last components of `path` without the extension.
+    ///
+    /// A text field ends at the next text of the pattern, a numeric field only takes digits.
+    /// Returns [`None`] if `path` does not match the pattern, empty values are left out.
+    #[must_use]
+    pub fn extract(&self, path: &Path) -> Option<Vec<(BatchField, String)>> {
+        let depth = self
+            .tokens
+            .iter()
+            .map(|v| match v {
+                Token::Text(text) => text.matches('/').count(),
+                Token::Field(..) => 0,
+            })
+            .sum::<usize>()
+            + 1;
+
+        let stem = path.file_stem()?.to_string_lossy().to_string();
+        let mut components: Vec<String> = path
+            .parent()
+            .into_iter()
+            .flat_map(Path::components)
+            .filter(|v| matches!(v, Component::Normal(_)))
+            .rev()
+            .take(depth - 1)
+            .map(|v| v.as_os_str().to_string_lossy().to_string())
+            .collect();
+        if components.len() < depth - 1 {
+            return None;
+        }
+        components.reverse();
+        components.push(stem);
+        let input = components.join("/");
+
+        let mut values = Vec::new();
+        let mut pos = 0;
+        for (idx, token) in self.tokens.iter().enumerate() {
+            let rest = &input[pos..];
+            match token {
+                Token::Text(text) => {
+                    if !rest.starts_with(text.as_str()) {
+                        return None;
+                    }
+                    pos += text.len();
+                }
+                Token::Field(field, width) if field.is_number() => {
+                    let digits = rest.chars().take_while(char::is_ascii_digit).count();
+                    let len = match width {
+                        Some(width) => *width,
+                        // leave the digits the directly following fixed-width numbers need
+                        None => digits.checked_sub(self.following_widths(idx + 1))?,
+                    };
+                    if len == 0 || len > digits {
+                        return None;
+                    }
+                    let number: u32 = rest[..len].parse().ok()?;
+                    values.push((*field, number.to_string()));
+                    pos += len;
+                }
+                Token::Field(field, _) => {
+                    let len = match self.tokens.get(idx + 1) {
+                        Some(Token::Text(text)) => rest.find(text.as_str())?,
+                        Some(Token::Field(..)) => return None,
+                        None => rest.len(),
+                    };
+                    let value = rest[..len].trim();
+                    if value.contains('/') {
+                        return None;
+                    }
+                    if !value.is_empty() {
+                        values.push((*field, value.to_string()));
+                    }
+                    pos += len;
+                }
+            }
+        }
+
+        (pos == input.len()).then_some(values)
+    }
+
+    /// The sum of widths of the fixed-width numeric fields directly starting at `idx`
+    fn following_widths(&self, idx: usize) -> usize {
+        self.tokens
+            .iter()
+            .skip(idx)
+            .map_while(|v| match v {
+                Token::Field(field, Some(width)) if field.is_number() => Some(*width),
+                _ => None,
+            })
+            .sum()
+    }
+}
+
+/// What would happen to a file when moving it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveStatus {
+    /// The file will be moved
+    Move,
+    /// The file already has the target path
+    Unchanged,
+    /// A different file already exists at the target path
+    TargetExists,
+    /// Multiple files would be moved to the same target path
+    Duplicate,
+}
+
+impl MoveStatus {
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Move => "move",
+            Self::Unchanged => "unchanged",
+            Self::TargetExists => "target exists",
+            Self::Duplicate => "duplicate target",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileMove {
+    pub from: PathBuf,
+    pub to: PathBuf,
+    pub status: MoveStatus,
+}
+
+/// Check the given `(from, to)` pairs for conflicts, only moves with [`MoveStatus::Move`] will be done
+#[must_use]
+pub fn plan_moves(pairs: Vec<(PathBuf, PathBuf)>) -> Vec<FileMove> {
+    let targets: Vec<PathBuf> = pairs.iter().map(|v| v.1.clone()).collect();
+    pairs
+        .into_iter()
+        .map(|(from, to)| {
+            let status = if from == to {
+                MoveStatus::Unchanged
+            } else if targets.iter().filter(|v| **v == to).count() > 1 {
+                MoveStatus::Duplicate
+            } else if to.exists() {
+                MoveStatus::TargetExists
+            } else {
+                MoveStatus::Move
+            };
+
+            FileMove { from, to, status }
+        })
+        .collect()
+}
+
+/// Move a single file, across filesystems if necessary
+///
+/// Fails if `to` already exists, as it may have been created since the moves were planned and renaming would overwrite it.
+fn move_file(from: &Path, to: &Path) -> Result<()> {
+    if to.exists() {
+        bail!("target \"{}\" already exists", to.display());
+    }
+    if let Some(parent) = to.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("create directory \"{}\"", parent.display()))?;
+    }
+    if std::fs::rename(from, to).is_err() {
+        std::fs::copy(from, to).context("copy file")?;
+        std::fs::remove_file(from).context("remove old file")?;
+    }
+
+    Ok(())
+}
+
+/// The result of [`apply_moves`]
+#[derive(Debug, Default)]
+pub struct MoveResult {
+    /// `(from, to)` of all moved files
+    pub moved: Vec<(PathBuf, PathBuf)>,
+    /// The files that could not be moved
+    pub failed: Vec<(PathBuf, anyhow::Error)>,
+    /// The directories that were removed as they were left empty
+    pub removed_dirs: Vec<PathBuf>,
+}
+
+/// Do all moves with [`MoveStatus::Move`], moving sidecar lyric files along.
+///
+/// Directories left empty are only removed if `remove_empty_dirs` is set.
+#[must_use]
+pub fn apply_moves(plan: &[FileMove], remove_empty_dirs: bool) -> MoveResult {
+    let mut result = MoveResult::default();
+    for file_move in plan.iter().filter(|v| v.status == MoveStatus::Move) {
+        if let Err(err) = move_file(&file_move.from, &file_move.to) {
+            result.failed.push((file_move.from.clone(), err));
+            continue;
+        }
+
+        // sidecars are named "<basename>.<ext>", see "sidecar::sidecar_candidates"
+        if let (Some(from_stem), Some(to_stem)) =
+            (file_move.from.file_stem(), file_move.to.file_stem())
+        {
+            for ext in SIDECAR_EXTENSIONS {
+                let from = file_move
+                    .from
+                    .with_file_name(format!("{}.{ext}", from_stem.to_string_lossy()));
+                let to = file_move
+                    .to
+                    .with_file_name(format!("{}.{ext}", to_stem.to_string_lossy()));
+                if from.exists() && !to.exists() {
+                    if let Err(err) = move_file(&from, &to) {
+                        warn!("Failed to move sidecar \"{}\": {err:#}", from.display());
+                    }
+                }
+            }
+        }
+
+        // only succeeds if the directory is empty
+        if let Some(parent) = file_move.from.parent().filter(|_| remove_empty_dirs) {
+            if std::fs::remove_dir(parent).is_ok() {
+                result.removed_dirs.push(parent.to_path_buf());
+            }
+        }
+
+        result
+            .moved
+            .push((file_move.from.clone(), file_move.to.clone()));
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use pretty_assertions::assert_eq;
+
+    use super::{apply_moves, plan_moves, MoveStatus, Pattern};
+    use crate::songtag::batch::{BatchField, BatchFile};
+
+    fn file() -> BatchFile {
+        BatchFile {
+            path: PathBuf::from("/music/incoming/track.01.mp3"),
+            artist: Some("Artist".to_string()),
+            title: Some("Who? What.".to_string()),
+            album: Some("AC/DC".to_string()),
+            year: Some("1999".to_string()),
+            track: Some("3".to_string()),
+            disc: Some("1".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn should_parse_patterns() {
+        assert!(Pattern::parse("{artist} - {title}").is_ok());
+        assert!(Pattern::parse("{disc}{track:02} {title}").is_ok());
+        assert!(Pattern::parse("").is_err());
+        assert!(Pattern::parse("{artist").is_err());
+        assert!(Pattern::parse("{composer}").is_err());
+        assert!(Pattern::parse("{title:02}").is_err());
+    }
+
+    #[test]
+    fn should_build_target() {
+        let pattern =
+            Pattern::parse("{albumartist}/{year} - {album}/{disc}{track:02} {title}").unwrap();
+        assert_eq!(
+            pattern.target(Path::new("/music"), &file()),
+            PathBuf::from("/music/Artist/1999 - AC_DC/103 Who_ What..mp3")
+        );
+
+        let mut file = file();
+        file.title = None;
+        file.artist = None;
+        file.album = None;
+        file.year = None;
+        assert_eq!(
+            pattern.target(Path::new("/music"), &file),
+            PathBuf::from("/music/Unknown Artist/- Unknown Album/103 track.01.mp3")
+        );
+    }
+
+    #[test]
+    fn should_extract_fields() {
+        let pattern = Pattern::parse("{artist} - {title}").unwrap();
+        assert_eq!(
+            pattern.extract(Path::new("/music/Some Artist - A - B.flac")),
+            Some(vec![
+                (BatchField::Artist, "Some Artist".to_string()),
+                (BatchField::Title, "A - B".to_string()),
+            ])
+        );
+        assert_eq!(pattern.extract(Path::new("/music/Title.flac")), None);
+
+        let pattern = Pattern::parse("{artist}/{album}/{disc}{track:02} {title}").unwrap();
+        assert_eq!(
+            pattern.extract(Path::new("/music/Artist/Album/105 Title.ogg")),
+            Some(vec![
+                (BatchField::Artist, "Artist".to_string()),
+                (BatchField::Album, "Album".to_string()),
+                (BatchField::Disc, "1".to_string()),
+                (BatchField::Track, "5".to_string()),
+                (BatchField::Title, "Title".to_string()),
+            ])
+        );
+        assert_eq!(pattern.extract(Path::new("Album/105 Title.ogg")), None);
+    }
+
+    #[test]
+    fn should_detect_conflicts() {
+        let dir = std::env::temp_dir().join(format!("termusic-pattern-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let existing = dir.join("existing.mp3");
+        std::fs::write(&existing, "").unwrap();
+
+        let moves = plan_moves(vec![
+            (dir.join("a.mp3"), dir.join("a.mp3")),
+            (dir.join("b.mp3"), existing),
+            (dir.join("c.mp3"), dir.join("same.mp3")),
+            (dir.join("d.mp3"), dir.join("same.mp3")),
+            (dir.join("e.mp3"), dir.join("new.mp3")),
+        ]);
+        let status: Vec<MoveStatus> = moves.iter().map(|v| v.status).collect();
+        assert_eq!(
+            status,
+            [
+                MoveStatus::Unchanged,
+                MoveStatus::TargetExists,
+                MoveStatus::Duplicate,
+                MoveStatus::Duplicate,
+                MoveStatus::Move,
+            ]
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn should_not_overwrite_targets_created_after_planning() {
+        let dir =
+            std::env::temp_dir().join(format!("termusic-pattern-apply-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let from = dir.join("from.mp3");
+        let to = dir.join("to.mp3");
+        std::fs::write(&from, "from").unwrap();
+
+        let moves = plan_moves(vec![(from.clone(), to.clone())]);
+        assert_eq!(moves[0].status, MoveStatus::Move);
+        std::fs::write(&to, "to").unwrap();
+
+        let result = apply_moves(&moves, true);
+        assert!(result.moved.is_empty());
+        assert_eq!(result.failed.len(), 1);
+        assert_eq!(result.failed[0].0, from);
+        assert_eq!(std::fs::read_to_string(&from).unwrap(), "from");
+        assert_eq!(std::fs::read_to_string(&to).unwrap(), "to");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn should_only_remove_empty_dirs_if_asked() {
+        let dir =
+            std::env::temp_dir().join(format!("termusic-pattern-dirs-{}", std::process::id()));
+        let old_a = dir.join("old_a");
+        let old_b = dir.join("old_b");
+        std::fs::create_dir_all(&old_a).unwrap();
+        std::fs::create_dir_all(&old_b).unwrap();
+        std::fs::write(old_a.join("a.mp3"), "a").unwrap();
+        std::fs::write(old_b.join("b.mp3"), "b").unwrap();
+
+        let moves = plan_moves(vec![(old_a.join("a.mp3"), dir.join("new/a.mp3"))]);
+        let result = apply_moves(&moves, false);
+        assert_eq!(result.moved.len(), 1);
+        assert!(result.removed_dirs.is_empty());
+        assert!(old_a.is_dir());
+
+        let moves = plan_moves(vec![(old_b.join("b.mp3"), dir.join("new/b.mp3"))]);
+        let result = apply_moves(&moves, true);
+        assert_eq!(result.moved.len(), 1);
+        assert_eq!(result.removed_dirs, vec![old_b.clone()]);
+        assert!(!old_b.exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
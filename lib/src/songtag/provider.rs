@@ -0,0 +1,243 @@
+use std::collections::HashSet;
+
+use anyhow::{anyhow, Result};
+use lofty::picture::Picture;
+
+use super::service::SongTagService;
+use super::{kugou, lrclib, migu, netease_v2, ServiceProvider, SongTag};
+use crate::config::v2::tui::LyricProvider;
+
+/// Call the same [`SongTagService`] function on whichever api is wrapped
+macro_rules! dispatch {
+    ($self:ident, $api:ident => $call:expr) => {
+        match $self {
+            Self::Netease($api) => $call.await.map_err(|v| anyhow!(v)),
+            Self::Kugou($api) => $call.await.map_err(|v| anyhow!(v)),
+            Self::Migu($api) => $call.await.map_err(|v| anyhow!(v)),
+            Self::Lrclib($api) => $call.await.map_err(|v| anyhow!(v)),
+        }
+    };
+}
+
+/// The api of any [`ServiceProvider`]
+pub enum ProviderApi {
+    Netease(netease_v2::Api),
+    Kugou(kugou::Api),
+    Migu(migu::Api),
+    Lrclib(lrclib::Api),
+}
+
+impl ProviderApi {
+    /// Create the api for `provider`, at `base_url` if set and supported
+    pub fn new(provider: ServiceProvider, base_url: Option<&str>) -> Self {
+        match (provider, base_url) {
+            (ServiceProvider::Netease, None) => Self::Netease(netease_v2::Api::new()),
+            (ServiceProvider::Netease, Some(url)) => {
+                Self::Netease(netease_v2::Api::with_base_url(url))
+            }
+            (ServiceProvider::Kugou, _) => Self::Kugou(kugou::Api::new()),
+            (ServiceProvider::Migu, _) => Self::Migu(migu::Api::new()),
+            (ServiceProvider::Lrclib, None) => Self::Lrclib(lrclib::Api::new()),
+            (ServiceProvider::Lrclib, Some(url)) => Self::Lrclib(lrclib::Api::with_base_url(url)),
+        }
+    }
+
+    /// Get whether the api of `provider` can be used at a different base url
+    pub const fn supports_base_url(provider: ServiceProvider) -> bool {
+        matches!(provider, ServiceProvider::Netease | ServiceProvider::Lrclib)
+    }
+
+    pub async fn search_recording(
+        &self,
+        keywords: &str,
+        offset: u32,
+        limit: u32,
+    ) -> Result<Vec<SongTag>> {
+        dispatch!(self, api => api.search_recording(keywords, offset, limit))
+    }
+
+    pub async fn get_lyrics(&self, song: &SongTag) -> Result<String> {
+        dispatch!(self, api => api.get_lyrics(song))
+    }
+
+    pub async fn get_picture(&self, song: &SongTag) -> Result<Picture> {
+        dispatch!(self, api => api.get_picture(song))
+    }
+
+    pub async fn download_recording(&self, song: &SongTag) -> Result<String> {
+        dispatch!(self, api => api.download_recording(song))
+    }
+}
+
+/// A registered provider (instance) to search with
+struct RegisteredProvider {
+    provider: ServiceProvider,
+    base_url: Option<String>,
+    api: ProviderApi,
+}
+
+/// The providers to search with, in order of priority
+#[derive(Default)]
+pub struct ProviderRegistry {
+    providers: Vec<RegisteredProvider>,
+}
+
+impl ProviderRegistry {
+    /// Register all enabled providers from `settings`, ordered by their priority
+    pub fn from_settings(settings: &[LyricProvider]) -> Self {
+        let mut enabled: Vec<&LyricProvider> = settings.iter().filter(|v| v.enabled).collect();
+        // stable, so equal priorities keep the order from the config
+        enabled.sort_by_key(|v| v.priority);
+
+        let mut registry = Self::default();
+        for entry in enabled {
+            registry.register(entry.provider, entry.base_url.as_deref());
+        }
+
+        registry
+    }
+
+    /// Register `provider` after all the existing ones
+    pub fn register(&mut self, provider: ServiceProvider, base_url: Option<&str>) {
+        let base_url = base_url.filter(|_| {
+            let supported = ProviderApi::supports_base_url(provider);
+            if !supported {
+                warn!(
+                    "Provider \"{provider}\" does not support a custom base_url, using the default"
+                );
+            }
+            supported
+        });
+
+        self.providers.push(RegisteredProvider {
+            provider,
+            base_url: base_url.map(ToOwned::to_owned),
+            api: ProviderApi::new(provider, base_url),
+        });
+    }
+
+    /// Search all providers in parallel for `keywords`, with up to `limit` results each.
+    ///
+    /// The results are in order of the providers, a song already found by a earlier provider is skipped.
+    /// Errors of a provider are only logged.
+    pub async fn search(&self, keywords: &str, limit: u32) -> Vec<SongTag> {
+        let searches = self.providers.iter().map(|entry| async move {
+            (entry, entry.api.search_recording(keywords, 0, limit).await)
+        });
+        let results = futures_util::future::join_all(searches).await;
+
+        let mut seen = HashSet::new();
+        let mut merged = Vec::new();
+        for (entry, result) in results {
+            let list = match result {
+                Ok(list) => list,
+                Err(err) => {
+                    error!("{} Error: {err:#}", entry.provider);
+                    continue;
+                }
+            };
+
+            for mut song in list {
+                if dedup_key(&song).is_some_and(|key| !seen.insert(key)) {
+                    continue;
+                }
+                song.base_url.clone_from(&entry.base_url);
+                merged.push(song);
+            }
+        }
+
+        merged
+    }
+}
+
+/// Get the key a song is considered a duplicate by, if it has a title
+fn dedup_key(song: &SongTag) -> Option<(String, String, String)> {
+    let normalize = |v: Option<&str>| v.unwrap_or_default().trim().to_lowercase();
+
+    song.title()?;
+    Some((
+        normalize(song.artist()),
+        normalize(song.title()),
+        normalize(song.album()),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProviderRegistry;
+    use crate::config::v2::tui::LyricProvider;
+    use crate::songtag::test_utils::mock_server;
+    use crate::songtag::{ServiceProvider, SongTag};
+
+    #[tokio::test]
+    async fn should_merge_by_priority_and_dedup() {
+        let instance_a = mock_server(vec![(
+            "/api/search",
+            r#"[
+                {"id": 1, "trackName": "Track", "artistName": "Artist", "albumName": "Album"},
+                {"id": 2, "trackName": "Only A", "artistName": "Artist", "albumName": "Album"}
+            ]"#
+            .to_string(),
+        )]);
+        let instance_b = mock_server(vec![(
+            "/api/search",
+            r#"[
+                {"id": 10, "trackName": "track ", "artistName": "ARTIST", "albumName": "Album"},
+                {"id": 11, "trackName": "Only B", "artistName": "Artist", "albumName": "Album"}
+            ]"#
+            .to_string(),
+        )]);
+        let disabled = mock_server(vec![(
+            "/api/search",
+            r#"[{"id": 20, "trackName": "Disabled", "artistName": "Artist"}]"#.to_string(),
+        )]);
+
+        let settings = vec![
+            LyricProvider {
+                priority: 2,
+                base_url: Some(instance_a.clone()),
+                ..Default::default()
+            },
+            LyricProvider {
+                priority: 1,
+                base_url: Some(instance_b.clone()),
+                ..Default::default()
+            },
+            LyricProvider {
+                enabled: false,
+                base_url: Some(disabled),
+                ..Default::default()
+            },
+        ];
+
+        let registry = ProviderRegistry::from_settings(&settings);
+        let res = registry.search("Artist", 30).await;
+
+        let ids: Vec<&str> = res.iter().map(SongTag::id).collect();
+        // "b" has the higher priority, so its "Track" is kept
+        assert_eq!(ids, ["10", "11", "2"]);
+        assert_eq!(res[0].base_url.as_deref(), Some(instance_b.as_str()));
+        assert_eq!(res[2].base_url.as_deref(), Some(instance_a.as_str()));
+        assert!(res
+            .iter()
+            .all(|v| v.service_provider() == ServiceProvider::Lrclib));
+    }
+
+    #[tokio::test]
+    async fn should_skip_failing_provider() {
+        let working = mock_server(vec![(
+            "/api/search",
+            r#"[{"id": 1, "trackName": "Track", "artistName": "Artist"}]"#.to_string(),
+        )]);
+        // serves nothing, so every request is a "404"
+        let failing = mock_server(Vec::new());
+
+        let mut registry = ProviderRegistry::default();
+        registry.register(ServiceProvider::Lrclib, Some(&failing));
+        registry.register(ServiceProvider::Lrclib, Some(&working));
+
+        let res = registry.search("Artist", 30).await;
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].id(), "1");
+    }
+}
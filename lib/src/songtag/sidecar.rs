@@ -0,0 +1,155 @@
+//! SPDX-License-Identifier: MIT
+//!
+//! Lyric files next to (or near) the audio file, like `<basename>.lrc` or `Artist - Title.lrc`.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use id3::frame::Lyrics;
+
+/// File extensions of sidecar lyric files, in order of precedence
+pub const SIDECAR_EXTENSIONS: [&str; 2] = ["lrc", "txt"];
+
+/// The description given to lyric frames read from a sidecar file
+pub const SIDECAR_DESCRIPTION: &str = "Sidecar";
+
+/// Get all paths a sidecar lyric file for the track at `track_path` may be at, in order of precedence.
+///
+/// The names searched are `<basename>` and `Artist - Title`, in all of `dirs`, which are relative to the track's directory
+/// (`.` being the track's directory itself). Synced (`.lrc`) lyrics in any directory take precedence over `.txt` files.
+#[must_use]
+pub fn sidecar_candidates(
+    track_path: &Path,
+    artist: Option<&str>,
+    title: Option<&str>,
+    dirs: &[PathBuf],
+) -> Vec<PathBuf> {
+    let track_dir = track_path.parent().unwrap_or(Path::new("."));
+
+    let mut stems = Vec::with_capacity(2);
+    if let Some(stem) = track_path.file_stem() {
+        stems.push(stem.to_string_lossy().to_string());
+    }
+    if let (Some(artist), Some(title)) = (artist, title) {
+        // path separators cannot be part of a file name
+        let stem = format!("{artist} - {title}").replace(['/', '\\'], "_");
+        if !stems.contains(&stem) {
+            stems.push(stem);
+        }
+    }
+
+    let mut candidates = Vec::new();
+    for ext in SIDECAR_EXTENSIONS {
+        for dir in dirs {
+            let dir = track_dir.join(dir);
+            for stem in &stems {
+                candidates.push(dir.join(format!("{stem}.{ext}")));
+            }
+        }
+    }
+
+    candidates
+}
+
+/// Find the first existing sidecar lyric file for the track at `track_path`, see [`sidecar_candidates`].
+#[must_use]
+pub fn find_sidecar(
+    track_path: &Path,
+    artist: Option<&str>,
+    title: Option<&str>,
+    dirs: &[PathBuf],
+) -> Option<PathBuf> {
+    sidecar_candidates(track_path, artist, title, dirs)
+        .into_iter()
+        .find(|path| path.is_file())
+}
+
+/// Read the sidecar lyric file at `path` as a lyric frame.
+///
+/// # Errors
+///
+/// - if reading the file fails
+pub fn read_sidecar(path: &Path) -> Result<Lyrics> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("reading sidecar lyrics \"{}\"", path.display()))?;
+
+    Ok(Lyrics {
+        lang: "eng".to_string(),
+        description: SIDECAR_DESCRIPTION.to_string(),
+        text,
+    })
+}
+
+/// Get the path lyrics of the track at `track_path` are exported to: `<basename>.lrc` next to the track,
+/// or `<basename>.txt` if the lyrics are not synced.
+#[must_use]
+pub fn sidecar_export_path(track_path: &Path, synced: bool) -> PathBuf {
+    let ext = if synced {
+        SIDECAR_EXTENSIONS[0]
+    } else {
+        SIDECAR_EXTENSIONS[1]
+    };
+
+    track_path.with_extension(ext)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use pretty_assertions::assert_eq;
+
+    use super::{sidecar_candidates, sidecar_export_path};
+
+    #[test]
+    fn should_list_candidates_in_order() {
+        let candidates = sidecar_candidates(
+            Path::new("/music/album/01 Song.mp3"),
+            Some("Artist"),
+            Some("AC/DC Title"),
+            &[PathBuf::from("."), PathBuf::from("lyrics")],
+        );
+
+        assert_eq!(
+            candidates,
+            [
+                "/music/album/./01 Song.lrc",
+                "/music/album/./Artist - AC_DC Title.lrc",
+                "/music/album/lyrics/01 Song.lrc",
+                "/music/album/lyrics/Artist - AC_DC Title.lrc",
+                "/music/album/./01 Song.txt",
+                "/music/album/./Artist - AC_DC Title.txt",
+                "/music/album/lyrics/01 Song.txt",
+                "/music/album/lyrics/Artist - AC_DC Title.txt",
+            ]
+            .map(PathBuf::from)
+        );
+    }
+
+    #[test]
+    fn should_not_repeat_same_stem() {
+        let candidates = sidecar_candidates(
+            Path::new("/music/Artist - Title.flac"),
+            Some("Artist"),
+            Some("Title"),
+            &[PathBuf::from("/lyrics")],
+        );
+
+        assert_eq!(
+            candidates,
+            ["/lyrics/Artist - Title.lrc", "/lyrics/Artist - Title.txt"].map(PathBuf::from)
+        );
+    }
+
+    #[test]
+    fn should_export_next_to_track() {
+        assert_eq!(
+            sidecar_export_path(Path::new("/music/Song.mp3"), true),
+            PathBuf::from("/music/Song.lrc")
+        );
+        assert_eq!(
+            sidecar_export_path(Path::new("/music/Song.mp3"), false),
+            PathBuf::from("/music/Song.txt")
+        );
+    }
+}
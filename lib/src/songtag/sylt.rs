@@ -0,0 +1,196 @@
+//! SPDX-License-Identifier: MIT
+//!
+//! ID3v2 `SYLT` (synchronized lyrics) frames, converted to and from [`Lyric`].
+//!
+//! Lyrics are kept as LRC text in a `USLT` frame, a synced lyric is additionally written as a `SYLT` frame with the same language and description
+//! for other players.
+
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::Result;
+use id3::frame::Lyrics;
+use lofty::config::ParseOptions;
+use lofty::file::AudioFile;
+use lofty::id3::v2::{
+    BinaryFrame, Frame, FrameId, SyncTextContentType, SynchronizedTextFrame, TimestampFormat,
+};
+use lofty::mpeg::MpegFile;
+use lofty::TextEncoding;
+
+use super::lrc::Lyric;
+
+/// The ID3v2 frame id for synchronized lyrics
+const SYLT_ID: &str = "SYLT";
+
+/// Read all `SYLT` lyric frames (with millisecond timestamps) from the ID3v2 tag of the given MPEG file, as LRC text.
+pub fn read_sylt_frames(path: &Path) -> Result<Vec<Lyrics>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let file = MpegFile::read_from(
+        &mut reader,
+        ParseOptions::new()
+            .read_properties(false)
+            .read_cover_art(false),
+    )?;
+
+    let Some(tag) = file.id3v2() else {
+        return Ok(Vec::new());
+    };
+
+    Ok(tag.into_iter().filter_map(sylt_as_lyrics).collect())
+}
+
+/// Parse the given frame, if it is a `SYLT` frame.
+fn parse_sylt(frame: &Frame<'_>) -> Option<SynchronizedTextFrame<'static>> {
+    let Frame::Binary(frame) = frame else {
+        return None;
+    };
+    if frame.id().as_str() != SYLT_ID {
+        return None;
+    }
+
+    SynchronizedTextFrame::parse(&frame.data, frame.flags())
+        .inspect_err(|err| warn!("Failed to parse SYLT frame: {err:#?}"))
+        .ok()
+}
+
+/// Check if the given frame is a `SYLT` frame with the same language and description as `lyrics`.
+#[must_use]
+pub fn is_sylt_frame_of(frame: &Frame<'_>, lyrics: &Lyrics) -> bool {
+    parse_sylt(frame).is_some_and(|sylt| {
+        sylt.language.escape_ascii().to_string() == lyrics.lang
+            && sylt.description.unwrap_or_default() == lyrics.description
+    })
+}
+
+/// Convert the given frame to [`Lyrics`], if it is a `SYLT` frame with lyrics in millisecond timestamps.
+fn sylt_as_lyrics(frame: &Frame<'_>) -> Option<Lyrics> {
+    let sylt = parse_sylt(frame)?;
+
+    // MPEG frame timestamps would need the frame duration of the file, which is rarely used
+    if sylt.timestamp_format != TimestampFormat::MS
+        || !matches!(
+            sylt.content_type,
+            SyncTextContentType::Lyrics | SyncTextContentType::TextTranscription
+        )
+    {
+        return None;
+    }
+
+    Some(Lyrics {
+        lang: sylt.language.escape_ascii().to_string(),
+        description: sylt.description.unwrap_or_default(),
+        text: Lyric::from_synced(&sylt.content).as_lrc_text(),
+    })
+}
+
+/// Add the `SYLT` lyrics `frames` to `lyric_frames`, unless there is already a frame with the same language and description.
+///
+/// Frames written by termusic have the same lyrics in both `USLT` and `SYLT`, so only the `USLT` frame is kept.
+pub fn merge_sylt_frames(lyric_frames: &mut Vec<Lyrics>, frames: Vec<Lyrics>) {
+    for frame in frames {
+        if !lyric_frames
+            .iter()
+            .any(|v| v.lang == frame.lang && v.description == frame.description)
+        {
+            lyric_frames.push(frame);
+        }
+    }
+}
+
+/// Create a `SYLT` frame for the given lyrics, if they are synced LRC.
+#[must_use]
+pub fn sylt_frame(lyrics: &Lyrics) -> Option<Frame<'static>> {
+    let lyric = Lyric::from_str(&lyrics.text)
+        .ok()
+        .filter(|v| !v.captions.is_empty())?;
+
+    let lang = lyrics.lang.as_bytes().try_into().unwrap_or(*b"eng");
+
+    let sylt = SynchronizedTextFrame::new(
+        TextEncoding::UTF8,
+        lang,
+        TimestampFormat::MS,
+        SyncTextContentType::Lyrics,
+        Some(lyrics.description.clone()),
+        lyric.as_synced(),
+    );
+    let data = sylt
+        .as_bytes()
+        .inspect_err(|err| warn!("Failed to create SYLT frame: {err:#?}"))
+        .ok()?;
+
+    Some(Frame::Binary(BinaryFrame::new(
+        FrameId::Valid(Cow::Borrowed(SYLT_ID)),
+        data,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use id3::frame::Lyrics;
+    use lofty::id3::v2::{Frame, SynchronizedTextFrame};
+    use pretty_assertions::assert_eq;
+
+    use super::{merge_sylt_frames, sylt_as_lyrics, sylt_frame};
+
+    fn lyrics(description: &str, text: &str) -> Lyrics {
+        Lyrics {
+            lang: "eng".to_string(),
+            description: description.to_string(),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn should_convert_synced_lyrics_to_sylt_and_back() {
+        let lyric = lyrics("Synced", "[00:01.00]First line\n[00:05.00]Second line\n");
+
+        let frame = sylt_frame(&lyric).unwrap();
+
+        let Frame::Binary(binary) = &frame else {
+            panic!("Expected a binary frame");
+        };
+        let sylt = SynchronizedTextFrame::parse(&binary.data, binary.flags()).unwrap();
+        assert_eq!(&sylt.language, b"eng");
+        assert_eq!(sylt.description.as_deref(), Some("Synced"));
+        assert_eq!(
+            sylt.content,
+            vec![
+                (1000, "First line".to_string()),
+                (5000, "Second line".to_string())
+            ]
+        );
+
+        assert_eq!(sylt_as_lyrics(&frame), Some(lyric));
+    }
+
+    #[test]
+    fn should_not_create_sylt_for_unsynced_lyrics() {
+        assert!(sylt_frame(&lyrics("", "Just some text")).is_none());
+    }
+
+    #[test]
+    fn should_merge_only_new_frames() {
+        let mut frames = vec![lyrics("Synced", "[00:01.00]Line")];
+
+        merge_sylt_frames(
+            &mut frames,
+            vec![
+                lyrics("Synced", "[00:01.00]Line\n"),
+                lyrics("Other", "[00:02.00]Other\n"),
+            ],
+        );
+
+        assert_eq!(
+            frames,
+            vec![
+                lyrics("Synced", "[00:01.00]Line"),
+                lyrics("Other", "[00:02.00]Other\n")
+            ]
+        );
+    }
+}
@@ -23,7 +23,9 @@ use lofty::{
 use lru::LruCache;
 
 use crate::{
-    player::playlist_helpers::PlaylistTrackSource, podcast::episode::Episode, songtag::lrc::Lyric,
+    player::playlist_helpers::PlaylistTrackSource,
+    podcast::episode::Episode,
+    songtag::{lrc::Lyric, sidecar, sylt},
     utils::SplitArrayIter,
 };
 
@@ -116,6 +118,12 @@ pub struct TrackData {
     path: PathBuf,
 
     album: Option<String>,
+    album_artist: Option<String>,
+    track_number: Option<u32>,
+    disc_number: Option<u32>,
+    /// Rating in `0..=100`
+    rating: Option<u8>,
+    play_count: Option<u64>,
 
     file_type: Option<FileType>,
 }
@@ -138,6 +146,33 @@ impl TrackData {
         self.album.as_deref()
     }
 
+    #[must_use]
+    pub fn album_artist(&self) -> Option<&str> {
+        self.album_artist.as_deref()
+    }
+
+    #[must_use]
+    pub fn track_number(&self) -> Option<u32> {
+        self.track_number
+    }
+
+    #[must_use]
+    pub fn disc_number(&self) -> Option<u32> {
+        self.disc_number
+    }
+
+    /// The rating stored in the file, normalized to `0..=100`
+    #[must_use]
+    pub fn rating(&self) -> Option<u8> {
+        self.rating
+    }
+
+    /// The play count stored in the file
+    #[must_use]
+    pub fn play_count(&self) -> Option<u64> {
+        self.play_count
+    }
+
     /// The lofty File-Type; may not exist if lofty could not parse the file.
     ///
     /// Note that if lofty cannot parse the file, that **does not** mean that symphonia cannot play it.
@@ -154,6 +189,11 @@ impl TrackData {
         Self {
             path,
             album: None,
+            album_artist: None,
+            track_number: None,
+            disc_number: None,
+            rating: None,
+            play_count: None,
             file_type: None,
         }
     }
@@ -251,9 +291,12 @@ impl Track {
             &path,
             MetadataOptions {
                 album: true,
+                album_artist: true,
                 artist: true,
                 title: true,
                 duration: true,
+                track_numbers: true,
+                rating: true,
                 ..Default::default()
             },
         ) {
@@ -269,18 +312,29 @@ impl Track {
             }
         };
 
+        Ok(Self::from_path_with_metadata(path, metadata))
+    }
+
+    /// Create a new Track from a local file with already known metadata, without reading the file.
+    #[must_use]
+    pub fn from_path_with_metadata(path: PathBuf, metadata: TrackMetadata) -> Self {
         let track_data = TrackData {
             path,
             album: metadata.album,
+            album_artist: metadata.album_artist,
+            track_number: metadata.track_number,
+            disc_number: metadata.disc_number,
+            rating: metadata.rating,
+            play_count: metadata.play_count,
             file_type: metadata.file_type,
         };
 
-        Ok(Self {
+        Self {
             inner: MediaTypes::Track(track_data),
             duration: metadata.duration,
             title: metadata.title,
             artist: metadata.artist,
-        })
+        }
     }
 
     #[must_use]
@@ -460,10 +514,25 @@ impl Track {
         }
     }
 
+    /// Remove the cached picture of the track at `path`, so that the next [`get_picture`](Self::get_picture) reads it again.
+    pub fn forget_cached_picture(path: &Path) {
+        PICTURE_CACHE.with_borrow_mut(|cache| {
+            cache.pop(path);
+        });
+    }
+
+    /// Remove the cached lyrics data of the track at `path`, so that the next [`get_lyrics`](Self::get_lyrics) reads it again.
+    pub fn forget_cached_lyrics(path: &Path) {
+        LYRIC_CACHE.with_borrow_mut(|cache| {
+            cache.pop(path);
+        });
+    }
+
     /// Get the lyrics data for the current Track.
     ///
+    /// A sidecar lyric file found in `sidecar_dirs` comes first, before the lyrics embedded in the file.
     /// Only works for Music Tracks.
-    pub fn get_lyrics(&self) -> Result<Option<Arc<LyricData>>> {
+    pub fn get_lyrics(&self, sidecar_dirs: &[PathBuf]) -> Result<Option<Arc<LyricData>>> {
         let Some(track_data) = self.as_track() else {
             bail!("Track is not a Music Track!");
         };
@@ -480,7 +549,20 @@ impl Track {
                             ..Default::default()
                         },
                     )?;
-                    let lyric_frames = result.lyric_frames.unwrap_or_default();
+                    let mut lyric_frames = result.lyric_frames.unwrap_or_default();
+
+                    let sidecar = sidecar::find_sidecar(
+                        track_data.path(),
+                        self.artist(),
+                        self.title(),
+                        sidecar_dirs,
+                    );
+                    if let Some(sidecar) = sidecar {
+                        match sidecar::read_sidecar(&sidecar) {
+                            Ok(frame) => lyric_frames.insert(0, frame),
+                            Err(err) => warn!("{err:#}"),
+                        }
+                    }
 
                     let parsed_lyric = lyric_frames
                         .first()
@@ -636,6 +718,10 @@ pub struct MetadataOptions<'a> {
     pub title: bool,
     pub duration: bool,
     pub genre: bool,
+    /// Read both the track number and disc number
+    pub track_numbers: bool,
+    /// Read both the rating and play count
+    pub rating: bool,
     pub cover: bool,
     pub lyrics: bool,
     pub file_times: bool,
@@ -655,6 +741,8 @@ impl MetadataOptions<'_> {
             title: true,
             duration: true,
             genre: true,
+            track_numbers: true,
+            rating: true,
             cover: true,
             lyrics: true,
             file_times: true,
@@ -684,6 +772,14 @@ pub struct TrackMetadata {
     pub duration: Option<Duration>,
     /// ID3v2 tag `TCON` or equivalent
     pub genre: Option<String>,
+    /// ID3v2 tag `TRCK` or equivalent
+    pub track_number: Option<u32>,
+    /// ID3v2 tag `TPOS` or equivalent
+    pub disc_number: Option<u32>,
+    /// ID3v2 tag `POPM` rating or equivalent (like `RATING`), normalized to `0..=100`
+    pub rating: Option<u8>,
+    /// ID3v2 tag `POPM` counter
+    pub play_count: Option<u64>,
     /// ID3v2 tag `APIC` or equivalent
     pub cover: Option<Picture>,
     /// ID3v2 tags `USLT` or equivalent
@@ -727,6 +823,16 @@ pub fn parse_metadata_from_file(
         handle_tag(tag, options, &mut res);
     }
 
+    // lofty does not parse "SYLT" frames into the generic tag
+    if options.lyrics && res.file_type == Some(FileType::Mpeg) {
+        let lyric_frames = res.lyric_frames.get_or_insert_with(Vec::new);
+        match sylt::read_sylt_frames(path) {
+            Ok(frames) => sylt::merge_sylt_frames(lyric_frames, frames),
+            Err(err) => warn!("Failed to read SYLT frames of {}: {err:#}", path.display()),
+        }
+        sort_lyric_frames(lyric_frames);
+    }
+
     if options.file_times {
         if let Ok(metadata) = std::fs::metadata(path) {
             let filetimes = FileTimes {
@@ -807,6 +913,15 @@ fn handle_tag(tag: &LoftyTag, options: MetadataOptions<'_>, res: &mut TrackMetad
     if options.genre {
         res.genre = tag.genre().map(Cow::into_owned);
     }
+    if options.track_numbers {
+        res.track_number = tag.track();
+        res.disc_number = tag.disk();
+    }
+    if options.rating {
+        if let Some(item) = tag.get(&ItemKey::Popularimeter) {
+            (res.rating, res.play_count) = parse_popularimeter(item.value());
+        }
+    }
 
     if options.cover {
         res.cover = tag
@@ -824,6 +939,55 @@ fn handle_tag(tag: &LoftyTag, options: MetadataOptions<'_>, res: &mut TrackMetad
     }
 }
 
+/// Parse a `Popularimeter` item into `(Rating, PlayCount)`, the rating is normalized to `0..=100`.
+///
+/// The value is either the raw ID3v2 `POPM` frame (`Email\0`, `Rating` as `0..=255`, `Counter` as big-endian)
+/// or a text rating as used in other formats, which may be stars (`0..=5`), percent (`0..=100`) or `0..=255`.
+fn parse_popularimeter(value: &ItemValue) -> (Option<u8>, Option<u64>) {
+    match value {
+        ItemValue::Binary(data) => {
+            let Some(email_end) = data.iter().position(|v| *v == 0) else {
+                return (None, None);
+            };
+            let Some((rating, counter)) = data[email_end + 1..].split_first() else {
+                return (None, None);
+            };
+            // the counter may be omitted, and may be bigger than 4 bytes (but we only support up to 8)
+            let play_count = (!counter.is_empty() && counter.len() <= 8).then(|| {
+                counter
+                    .iter()
+                    .fold(0u64, |acc, v| (acc << 8) | u64::from(*v))
+            });
+
+            (
+                Some(normalize_rating(f64::from(*rating), 255.0)),
+                play_count,
+            )
+        }
+        ItemValue::Text(text) => {
+            let Ok(rating) = text.trim().parse::<f64>() else {
+                return (None, None);
+            };
+            let scale = if rating <= 5.0 {
+                5.0
+            } else if rating <= 100.0 {
+                100.0
+            } else {
+                255.0
+            };
+
+            (Some(normalize_rating(rating, scale)), None)
+        }
+        ItemValue::Locator(_) => (None, None),
+    }
+}
+
+/// Normalize `rating` in `0..=scale` to `0..=100`
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // clamped to 0..=100 before casting
+fn normalize_rating(rating: f64, scale: f64) -> u8 {
+    (rating / scale * 100.0).round().clamp(0.0, 100.0) as u8
+}
+
 /// Create a iterator which separates `artist` with options from `options`
 #[inline]
 fn split_artists<'a>(
@@ -836,11 +1000,24 @@ fn split_artists<'a>(
         .map(ToString::to_string)
 }
 
+/// The Vorbis comment some taggers use for lyrics without timestamps, in addition to `LYRICS`
+pub(crate) const UNSYNCED_LYRICS_KEY: &str = "UNSYNCEDLYRICS";
+
 /// Fetch all lyrics from the given Lofty tag into the given array.
+///
+/// This includes ID3v2 `USLT`, MP4 `©lyr` and the `LYRICS` and `UNSYNCEDLYRICS` Vorbis comments.
 fn get_lyrics_from_tags(tag: &LoftyTag, lyric_frames: &mut Vec<Id3Lyrics>) {
-    let lyrics = tag.get_items(&ItemKey::Lyrics);
+    let unsynced_key = ItemKey::Unknown(UNSYNCED_LYRICS_KEY.to_string());
+    let lyrics = tag
+        .get_items(&ItemKey::Lyrics)
+        .chain(tag.get_items(&unsynced_key));
     for lyric in lyrics {
         if let ItemValue::Text(lyrics_text) = lyric.value() {
+            // some taggers write the same text to both "LYRICS" and "UNSYNCEDLYRICS"
+            if lyric_frames.iter().any(|v| v.text == *lyrics_text) {
+                continue;
+            }
+
             lyric_frames.push(Id3Lyrics {
                 lang: lyric.lang().escape_ascii().to_string(),
                 description: lyric.description().to_string(),
@@ -849,6 +1026,11 @@ fn get_lyrics_from_tags(tag: &LoftyTag, lyric_frames: &mut Vec<Id3Lyrics>) {
         }
     }
 
+    sort_lyric_frames(lyric_frames);
+}
+
+/// Sort the lyric frames by their description.
+fn sort_lyric_frames(lyric_frames: &mut [Id3Lyrics]) {
     lyric_frames.sort_by(|a, b| {
         a.description
             .to_lowercase()
@@ -879,4 +1061,36 @@ mod tests {
             );
         }
     }
+
+    mod popularimeter {
+        use lofty::tag::ItemValue;
+
+        use crate::track::parse_popularimeter;
+
+        #[test]
+        fn should_parse_id3_frame() {
+            // email, rating 255, counter 258
+            let data = b"someone@example.com\0\xFF\0\0\x01\x02".to_vec();
+            assert_eq!(
+                parse_popularimeter(&ItemValue::Binary(data)),
+                (Some(100), Some(258))
+            );
+
+            // no counter
+            let data = b"\0\x80".to_vec();
+            assert_eq!(
+                parse_popularimeter(&ItemValue::Binary(data)),
+                (Some(50), None)
+            );
+        }
+
+        #[test]
+        fn should_parse_text_ratings() {
+            let parse = |v: &str| parse_popularimeter(&ItemValue::Text(v.to_string())).0;
+            assert_eq!(parse("4"), Some(80));
+            assert_eq!(parse("60"), Some(60));
+            assert_eq!(parse("255"), Some(100));
+            assert_eq!(parse("not a number"), None);
+        }
+    }
 }
@@ -5,7 +5,10 @@ use crate::config::v2::tui::{keys::KeyBinding, theme::styles::ColorTermusic};
 use crate::ids::{IdConfigEditor, IdKey};
 use crate::invidious::{Instance, YoutubeVideo};
 use crate::library_db::SearchCriteria;
+use crate::player::playlist_helpers::PlaylistSort;
 use crate::podcast::{EpData, PodcastFeed, PodcastNoId};
+use crate::songtag::cover::CoverKind;
+use crate::songtag::musicbrainz::{FileChange, Release, ReleaseCandidate};
 use crate::songtag::SongTag;
 use anyhow::{anyhow, Result};
 use image::DynamicImage;
@@ -28,9 +31,17 @@ pub enum Msg {
     LyricMessage(LyricMsg),
     LyricCycle,
     LyricAdjustDelay(i64),
+    /// Shows the confirmation to save the adjusted lyric offset
+    LyricOffsetConfirmShow,
+    /// Saves the adjusted lyric offset and closes the confirmation
+    LyricOffsetConfirmCloseOk,
+    /// Closes the confirmation without saving
+    LyricOffsetConfirmCloseCancel,
+    NamedPlaylist(NamedPlaylistMsg),
     Player(PlayerMsg),
     Playlist(PLMsg),
     Podcast(PCMsg),
+    Queue(QueueMsg),
     /// Closes the Quit Popup, if it was shown without quitting.
     QuitPopupCloseCancel,
     /// Always will directly quit.
@@ -70,8 +81,15 @@ pub enum PlayerMsg {
     VolumeDown,
     SpeedUp,
     SpeedDown,
+    PitchUp,
+    PitchDown,
+    PitchFineUp,
+    PitchFineDown,
     SeekForward,
     SeekBackward,
+    ABLoopMarkA,
+    ABLoopMarkB,
+    ABLoopClear,
 }
 
 /// Save Playlist Popup related messages
@@ -85,6 +103,48 @@ pub enum SavePlaylistMsg {
     ConfirmCloseOk(String),
 }
 
+/// Messages for the named playlists stored on the server
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NamedPlaylistMsg {
+    /// Show the list of named playlists and request it from the server
+    PopupShow,
+    PopupCloseCancel,
+    /// Load the named playlist at INDEX in the list
+    Load(usize),
+    /// Show the input to save the current playlist under a (new) name
+    SaveAsShow,
+    /// Show the input to rename the named playlist at INDEX in the list
+    RenameShow(usize),
+    InputCloseCancel,
+    /// Save the current playlist as NAME
+    SaveAs(String),
+    /// Save the marked tracks of the current playlist as NAME
+    SaveMarkedAs(String),
+    /// Rename named playlist `(FROM, TO)`
+    Rename(String, String),
+    /// Show the confirmation to delete the named playlist at INDEX in the list
+    DeleteShow(usize),
+    DeleteCloseCancel,
+    /// Delete named playlist NAME
+    DeleteCloseOk(String),
+}
+
+/// Messages for the "play next" queue
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum QueueMsg {
+    /// Add a directory / file to the queue
+    Add(PathBuf),
+    /// Add the playlist track at INDEX to the queue
+    AddFromPlaylist(usize),
+    /// Show the queue
+    PopupShow,
+    PopupCloseCancel,
+    /// Remove INDEX from the queue
+    Delete(usize),
+    /// Clear the queue
+    DeleteAll,
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum XYWHMsg {
     /// Toggle the hidden / shown status of the displayed image.
@@ -285,6 +345,8 @@ pub enum KFMsg {
     GlobalLyricAdjustBackwardBlurUp,
     GlobalLyricCycleBlurDown,
     GlobalLyricCycleBlurUp,
+    GlobalLyricSaveOffsetBlurDown,
+    GlobalLyricSaveOffsetBlurUp,
     GlobalPlayerNextBlurDown,
     GlobalPlayerNextBlurUp,
     GlobalPlayerPreviousBlurDown,
@@ -347,6 +409,30 @@ pub enum KFMsg {
     PlaylistAddRandomAlbumBlurUp,
     PlaylistAddRandomTracksBlurDown,
     PlaylistAddRandomTracksBlurUp,
+    PlaylistNamedPlaylistsBlurDown,
+    PlaylistNamedPlaylistsBlurUp,
+    PlaylistQueueNextBlurDown,
+    PlaylistQueueNextBlurUp,
+    PlaylistShowQueueBlurDown,
+    PlaylistShowQueueBlurUp,
+    LibraryQueueNextBlurDown,
+    LibraryQueueNextBlurUp,
+    PlaylistUndoBlurDown,
+    PlaylistUndoBlurUp,
+    PlaylistRedoBlurDown,
+    PlaylistRedoBlurUp,
+    PlaylistSortBlurDown,
+    PlaylistSortBlurUp,
+    PlaylistMarkBlurDown,
+    PlaylistMarkBlurUp,
+    PlaylistMarkRangeBlurDown,
+    PlaylistMarkRangeBlurUp,
+    PlaylistMoveTopBlurDown,
+    PlaylistMoveTopBlurUp,
+    PlaylistMoveBottomBlurDown,
+    PlaylistMoveBottomBlurUp,
+    PlaylistSaveMarkedBlurDown,
+    PlaylistSaveMarkedBlurUp,
     LibrarySwitchRootBlurDown,
     LibrarySwitchRootBlurUp,
     LibraryAddRootBlurDown,
@@ -369,6 +455,20 @@ pub enum KFMsg {
     GlobalXywhZoomOutBlurUp,
     GlobalXywhHideBlurDown,
     GlobalXywhHideBlurUp,
+    GlobalPlayerABLoopMarkABlurDown,
+    GlobalPlayerABLoopMarkABlurUp,
+    GlobalPlayerABLoopMarkBBlurDown,
+    GlobalPlayerABLoopMarkBBlurUp,
+    GlobalPlayerABLoopClearBlurDown,
+    GlobalPlayerABLoopClearBlurUp,
+    GlobalPlayerPitchUpBlurDown,
+    GlobalPlayerPitchUpBlurUp,
+    GlobalPlayerPitchDownBlurDown,
+    GlobalPlayerPitchDownBlurUp,
+    GlobalPlayerPitchFineUpBlurDown,
+    GlobalPlayerPitchFineUpBlurUp,
+    GlobalPlayerPitchFineDownBlurDown,
+    GlobalPlayerPitchFineDownBlurUp,
     PodcastMarkPlayedBlurDown,
     PodcastMarkPlayedBlurUp,
     PodcastMarkAllPlayedBlurDown,
@@ -433,6 +533,8 @@ pub enum DBMsg {
     SearchTrack(usize),
     SearchTracksBlurDown,
     SearchTracksBlurUp,
+    /// Open the tag editor for all tracks of a single result (from view `Result`)
+    OpenTagEditor(usize),
 
     AddAllResultsConfirmShow,
     AddAllResultsConfirmCancel,
@@ -488,7 +590,7 @@ pub enum PLMsg {
     PlaylistTableBlurUp,
     /// Add a directory / file to the playlist
     Add(PathBuf),
-    /// Remove INDEX from playlist
+    /// Remove the marked tracks from the playlist, or INDEX if none are marked
     Delete(usize),
     /// Clear the Playlist
     DeleteAll,
@@ -500,14 +602,43 @@ pub enum PLMsg {
     PlaySelected(usize),
     /// Shuffle the current items in the playlist
     Shuffle,
-    /// Swap a entry at INDEX with +1 (down)
+    /// Swap a entry at INDEX with +1 (down), or move the marked tracks down by one as a block
     SwapDown(usize),
-    /// Swap a entry at INDEX with -1 (up)
+    /// Swap a entry at INDEX with -1 (up), or move the marked tracks up by one as a block
     SwapUp(usize),
+    /// Move the marked tracks to the top of the playlist, or INDEX if none are marked
+    MoveTop(usize),
+    /// Move the marked tracks to the bottom of the playlist, or INDEX if none are marked
+    MoveBottom(usize),
+    /// Mark / unmark the track at INDEX
+    Mark(usize),
+    /// Start marking a range of tracks at INDEX
+    MarkRangeStart(usize),
+    /// Move the end of the range being marked to INDEX
+    MarkRangeUpdate(usize),
+    /// Stop marking a range, keeping the tracks in it marked
+    MarkRangeEnd,
+    /// Unmark all tracks
+    MarkClear,
+    /// Show the input to save the marked tracks as a named playlist
+    SaveMarkedShow,
+    /// Open the tag editor for the marked tracks, or INDEX if none are marked
+    OpenTagEditor(usize),
     /// Start choosing random albums to be added to the playlist
     AddRandomAlbum,
     /// Start choosing random tracks to be added to the playlist
     AddRandomTracks,
+    /// Undo the last edit to the playlist
+    Undo,
+    /// Redo the last undone edit to the playlist
+    Redo,
+    /// Show the popup to sort or deduplicate the playlist
+    SortPopupShow,
+    SortPopupCloseCancel,
+    /// Sort the playlist
+    Sort(PlaylistSort),
+    /// Remove repeated tracks from the playlist
+    Deduplicate,
 }
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum GSMsg {
@@ -554,6 +685,8 @@ pub enum YSMsg {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum TEMsg {
     TagEditorRun(String),
+    /// Open the tag editor for all the given files at once, editing the fields they have in common
+    TagEditorRunBatch(Vec<PathBuf>),
     TagEditorClose,
     TECounterDeleteOk,
     TEDownload(usize),
@@ -564,10 +697,148 @@ pub enum TEMsg {
     TESelectLyricOk(usize),
 
     TESearchLyricResult(SongTagRecordingResult),
+
+    /// Analyze the loudness of the current track's album and write `ReplayGain` tags.
+    TEAnalyzeLoudness,
+    /// Progress of the loudness analysis.
+    ///
+    /// `(Current, Total)`
+    TELoudnessProgress(usize, usize),
+    /// The loudness analysis has finished.
+    ///
+    /// `(Tagged, Failed)`
+    TELoudnessDone(usize, usize),
+
+    /// Add the sidecar lyric file of the current track as a new lyric frame.
+    TEEmbedSidecar,
+    /// Export the selected lyric frame of the current track to a sidecar lyric file.
+    TEExportSidecar,
+
+    /// Tap-to-sync lyric editor related messages
+    TELyricSync(LyricSyncMsg),
+
+    /// `MusicBrainz` release lookup related messages
+    TEMusicBrainz(MusicBrainzMsg),
+
+    /// Batch tag editing related messages
+    TEBatch(BatchTagMsg),
+
+    /// File name pattern related messages
+    TEPattern(PatternMsg),
+
+    /// Cover art related messages
+    TECoverArt(CoverArtMsg),
+}
+
+/// Cover art related messages
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CoverArtMsg {
+    /// Show the cover art actions
+    Open,
+    /// Close the cover art actions
+    Close,
+    /// Ask for a image file to embed as the given cover
+    Choose(CoverKind),
+    /// Go back from the image file input to the actions
+    ChooseClose,
+    /// Embed the image file at the given path as the given cover
+    Embed(CoverKind, String),
+    /// Write the given cover to a image file next to the track
+    Extract(CoverKind),
+    /// Remove the given cover, or all pictures if [`None`]
+    Remove(Option<CoverKind>),
+    /// Scale down and re-compress oversized pictures
+    Shrink,
+    /// The pictures of the given files have been changed
+    ///
+    /// `(What, Paths, Changed, Failed)`
+    Done(String, Vec<PathBuf>, usize, usize),
+}
+
+/// File name pattern related messages
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PatternMsg {
+    /// Show where the rename pattern would move the files to
+    Rename,
+    /// Show the tags the parse pattern would read from the file paths
+    TagsFromFilename,
+    /// Do the shown moves or write the shown tags
+    Apply,
+    /// Close the preview without changes
+    Close,
+    /// The files have been moved
+    ///
+    /// `(Moved (from, to), Failed, Removed empty directories)`
+    RenameDone(Vec<(PathBuf, PathBuf)>, usize, usize),
+    /// The tags have been written
+    ///
+    /// `(Written, Failed)`
+    TagsDone(usize, usize),
+}
+
+/// Batch tag editing related messages
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BatchTagMsg {
+    /// Show the changes the current field values would make to all files
+    Preview,
+    /// Go back from the preview to editing the fields
+    PreviewClose,
+    /// Write the shown changes to all files
+    Write,
+    /// The changes have been written
+    ///
+    /// `(Written, Failed)`
+    Done(usize, usize),
+}
+
+/// `MusicBrainz` release lookup related messages
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MusicBrainzMsg {
+    /// Search releases for the album of the current track
+    Search,
+    /// The search was a success, with all candidates
+    SearchSuccess(Vec<ReleaseCandidate>),
+    /// Fetch the candidate at INDEX and show the changes to the album
+    Select(usize),
+    /// The release was fetched and matched to the files of the album
+    ReleaseLoaded(Box<Release>, Vec<FileChange>),
+    /// The search or fetching the release has failed, with error message
+    Fail(String),
+    /// Write the shown changes to all files of the album
+    Apply,
+    /// The changes have been written
+    ///
+    /// `(Written, Failed)`
+    Done(usize, usize),
+    /// Go back from the changes to the candidates
+    Back,
+    /// Close the lookup without changes
+    Close,
+}
+
+/// Tap-to-sync lyric editor related messages
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LyricSyncMsg {
+    /// Start the editor with the selected lyric frame and play the track
+    Start,
+    /// Stamp the next line with the current position
+    Stamp,
+    /// Remove the timestamp of the last stamped line
+    Undo,
+    /// Move the last stamped line by the given milliseconds
+    Nudge(i64),
+    /// Toggle showing the line at the current position
+    TogglePreview,
+    /// Save the synced lyrics into the selected lyric frame and the file
+    Save,
+    /// Stop the editor without saving
+    Cancel,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum TFMsg {
+    BatchFilesBlurDown,
+    BatchFilesBlurUp,
     CounterDeleteBlurDown,
     CounterDeleteBlurUp,
     InputArtistBlurDown,
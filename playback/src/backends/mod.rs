@@ -2,7 +2,7 @@ use std::{error::Error, fmt::Display};
 
 use termusiclib::config::{v2::server::Backend as ConfigBackend, SharedServerSettings};
 
-use crate::{PlayerCmdSender, PlayerTrait};
+use crate::{PlayerCmdSender, PlayerTrait, SpectrumTX};
 
 #[cfg(feature = "gst")]
 mod gstreamer;
@@ -75,13 +75,14 @@ impl Backend {
         backend: BackendSelect,
         config: SharedServerSettings,
         cmd_tx: PlayerCmdSender,
+        spectrum_tx: SpectrumTX,
     ) -> Self {
         match backend {
             #[cfg(feature = "mpv")]
             BackendSelect::Mpv => Self::new_mpv(&config, cmd_tx),
             #[cfg(feature = "gst")]
             BackendSelect::GStreamer => Self::new_gstreamer(&config, cmd_tx),
-            BackendSelect::Rusty => Self::new_rusty(config, cmd_tx),
+            BackendSelect::Rusty => Self::new_rusty(config, cmd_tx, spectrum_tx),
         }
     }
 
@@ -98,9 +99,13 @@ impl Backend {
     // }
 
     /// Explicitly choose Backend [`RustyBackend`](rusty::RustyBackend)
-    fn new_rusty(config: SharedServerSettings, cmd_tx: PlayerCmdSender) -> Self {
+    fn new_rusty(
+        config: SharedServerSettings,
+        cmd_tx: PlayerCmdSender,
+        spectrum_tx: SpectrumTX,
+    ) -> Self {
         info!("Using Backend \"rusty\"");
-        Self::Rusty(rusty::RustyBackend::new(config, cmd_tx))
+        Self::Rusty(rusty::RustyBackend::new(config, cmd_tx, spectrum_tx))
     }
 
     /// Explicitly choose Backend [`GstreamerBackend`](gstreamer::GStreamerBackend)
@@ -27,24 +27,29 @@ use stream_download::{Settings as StreamSettings, StreamDownload};
 use symphonia::core::io::{
     MediaSource, MediaSourceStream, MediaSourceStreamOptions, ReadOnlySource,
 };
+use termusiclib::config::v2::server::backends::SilenceSkipSettings;
 use termusiclib::config::SharedServerSettings;
+use termusiclib::player::ABLoopRange;
 use termusiclib::track::{MediaTypes, Track};
 use tokio::runtime::Handle;
 use tokio::select;
 
 use crate::backends::rusty::decoder::SymphoniaDecoderError;
-use crate::{MediaInfo, PlayerCmd, PlayerProgress, PlayerTrait, Speed, Volume};
+use crate::{MediaInfo, Pitch, PlayerCmd, PlayerProgress, PlayerTrait, SpectrumTX, Speed, Volume};
 use decoder::buffered_source::BufferedSource;
 use decoder::read_seek_source::ReadSeekSource;
 use decoder::{MediaTitleRx, MediaTitleType, Symphonia};
 use sink::{Sink, SourceOptions};
 use source::async_ring::{AsyncRingSource, AsyncRingSourceProvider, SeekData};
+use source::{SilenceSkipOptions, SourceExt};
+use spectrum::SpectrumAnalyzer;
 
 mod decoder;
 mod icy_metadata;
 mod sink;
 // public to bench lower modules
 pub(crate) mod source;
+mod spectrum;
 
 pub type TotalDuration = Option<Duration>;
 pub type ArcTotalDuration = Arc<Mutex<TotalDuration>>;
@@ -59,17 +64,24 @@ enum PlayerInternalCmd {
     SeekRelative(i64),
     Skip,
     Speed(i32),
+    /// Set the pitch shift in cents
+    Pitch(i32),
     Stop,
     TogglePause,
     Volume(u16),
     Eos,
+    /// Set or clear the A-B loop of the current track
+    ABLoop(Option<ABLoopRange>),
 }
 pub struct RustyBackend {
     volume: Arc<AtomicU16>,
     speed: i32,
+    pitch: Pitch,
     gapless: bool,
     command_tx: Sender<PlayerInternalCmd>,
     position: Arc<Mutex<Duration>>,
+    /// The time skipped as silence in the current track
+    silence_skipped: Arc<Mutex<Option<Duration>>>,
     total_duration: ArcTotalDuration,
     media_title: Arc<Mutex<String>>,
     pub radio_downloaded: Arc<Mutex<u64>>,
@@ -79,7 +91,11 @@ pub struct RustyBackend {
 
 impl RustyBackend {
     #[allow(clippy::similar_names)]
-    pub fn new(config: SharedServerSettings, cmd_tx: crate::PlayerCmdSender) -> Self {
+    pub fn new(
+        config: SharedServerSettings,
+        cmd_tx: crate::PlayerCmdSender,
+        spectrum_tx: SpectrumTX,
+    ) -> Self {
         let config_read = config.read();
         let (picmd_tx, picmd_rx): (Sender<PlayerInternalCmd>, Receiver<PlayerInternalCmd>) =
             mpsc::channel();
@@ -87,12 +103,19 @@ impl RustyBackend {
         let volume = Arc::new(AtomicU16::from(config_read.settings.player.volume));
         let volume_local = volume.clone();
         let speed = config_read.settings.player.speed;
+        let pitch = config_read
+            .settings
+            .player
+            .pitch
+            .clamp(crate::MIN_PITCH, crate::MAX_PITCH);
         let gapless = config_read.settings.player.gapless;
         drop(config_read);
         let position = Arc::new(Mutex::new(Duration::default()));
         let total_duration = Arc::new(Mutex::new(None));
         let total_duration_local = total_duration.clone();
         let position_local = position.clone();
+        let silence_skipped = Arc::new(Mutex::new(None));
+        let silence_skipped_local = silence_skipped.clone();
         let pcmd_tx_local = cmd_tx;
         let media_title = Arc::new(Mutex::new(String::new()));
         let media_title_local = media_title.clone();
@@ -112,8 +135,11 @@ impl RustyBackend {
                     media_title: media_title_local,
                     // radio_downloaded_local,
                     position: position_local,
+                    silence_skipped: silence_skipped_local,
                     volume_inside: volume_local,
                     speed_inside: speed,
+                    pitch_inside: pitch,
+                    spectrum_tx,
                 }));
             })
             .expect("failed to spawn thread");
@@ -122,9 +148,11 @@ impl RustyBackend {
             total_duration,
             volume,
             speed,
+            pitch,
             gapless,
             command_tx: picmd_tx,
             position,
+            silence_skipped,
             media_title,
             radio_downloaded,
             // cmd_tx_outside: cmd_tx,
@@ -145,6 +173,7 @@ impl PlayerTrait for RustyBackend {
     async fn add_and_play(&mut self, track: &Track) {
         let config_read = self.config.read_recursive();
         let soundtouch = config_read.settings.backends.rusty.soundtouch;
+        let silence_skip = config_read.settings.backends.rusty.silence_skip;
         let file_buf_size = usize::try_from(
             config_read
                 .settings
@@ -171,6 +200,7 @@ impl PlayerTrait for RustyBackend {
             QueueNextOptions {
                 gapless_decode: self.gapless,
                 soundtouch,
+                silence_skip,
                 file_buf_size,
                 ringbuf_size,
                 enqueue: false,
@@ -225,6 +255,17 @@ impl PlayerTrait for RustyBackend {
         self.speed
     }
 
+    fn set_pitch(&mut self, pitch: Pitch) -> Pitch {
+        self.pitch = pitch.clamp(crate::MIN_PITCH, crate::MAX_PITCH);
+        self.command(PlayerInternalCmd::Pitch(self.pitch));
+
+        self.pitch
+    }
+
+    fn pitch(&self) -> Pitch {
+        self.pitch
+    }
+
     fn stop(&mut self) {
         self.command(PlayerInternalCmd::Stop);
     }
@@ -236,6 +277,10 @@ impl PlayerTrait for RustyBackend {
         })
     }
 
+    fn silence_skipped(&self) -> Option<Duration> {
+        *self.silence_skipped.lock()
+    }
+
     fn gapless(&self) -> bool {
         self.gapless
     }
@@ -251,6 +296,7 @@ impl PlayerTrait for RustyBackend {
     fn enqueue_next(&mut self, track: &Track) {
         let config_read = self.config.read_recursive();
         let soundtouch = config_read.settings.backends.rusty.soundtouch;
+        let silence_skip = config_read.settings.backends.rusty.silence_skip;
         let file_buf_size = usize::try_from(
             config_read
                 .settings
@@ -277,6 +323,7 @@ impl PlayerTrait for RustyBackend {
             QueueNextOptions {
                 gapless_decode: self.gapless,
                 soundtouch,
+                silence_skip,
                 file_buf_size,
                 ringbuf_size,
                 enqueue: true,
@@ -284,6 +331,12 @@ impl PlayerTrait for RustyBackend {
         ));
     }
 
+    fn set_ab_loop(&mut self, range: Option<ABLoopRange>) -> bool {
+        self.command(PlayerInternalCmd::ABLoop(range));
+
+        true
+    }
+
     fn media_info(&self) -> MediaInfo {
         let media_title_r = self.media_title.lock();
         if media_title_r.is_empty() {
@@ -303,10 +356,14 @@ struct CommonAppendOptions {
     gapless_decode: bool,
     /// Enable or disable soundtouch speed modifier or with `false` use rodio's speed modifier
     soundtouch: bool,
+    /// Skip silence with the given options, `None` to disable
+    silence_skip: Option<SilenceSkipOptions>,
     /// Enable or disable async decoding (decode to happen on a different thread than the playback)
     async_decode: bool,
     /// The size for the ring buffer.
     ringbuf_size: usize,
+    /// Where to send spectrum data of the played samples to, `None` to disable
+    spectrum_tx: Option<SpectrumTX>,
 }
 
 /// Extra options specific to [`append_to_sink_test`]
@@ -340,6 +397,11 @@ fn append_to_sink_inner<TD: Display, F: FnOnce(&mut Symphonia, Option<MediaTitle
 
     (func)(&mut decoder, rx);
 
+    let spectrum = common_options
+        .spectrum_tx
+        .clone()
+        .map(SpectrumAnalyzer::new);
+
     if common_options.async_decode {
         let handle = tokio::runtime::Handle::current();
         let (spec, current_frame_len) = decoder.get_spec();
@@ -357,16 +419,18 @@ fn append_to_sink_inner<TD: Display, F: FnOnce(&mut Symphonia, Option<MediaTitle
         });
 
         sink.append(
-            cons,
+            cons.spectrum_tap(spectrum),
             &SourceOptions {
                 soundtouch: common_options.soundtouch,
+                silence_skip: common_options.silence_skip,
             },
         );
     } else {
         sink.append(
-            decoder,
+            decoder.spectrum_tap(spectrum),
             &SourceOptions {
                 soundtouch: common_options.soundtouch,
+                silence_skip: common_options.silence_skip,
             },
         );
     }
@@ -567,9 +631,15 @@ struct PlayerThreadArgs {
     media_title: Arc<Mutex<String>>,
     // radio_downloaded: Arc<Mutex<u64>>,
     position: Arc<Mutex<Duration>>,
+    /// The time skipped as silence in the current track
+    silence_skipped: Arc<Mutex<Option<Duration>>>,
 
     volume_inside: Arc<AtomicU16>,
     speed_inside: i32,
+    /// Pitch shift in cents
+    pitch_inside: i32,
+    /// Where to send spectrum data to
+    spectrum_tx: SpectrumTX,
 }
 
 /// Player thread loop
@@ -581,6 +651,8 @@ struct PlayerThreadArgs {
 )]
 async fn player_thread(mut args: PlayerThreadArgs) {
     let mut is_radio = false;
+    // the active A-B loop of the current track
+    let mut ab_loop: Option<ABLoopRange> = None;
 
     // option to store enqueued's duration
     // note that the current implementation is only meant to have 1 enqueued next after the current playing song
@@ -588,6 +660,7 @@ async fn player_thread(mut args: PlayerThreadArgs) {
     let (_stream, handle) = OutputStream::try_default().unwrap();
     let sink = Sink::try_new(&handle, args.picmd_tx.clone(), args.pcmd_tx.clone()).unwrap();
     sink.set_speed(args.speed_inside as f32 / 10.0);
+    sink.set_pitch(args.pitch_inside as f32 / 100.0);
     sink.set_volume(f32::from(args.volume_inside.load(Ordering::SeqCst)) / 100.0);
     loop {
         let Ok(cmd) = args.picmd_rx.recv() else {
@@ -597,6 +670,9 @@ async fn player_thread(mut args: PlayerThreadArgs) {
 
         match cmd {
             PlayerInternalCmd::Play(track, options) => {
+                if !options.enqueue {
+                    ab_loop = None;
+                }
                 if let Err(err) = queue_next(
                     &track,
                     &sink,
@@ -605,6 +681,7 @@ async fn player_thread(mut args: PlayerThreadArgs) {
                     &args.total_duration,
                     &mut next_duration_opt,
                     &args.media_title,
+                    &args.spectrum_tx,
                     // &radio_downloaded,
                 )
                 .await
@@ -632,6 +709,10 @@ async fn player_thread(mut args: PlayerThreadArgs) {
                 args.speed_inside = speed;
                 sink.set_speed(args.speed_inside as f32 / 10.0);
             }
+            PlayerInternalCmd::Pitch(pitch) => {
+                args.pitch_inside = pitch;
+                sink.set_pitch(args.pitch_inside as f32 / 100.0);
+            }
             PlayerInternalCmd::Stop => {
                 sink.stop();
             }
@@ -655,6 +736,17 @@ async fn player_thread(mut args: PlayerThreadArgs) {
                 // let position = sink.elapsed().as_secs() as i64;
                 // error!("position in rusty backend is: {}", position);
                 *args.position.lock() = new_position;
+                *args.silence_skipped.lock() = sink.silence_skipped();
+
+                if let Some(range) = ab_loop {
+                    if new_position >= range.end {
+                        sink.seek(range.start);
+                        *args.position.lock() = range.start;
+                    }
+
+                    // while looping, the track will not finish, so no need to prepare the next one
+                    continue;
+                }
 
                 // About to finish signal is a simulation of gstreamer, and used for gapless
                 if !is_radio {
@@ -706,6 +798,10 @@ async fn player_thread(mut args: PlayerThreadArgs) {
                 if next_duration_opt.is_some() {
                     *args.total_duration.lock() = next_duration_opt;
                 }
+                ab_loop = None;
+            }
+            PlayerInternalCmd::ABLoop(range) => {
+                ab_loop = range;
             }
         }
     }
@@ -717,6 +813,8 @@ struct QueueNextOptions {
     gapless_decode: bool,
     /// Enable or disable soundtouch speed modifier or with `false` use rodio's speed modifier
     soundtouch: bool,
+    /// Settings to decide if and how to skip silence, depending on the track's media type
+    silence_skip: SilenceSkipSettings,
     /// Determines which append function and which duration type to use.
     enqueue: bool,
     /// Determines the size of the [`BufferedSource`].
@@ -736,7 +834,16 @@ async fn queue_next(
     total_duration: &ArcTotalDuration,
     next_duration_opt: &mut Option<Duration>,
     media_title: &Arc<Mutex<String>>,
+    spectrum_tx: &SpectrumTX,
 ) -> Result<()> {
+    let silence_skip = options
+        .silence_skip
+        .is_enabled_for(track.media_type())
+        .then(|| SilenceSkipOptions {
+            threshold_db: options.silence_skip.threshold_db,
+            max_silence: Duration::from_millis(options.silence_skip.max_silence_ms.into()),
+        });
+
     match track.inner() {
         MediaTypes::Track(track_data) => {
             *is_radio = false;
@@ -751,8 +858,10 @@ async fn queue_next(
                     &CommonAppendOptions {
                         gapless_decode: options.gapless_decode,
                         soundtouch: options.soundtouch,
+                        silence_skip,
                         ringbuf_size: options.ringbuf_size,
                         async_decode: true,
+                        spectrum_tx: Some(spectrum_tx.clone()),
                     },
                     next_duration_opt,
                     common_media_title_cb(media_title.clone()),
@@ -765,8 +874,10 @@ async fn queue_next(
                     &CommonAppendOptions {
                         gapless_decode: options.gapless_decode,
                         soundtouch: options.soundtouch,
+                        silence_skip,
                         ringbuf_size: options.ringbuf_size,
                         async_decode: true,
+                        spectrum_tx: Some(spectrum_tx.clone()),
                     },
                     total_duration,
                     common_media_title_cb(media_title.clone()),
@@ -844,8 +955,10 @@ async fn queue_next(
                     &CommonAppendOptions {
                         gapless_decode: options.gapless_decode,
                         soundtouch: options.soundtouch,
+                        silence_skip,
                         ringbuf_size: options.ringbuf_size,
                         async_decode: false,
+                        spectrum_tx: None,
                     },
                     next_duration_opt,
                 )?;
@@ -857,8 +970,10 @@ async fn queue_next(
                     &CommonAppendOptions {
                         gapless_decode: options.gapless_decode,
                         soundtouch: options.soundtouch,
+                        silence_skip,
                         ringbuf_size: options.ringbuf_size,
                         async_decode: false,
+                        spectrum_tx: None,
                     },
                     total_duration,
                 )?;
@@ -879,8 +994,10 @@ async fn queue_next(
                         &CommonAppendOptions {
                             gapless_decode: options.gapless_decode,
                             soundtouch: options.soundtouch,
+                            silence_skip,
                             ringbuf_size: options.ringbuf_size,
                             async_decode: true,
+                            spectrum_tx: Some(spectrum_tx.clone()),
                         },
                         next_duration_opt,
                         common_media_title_cb(media_title.clone()),
@@ -893,8 +1010,10 @@ async fn queue_next(
                         &CommonAppendOptions {
                             gapless_decode: options.gapless_decode,
                             soundtouch: options.soundtouch,
+                            silence_skip,
                             ringbuf_size: options.ringbuf_size,
                             async_decode: true,
+                            spectrum_tx: Some(spectrum_tx.clone()),
                         },
                         total_duration,
                         common_media_title_cb(media_title.clone()),
@@ -925,8 +1044,10 @@ async fn queue_next(
                     &CommonAppendOptions {
                         gapless_decode: options.gapless_decode,
                         soundtouch: options.soundtouch,
+                        silence_skip,
                         ringbuf_size: options.ringbuf_size,
                         async_decode: false,
+                        spectrum_tx: None,
                     },
                     next_duration_opt,
                     common_media_title_cb(media_title.clone()),
@@ -939,8 +1060,10 @@ async fn queue_next(
                     &CommonAppendOptions {
                         gapless_decode: options.gapless_decode,
                         soundtouch: options.soundtouch,
+                        silence_skip,
                         ringbuf_size: options.ringbuf_size,
                         async_decode: false,
+                        spectrum_tx: None,
                     },
                     total_duration,
                     common_media_title_cb(media_title.clone()),
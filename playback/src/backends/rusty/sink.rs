@@ -10,7 +10,7 @@ use rodio::{queue, Source};
 use rodio::{OutputStreamHandle, PlayError};
 
 use super::source::SourceExt as _;
-use super::source::{SampleType, SpecificType};
+use super::source::{SampleType, SilenceSkipOptions, SpecificType};
 use super::PlayerInternalCmd;
 use crate::PlayerCmd;
 
@@ -47,23 +47,32 @@ struct Controls {
     stopped: AtomicBool,
     /// Stores the speed to be applied.
     speed: Mutex<f32>,
+    /// Stores the pitch shift in semitones to be applied.
+    pitch: Mutex<f32>,
     /// Stores how many sources should be skipped.
     ///
     /// Used for skipping / clearing while accounting for the case that a new source is added before finishing clearing.
     to_clear: Mutex<u32>,
     /// The current position in the currently playing source (may be off by a few milliseconds).
     position: RwLock<Duration>,
+    /// The time skipped as silence in the currently playing source, `None` if not enabled for the source.
+    silence_skipped: RwLock<Option<Duration>>,
 }
 
 /// Options to apply to a specific source
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SourceOptions {
     pub soundtouch: bool,
+    /// Skip silence in the source, `None` to disable
+    pub silence_skip: Option<SilenceSkipOptions>,
 }
 
 impl Default for SourceOptions {
     fn default() -> Self {
-        Self { soundtouch: true }
+        Self {
+            soundtouch: true,
+            silence_skip: None,
+        }
     }
 }
 
@@ -98,8 +107,10 @@ impl Sink {
                 stopped: AtomicBool::new(false),
                 seek: Mutex::new(None),
                 speed: Mutex::new(1.0),
+                pitch: Mutex::new(0.0),
                 to_clear: Mutex::new(0),
                 position: RwLock::new(Duration::from_secs(0)),
+                silence_skipped: RwLock::new(None),
             }),
             sound_count: Arc::new(AtomicUsize::new(0)),
             picmd_tx,
@@ -129,6 +140,7 @@ impl Sink {
         let progress_tx = self.picmd_tx.clone();
         let source = source
             .track_position()
+            .silence_skip(options.silence_skip)
             .custom_speed(1.0, SpecificType::soundtouch(options.soundtouch))
             .amplify(1.0)
             .pausable(false)
@@ -137,7 +149,7 @@ impl Sink {
             // .stoppable()
             .periodic_access(Duration::from_millis(500), move |src| {
                 let _ = progress_tx.send(PlayerInternalCmd::Progress(
-                    src.inner().inner().inner().inner().get_pos(),
+                    src.inner().inner().inner().inner().inner().get_pos(),
                 ));
             })
             .periodic_access(Duration::from_millis(5), move |src| {
@@ -146,6 +158,7 @@ impl Sink {
                     src.skip();
                     // reset position to be at 0, otherwise the position could be stale if there is no new source
                     *controls.position.write() = Duration::ZERO;
+                    *controls.silence_skipped.write() = None;
                 } else {
                     if let Some(seek_time) = controls.seek.lock().take() {
                         let _ = src.try_seek(seek_time);
@@ -157,19 +170,22 @@ impl Sink {
                             *to_clear -= 1;
                             // reset position to be at 0, otherwise the position could be stale if there is no new source
                             *controls.position.write() = Duration::ZERO;
+                            *controls.silence_skipped.write() = None;
 
                             return;
                         }
                     }
-                    *controls.position.write() = src.inner().inner().inner().inner().get_pos();
+                    let silence = src.inner().inner().inner().inner();
+                    *controls.position.write() = silence.inner().get_pos();
+                    *controls.silence_skipped.write() = silence.skipped();
 
                     let amp = src.inner_mut();
                     amp.inner_mut().set_factor(*controls.volume.lock());
                     amp.set_paused(controls.pause.load(Ordering::SeqCst));
 
-                    amp.inner_mut()
-                        .inner_mut()
-                        .set_factor(*controls.speed.lock());
+                    let speed = amp.inner_mut().inner_mut();
+                    speed.set_factor(*controls.speed.lock());
+                    speed.set_pitch(*controls.pitch.lock());
                 }
             });
 
@@ -233,6 +249,20 @@ impl Sink {
         *self.controls.speed.lock() = value;
     }
 
+    /// Gets the pitch shift of the sound in semitones.
+    #[inline]
+    pub fn pitch(&self) -> f32 {
+        *self.controls.pitch.lock()
+    }
+
+    /// Changes the pitch shift of the sound in semitones, independent of the speed.
+    ///
+    /// The value `0.0` is the original pitch. This only has a effect if the source uses soundtouch.
+    #[inline]
+    pub fn set_pitch(&self, value: f32) {
+        *self.controls.pitch.lock() = value;
+    }
+
     /// Resumes playback of a paused sink.
     ///
     /// No effect if not paused.
@@ -335,6 +365,14 @@ impl Sink {
     pub fn elapsed(&self) -> Duration {
         *self.controls.position.read()
     }
+
+    /// Returns the time skipped as silence in the currently playing source
+    ///
+    /// Returns `None` if skipping silence is not enabled for the currently playing source
+    #[inline]
+    pub fn silence_skipped(&self) -> Option<Duration> {
+        *self.controls.silence_skipped.read()
+    }
 }
 
 impl Drop for Sink {
@@ -140,4 +140,15 @@ where
             CustomSpeed::SoundTouch(soundtouch) => soundtouch.set_factor(f64::from(factor)),
         }
     }
+
+    /// Modifies the pitch shift in semitones, has no effect if not using soundtouch.
+    #[inline]
+    #[allow(unused_variables)] // because of feature gates
+    pub fn set_pitch(&mut self, semitones: f32) {
+        match self {
+            CustomSpeed::Rodio(_) => (),
+            #[cfg(feature = "rusty-soundtouch")]
+            CustomSpeed::SoundTouch(soundtouch) => soundtouch.set_pitch(f64::from(semitones)),
+        }
+    }
 }
@@ -2,6 +2,9 @@
 
 pub use custom_speed::SpecificType;
 use rodio::{Sample, Source};
+pub use silence_skip::SilenceSkipOptions;
+
+use super::spectrum::SpectrumAnalyzer;
 
 #[cfg(feature = "rusty-soundtouch")]
 pub mod soundtouch;
@@ -9,6 +12,8 @@ pub mod soundtouch;
 pub mod async_ring;
 mod cb_done;
 mod custom_speed;
+mod silence_skip;
+pub mod spectrum_tap;
 
 /// Our sample type we choose to use across all places
 pub type SampleType = f32;
@@ -33,6 +38,24 @@ where
         custom_speed::custom_speed(self, initial_speed, specific)
     }
 
+    /// A [`Source`] that shortens long silent stretches, with `None` nothing is skipped.
+    fn silence_skip(self, options: Option<SilenceSkipOptions>) -> silence_skip::SilenceSkip<Self>
+    where
+        Self: Sized,
+        Self: Source<Item = f32>,
+    {
+        silence_skip::silence_skip(self, options)
+    }
+
+    /// A [`Source`] that feeds the played samples to a spectrum analyzer, with `None` nothing is analyzed.
+    fn spectrum_tap(self, analyzer: Option<SpectrumAnalyzer>) -> spectrum_tap::SpectrumTap<Self>
+    where
+        Self: Sized,
+        Self: Source<Item = f32>,
+    {
+        spectrum_tap::spectrum_tap(self, analyzer)
+    }
+
     /// Run a function once at the end of a source.
     fn cbdone<Fn: FnOnce()>(self, fun: Fn) -> cb_done::CbDone<Self, Fn>
     where
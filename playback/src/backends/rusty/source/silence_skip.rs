@@ -0,0 +1,297 @@
+//! A [`Source`] that shortens long silent stretches ("smart speed").
+
+use std::time::Duration;
+
+use rodio::{source::SeekError, Source};
+
+use super::SampleType;
+
+/// The length of one analysis window, silence is detected and skipped in steps of this length.
+const WINDOW: Duration = Duration::from_millis(10);
+
+/// The maximal amount of windows to skip in one go, before letting one window through.
+///
+/// This is to not block the audio output for too long on very long silent stretches.
+const MAX_SKIP_WINDOWS: usize = 100;
+
+/// Options to configure [`SilenceSkip`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SilenceSkipOptions {
+    /// Windows with a RMS level below this (in dBFS) are considered silence.
+    pub threshold_db: i16,
+    /// Silent stretches longer than this are shortened to this length.
+    pub max_silence: Duration,
+}
+
+impl SilenceSkipOptions {
+    /// Get the threshold as a linear amplitude.
+    fn threshold(&self) -> f32 {
+        10f32.powf(f32::from(self.threshold_db) / 20.0)
+    }
+}
+
+/// Create a new [`SilenceSkip`] source, with `None` all samples are passed through as-is.
+pub fn silence_skip<I>(input: I, options: Option<SilenceSkipOptions>) -> SilenceSkip<I>
+where
+    I: Source<Item = SampleType>,
+{
+    SilenceSkip {
+        input,
+        options,
+        buffer: Vec::new(),
+        buffer_pos: 0,
+        buffer_channels: 0,
+        buffer_sample_rate: 0,
+        silence: Duration::ZERO,
+        skipped: Duration::ZERO,
+    }
+}
+
+/// A [`Source`] that detects low-energy stretches and drops everything of them past [`SilenceSkipOptions::max_silence`].
+///
+/// This source should be placed before any speed modifier, so that the detection happens on the original samples
+/// and the skipped time is in the track's time.
+#[derive(Debug)]
+pub struct SilenceSkip<I> {
+    input: I,
+    /// `None` if disabled.
+    options: Option<SilenceSkipOptions>,
+
+    /// The current analyzed window.
+    buffer: Vec<SampleType>,
+    /// The position of the next sample to return from `buffer`.
+    buffer_pos: usize,
+    /// The channels `buffer` was read with.
+    buffer_channels: u16,
+    /// The sample rate `buffer` was read with.
+    buffer_sample_rate: u32,
+
+    /// The length of the current silent stretch.
+    silence: Duration,
+    /// The total time skipped since the creation of this source.
+    skipped: Duration,
+}
+
+impl<I> SilenceSkip<I>
+where
+    I: Source<Item = SampleType>,
+{
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns a mutable reference to the inner source.
+    #[inline]
+    #[expect(dead_code)]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Get the total time skipped, or `None` if skipping silence is disabled for this source.
+    #[inline]
+    pub fn skipped(&self) -> Option<Duration> {
+        self.options.map(|_| self.skipped)
+    }
+
+    /// Get how many samples are still left in the current window.
+    #[inline]
+    fn buffer_remaining(&self) -> usize {
+        self.buffer.len() - self.buffer_pos
+    }
+
+    /// Read the next window from the input, skipping over windows that are past the allowed silence.
+    fn fill_buffer(&mut self, options: SilenceSkipOptions) {
+        let threshold = options.threshold();
+
+        for _ in 0..MAX_SKIP_WINDOWS {
+            self.buffer.clear();
+            self.buffer_pos = 0;
+            self.buffer_channels = self.input.channels();
+            self.buffer_sample_rate = self.input.sample_rate();
+
+            let frames =
+                usize::try_from(u128::from(self.buffer_sample_rate) * WINDOW.as_millis() / 1000)
+                    .unwrap_or(usize::MAX)
+                    .max(1);
+            let mut len = frames.saturating_mul(usize::from(self.buffer_channels.max(1)));
+            // dont read across a span boundary, as channels or sample rate may change there
+            if let Some(frame_len) = self.input.current_frame_len() {
+                len = len.min(frame_len);
+            }
+
+            self.buffer.extend(self.input.by_ref().take(len));
+
+            if self.buffer.is_empty() {
+                return;
+            }
+
+            if rms(&self.buffer) >= threshold {
+                self.silence = Duration::ZERO;
+                return;
+            }
+
+            let duration = samples_duration(
+                self.buffer.len(),
+                self.buffer_channels,
+                self.buffer_sample_rate,
+            );
+            self.silence += duration;
+
+            if self.silence <= options.max_silence {
+                return;
+            }
+
+            self.skipped += duration;
+        }
+    }
+}
+
+/// Calculate the root mean square of the given samples.
+#[allow(clippy::cast_precision_loss)]
+fn rms(samples: &[SampleType]) -> f32 {
+    let sum: f32 = samples.iter().map(|v| v * v).sum();
+
+    (sum / samples.len() as f32).sqrt()
+}
+
+/// Calculate the [`Duration`] the given amount of samples represent.
+fn samples_duration(samples: usize, channels: u16, sample_rate: u32) -> Duration {
+    let per_second = u128::from(channels.max(1)) * u128::from(sample_rate.max(1));
+    let nanos = samples as u128 * 1_000_000_000 / per_second;
+
+    Duration::from_nanos(u64::try_from(nanos).unwrap_or(u64::MAX))
+}
+
+impl<I> Iterator for SilenceSkip<I>
+where
+    I: Source<Item = SampleType>,
+{
+    type Item = SampleType;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let Some(options) = self.options else {
+            return self.input.next();
+        };
+
+        if self.buffer_remaining() == 0 {
+            self.fill_buffer(options);
+        }
+
+        let sample = self.buffer.get(self.buffer_pos).copied();
+        self.buffer_pos += usize::from(sample.is_some());
+
+        sample
+    }
+}
+
+impl<I> Source for SilenceSkip<I>
+where
+    I: Source<Item = SampleType>,
+{
+    #[inline]
+    fn current_frame_len(&self) -> Option<usize> {
+        let remaining = self.buffer_remaining();
+        if remaining > 0 {
+            return Some(remaining);
+        }
+
+        self.input.current_frame_len()
+    }
+
+    #[inline]
+    fn channels(&self) -> u16 {
+        if self.buffer_remaining() > 0 {
+            return self.buffer_channels;
+        }
+
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> u32 {
+        if self.buffer_remaining() > 0 {
+            return self.buffer_sample_rate;
+        }
+
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        // the current window and silent stretch are not valid anymore after a seek
+        self.buffer.clear();
+        self.buffer_pos = 0;
+        self.silence = Duration::ZERO;
+
+        self.input.try_seek(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use rodio::{buffer::SamplesBuffer, Source};
+
+    use super::{silence_skip, SilenceSkipOptions};
+
+    const OPTIONS: SilenceSkipOptions = SilenceSkipOptions {
+        threshold_db: -45,
+        max_silence: Duration::from_millis(300),
+    };
+
+    /// Create a mono 1000Hz source of "loud" for `loud_ms`, then silence for `silence_ms`, then "loud" again for `loud_ms`.
+    fn source(loud_ms: usize, silence_ms: usize) -> SamplesBuffer<f32> {
+        let mut samples = vec![0.5; loud_ms];
+        samples.extend(std::iter::repeat_n(0.0, silence_ms));
+        samples.extend(std::iter::repeat_n(0.5, loud_ms));
+
+        SamplesBuffer::new(1, 1000, samples)
+    }
+
+    #[test]
+    fn should_pass_through_if_disabled() {
+        let source = silence_skip(source(100, 1000), None);
+        assert_eq!(source.skipped(), None);
+        assert_eq!(source.count(), 1200);
+    }
+
+    #[test]
+    fn should_shorten_long_silence() {
+        let mut source = silence_skip(source(100, 1000), Some(OPTIONS));
+        let samples: Vec<f32> = source.by_ref().collect();
+
+        assert_eq!(samples.len(), 100 + 300 + 100);
+        assert_eq!(source.skipped(), Some(Duration::from_millis(700)));
+        // all loud samples need to be kept
+        assert_eq!(samples.iter().filter(|v| **v > 0.0).count(), 200);
+    }
+
+    #[test]
+    fn should_keep_short_silence() {
+        let mut source = silence_skip(source(100, 200), Some(OPTIONS));
+        assert_eq!(source.by_ref().count(), 400);
+        assert_eq!(source.skipped(), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn should_reset_silence_on_seek() {
+        let mut source = silence_skip(source(100, 1000), Some(OPTIONS));
+        // read into the silence, but not past "max_silence"
+        assert_eq!(source.by_ref().take(300).count(), 300);
+
+        source.try_seek(Duration::from_millis(100)).unwrap();
+        let samples: Vec<f32> = source.by_ref().collect();
+
+        assert_eq!(samples.len(), 300 + 100);
+        assert_eq!(source.skipped(), Some(Duration::from_millis(700)));
+    }
+}
@@ -26,6 +26,7 @@ where
         out_buffer: VecDeque::new(),
         in_buffer: VecDeque::new(),
         factor: 1.0,
+        pitch: 0.0,
     }
 }
 
@@ -43,6 +44,8 @@ pub struct SoundTouchSource<I> {
     in_buffer: VecDeque<f32>,
     /// The timescale factor. `1.0` means no change from the source.
     factor: f64,
+    /// The pitch shift in semitones. `0.0` means no change from the source.
+    pitch: f64,
 }
 
 impl<I> Iterator for SoundTouchSource<I>
@@ -52,8 +55,8 @@ where
     type Item = f32;
 
     fn next(&mut self) -> Option<Self::Item> {
-        // This is to skip calculation if speed is not changed
-        if (self.factor - 1.0).abs() < 0.05 {
+        // This is to skip calculation if speed and pitch are not changed
+        if (self.factor - 1.0).abs() < 0.05 && self.pitch.abs() < 0.005 {
             // use the samples from the in_buffer, otherwise we could be dropping samples without actually playing them
             // when quickly changing between 1.0 and other speeds, there may still be a audible drop, but this lowers it
             if !self.in_buffer.is_empty() {
@@ -126,6 +129,12 @@ where
         self.factor = factor;
     }
 
+    /// Modifies the pitch shift in semitones.
+    #[inline]
+    pub fn set_pitch(&mut self, semitones: f64) {
+        self.pitch = semitones;
+    }
+
     /// Get new samples into the `out_buffer`.
     ///
     /// Will completely overwrite the `in_buffer` & `out_buffer`.
@@ -134,6 +143,7 @@ where
 
         // in rodio and symphonia, any of these factors could have changed since the last time
         self.soundtouch.set_tempo(self.factor);
+        self.soundtouch.set_pitch_octaves(self.pitch / 12.0);
         self.soundtouch.set_channels(channels);
         self.soundtouch.set_sample_rate(self.input.sample_rate());
 
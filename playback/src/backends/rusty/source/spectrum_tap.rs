@@ -0,0 +1,142 @@
+//! A [`Source`] that feeds the samples into a [`SpectrumAnalyzer`] as they are played.
+
+use std::time::Duration;
+
+use rodio::{source::SeekError, Source};
+
+use super::super::spectrum::SpectrumAnalyzer;
+use super::SampleType;
+
+/// The amount of samples to collect before feeding them to the analyzer at once.
+///
+/// This is small enough (about 10ms at 48kHz stereo) to not delay the frames noticeably.
+const CHUNK_SIZE: usize = 1024;
+
+/// Create a new [`SpectrumTap`] source, with `None` all samples are passed through as-is.
+pub fn spectrum_tap<I>(input: I, analyzer: Option<SpectrumAnalyzer>) -> SpectrumTap<I>
+where
+    I: Source<Item = SampleType>,
+{
+    SpectrumTap {
+        input,
+        analyzer,
+        buffer: Vec::with_capacity(CHUNK_SIZE),
+    }
+}
+
+/// A [`Source`] that passes all samples through, while also feeding them to a [`SpectrumAnalyzer`].
+///
+/// As the samples are only read from this source when they are about to be played, the spectrum frames are in time with the audio,
+/// unlike feeding the decoder output, which is ahead by the buffered amount.
+#[derive(Debug)]
+pub struct SpectrumTap<I> {
+    input: I,
+    /// `None` if disabled.
+    analyzer: Option<SpectrumAnalyzer>,
+    /// The samples which have not been fed to the analyzer yet.
+    buffer: Vec<SampleType>,
+}
+
+impl<I> Iterator for SpectrumTap<I>
+where
+    I: Source<Item = SampleType>,
+{
+    type Item = SampleType;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let sample = self.input.next()?;
+
+        if let Some(analyzer) = &mut self.analyzer {
+            self.buffer.push(sample);
+            // only feed whole frames, so that the channels are not mixed up
+            let channels = usize::from(self.input.channels().max(1));
+            if self.buffer.len() >= CHUNK_SIZE && self.buffer.len() % channels == 0 {
+                analyzer.push(
+                    &self.buffer,
+                    self.input.channels(),
+                    self.input.sample_rate(),
+                );
+                self.buffer.clear();
+            }
+        }
+
+        Some(sample)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl<I> Source for SpectrumTap<I>
+where
+    I: Source<Item = SampleType>,
+{
+    #[inline]
+    fn current_frame_len(&self) -> Option<usize> {
+        self.input.current_frame_len()
+    }
+
+    #[inline]
+    fn channels(&self) -> u16 {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> u32 {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        // the audio before the seek should not be part of the frames after it
+        self.buffer.clear();
+        if let Some(analyzer) = &mut self.analyzer {
+            analyzer.reset();
+        }
+
+        self.input.try_seek(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use rodio::{buffer::SamplesBuffer, Source};
+    use tokio::sync::broadcast;
+
+    use super::spectrum_tap;
+    use crate::backends::rusty::spectrum::{SpectrumAnalyzer, SPECTRUM_FPS};
+
+    #[test]
+    fn should_send_frames_only_when_played() {
+        let (tx, mut rx) = broadcast::channel(SPECTRUM_FPS as usize * 2);
+        // one second of stereo samples
+        let input = SamplesBuffer::new(2, 48000, vec![0.5; 48000 * 2]);
+        let mut source = spectrum_tap(input, Some(SpectrumAnalyzer::new(tx)));
+
+        // nothing is sent before the samples are played
+        assert!(rx.try_recv().is_err());
+
+        // a quarter second
+        for _ in 0..24000 {
+            source.next().unwrap();
+        }
+        let mut count = 0;
+        while rx.try_recv().is_ok() {
+            count += 1;
+        }
+        assert!((SPECTRUM_FPS / 4 - 1..=SPECTRUM_FPS / 4).contains(&count));
+
+        source.try_seek(Duration::ZERO).unwrap();
+        assert_eq!(source.count(), 48000 * 2);
+    }
+}
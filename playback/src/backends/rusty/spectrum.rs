@@ -0,0 +1,263 @@
+//! Module for computing spectrum data from played samples.
+
+use std::f32::consts::PI;
+
+use termusiclib::player::SpectrumData;
+
+use crate::SpectrumTX;
+
+use super::source::SampleType;
+
+/// The amount of bands a spectrum frame has
+pub const SPECTRUM_BANDS: usize = 32;
+/// The amount of spectrum frames to compute per second of audio
+pub const SPECTRUM_FPS: u32 = 30;
+/// The amount of samples used for one FFT, needs to be a power of 2
+const FFT_SIZE: usize = 2048;
+
+/// Lowest frequency to consider for the bands
+const MIN_FREQ: f32 = 20.0;
+/// Highest frequency to consider for the bands
+const MAX_FREQ: f32 = 20_000.0;
+/// The decibel value that maps to `0.0`, everything at or above `0dB` maps to `1.0`
+const MIN_DB: f32 = -60.0;
+
+/// Tap for played samples, which sends a new [`SpectrumData`] frame every [`SPECTRUM_FPS`] of audio time.
+///
+/// Samples should be fed in as they are played (see [`SpectrumTap`](super::source::spectrum_tap::SpectrumTap)),
+/// so that the frames are sent in time with the audio.
+#[derive(Debug)]
+pub struct SpectrumAnalyzer {
+    tx: SpectrumTX,
+    /// Circular buffer of the last [`FFT_SIZE`] mono samples
+    history: Vec<f32>,
+    /// The next index to write in `history`
+    write_idx: usize,
+    /// Mono samples since the last frame was computed
+    since_last_frame: usize,
+    /// Pre-computed Hann window
+    window: Vec<f32>,
+}
+
+impl SpectrumAnalyzer {
+    #[allow(clippy::cast_precision_loss)]
+    pub fn new(tx: SpectrumTX) -> Self {
+        let window = (0..FFT_SIZE)
+            .map(|i| 0.5 - 0.5 * (2.0 * PI * i as f32 / (FFT_SIZE - 1) as f32).cos())
+            .collect();
+
+        Self {
+            tx,
+            history: vec![0.0; FFT_SIZE],
+            write_idx: 0,
+            since_last_frame: 0,
+            window,
+        }
+    }
+
+    /// Feed newly played interleaved `samples` into the analyzer.
+    ///
+    /// This is a no-op if there are no subscribers.
+    pub fn push(&mut self, samples: &[SampleType], channels: u16, sample_rate: u32) {
+        if self.tx.receiver_count() == 0 {
+            return;
+        }
+
+        let channels = usize::from(channels.max(1));
+        let frame_interval = (sample_rate / SPECTRUM_FPS).max(1) as usize;
+
+        for frame in samples.chunks(channels) {
+            #[allow(clippy::cast_precision_loss)]
+            let mono = frame.iter().sum::<f32>() / frame.len() as f32;
+            self.history[self.write_idx] = mono;
+            self.write_idx = (self.write_idx + 1) % FFT_SIZE;
+            self.since_last_frame += 1;
+
+            if self.since_last_frame >= frame_interval {
+                self.since_last_frame = 0;
+                let data = self.compute(sample_rate);
+                // a error only means there are no receivers anymore, which is not a problem
+                let _ = self.tx.send(data);
+            }
+        }
+    }
+
+    /// Forget all previous samples, for example after a seek, so that old audio does not show up in new frames.
+    pub fn reset(&mut self) {
+        self.history.fill(0.0);
+        self.write_idx = 0;
+        self.since_last_frame = 0;
+    }
+
+    /// Compute the current spectrum frame from the history.
+    fn compute(&self, sample_rate: u32) -> SpectrumData {
+        let mut re: Vec<f32> = (0..FFT_SIZE)
+            .map(|i| self.history[(self.write_idx + i) % FFT_SIZE] * self.window[i])
+            .collect();
+        let mut im = vec![0.0; FFT_SIZE];
+        fft(&mut re, &mut im);
+
+        // normalize so that a full-scale sine (with the hann window) is roughly 0dB
+        #[allow(clippy::cast_precision_loss)]
+        let norm = FFT_SIZE as f32 / 4.0;
+        let magnitudes: Vec<f32> = re
+            .iter()
+            .zip(im.iter())
+            .take(FFT_SIZE / 2)
+            .map(|(re, im)| (re * re + im * im).sqrt() / norm)
+            .collect();
+
+        SpectrumData {
+            bands: group_bands(&magnitudes, sample_rate, SPECTRUM_BANDS),
+        }
+    }
+}
+
+/// Group the FFT bins in `magnitudes` into `bands` logarithmically spaced bands, normalized to `0.0..=1.0`.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn group_bands(magnitudes: &[f32], sample_rate: u32, bands: usize) -> Vec<f32> {
+    // each bin covers "nyquist / len" hz
+    let nyquist = sample_rate as f32 / 2.0;
+    let bin_width = nyquist / magnitudes.len() as f32;
+    let max_freq = MAX_FREQ.min(nyquist);
+    let ratio = (max_freq / MIN_FREQ).ln();
+
+    (0..bands)
+        .map(|band| {
+            let lo = MIN_FREQ * (ratio * band as f32 / bands as f32).exp();
+            let hi = MIN_FREQ * (ratio * (band + 1) as f32 / bands as f32).exp();
+            let lo_bin = ((lo / bin_width) as usize).min(magnitudes.len() - 1);
+            // always use at least one bin, even if the band is smaller than the bin width
+            let hi_bin = ((hi / bin_width) as usize).clamp(lo_bin + 1, magnitudes.len());
+
+            let peak = magnitudes[lo_bin..hi_bin]
+                .iter()
+                .copied()
+                .fold(0.0, f32::max);
+
+            let db = 20.0 * peak.max(f32::MIN_POSITIVE).log10();
+            ((db - MIN_DB) / -MIN_DB).clamp(0.0, 1.0)
+        })
+        .collect()
+}
+
+/// In-place iterative radix-2 FFT.
+///
+/// # Panics
+///
+/// If `re` and `im` do not have the same length or the length is not a power of 2.
+fn fft(re: &mut [f32], im: &mut [f32]) {
+    let n = re.len();
+    assert_eq!(n, im.len());
+    assert!(n.is_power_of_two());
+
+    // bit-reversal permutation
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        #[allow(clippy::cast_precision_loss)]
+        let angle = -2.0 * PI / len as f32;
+        let (w_im, w_re) = angle.sin_cos();
+        for start in (0..n).step_by(len) {
+            let mut cur_re = 1.0;
+            let mut cur_im = 0.0;
+            for k in 0..len / 2 {
+                let a = start + k;
+                let b = a + len / 2;
+                let t_re = re[b] * cur_re - im[b] * cur_im;
+                let t_im = re[b] * cur_im + im[b] * cur_re;
+                re[b] = re[a] - t_re;
+                im[b] = im[a] - t_im;
+                re[a] += t_re;
+                im[a] += t_im;
+
+                let next_re = cur_re * w_re - cur_im * w_im;
+                cur_im = cur_re * w_im + cur_im * w_re;
+                cur_re = next_re;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+
+    use tokio::sync::broadcast;
+
+    use super::{fft, group_bands, SpectrumAnalyzer, FFT_SIZE, SPECTRUM_BANDS, SPECTRUM_FPS};
+
+    #[test]
+    #[allow(clippy::cast_precision_loss)]
+    fn fft_should_find_sine_bin() {
+        let n = 64;
+        let mut re: Vec<f32> = (0..n)
+            .map(|i| (2.0 * PI * 4.0 * i as f32 / n as f32).sin())
+            .collect();
+        let mut im = vec![0.0; n];
+        fft(&mut re, &mut im);
+
+        let mags: Vec<f32> = re
+            .iter()
+            .zip(im.iter())
+            .map(|(re, im)| (re * re + im * im).sqrt())
+            .collect();
+        let max_idx = mags[..n / 2]
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .unwrap()
+            .0;
+
+        assert_eq!(max_idx, 4);
+        assert!((mags[4] - n as f32 / 2.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn group_bands_should_be_normalized() {
+        let mut mags = vec![0.0; FFT_SIZE / 2];
+        mags[100] = 1.0;
+        mags[200] = 10.0;
+        let bands = group_bands(&mags, 44100, SPECTRUM_BANDS);
+
+        assert_eq!(bands.len(), SPECTRUM_BANDS);
+        assert!(bands.iter().all(|v| (0.0..=1.0).contains(v)));
+        assert!(bands.iter().any(|v| (*v - 1.0).abs() < f32::EPSILON));
+        assert!(bands[0].abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn analyzer_should_send_at_fixed_rate() {
+        let (tx, mut rx) = broadcast::channel(SPECTRUM_FPS as usize * 2);
+        let mut analyzer = SpectrumAnalyzer::new(tx);
+
+        // one second of stereo samples
+        let samples = vec![0.5; 48000 * 2];
+        analyzer.push(&samples, 2, 48000);
+
+        let mut count = 0;
+        while let Ok(data) = rx.try_recv() {
+            assert_eq!(data.bands.len(), SPECTRUM_BANDS);
+            count += 1;
+        }
+        assert_eq!(count, SPECTRUM_FPS);
+    }
+}
@@ -8,13 +8,17 @@ use async_trait::async_trait;
 use parking_lot::RwLock;
 pub use playlist::Playlist;
 use termusiclib::config::v2::server::config_extra::ServerConfigVersionedDefaulted;
+use termusiclib::config::v2::server::LoopMode;
 use termusiclib::config::SharedServerSettings;
 use termusiclib::library_db::DataBase;
 use termusiclib::player::playlist_helpers::{
-    PlaylistAddTrack, PlaylistPlaySpecific, PlaylistRemoveTrackIndexed, PlaylistSwapTrack,
+    PlaylistAddTrack, PlaylistMoveSelection, PlaylistMoveTrack, PlaylistPlaySpecific,
+    PlaylistQueueRemoveTrack, PlaylistQueueTracks, PlaylistRemoveTrackIndexed, PlaylistSelection,
+    PlaylistSort, PlaylistSwapTrack,
 };
 use termusiclib::player::{
-    PlayerProgress, PlayerTimeUnit, RunningStatus, TrackChangedInfo, UpdateEvents,
+    ABLoopRange, PlayerProgress, PlayerTimeUnit, RunningStatus, SpectrumData, TrackChangedInfo,
+    UpdateEvents,
 };
 use termusiclib::podcast::db::Database as DBPod;
 use termusiclib::track::{MediaTypesSimple, Track};
@@ -106,6 +110,9 @@ pub enum PlayerErrorType {
 pub enum PlayerCmd {
     AboutToFinish,
     CycleLoop,
+    SetLoopMode(LoopMode),
+    ToggleStopAfterCurrent,
+    SetRepeatCount(u32),
     Eos,
     GetProgress,
     SkipPrevious,
@@ -119,11 +126,18 @@ pub enum PlayerCmd {
     SkipNext,
     SpeedDown,
     SpeedUp,
+    PitchDown,
+    PitchUp,
+    PitchFineDown,
+    PitchFineUp,
+    SetPitch(Pitch),
     Tick,
     ToggleGapless,
     TogglePause,
     VolumeDown,
     VolumeUp,
+    SetABLoop(ABLoopRange),
+    ClearABLoop,
     /// A Error happened in the backend (for example `NotFound`) that makes it unrecoverable to continue to play the current track.
     /// This will basically be treated as a [`Eos`](PlayerCmd::Eos), with some extra handling.
     ///
@@ -135,11 +149,32 @@ pub enum PlayerCmd {
     PlaylistRemoveTrack(PlaylistRemoveTrackIndexed),
     PlaylistClear,
     PlaylistSwapTrack(PlaylistSwapTrack),
+    /// Move some tracks next to each other as one block
+    PlaylistMoveTracks(PlaylistMoveTrack),
+    /// Remove the selected tracks as one edit
+    PlaylistRemoveSelection(PlaylistSelection),
+    /// Move the selected tracks as one block
+    PlaylistMoveSelection(PlaylistMoveSelection),
     PlaylistShuffle,
     PlaylistRemoveDeletedTracks,
+    /// Undo the last edit to the playlist
+    PlaylistUndo,
+    /// Redo the last undone edit to the playlist
+    PlaylistRedo,
+    PlaylistSort(PlaylistSort),
+    PlaylistDeduplicate,
+    /// Store the current playlist as the given named playlist
+    PlaylistSaveNamed(String),
+    /// Replace the current playlist with the given named playlist and start playing it
+    PlaylistLoadNamed(String),
+    /// Add tracks to the end of the "play next" queue
+    QueueAdd(PlaylistQueueTracks),
+    QueueRemove(PlaylistQueueRemoveTrack),
+    QueueClear,
 }
 
 pub type StreamTX = broadcast::Sender<UpdateEvents>;
+pub type SpectrumTX = broadcast::Sender<SpectrumData>;
 pub type SharedPlaylist = Arc<RwLock<Playlist>>;
 
 #[allow(clippy::module_name_repetitions)]
@@ -154,6 +189,10 @@ pub struct GeneralPlayer {
     pub db_podcast: DBPod,
     pub cmd_tx: PlayerCmdSender,
     pub stream_tx: StreamTX,
+    /// The currently active A-B loop of the current track, if any
+    pub ab_loop: Option<ABLoopRange>,
+    /// Position to seek to once the next track has started, used to resume a loaded named playlist
+    pub pending_position: Option<Duration>,
 
     /// Keep track of continues backend errors (like `NotFound`) to not keep trying infinitely.
     pub errors_since_last_progress: usize,
@@ -171,9 +210,10 @@ impl GeneralPlayer {
         config: SharedServerSettings,
         cmd_tx: PlayerCmdSender,
         stream_tx: StreamTX,
+        spectrum_tx: SpectrumTX,
         playlist: SharedPlaylist,
     ) -> Result<Self> {
-        let backend = Backend::new_select(backend, config.clone(), cmd_tx.clone());
+        let backend = Backend::new_select(backend, config.clone(), cmd_tx.clone(), spectrum_tx);
 
         let db_path = get_app_config_path().with_context(|| "failed to get podcast db path.")?;
 
@@ -205,6 +245,8 @@ impl GeneralPlayer {
             cmd_tx,
             stream_tx,
             current_track_updated: false,
+            ab_loop: None,
+            pending_position: None,
 
             errors_since_last_progress: 0,
         })
@@ -230,6 +272,7 @@ impl GeneralPlayer {
         config: SharedServerSettings,
         cmd_tx: PlayerCmdSender,
         stream_tx: StreamTX,
+        spectrum_tx: SpectrumTX,
         playlist: SharedPlaylist,
     ) -> Result<Self> {
         Self::new_backend(
@@ -237,6 +280,7 @@ impl GeneralPlayer {
             config,
             cmd_tx,
             stream_tx,
+            spectrum_tx,
             playlist,
         )
     }
@@ -308,6 +352,11 @@ impl GeneralPlayer {
     ///
     /// if `current_track_index` in playlist is above u32
     pub fn start_play(&mut self) {
+        // a loop region only makes sense for the track it was set on
+        if self.ab_loop.is_some() {
+            <Self as PlayerTrait>::set_ab_loop(self, None);
+        }
+
         let mut playlist = self.playlist.write();
         if playlist.is_stopped() | playlist.is_paused() {
             playlist.set_status(RunningStatus::Running);
@@ -336,6 +385,9 @@ impl GeneralPlayer {
 
             self.add_and_play_mpris_discord();
             self.player_restore_last_position();
+            if let Some(position) = self.pending_position.take() {
+                self.seek_to(position);
+            }
 
             self.send_stream_ev(UpdateEvents::TrackChanged(TrackChangedInfo {
                 current_track_index: u64::try_from(self.playlist.read().get_current_track_index())
@@ -343,6 +395,11 @@ impl GeneralPlayer {
                 current_track_updated: self.current_track_updated,
                 title: self.media_info().media_title,
                 progress: self.get_progress(),
+                queued_track: self
+                    .playlist
+                    .read()
+                    .current_queued_track()
+                    .map(Track::as_track_source),
             }));
         }
     }
@@ -376,7 +433,14 @@ impl GeneralPlayer {
 
     /// Skip to the next track, if there is one
     pub fn next(&mut self) {
-        if self.playlist.read().current_track().is_some() {
+        if self.playlist.read().is_held() {
+            info!("skip route 3 cause playback is held stopped.");
+            let mut playlist = self.playlist.write();
+            playlist.next();
+            playlist.proceed_false();
+            drop(playlist);
+            self.start_play();
+        } else if self.playlist.read().current_track().is_some() {
             info!("skip route 1 which is in most cases.");
             self.playlist.write().set_next_track(None);
             self.skip_one();
@@ -386,13 +450,76 @@ impl GeneralPlayer {
         }
     }
 
+    /// Set (or clear with `None`) a A-B loop on the current track, checking it against the track's duration.
+    ///
+    /// The end is moved to [`AB_LOOP_END_MARGIN`] before the end of the track, if it would be after that.
+    ///
+    /// # Errors
+    ///
+    /// - if the start is not before the (moved) end
+    /// - if the backend does not support A-B loops
+    pub fn set_ab_loop_checked(&mut self, range: Option<ABLoopRange>) -> Result<(), ABLoopError> {
+        let range = match range {
+            Some(range) => {
+                let duration = self
+                    .get_progress()
+                    .and_then(|v| v.total_duration)
+                    .or_else(|| {
+                        self.playlist
+                            .read()
+                            .current_track()
+                            .and_then(Track::duration)
+                    });
+                // without a known duration, the end can only be checked while playing
+                let end = duration.map_or(range.end, |duration| {
+                    range.end.min(duration.saturating_sub(AB_LOOP_END_MARGIN))
+                });
+                Some(ABLoopRange::new(range.start, end).ok_or(ABLoopError::OutOfRange)?)
+            }
+            None => None,
+        };
+
+        if !<Self as PlayerTrait>::set_ab_loop(self, range) {
+            return Err(ABLoopError::Unsupported);
+        }
+
+        Ok(())
+    }
+
     /// Switch & Play the previous track in the playlist
     pub fn previous(&mut self) {
         let mut playlist = self.playlist.write();
         playlist.previous();
         playlist.proceed_false();
+        let held = playlist.is_held();
         drop(playlist);
-        self.next();
+        if held {
+            self.start_play();
+        } else {
+            self.next();
+        }
+    }
+
+    /// Stop playback and keep it stopped until it is started again via [`play`](Self::play), [`toggle_pause`](Self::toggle_pause) or a track change.
+    ///
+    /// Unlike [`PlayerTrait::stop`], which gets started again on the next tick.
+    /// Starting again plays the current track from the beginning, unless `proceed` is set, then the next track is played.
+    pub fn stop_held(&mut self, proceed: bool) {
+        let mut playlist = self.playlist.write();
+        if !proceed {
+            playlist.proceed_false();
+        }
+        playlist.set_next_track(None);
+        playlist.hold();
+        drop(playlist);
+        self.get_player_mut().stop();
+        if let Some(ref mut mpris) = self.mpris {
+            mpris.stop();
+        }
+
+        self.send_stream_ev(UpdateEvents::PlayStateChanged {
+            playing: RunningStatus::Stopped.as_u32(),
+        });
     }
 
     /// Resume playback if paused, pause playback if running
@@ -404,7 +531,11 @@ impl GeneralPlayer {
             RunningStatus::Running => {
                 <Self as PlayerTrait>::pause(self);
             }
-            RunningStatus::Stopped => {}
+            RunningStatus::Stopped => {
+                if self.playlist.read().is_held() {
+                    self.start_play();
+                }
+            }
             RunningStatus::Paused => {
                 <Self as PlayerTrait>::resume(self);
             }
@@ -430,7 +561,12 @@ impl GeneralPlayer {
         // see https://github.com/rust-lang/rust/issues/93883
         let status = self.playlist.read().status();
         match status {
-            RunningStatus::Running | RunningStatus::Stopped => {}
+            RunningStatus::Running => {}
+            RunningStatus::Stopped => {
+                if self.playlist.read().is_held() {
+                    self.start_play();
+                }
+            }
             RunningStatus::Paused => {
                 <Self as PlayerTrait>::resume(self);
             }
@@ -645,6 +781,24 @@ impl PlayerTrait for GeneralPlayer {
         self.get_player().speed()
     }
 
+    fn set_pitch(&mut self, pitch: Pitch) -> Pitch {
+        let pitch = self.get_player_mut().set_pitch(pitch);
+        self.send_stream_ev(UpdateEvents::PitchChanged { pitch });
+
+        pitch
+    }
+
+    fn add_pitch(&mut self, pitch: PitchSigned) -> Pitch {
+        let pitch = self.get_player_mut().add_pitch(pitch);
+        self.send_stream_ev(UpdateEvents::PitchChanged { pitch });
+
+        pitch
+    }
+
+    fn pitch(&self) -> Pitch {
+        self.get_player().pitch()
+    }
+
     fn stop(&mut self) {
         self.playlist.write().stop();
         self.get_player_mut().stop();
@@ -654,6 +808,10 @@ impl PlayerTrait for GeneralPlayer {
         self.get_player().get_progress()
     }
 
+    fn silence_skipped(&self) -> Option<PlayerTimeUnit> {
+        self.get_player().silence_skipped()
+    }
+
     fn gapless(&self) -> bool {
         self.get_player().gapless()
     }
@@ -678,6 +836,18 @@ impl PlayerTrait for GeneralPlayer {
     fn media_info(&self) -> MediaInfo {
         self.get_player().media_info()
     }
+
+    fn set_ab_loop(&mut self, range: Option<ABLoopRange>) -> bool {
+        if !self.get_player_mut().set_ab_loop(range) {
+            warn!("Current backend does not support A-B loops");
+            return false;
+        }
+
+        self.ab_loop = range;
+        self.send_stream_ev(UpdateEvents::ABLoopChanged { range });
+
+        true
+    }
 }
 
 /// Some information that may be available from the backend
@@ -696,9 +866,33 @@ pub type Speed = i32;
 // yes this is currently the same as speed, but for consistentcy with VolumeSigned (and maybe other types)
 pub type SpeedSigned = Speed;
 
+/// How long before the end of a track a A-B loop has to end.
+///
+/// Backends check the position periodically, so a loop ending right at the end of the track could be missed.
+pub const AB_LOOP_END_MARGIN: Duration = Duration::from_secs(1);
+
+/// Why a A-B loop could not be set, see [`GeneralPlayer::set_ab_loop_checked`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ABLoopError {
+    /// The loop does not start before the end of the track
+    OutOfRange,
+    /// The current backend does not support A-B loops
+    Unsupported,
+}
+
 pub const MIN_SPEED: Speed = 1;
 pub const MAX_SPEED: Speed = 30;
 
+/// Pitch shift in cents, 100 cents = 1 semitone
+pub type Pitch = i32;
+// for consistentcy with SpeedSigned
+pub type PitchSigned = Pitch;
+
+/// One octave down
+pub const MIN_PITCH: Pitch = -1200;
+/// One octave up
+pub const MAX_PITCH: Pitch = 1200;
+
 #[allow(clippy::module_name_repetitions)]
 #[async_trait]
 pub trait PlayerTrait {
@@ -731,6 +925,12 @@ pub trait PlayerTrait {
     fn seek_to(&mut self, position: Duration);
     /// Get current track time position
     fn get_progress(&self) -> Option<PlayerProgress>;
+    /// Get the time skipped as silence in the current track.
+    ///
+    /// Returns `None` if the backend does not support skipping silence or it is not enabled for the current track.
+    fn silence_skipped(&self) -> Option<PlayerTimeUnit> {
+        None
+    }
     /// Set the speed to a specific amount.
     ///
     /// Returns the new speed
@@ -745,6 +945,23 @@ pub trait PlayerTrait {
     }
     /// Get the currently set speed
     fn speed(&self) -> Speed;
+    /// Set the pitch shift to a specific amount, independent of the speed.
+    ///
+    /// Returns the new pitch, which is always `0` if the backend does not support pitch shifting.
+    fn set_pitch(&mut self, _pitch: Pitch) -> Pitch {
+        0
+    }
+    /// Add a relative amount to the current pitch
+    ///
+    /// Returns the new pitch
+    fn add_pitch(&mut self, pitch: PitchSigned) -> Pitch {
+        let pitch = (self.pitch() + pitch).clamp(MIN_PITCH, MAX_PITCH);
+        self.set_pitch(pitch)
+    }
+    /// Get the currently set pitch
+    fn pitch(&self) -> Pitch {
+        0
+    }
     fn stop(&mut self);
     fn gapless(&self) -> bool;
     fn set_gapless(&mut self, to: bool);
@@ -759,4 +976,12 @@ pub trait PlayerTrait {
     fn enqueue_next(&mut self, track: &Track);
     /// Get info of the current media
     fn media_info(&self) -> MediaInfo;
+    /// Set (or clear with `None`) a A-B loop on the current track.
+    ///
+    /// The loop should be cleared by the backend when the track changes.
+    ///
+    /// Returns `false` if the backend does not support A-B loops.
+    fn set_ab_loop(&mut self, _range: Option<ABLoopRange>) -> bool {
+        false
+    }
 }
@@ -108,6 +108,9 @@ impl Mpris {
             .set_playback(MediaPlayback::Paused { progress: None })
             .ok();
     }
+    pub fn stop(&mut self) {
+        self.controls.set_playback(MediaPlayback::Stopped).ok();
+    }
     pub fn resume(&mut self) {
         self.controls
             .set_playback(MediaPlayback::Playing { progress: None })
@@ -128,12 +131,13 @@ impl Mpris {
                         progress: Some(souvlaki::MediaPosition(position)),
                     })
                     .ok(),
-                RunningStatus::Paused | RunningStatus::Stopped => self
+                RunningStatus::Paused => self
                     .controls
                     .set_playback(MediaPlayback::Paused {
                         progress: Some(souvlaki::MediaPosition(position)),
                     })
                     .ok(),
+                RunningStatus::Stopped => self.controls.set_playback(MediaPlayback::Stopped).ok(),
             };
         }
     }
@@ -229,9 +233,11 @@ impl GeneralPlayer {
                 // ignore error if sending failed
                 self.cmd_tx.send(PlayerCmd::Quit).ok();
             }
+            // "stop after current" and the repeat count cannot be exposed via mpris,
+            // as there are no standard properties for them and souvlaki does not allow custom ones (or "LoopStatus"),
+            // they are only available via the grpc api and the tui
             MediaControlEvent::Stop => {
-                // TODO: handle "Stop"
-                info!("Unimplemented Event: Stop");
+                self.stop_held(false);
             }
             // explicitly unsupported events
             MediaControlEvent::Raise => {}
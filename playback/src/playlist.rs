@@ -1,22 +1,36 @@
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::error::Error;
-use std::fmt::{Display, Write as _};
+use std::fmt::Display;
 use std::fs::File;
-use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 
 use anyhow::{bail, Context, Result};
 use parking_lot::RwLock;
 use pathdiff::diff_paths;
-use rand::seq::SliceRandom;
+use rand::distr::weighted::WeightedIndex;
+use rand::distr::Distribution;
+use rand::seq::{IndexedRandom, SliceRandom};
 use rand::Rng;
 use termusiclib::config::v2::server::LoopMode;
 use termusiclib::config::SharedServerSettings;
 use termusiclib::player::playlist_helpers::PlaylistPlaySpecific;
 use termusiclib::player::playlist_helpers::PlaylistSwapTrack;
 use termusiclib::player::playlist_helpers::PlaylistTrackSource;
+use termusiclib::player::playlist_helpers::{move_range, moved_index, PlaylistMoveTrack};
 use termusiclib::player::playlist_helpers::{PlaylistAddTrack, PlaylistRemoveTrackIndexed};
+use termusiclib::player::playlist_helpers::{PlaylistMoveSelection, PlaylistSelection};
+use termusiclib::player::playlist_helpers::{PlaylistQueueRemoveTrack, PlaylistQueueTracks};
+use termusiclib::player::playlist_helpers::{PlaylistSort, PlaylistSortKey};
+use termusiclib::player::PlaybackControls;
+use termusiclib::player::PlaylistLoadedInfo;
 use termusiclib::player::PlaylistLoopModeInfo;
+use termusiclib::player::PlaylistMoveInfo;
+use termusiclib::player::PlaylistQueueChangedInfo;
 use termusiclib::player::PlaylistShuffledInfo;
 use termusiclib::player::PlaylistSwapInfo;
 use termusiclib::player::PlaylistTracks;
@@ -24,6 +38,10 @@ use termusiclib::player::UpdateEvents;
 use termusiclib::player::UpdatePlaylistEvents;
 use termusiclib::player::{self, RunningStatus};
 use termusiclib::player::{PlaylistAddTrackInfo, PlaylistRemoveTrackInfo};
+use termusiclib::playlist::state::{
+    self as playlist_state, CachedMetadata, PlaylistState, PlaylistStateEntry, PlaylistStateSource,
+};
+use termusiclib::playlist::{self as playlist_file, PlaylistEntry, PlaylistFormat, PlaylistValue};
 use termusiclib::podcast::{db::Database as DBPod, episode::Episode};
 use termusiclib::track::{MediaTypes, Track, TrackData};
 use termusiclib::utils::{filetype_supported, get_app_config_path, get_parent_folder};
@@ -32,6 +50,7 @@ use crate::SharedPlaylist;
 use crate::StreamTX;
 
 #[derive(Debug)]
+#[allow(clippy::struct_excessive_bools)] // independent flags, most of them not playback states
 pub struct Playlist {
     /// All tracks in the playlist
     tracks: Vec<Track>,
@@ -40,6 +59,8 @@ pub struct Playlist {
     /// Index into `tracks` for the next track to play after the current
     next_track_index: Option<usize>,
     /// The currently playing [`Track`]. Does not need to be in `tracks`
+    ///
+    /// Set when the current track was taken from `queue`, in which case `current_track_index` stays at the last playlist track.
     current_track: Option<Track>,
     /// The current playing running status of the playlist
     status: RunningStatus,
@@ -53,6 +74,141 @@ pub struct Playlist {
 
     /// Indicator if we need to save the playlist for interval saving
     is_modified: bool,
+    /// The name of the named playlist the current tracks were loaded from or saved as, if any
+    name: Option<String>,
+    /// The "play next" queue, tracks in here are played before the playlist continues.
+    /// This is transient and not saved.
+    queue: VecDeque<Track>,
+    /// The track from `queue` that has been enqueued in the backend for gapless playback
+    next_queued: Option<Track>,
+    /// Edits that can be undone, newest last
+    undo_journal: Vec<PlaylistEdit>,
+    /// Edits that have been undone and can be redone, newest last
+    redo_journal: Vec<PlaylistEdit>,
+    /// Tracks that have been played in the current [`LoopMode::ShuffleNoRepeat`] cycle
+    shuffle_played: HashSet<PlaylistTrackSource>,
+    /// How often tracks have been played in this session, used for [`LoopMode::Weighted`]
+    play_counts: HashMap<PlaylistTrackSource, u64>,
+    /// The ids of the entries in the playlist state file, by source in playlist order, see [`Self::assign_entry_ids`]
+    entry_ids: HashMap<PlaylistTrackSource, VecDeque<u64>>,
+    /// The id the next new entry will get
+    next_entry_id: u64,
+    /// Stop playback once the current track has finished, reset once honored
+    stop_after_current: bool,
+    /// Playback was stopped on purpose and stays stopped until started again,
+    /// instead of being started again on the next tick like any other stop
+    held: bool,
+    /// How many more times the current track will be repeated before continuing
+    repeat_count: u32,
+}
+
+/// The maximum amount of edits kept in the undo journal
+const EDIT_JOURNAL_LIMIT: usize = 50;
+
+/// A single recorded edit to the playlist tracks, see [`Playlist::undo`]
+#[derive(Debug, Clone)]
+enum PlaylistEdit {
+    /// `tracks` were inserted starting at `at_index`
+    Add { at_index: usize, tracks: Vec<Track> },
+    /// `tracks` were removed starting at `at_index`
+    Remove { at_index: usize, tracks: Vec<Track> },
+    /// The `(index, track)` pairs were removed, sorted by index, which do not need to be next to each other
+    RemoveIndexed { tracks: Vec<(usize, Track)> },
+    /// The `(index, track)` pairs were inserted, sorted by index, only exists as the inverse of [`PlaylistEdit::RemoveIndexed`]
+    InsertIndexed { tracks: Vec<(usize, Track)> },
+    /// The tracks at both indexes were swapped
+    Swap { index_a: usize, index_b: usize },
+    /// `count` tracks starting at `from_index` were moved to `to_index`
+    Move {
+        from_index: usize,
+        count: usize,
+        to_index: usize,
+    },
+    /// Single tracks were moved `(from_index, to_index)`, one after another
+    MoveEach { moves: Vec<(usize, usize)> },
+    /// The whole playlist was replaced with the same tracks in a different order (like shuffle or sort)
+    Reorder {
+        before: Vec<Track>,
+        after: Vec<Track>,
+    },
+    /// The playlist was cleared of `tracks`
+    Clear {
+        tracks: Vec<Track>,
+        current_track_index: usize,
+        played_index: Vec<usize>,
+    },
+    /// The playlist was empty and got `tracks` restored, only exists as the inverse of [`PlaylistEdit::Clear`]
+    Restore {
+        tracks: Vec<Track>,
+        current_track_index: usize,
+        played_index: Vec<usize>,
+    },
+}
+
+impl PlaylistEdit {
+    /// Get the edit that reverts this edit
+    fn inverse(self) -> Self {
+        match self {
+            Self::Add { at_index, tracks } => Self::Remove { at_index, tracks },
+            Self::Remove { at_index, tracks } => Self::Add { at_index, tracks },
+            Self::RemoveIndexed { tracks } => Self::InsertIndexed { tracks },
+            Self::InsertIndexed { tracks } => Self::RemoveIndexed { tracks },
+            Self::Swap { index_a, index_b } => Self::Swap { index_a, index_b },
+            Self::Move {
+                from_index,
+                count,
+                to_index,
+            } => Self::Move {
+                from_index: to_index,
+                count,
+                to_index: from_index,
+            },
+            Self::MoveEach { moves } => Self::MoveEach {
+                moves: moves
+                    .into_iter()
+                    .rev()
+                    .map(|(from, to)| (to, from))
+                    .collect(),
+            },
+            Self::Reorder { before, after } => Self::Reorder {
+                before: after,
+                after: before,
+            },
+            Self::Clear {
+                tracks,
+                current_track_index,
+                played_index,
+            } => Self::Restore {
+                tracks,
+                current_track_index,
+                played_index,
+            },
+            Self::Restore {
+                tracks,
+                current_track_index,
+                played_index,
+            } => Self::Clear {
+                tracks,
+                current_track_index,
+                played_index,
+            },
+        }
+    }
+}
+
+/// The values parsed from a playlist file, see [`Playlist::load_from`]
+#[derive(Debug, Default)]
+struct LoadedPlaylist {
+    current_track_index: usize,
+    tracks: Vec<Track>,
+    /// The name of the current named playlist, only written to the main playlist file
+    name: Option<String>,
+    /// The position in the current track, only written to named playlist files
+    position: Option<Duration>,
+    /// The entry ids, in the same order as `tracks`
+    entry_ids: Vec<u64>,
+    /// The id the next new entry will get
+    next_entry_id: u64,
 }
 
 impl Playlist {
@@ -73,6 +229,18 @@ impl Playlist {
             need_proceed_to_next: false,
             stream_tx,
             is_modified: false,
+            name: None,
+            queue: VecDeque::new(),
+            next_queued: None,
+            undo_journal: Vec::new(),
+            redo_journal: Vec::new(),
+            shuffle_played: HashSet::new(),
+            play_counts: HashMap::new(),
+            entry_ids: HashMap::new(),
+            next_entry_id: 0,
+            stop_after_current: false,
+            held: false,
+            repeat_count: 0,
         }
     }
 
@@ -109,41 +277,164 @@ impl Playlist {
 
     /// Load the playlist from the file.
     ///
-    /// Path in `$config$/playlist.log`.
+    /// Path in `$config$/playlist.toml`.
     ///
     /// Returns `(Position, Tracks[])`.
     ///
     /// # Errors
-    /// - When the playlist path is not write-able
+    /// - When the playlist file cannot be read or parsed
     /// - When podcasts cannot be loaded
     pub fn load() -> Result<(usize, Vec<Track>)> {
-        let path = get_playlist_path()?;
+        let loaded = Self::load_from(&get_playlist_path()?, true)?;
+
+        Ok((loaded.current_track_index, loaded.tracks))
+    }
+
+    /// Load a playlist state file at `path`, in the format written by [`write_to`](Self::write_to).
+    ///
+    /// If the file does not exist, but one in the old format does, it is migrated, see [`migrate_legacy`](Self::migrate_legacy).
+    /// If `create` is `true`, a non-existing file results in a empty playlist, otherwise it is a error.
+    ///
+    /// # Errors
+    /// - When the file cannot be read or parsed
+    /// - When podcasts cannot be loaded
+    fn load_from(path: &Path, create: bool) -> Result<LoadedPlaylist> {
+        let legacy_path = path.with_extension(playlist_state::LEGACY_FILE_EXTENSION);
+        if !path.exists() && legacy_path.exists() {
+            let db_pod = DBPod::new(&get_app_config_path()?)?;
+            return Self::migrate_legacy(&legacy_path, path, &db_pod);
+        }
+
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if create && err.kind() == std::io::ErrorKind::NotFound => {
+                // new file, nothing to parse from it
+                return Ok(LoadedPlaylist::default());
+            }
+            Err(err) => {
+                return Err(err).with_context(|| format!("read \"{}\"", path.display()));
+            }
+        };
+
+        let state = playlist_state::decode(&content)
+            .with_context(|| format!("in \"{}\"", path.display()))?;
+
+        let db_pod = DBPod::new(&get_app_config_path()?)?;
+        Ok(Self::load_state(state, &db_pod))
+    }
+
+    /// Create the tracks for all entries in `state`.
+    ///
+    /// Local files only have their tags read if they changed since the state was saved.
+    /// Podcast episodes that are not in `db_pod` are added as radio.
+    fn load_state(state: PlaylistState, db_pod: &DBPod) -> LoadedPlaylist {
+        let mut loaded = LoadedPlaylist {
+            name: state.name,
+            position: state
+                .position
+                .and_then(|v| Duration::try_from_secs_f64(v).ok()),
+            next_entry_id: state.next_id,
+            ..Default::default()
+        };
+
+        for entry in state.entries {
+            let track = match entry.source {
+                PlaylistStateSource::Path { path } => match entry.metadata {
+                    Some(metadata) if metadata.is_current(&path) => metadata.into_track(path),
+                    _ => match Track::read_track_from_path(&path) {
+                        Ok(track) => track,
+                        Err(err) => {
+                            warn!("Skipping \"{}\": {err:#}", path.display());
+                            continue;
+                        }
+                    },
+                },
+                PlaylistStateSource::Radio { url } => Track::new_radio(url),
+                PlaylistStateSource::Podcast { url } => match db_pod.get_episode_by_url(&url) {
+                    Ok(episode) => Track::from_podcast_episode(&episode),
+                    Err(err) => {
+                        warn!("Podcast episode \"{url}\" not found, adding as radio: {err:#}");
+                        Track::new_radio(url)
+                    }
+                },
+            };
+
+            loaded.next_entry_id = loaded.next_entry_id.max(entry.id.saturating_add(1));
+            loaded.entry_ids.push(entry.id);
+            loaded.tracks.push(track);
+        }
+
+        // protect against the listed index in the playlist file not matching the elements in the playlist
+        // for example lets say it has "100", but there are only 2 elements in the playlist
+        loaded.current_track_index = state
+            .current_track_index
+            .min(loaded.tracks.len().saturating_sub(1));
 
-        let Ok(file) = File::open(&path) else {
-            // new file, nothing to parse from it
-            File::create(&path)?;
+        loaded
+    }
 
-            return Ok((0, Vec::new()));
+    /// Load a playlist file in the old line-based format at `legacy_path` and store it in the new format at `path`.
+    ///
+    /// The old file is kept with a additional `.bak` extension.
+    ///
+    /// # Errors
+    /// - When the old file cannot be read
+    /// - When the new file cannot be written
+    /// - When podcasts cannot be loaded
+    fn migrate_legacy(legacy_path: &Path, path: &Path, db_pod: &DBPod) -> Result<LoadedPlaylist> {
+        info!(
+            "Migrating playlist \"{}\" to \"{}\"",
+            legacy_path.display(),
+            path.display()
+        );
+
+        let mut loaded = Self::load_legacy_from(legacy_path, db_pod)?;
+        loaded.entry_ids = (0..).take(loaded.tracks.len()).collect();
+        loaded.next_entry_id = loaded.entry_ids.last().map_or(0, |v| v + 1);
+
+        let state = PlaylistState {
+            current_track_index: loaded.current_track_index,
+            name: loaded.name.clone(),
+            position: loaded.position.map(|v| v.as_secs_f64()),
+            next_id: loaded.next_entry_id,
+            entries: state_entries(&loaded.tracks, &loaded.entry_ids),
         };
+        std::fs::write(path, playlist_state::encode(&state)?)
+            .with_context(|| format!("write \"{}\"", path.display()))?;
+
+        let mut backup_path = legacy_path.as_os_str().to_owned();
+        backup_path.push(".bak");
+        std::fs::rename(legacy_path, &backup_path)
+            .with_context(|| format!("rename \"{}\"", legacy_path.display()))?;
+
+        Ok(loaded)
+    }
+
+    /// Load a playlist file at `path` in the old line-based format (`playlist.log`).
+    ///
+    /// # Errors
+    /// - When the file cannot be read
+    /// - When podcasts cannot be loaded
+    fn load_legacy_from(path: &Path, db_pod: &DBPod) -> Result<LoadedPlaylist> {
+        let mut loaded = LoadedPlaylist::default();
+
+        let file = File::open(path).with_context(|| format!("open \"{}\"", path.display()))?;
 
         let reader = BufReader::new(file);
         let mut lines = reader.lines();
 
-        let mut current_track_index = 0;
         if let Some(line) = lines.next() {
             let index_line = line?;
             if let Ok(index) = index_line.trim().parse() {
-                current_track_index = index;
+                loaded.current_track_index = index;
             }
         } else {
             // empty file, nothing to parse from it
-            return Ok((0, Vec::new()));
+            return Ok(loaded);
         }
 
         let mut playlist_items = Vec::new();
-        let db_path = get_app_config_path()?;
-        let db_podcast = DBPod::new(&db_path)?;
-        let podcasts = db_podcast
+        let podcasts = db_pod
             .get_podcasts()
             .with_context(|| "failed to get podcasts from db.")?;
         for line in lines {
@@ -151,6 +442,18 @@ impl Playlist {
 
             let trimmed_line = line.trim();
 
+            if let Some(name) = trimmed_line.strip_prefix(LEGACY_NAME_PREFIX) {
+                loaded.name = Some(name.to_string());
+                continue;
+            }
+            if let Some(position) = trimmed_line.strip_prefix(LEGACY_POSITION_PREFIX) {
+                loaded.position = position
+                    .parse()
+                    .ok()
+                    .and_then(|v| Duration::try_from_secs_f64(v).ok());
+                continue;
+            }
+
             // skip empty lines without trying to process them
             // skip lines that are comments (m3u-like)
             if trimmed_line.is_empty() || trimmed_line.starts_with('#') {
@@ -182,9 +485,12 @@ impl Playlist {
 
         // protect against the listed index in the playlist file not matching the elements in the playlist
         // for example lets say it has "100", but there are only 2 elements in the playlist
-        let current_track_index = current_track_index.min(playlist_items.len().saturating_sub(1));
+        loaded.current_track_index = loaded
+            .current_track_index
+            .min(playlist_items.len().saturating_sub(1));
+        loaded.tracks = playlist_items;
 
-        Ok((current_track_index, playlist_items))
+        Ok(loaded)
     }
 
     /// Run [`load`](Self::load), but also apply the values directly to the current instance.
@@ -193,10 +499,13 @@ impl Playlist {
     ///
     /// See [`load`](Self::load)
     pub fn load_apply(&mut self) -> Result<()> {
-        let (current_track_index, tracks) = Self::load()?;
-        self.current_track_index = current_track_index;
-        self.tracks = tracks;
+        let loaded = Self::load_from(&get_playlist_path()?, true)?;
+        self.current_track_index = loaded.current_track_index;
+        self.tracks = loaded.tracks;
+        self.set_entry_ids(&loaded.entry_ids, loaded.next_entry_id);
+        self.name = loaded.name;
         self.is_modified = false;
+        self.clear_edit_journal();
 
         Ok(())
     }
@@ -243,6 +552,7 @@ impl Playlist {
         self.current_track_index = current_track_index;
         self.tracks = playlist_items;
         self.is_modified = true;
+        self.clear_edit_journal();
 
         Ok(())
     }
@@ -256,17 +566,20 @@ impl Playlist {
     ///
     /// See [`Self::load`]
     pub fn reload_tracks(&mut self) -> Result<()> {
-        let (current_track_index, tracks) = Self::load()?;
-        self.tracks = tracks;
-        self.current_track_index = current_track_index;
+        let loaded = Self::load_from(&get_playlist_path()?, true)?;
+        self.tracks = loaded.tracks;
+        self.set_entry_ids(&loaded.entry_ids, loaded.next_entry_id);
+        self.current_track_index = loaded.current_track_index;
+        self.name = loaded.name;
         self.is_modified = false;
+        self.clear_edit_journal();
 
         Ok(())
     }
 
-    /// Save the current playlist and playing index to the playlist log
+    /// Save the current playlist and playing index to the playlist state file
     ///
-    /// Path in `$config$/playlist.log`
+    /// Path in `$config$/playlist.toml`
     ///
     /// # Errors
     ///
@@ -274,28 +587,265 @@ impl Playlist {
     pub fn save(&mut self) -> Result<()> {
         let path = get_playlist_path()?;
 
-        let file = File::create(&path)?;
+        self.write_to(&path, self.name.clone(), None)?;
+        self.is_modified = false;
 
-        // If the playlist is empty, truncate the file, but dont write anything else (like a index number)
-        if self.is_empty() {
-            self.is_modified = false;
-            return Ok(());
+        Ok(())
+    }
+
+    /// Write the current playing index and all tracks to `path`, as a playlist state file.
+    ///
+    /// `name` is only meant for the main playlist file, `position` only for named playlists.
+    ///
+    /// # Errors
+    ///
+    /// Errors could happen when writing files
+    fn write_to(
+        &mut self,
+        path: &Path,
+        name: Option<String>,
+        position: Option<Duration>,
+    ) -> Result<()> {
+        let entry_ids = self.assign_entry_ids();
+        let state = PlaylistState {
+            current_track_index: self.current_track_index,
+            name,
+            position: position.map(|v| v.as_secs_f64()),
+            next_id: self.next_entry_id,
+            entries: state_entries(&self.tracks, &entry_ids),
+        };
+
+        std::fs::write(path, playlist_state::encode(&state)?)
+            .with_context(|| format!("write \"{}\"", path.display()))?;
+
+        Ok(())
+    }
+
+    /// Set the entry ids of the current tracks, as loaded from a playlist state file.
+    fn set_entry_ids(&mut self, entry_ids: &[u64], next_entry_id: u64) {
+        self.entry_ids.clear();
+        for (track, id) in self.tracks.iter().zip(entry_ids) {
+            self.entry_ids
+                .entry(track.as_track_source())
+                .or_default()
+                .push_back(*id);
         }
+        self.next_entry_id = next_entry_id;
+    }
 
-        let mut writer = BufWriter::new(file);
-        writer.write_all(self.current_track_index.to_string().as_bytes())?;
-        writer.write_all(b"\n")?;
-        for track in &self.tracks {
-            let id = match track.inner() {
-                MediaTypes::Track(track_data) => track_data.path().to_string_lossy(),
-                MediaTypes::Radio(radio_track_data) => radio_track_data.url().into(),
-                MediaTypes::Podcast(podcast_track_data) => podcast_track_data.url().into(),
-            };
-            writeln!(writer, "{id}")?;
+    /// Get the entry ids for the current tracks, in the same order.
+    ///
+    /// Tracks keep the id they were loaded or last saved with, new tracks get a new id.
+    /// As the ids are tracked by source, tracks that exist multiple times may exchange ids with each other.
+    fn assign_entry_ids(&mut self) -> Vec<u64> {
+        let mut available = std::mem::take(&mut self.entry_ids);
+
+        self.tracks
+            .iter()
+            .map(|track| {
+                let source = track.as_track_source();
+                let id = available
+                    .get_mut(&source)
+                    .and_then(VecDeque::pop_front)
+                    .unwrap_or_else(|| {
+                        let id = self.next_entry_id;
+                        self.next_entry_id += 1;
+                        id
+                    });
+                self.entry_ids.entry(source).or_default().push_back(id);
+
+                id
+            })
+            .collect()
+    }
+
+    /// Get the name of the named playlist that is currently loaded, if any.
+    #[must_use]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Get the names of all stored named playlists, sorted.
+    ///
+    /// Path in `$config$/playlists/`.
+    ///
+    /// # Errors
+    ///
+    /// - When the config path cannot be determined
+    /// - When the directory cannot be read
+    pub fn list_named() -> Result<Vec<String>> {
+        let dir = get_named_playlists_dir()?;
+
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            // no playlist has been saved yet
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err).with_context(|| format!("read dir \"{}\"", dir.display())),
+        };
+
+        let mut names = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            // playlists in the old format are listed too, they are migrated once loaded
+            if !path.is_file()
+                || path.extension().is_none_or(|v| {
+                    v != playlist_state::FILE_EXTENSION
+                        && v != playlist_state::LEGACY_FILE_EXTENSION
+                })
+            {
+                continue;
+            }
+            if let Some(name) = path.file_stem().and_then(|v| v.to_str()) {
+                names.push(name.to_string());
+            }
         }
+        names.sort_unstable();
+        names.dedup();
 
-        writer.flush()?;
-        self.is_modified = false;
+        Ok(names)
+    }
+
+    /// Store the current tracks, playing index and `position` as named playlist `name`,
+    /// and make it the current named playlist.
+    ///
+    /// An existing playlist with the same name is overwritten.
+    ///
+    /// # Errors
+    ///
+    /// - When the name is not valid, see [`named_playlist_path`]
+    /// - When writing the file fails
+    pub fn save_named(&mut self, name: &str, position: Option<Duration>) -> Result<()> {
+        let path = named_playlist_path(name)?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        self.write_to(&path, None, position)?;
+        self.name = Some(name.to_string());
+        // store the new name in the main playlist file
+        self.is_modified = true;
+
+        Ok(())
+    }
+
+    /// Store the selected tracks as named playlist `name`, starting at the first track.
+    ///
+    /// Unlike [`save_named`](Self::save_named), the current named playlist is not changed.
+    /// An existing playlist with the same name is overwritten.
+    ///
+    /// # Errors
+    ///
+    /// - When the name is not valid, see [`named_playlist_path`]
+    /// - When the selection does not match the tracks, see [`selected_indexes`](Self::selected_indexes)
+    /// - When writing the file fails
+    pub fn save_selection_named(&self, name: &str, selection: &PlaylistSelection) -> Result<()> {
+        let path = named_playlist_path(name)?;
+        let indexes = self.selected_indexes(selection)?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        // a new file, so the ids can just start from 0
+        let entry_ids: Vec<u64> = (0..).take(indexes.len()).collect();
+        let state = PlaylistState {
+            current_track_index: 0,
+            name: None,
+            position: None,
+            next_id: u64::try_from(entry_ids.len()).unwrap_or(u64::MAX),
+            entries: state_entries(indexes.iter().map(|index| &self.tracks[*index]), &entry_ids),
+        };
+
+        std::fs::write(&path, playlist_state::encode(&state)?)
+            .with_context(|| format!("write \"{}\"", path.display()))?;
+
+        Ok(())
+    }
+
+    /// Replace the current tracks with the ones from named playlist `name`, and make it the current named playlist.
+    ///
+    /// Returns the position stored in the playlist for the current track.
+    ///
+    /// # Errors
+    ///
+    /// - When the name is not valid, see [`named_playlist_path`]
+    /// - When there is no playlist with that name
+    /// - When podcasts cannot be loaded
+    pub fn load_named(&mut self, name: &str) -> Result<Option<Duration>> {
+        if existing_named_playlist_path(name)?.is_none() {
+            bail!("Playlist \"{name}\" does not exist");
+        }
+
+        let loaded = Self::load_from(&named_playlist_path(name)?, false)?;
+
+        self.tracks = loaded.tracks;
+        self.set_entry_ids(&loaded.entry_ids, loaded.next_entry_id);
+        self.current_track_index = loaded.current_track_index;
+        self.played_index.clear();
+        self.next_track_index.take();
+        self.name = Some(name.to_string());
+        self.is_modified = true;
+        self.clear_edit_journal();
+
+        match self.as_grpc_playlist_tracks() {
+            Ok(tracks) => {
+                self.send_stream_ev(UpdatePlaylistEvents::PlaylistLoaded(PlaylistLoadedInfo {
+                    tracks,
+                }));
+            }
+            Err(err) => error!("Error converting loaded playlist to grpc: {err:#?}"),
+        }
+
+        Ok(loaded.position)
+    }
+
+    /// Rename named playlist `from` to `to`.
+    ///
+    /// # Errors
+    ///
+    /// - When either name is not valid, see [`named_playlist_path`]
+    /// - When there is no playlist `from` or there is already a playlist `to`
+    /// - When renaming the file fails
+    pub fn rename_named(&mut self, from: &str, to: &str) -> Result<()> {
+        let Some(from_path) = existing_named_playlist_path(from)? else {
+            bail!("Playlist \"{from}\" does not exist");
+        };
+        if existing_named_playlist_path(to)?.is_some() {
+            bail!("Playlist \"{to}\" already exists");
+        }
+        // keep the format, a playlist in the old format is migrated once loaded
+        let mut to_path = named_playlist_path(to)?;
+        if let Some(extension) = from_path.extension() {
+            to_path.set_extension(extension);
+        }
+
+        std::fs::rename(&from_path, &to_path)?;
+
+        if self.name.as_deref() == Some(from) {
+            self.name = Some(to.to_string());
+            self.is_modified = true;
+        }
+
+        Ok(())
+    }
+
+    /// Delete named playlist `name`. This does not modify the current tracks.
+    ///
+    /// # Errors
+    ///
+    /// - When the name is not valid, see [`named_playlist_path`]
+    /// - When removing the file fails
+    pub fn delete_named(&mut self, name: &str) -> Result<()> {
+        let Some(path) = existing_named_playlist_path(name)? else {
+            bail!("Playlist \"{name}\" does not exist");
+        };
+
+        std::fs::remove_file(&path)
+            .with_context(|| format!("delete playlist \"{}\"", path.display()))?;
+
+        if self.name.as_deref() == Some(name) {
+            self.name = None;
+            self.is_modified = true;
+        }
 
         Ok(())
     }
@@ -320,15 +870,52 @@ impl Playlist {
     }
 
     /// Change to the next track.
+    ///
+    /// If the current track should be repeated, it stays the current track.
+    /// Tracks from the "play next" queue take priority over the playlist,
+    /// unless a playlist track had already been enqueued for gapless playback.
     pub fn next(&mut self) {
-        self.played_index.push(self.current_track_index);
-        // Note: the next index is *not* taken here, as ".proceed/next" is called first,
+        if self.repeat_count > 0 {
+            if self.next_is_current_or_none() {
+                self.set_repeat_count(self.repeat_count - 1);
+                return;
+            }
+            // another track was already enqueued for gapless playback before the repeat was set
+            info!("Cancelling repeat, as another track has already been enqueued");
+            self.set_repeat_count(0);
+        }
+        // a queued track has no index, the playlist will continue after "current_track_index"
+        if self.current_track.take().is_none() {
+            self.played_index.push(self.current_track_index);
+            self.mark_current_played();
+        }
+        // Note: the next index / track is *not* taken here, as ".proceed/next" is called first,
         // then "has_next_track" is later used to check if enqueing has used.
+        if let Some(track) = self.next_queued.clone() {
+            // the queue may have been modified since enqueuing, so the track may not be the first anymore
+            if let Some(pos) = self
+                .queue
+                .iter()
+                .position(|v| v.as_track_source() == track.as_track_source())
+            {
+                self.queue.remove(pos);
+                self.send_queue_changed();
+            }
+            self.current_track = Some(track);
+            return;
+        }
         if let Some(index) = self.next_track_index {
             self.current_track_index = index;
+            self.check_shuffle_cycle();
+            return;
+        }
+        if let Some(track) = self.queue.pop_front() {
+            self.current_track = Some(track);
+            self.send_queue_changed();
             return;
         }
         self.current_track_index = self.get_next_track_index();
+        self.check_shuffle_cycle();
     }
 
     /// Check that the given `info` track source matches the given `track_inner` types.
@@ -393,7 +980,11 @@ impl Playlist {
 
         Self::check_same_source(&info.id, track_at_idx.inner(), new_index)?;
 
-        self.played_index.push(self.current_track_index);
+        self.set_repeat_count(0);
+        if self.current_track.take().is_none() {
+            self.played_index.push(self.current_track_index);
+            self.mark_current_played();
+        }
         self.set_next_track(None);
         self.set_current_track_index(new_index);
         self.proceed_false();
@@ -416,14 +1007,57 @@ impl Playlist {
             LoopMode::Random => {
                 next_track_index = self.get_random_index();
             }
+            LoopMode::ShuffleNoRepeat => {
+                next_track_index = self.get_unplayed_random_index();
+            }
+            LoopMode::ShuffleAlbums => {
+                next_track_index = self.get_album_shuffle_index();
+            }
+            LoopMode::Weighted => {
+                next_track_index = self.get_weighted_random_index();
+            }
         }
         next_track_index
     }
 
+    /// Record the current playlist track as played, for the shuffle modes that need it.
+    fn mark_current_played(&mut self) {
+        let Some(track) = self.tracks.get(self.current_track_index) else {
+            return;
+        };
+        let source = track.as_track_source();
+
+        *self.play_counts.entry(source.clone()).or_default() += 1;
+        self.shuffle_played.insert(source);
+    }
+
+    /// Start a new [`LoopMode::ShuffleNoRepeat`] cycle if the new current track has already been played in this one.
+    ///
+    /// This only happens once all tracks have been played, as [`get_unplayed_random_index`](Self::get_unplayed_random_index)
+    /// only falls back to played tracks then.
+    fn check_shuffle_cycle(&mut self) {
+        if self.loop_mode != LoopMode::ShuffleNoRepeat {
+            return;
+        }
+        let Some(track) = self.tracks.get(self.current_track_index) else {
+            return;
+        };
+
+        if self.shuffle_played.contains(&track.as_track_source()) {
+            self.shuffle_played.clear();
+        }
+    }
+
     /// Change to the previous track played.
     ///
     /// This uses `played_index` vec, if available, otherwise uses [`LoopMode`].
     pub fn previous(&mut self) {
+        self.set_repeat_count(0);
+        // when playing a queued track, go back to the playlist track played before it
+        if self.current_track.take().is_some() {
+            self.is_modified = true;
+            return;
+        }
         if !self.played_index.is_empty() {
             if let Some(index) = self.played_index.pop() {
                 self.current_track_index = index;
@@ -432,7 +1066,11 @@ impl Playlist {
             }
         }
         match self.loop_mode {
-            LoopMode::Single => {}
+            // without any history, there is no previous track to go back to in the shuffle modes
+            LoopMode::Single
+            | LoopMode::ShuffleNoRepeat
+            | LoopMode::ShuffleAlbums
+            | LoopMode::Weighted => {}
             LoopMode::Playlist => {
                 if self.current_track_index == 0 {
                     self.current_track_index = self.len() - 1;
@@ -514,6 +1152,38 @@ impl Playlist {
         Ok(())
     }
 
+    /// Move `count` tracks starting at `from_index` as one block, so that the first of them is at `to_index` afterwards, sends move event.
+    ///
+    /// # Errors
+    ///
+    /// - if the tracks to move or the target are out-of-bounds
+    ///
+    /// # Panics
+    ///
+    /// If `usize` cannot be converted to `u64`
+    pub fn move_range(&mut self, from_index: usize, count: usize, to_index: usize) -> Result<()> {
+        move_range(&mut self.tracks, from_index, count, to_index)?;
+        if count == 0 || from_index == to_index {
+            return Ok(());
+        }
+
+        let moved = |index| moved_index(index, from_index, count, to_index);
+        self.current_track_index = moved(self.current_track_index);
+        for index in &mut self.played_index {
+            *index = moved(*index);
+        }
+        self.next_track_index = self.next_track_index.map(moved);
+
+        self.send_stream_ev(UpdatePlaylistEvents::PlaylistMoveTracks(PlaylistMoveInfo {
+            from_index: u64::try_from(from_index).unwrap(),
+            count: u64::try_from(count).unwrap(),
+            to_index: u64::try_from(to_index).unwrap(),
+        }));
+        self.is_modified = true;
+
+        Ok(())
+    }
+
     /// Get the current track's Path/Url.
     // TODO: refactor this function to likely return either a consistent URI format or a enum
     // TODO: refactor to return a reference if possible
@@ -536,16 +1206,48 @@ impl Playlist {
     }
 
     /// Get the next track index and return a reference to it.
+    ///
+    /// If the current track should be repeated, the current track is returned.
+    /// If the "play next" queue is not empty, its first track is returned instead.
     pub fn fetch_next_track(&mut self) -> Option<&Track> {
+        if self.repeat_count > 0 {
+            if let Some(track) = self.current_track.clone() {
+                self.next_queued = Some(track);
+                return self.next_queued.as_ref();
+            }
+            self.next_track_index = Some(self.current_track_index);
+            return self.tracks.get(self.current_track_index);
+        }
+        if let Some(track) = self.queue.front() {
+            self.next_queued = Some(track.clone());
+            return self.next_queued.as_ref();
+        }
         let next_index = self.get_next_track_index();
         self.next_track_index = Some(next_index);
         self.tracks.get(next_index)
     }
 
     pub fn set_status(&mut self, status: RunningStatus) {
+        if status != RunningStatus::Stopped {
+            self.held = false;
+        }
         self.status = status;
     }
 
+    /// Keep playback stopped until it is started again, see [`is_held`](Self::is_held).
+    pub fn hold(&mut self) {
+        self.set_status(RunningStatus::Stopped);
+        self.held = true;
+    }
+
+    /// Get whether playback was stopped on purpose and should not be started again automatically.
+    ///
+    /// This is reset once the status changes from [`RunningStatus::Stopped`].
+    #[must_use]
+    pub fn is_held(&self) -> bool {
+        self.held
+    }
+
     #[must_use]
     pub fn is_stopped(&self) -> bool {
         self.status == RunningStatus::Stopped
@@ -563,16 +1265,9 @@ impl Playlist {
 
     /// Cycle through the loop modes and return the new mode.
     ///
-    /// order:
-    /// [Random](LoopMode::Random) -> [Playlist](LoopMode::Playlist)
-    /// [Playlist](LoopMode::Playlist) -> [Single](LoopMode::Single)
-    /// [Single](LoopMode::Single) -> [Random](LoopMode::Random)
+    /// For the order see [`LoopMode::cycle`].
     pub fn cycle_loop_mode(&mut self) -> LoopMode {
-        let new_mode = match self.loop_mode {
-            LoopMode::Random => LoopMode::Playlist,
-            LoopMode::Playlist => LoopMode::Single,
-            LoopMode::Single => LoopMode::Random,
-        };
+        let new_mode = self.loop_mode.cycle();
 
         self.set_loop_mode(new_mode);
 
@@ -594,47 +1289,59 @@ impl Playlist {
         ));
     }
 
-    /// Export the current playlist to a `.m3u` playlist file.
+    /// Encode the current playlist for a playlist file at `filename`, the format is picked from its extension.
     ///
-    /// Might be confused with [save](Self::save).
+    /// See [`PlaylistFormat::from_path`] for the supported extensions.
+    /// This only encodes, so that the file can be written without holding a lock on the playlist.
     ///
     /// # Errors
-    /// Error could happen when writing file to local disk.
-    pub fn save_m3u(&self, filename: &Path) -> Result<()> {
+    ///
+    /// - if the playlist is empty
+    /// - if the extension of `filename` is not a supported format
+    pub fn encode_to_file(&self, filename: &Path) -> Result<String> {
         if self.tracks.is_empty() {
             bail!("Unable to save since the playlist is empty.");
         }
 
-        let parent_folder = get_parent_folder(filename);
+        let Some(format) = PlaylistFormat::from_path(filename) else {
+            bail!(
+                "Unsupported playlist format for {}, expected one of m3u, m3u8, pls or xspf",
+                filename.display()
+            );
+        };
 
-        let m3u = self.get_m3u_file(&parent_folder);
+        let parent_folder = get_parent_folder(filename);
 
-        std::fs::write(filename, m3u)?;
-        Ok(())
+        Ok(playlist_file::encode(
+            format,
+            &self.get_file_entries(&parent_folder),
+        ))
     }
 
-    /// Generate the m3u's file content.
+    /// Get the entries to write into a playlist file.
     ///
     /// All Paths are relative to the `parent_folder` directory.
-    fn get_m3u_file(&self, parent_folder: &Path) -> String {
-        let mut m3u = String::from("#EXTM3U\n");
-        for track in &self.tracks {
-            let file = match track.inner() {
-                MediaTypes::Track(track_data) => {
-                    let path_relative = diff_paths(track_data.path(), parent_folder);
+    fn get_file_entries(&self, parent_folder: &Path) -> Vec<PlaylistEntry> {
+        self.tracks
+            .iter()
+            .map(|track| {
+                let location = match track.inner() {
+                    MediaTypes::Track(track_data) => PlaylistValue::Path(
+                        diff_paths(track_data.path(), parent_folder)
+                            .unwrap_or_else(|| track_data.path().to_path_buf()),
+                    ),
+                    MediaTypes::Radio(radio_track_data) => url_value(radio_track_data.url()),
+                    MediaTypes::Podcast(podcast_track_data) => url_value(podcast_track_data.url()),
+                };
 
-                    path_relative.map_or_else(
-                        || track_data.path().to_string_lossy(),
-                        |v| v.to_string_lossy().to_string().into(),
-                    )
+                PlaylistEntry {
+                    location,
+                    title: track.title().map(ToString::to_string),
+                    artist: track.artist().map(ToString::to_string),
+                    duration: track.duration(),
                 }
-                MediaTypes::Radio(radio_track_data) => radio_track_data.url().into(),
-                MediaTypes::Podcast(podcast_track_data) => podcast_track_data.url().into(),
-            };
-
-            let _ = writeln!(m3u, "{file}");
-        }
-        m3u
+            })
+            .collect()
     }
 
     /// Add a podcast episode to the playlist.
@@ -725,59 +1432,63 @@ impl Playlist {
     /// # Panics
     ///
     /// If `usize` cannot be converted to `u64`
-    pub fn add_tracks(&mut self, tracks: PlaylistAddTrack, db_pod: &DBPod) -> Result<()> {
-        self.tracks.reserve(tracks.tracks.len());
-        let at_index = usize::try_from(tracks.at_index).unwrap();
-        if at_index >= self.len() {
-            // insert tracks at the end
-            for track_location in tracks.tracks {
-                let track = match &track_location {
-                    PlaylistTrackSource::Path(path) => Self::track_from_path(path)?,
-                    PlaylistTrackSource::Url(uri) => Self::track_from_uri(uri),
-                    PlaylistTrackSource::PodcastUrl(uri) => {
-                        Self::track_from_podcasturi(uri, db_pod)?
-                    }
-                };
+    pub fn add_tracks(&mut self, tracks: &PlaylistAddTrack, db_pod: &DBPod) -> Result<()> {
+        let at_index = usize::try_from(tracks.at_index).unwrap().min(self.len());
 
-                self.send_stream_ev(UpdatePlaylistEvents::PlaylistAddTrack(
-                    PlaylistAddTrackInfo {
-                        at_index: u64::try_from(self.tracks.len()).unwrap(),
-                        title: track.title().map(ToOwned::to_owned),
-                        duration: track.duration().unwrap_or_default(),
-                        trackid: track_location,
-                    },
-                ));
+        let tracks = tracks
+            .tracks
+            .iter()
+            .map(|source| Self::track_from_source(source, db_pod))
+            .collect::<Result<Vec<_>>>()?;
 
-                self.tracks.push(track);
-                self.is_modified = true;
-            }
+        self.insert_tracks(at_index, tracks.clone());
+        self.record_edit(PlaylistEdit::Add { at_index, tracks });
 
-            return Ok(());
-        }
-        let mut at_index = at_index;
-        // insert tracks at position
-        for track_location in tracks.tracks {
-            let track = match &track_location {
-                PlaylistTrackSource::Path(path) => Self::track_from_path(path)?,
-                PlaylistTrackSource::Url(uri) => Self::track_from_uri(uri),
-                PlaylistTrackSource::PodcastUrl(uri) => Self::track_from_podcasturi(uri, db_pod)?,
-            };
+        Ok(())
+    }
 
+    /// Internal common insert handling for [`add_tracks`](Self::add_tracks) and edits, does not record a edit.
+    ///
+    /// The current, played and next track indexes keep pointing at the same tracks.
+    ///
+    /// # Panics
+    ///
+    /// If `usize` cannot be converted to `u64`
+    fn insert_tracks(&mut self, at_index: usize, tracks: Vec<Track>) {
+        self.tracks.reserve(tracks.len());
+        for (at_index, track) in (at_index..).zip(tracks) {
             self.send_stream_ev(UpdatePlaylistEvents::PlaylistAddTrack(
                 PlaylistAddTrackInfo {
                     at_index: u64::try_from(at_index).unwrap(),
                     title: track.title().map(ToOwned::to_owned),
                     duration: track.duration().unwrap_or_default(),
-                    trackid: track_location,
+                    trackid: track.as_track_source(),
                 },
             ));
 
-            self.tracks.insert(at_index, track);
-            self.is_modified = true;
-            at_index += 1;
+            self.handle_insert(at_index, track);
         }
+    }
 
-        Ok(())
+    /// Internal common `insert` handling, does not send a event.
+    fn handle_insert(&mut self, index: usize, track: Track) {
+        // the current track only moves if there is one
+        if !self.tracks.is_empty() && index <= self.current_track_index {
+            self.current_track_index += 1;
+        }
+        for played in &mut self.played_index {
+            if *played >= index {
+                *played += 1;
+            }
+        }
+        if let Some(next) = &mut self.next_track_index {
+            if *next >= index {
+                *next += 1;
+            }
+        }
+
+        self.tracks.insert(index, track);
+        self.is_modified = true;
     }
 
     /// Remove Tracks from the music service
@@ -791,38 +1502,114 @@ impl Playlist {
     /// # Panics
     ///
     /// If `usize` cannot be converted to `u64`
-    pub fn remove_tracks(&mut self, tracks: PlaylistRemoveTrackIndexed) -> Result<()> {
+    pub fn remove_tracks(&mut self, tracks: &PlaylistRemoveTrackIndexed) -> Result<()> {
         let at_index = usize::try_from(tracks.at_index).unwrap();
 
+        let removed = self.remove_range(at_index, &tracks.tracks)?;
+        self.record_edit(PlaylistEdit::Remove {
+            at_index,
+            tracks: removed,
+        });
+
+        Ok(())
+    }
+
+    /// Remove the selected tracks, which do not need to be next to each other.
+    ///
+    /// This is recorded as one edit, so it can be undone at once.
+    ///
+    /// # Errors
+    ///
+    /// see [`selected_indexes`](Self::selected_indexes)
+    ///
+    /// # Panics
+    ///
+    /// If `usize` cannot be converted to `u64`
+    pub fn remove_selection(&mut self, selection: &PlaylistSelection) -> Result<()> {
+        let indexes = self.selected_indexes(selection)?;
+        if indexes.is_empty() {
+            return Ok(());
+        }
+
+        let tracks = self.remove_indexes(&indexes);
+        self.record_edit(PlaylistEdit::RemoveIndexed { tracks });
+
+        Ok(())
+    }
+
+    /// Internal common handling for removing tracks which are not next to each other, does not record a edit.
+    ///
+    /// `indexes` need to be sorted, without duplicates and within bounds. Returns the removed `(index, track)` pairs, sorted by index.
+    ///
+    /// # Panics
+    ///
+    /// If `usize` cannot be converted to `u64`
+    fn remove_indexes(&mut self, indexes: &[usize]) -> Vec<(usize, Track)> {
+        let mut removed = Vec::with_capacity(indexes.len());
+        // remove from the back, so that the remaining indexes do not shift
+        for index in indexes.iter().rev().copied() {
+            let track = self.handle_remove(index);
+
+            self.send_stream_ev(UpdatePlaylistEvents::PlaylistRemoveTrack(
+                PlaylistRemoveTrackInfo {
+                    at_index: u64::try_from(index).unwrap(),
+                    trackid: track.as_track_source(),
+                },
+            ));
+            removed.push((index, track));
+        }
+        self.is_modified = true;
+
+        removed.reverse();
+        removed
+    }
+
+    /// Internal common remove handling for [`remove_tracks`](Self::remove_tracks) and edits, does not record a edit.
+    ///
+    /// Returns the removed tracks.
+    ///
+    /// # Errors
+    ///
+    /// see [`remove_tracks`](Self::remove_tracks)
+    ///
+    /// # Panics
+    ///
+    /// If `usize` cannot be converted to `u64`
+    fn remove_range(
+        &mut self,
+        at_index: usize,
+        sources: &[PlaylistTrackSource],
+    ) -> Result<Vec<Track>> {
         if at_index >= self.tracks.len() {
             bail!("at_index is higher than the length of the playlist! at_index is \"{at_index}\" and playlist length is \"{}\"", self.tracks.len());
         }
 
-        if at_index + tracks.tracks.len().saturating_sub(1) >= self.tracks.len() {
+        if at_index + sources.len().saturating_sub(1) >= self.tracks.len() {
             bail!("at_index + tracks to remove is higher than the length of the playlist! playlist lenght is \"{}\"", self.tracks.len());
         }
 
-        for input_track in tracks.tracks {
+        let mut removed = Vec::with_capacity(sources.len());
+        for input_track in sources {
             // verify that it is the track to be removed via id matching
             let Some(track_at_idx) = self.tracks.get(at_index) else {
                 // this should not happen as it is verified before the loop, but just in case
                 bail!("Failed to get track at index \"{at_index}\"");
             };
 
-            Self::check_same_source(&input_track, track_at_idx.inner(), at_index)?;
+            Self::check_same_source(input_track, track_at_idx.inner(), at_index)?;
 
             // verified that at index "at_index" the track is of the type and has the URI that was requested to be removed
-            self.handle_remove(at_index);
+            removed.push(self.handle_remove(at_index));
 
             self.send_stream_ev(UpdatePlaylistEvents::PlaylistRemoveTrack(
                 PlaylistRemoveTrackInfo {
                     at_index: u64::try_from(at_index).unwrap(),
-                    trackid: input_track,
+                    trackid: input_track.clone(),
                 },
             ));
         }
 
-        Ok(())
+        Ok(removed)
     }
 
     /// Create a Track from a given Path
@@ -847,6 +1634,17 @@ impl Playlist {
         Ok(track)
     }
 
+    /// Create a Track from any given source
+    fn track_from_source(source: &PlaylistTrackSource, db_pod: &DBPod) -> Result<Track> {
+        let track = match source {
+            PlaylistTrackSource::Path(path) => Self::track_from_path(path)?,
+            PlaylistTrackSource::Url(uri) => Self::track_from_uri(uri),
+            PlaylistTrackSource::PodcastUrl(uri) => Self::track_from_podcasturi(uri, db_pod)?,
+        };
+
+        Ok(track)
+    }
+
     /// Create a Track from a given uri (radio only)
     fn track_from_uri(uri: &str) -> Track {
         Track::new_radio(uri)
@@ -860,6 +1658,78 @@ impl Playlist {
         Ok(track)
     }
 
+    /// Move the selected tracks as one block, so that the first selected track is at `to_index` afterwards.
+    ///
+    /// `to_index` is clamped so that the whole block fits, the selected tracks keep their relative order.
+    ///
+    /// # Errors
+    ///
+    /// see [`selected_indexes`](Self::selected_indexes)
+    pub fn move_selection(&mut self, info: &PlaylistMoveSelection) -> Result<()> {
+        let indexes = self.selected_indexes(&info.selection)?;
+        if indexes.is_empty() {
+            return Ok(());
+        }
+
+        let to_index = usize::try_from(info.to_index)
+            .unwrap_or(usize::MAX)
+            .min(self.tracks.len() - indexes.len());
+
+        // move the selected tracks one by one into place, so that each move can be sent as a event;
+        // the ones moving down are moved last-first, so that no move shifts a already placed track
+        let mut positions = indexes;
+        let moving_down = positions
+            .iter()
+            .enumerate()
+            .take_while(|(selected, index)| **index < to_index + selected)
+            .count();
+        let order = (moving_down..positions.len()).chain((0..moving_down).rev());
+        let mut moves = Vec::with_capacity(positions.len());
+        for selected in order {
+            let (from, to) = (positions[selected], to_index + selected);
+            if from == to {
+                continue;
+            }
+
+            self.move_range(from, 1, to)?;
+            moves.push((from, to));
+            for position in &mut positions {
+                *position = moved_index(*position, from, 1, to);
+            }
+        }
+
+        if !moves.is_empty() {
+            self.record_edit(PlaylistEdit::MoveEach { moves });
+        }
+
+        Ok(())
+    }
+
+    /// Get the indexes of all tracks in `selection`, sorted and without duplicates.
+    ///
+    /// # Errors
+    ///
+    /// - if a index is not within bounds
+    /// - if the track at a index does not match the selected track's type and URI
+    fn selected_indexes(&self, selection: &PlaylistSelection) -> Result<Vec<usize>> {
+        let mut indexes = Vec::with_capacity(selection.tracks.len());
+        for selected in &selection.tracks {
+            let index =
+                usize::try_from(selected.index).context("Failed to convert index to usize")?;
+            let Some(track) = self.tracks.get(index) else {
+                bail!("Index {index} not within tracks bounds");
+            };
+
+            Self::check_same_source(&selected.id, track.inner(), index)?;
+            indexes.push(index);
+        }
+
+        indexes.sort_unstable();
+        indexes.dedup();
+
+        Ok(indexes)
+    }
+
     /// Swap tracks based on [`PlaylistSwapTrack`]
     ///
     /// # Errors
@@ -877,6 +1747,32 @@ impl Playlist {
             usize::try_from(info.index_b).context("Failed to convert index_b to usize")?;
 
         self.swap(index_a, index_b)?;
+        self.record_edit(PlaylistEdit::Swap { index_a, index_b });
+
+        Ok(())
+    }
+
+    /// Move tracks based on [`PlaylistMoveTrack`]
+    ///
+    /// # Errors
+    ///
+    /// - if the tracks to move or the target are not within bounds
+    /// - if the indexes cannot be converted to `usize`
+    pub fn move_tracks(&mut self, info: &PlaylistMoveTrack) -> Result<()> {
+        let from_index =
+            usize::try_from(info.from_index).context("Failed to convert from_index to usize")?;
+        let count = usize::try_from(info.count).context("Failed to convert count to usize")?;
+        let to_index =
+            usize::try_from(info.to_index).context("Failed to convert to_index to usize")?;
+
+        self.move_range(from_index, count, to_index)?;
+        if count > 0 && from_index != to_index {
+            self.record_edit(PlaylistEdit::Move {
+                from_index,
+                count,
+                to_index,
+            });
+        }
 
         Ok(())
     }
@@ -909,9 +1805,9 @@ impl Playlist {
         ));
     }
 
-    /// Internal common `remove` handling, does not send a event
-    fn handle_remove(&mut self, index: usize) {
-        self.tracks.remove(index);
+    /// Internal common `remove` handling, does not send a event. Returns the removed track.
+    fn handle_remove(&mut self, index: usize) -> Track {
+        let track = self.tracks.remove(index);
 
         // Handle index
         if index <= self.current_track_index {
@@ -920,11 +1816,40 @@ impl Playlist {
                 self.current_track_index -= 1;
             }
         }
+
+        // keep the history and next track pointing at the same tracks, forgetting the removed one
+        self.played_index.retain(|played| *played != index);
+        for played in &mut self.played_index {
+            if *played > index {
+                *played -= 1;
+            }
+        }
+        self.next_track_index = self
+            .next_track_index
+            .filter(|next| *next != index)
+            .map(|next| if next > index { next - 1 } else { next });
+
+        track
     }
 
     /// Clear the current playlist.
     /// This does not stop the playlist or clear [`current_track`](Self::current_track).
     pub fn clear(&mut self) {
+        let current_track_index = self.current_track_index;
+        let tracks = std::mem::take(&mut self.tracks);
+        if !tracks.is_empty() {
+            self.record_edit(PlaylistEdit::Clear {
+                tracks,
+                current_track_index,
+                played_index: self.played_index.clone(),
+            });
+        }
+
+        self.clear_tracks();
+    }
+
+    /// Internal common clear handling for [`clear`](Self::clear) and edits, does not record a edit.
+    fn clear_tracks(&mut self) {
         self.tracks.clear();
         self.played_index.clear();
         self.next_track_index.take();
@@ -940,9 +1865,76 @@ impl Playlist {
     ///
     /// see [`as_grpc_playlist_tracks#Errors`](Self::as_grpc_playlist_tracks)
     pub fn shuffle(&mut self) {
+        let before = self.tracks.clone();
+        let mut after = self.tracks.clone();
+        after.shuffle(&mut rand::rng());
+
+        self.set_order(after.clone());
+        self.record_edit(PlaylistEdit::Reorder { before, after });
+    }
+
+    /// Sort the playlist by the given metadata, tracks without that metadata are sorted last.
+    ///
+    /// Like [`shuffle`](Self::shuffle), this sends the whole playlist.
+    ///
+    /// # Panics
+    ///
+    /// see [`as_grpc_playlist_tracks#Errors`](Self::as_grpc_playlist_tracks)
+    pub fn sort(&mut self, sort: PlaylistSort) {
+        let before = self.tracks.clone();
+        let after = sort_tracks(self.tracks.clone(), sort);
+
+        self.set_order(after.clone());
+        if before != after {
+            self.record_edit(PlaylistEdit::Reorder { before, after });
+        }
+    }
+
+    /// Remove repeated tracks from the playlist, only keeping the first occurrence.
+    ///
+    /// If the current track is a removed repetition, the kept occurrence becomes the current track.
+    ///
+    /// # Panics
+    ///
+    /// If `usize` cannot be converted to `u64`
+    pub fn deduplicate(&mut self) {
+        let mut seen = HashSet::with_capacity(self.tracks.len());
+        let indexes: Vec<usize> = self
+            .tracks
+            .iter()
+            .enumerate()
+            .filter(|(_, track)| !seen.insert(track.as_track_source()))
+            .map(|(index, _)| index)
+            .collect();
+        if indexes.is_empty() {
+            return;
+        }
+
+        let current_track = self.tracks.get(self.current_track_index).cloned();
+        let tracks = self.remove_indexes(&indexes);
+        if let Some(current_track) = current_track {
+            if let Some(index) = self.tracks.iter().position(|v| *v == current_track) {
+                self.current_track_index = index;
+            }
+        }
+
+        self.record_edit(PlaylistEdit::RemoveIndexed { tracks });
+    }
+
+    /// Replace the tracks with the same tracks in a different order and keep the current track selected.
+    ///
+    /// Internal common handling for [`shuffle`](Self::shuffle) and edits, does not record a edit.
+    ///
+    /// # Panics
+    ///
+    /// see [`as_grpc_playlist_tracks#Errors`](Self::as_grpc_playlist_tracks)
+    fn set_order(&mut self, tracks: Vec<Track>) {
         let current_track_file = self.get_current_track();
 
-        self.tracks.shuffle(&mut rand::rng());
+        let before = std::mem::replace(&mut self.tracks, tracks);
+        self.is_modified = true;
+
+        self.remap_indexes(&before);
 
         if let Some(current_track_file) = current_track_file {
             if let Some(index) = self.find_index_from_file(&current_track_file) {
@@ -957,6 +1949,183 @@ impl Playlist {
         ));
     }
 
+    /// Point `played_index` and `next_track_index` at the same tracks after the tracks changed from `before`,
+    /// dropping the indexes of tracks which are not in the playlist anymore.
+    ///
+    /// A repeated track keeps its occurrence, or uses the first one if there are less occurrences now.
+    fn remap_indexes(&mut self, before: &[Track]) {
+        let tracks = &self.tracks;
+        let remap = |index: usize| {
+            let track = before.get(index)?;
+            let occurrence = before[..index].iter().filter(|v| *v == track).count();
+            let mut positions = tracks
+                .iter()
+                .enumerate()
+                .filter(|(_, v)| *v == track)
+                .map(|(index, _)| index);
+            let first = positions.next()?;
+
+            if occurrence == 0 {
+                return Some(first);
+            }
+            Some(positions.nth(occurrence - 1).unwrap_or(first))
+        };
+
+        self.played_index = self
+            .played_index
+            .iter()
+            .filter_map(|index| remap(*index))
+            .collect();
+        self.next_track_index = self.next_track_index.and_then(remap);
+    }
+
+    /// Undo the last recorded edit to the playlist, sending the same events as the inverse edit would.
+    ///
+    /// Does nothing if there is no edit to undo.
+    ///
+    /// # Errors
+    ///
+    /// - if the playlist does not match the recorded edit anymore, in which case the journal is cleared
+    pub fn undo(&mut self) -> Result<()> {
+        let Some(edit) = self.undo_journal.pop() else {
+            info!("Nothing to undo");
+            return Ok(());
+        };
+
+        if let Err(err) = self.apply_edit(edit.clone().inverse()) {
+            self.clear_edit_journal();
+            return Err(err.context("undo"));
+        }
+        self.redo_journal.push(edit);
+
+        Ok(())
+    }
+
+    /// Redo the last undone edit to the playlist.
+    ///
+    /// Does nothing if there is no edit to redo.
+    ///
+    /// # Errors
+    ///
+    /// - if the playlist does not match the recorded edit anymore, in which case the journal is cleared
+    pub fn redo(&mut self) -> Result<()> {
+        let Some(edit) = self.redo_journal.pop() else {
+            info!("Nothing to redo");
+            return Ok(());
+        };
+
+        if let Err(err) = self.apply_edit(edit.clone()) {
+            self.clear_edit_journal();
+            return Err(err.context("redo"));
+        }
+        self.undo_journal.push(edit);
+
+        Ok(())
+    }
+
+    /// Record a new edit for [`undo`](Self::undo), this invalidates all edits that could be redone.
+    fn record_edit(&mut self, edit: PlaylistEdit) {
+        if self.undo_journal.len() >= EDIT_JOURNAL_LIMIT {
+            self.undo_journal.remove(0);
+        }
+        self.undo_journal.push(edit);
+        self.redo_journal.clear();
+    }
+
+    /// Forget all recorded edits, for example when the tracks got replaced.
+    fn clear_edit_journal(&mut self) {
+        self.undo_journal.clear();
+        self.redo_journal.clear();
+    }
+
+    /// Apply a recorded edit without recording it again.
+    ///
+    /// # Errors
+    ///
+    /// - if the playlist does not match the state the edit expects
+    fn apply_edit(&mut self, edit: PlaylistEdit) -> Result<()> {
+        match edit {
+            PlaylistEdit::Add { at_index, tracks } => {
+                if at_index > self.tracks.len() {
+                    bail!(
+                        "Index {at_index} not within tracks bounds {}",
+                        self.tracks.len()
+                    );
+                }
+                self.insert_tracks(at_index, tracks);
+            }
+            PlaylistEdit::Remove { at_index, tracks } => {
+                let sources: Vec<_> = tracks.iter().map(Track::as_track_source).collect();
+                self.remove_range(at_index, &sources)?;
+            }
+            PlaylistEdit::RemoveIndexed { tracks } => {
+                if tracks
+                    .iter()
+                    .any(|(index, track)| self.tracks.get(*index) != Some(track))
+                {
+                    bail!("Playlist does not match the recorded tracks");
+                }
+                let indexes: Vec<usize> = tracks.iter().map(|(index, _)| *index).collect();
+                self.remove_indexes(&indexes);
+            }
+            PlaylistEdit::InsertIndexed { tracks } => {
+                // each index is within bounds after inserting the ones before it
+                let len = self.tracks.len();
+                if tracks
+                    .iter()
+                    .enumerate()
+                    .any(|(inserted, (index, _))| *index > len + inserted)
+                {
+                    bail!("Index not within tracks bounds {len}");
+                }
+                for (index, track) in tracks {
+                    self.insert_tracks(index, vec![track]);
+                }
+            }
+            PlaylistEdit::Swap { index_a, index_b } => self.swap(index_a, index_b)?,
+            PlaylistEdit::Move {
+                from_index,
+                count,
+                to_index,
+            } => self.move_range(from_index, count, to_index)?,
+            PlaylistEdit::MoveEach { moves } => {
+                let len = self.tracks.len();
+                if moves.iter().any(|(from, to)| *from >= len || *to >= len) {
+                    bail!("Moves not within tracks bounds {len}");
+                }
+                for (from_index, to_index) in moves {
+                    self.move_range(from_index, 1, to_index)?;
+                }
+            }
+            PlaylistEdit::Reorder { before, after } => {
+                if self.tracks != before {
+                    bail!("Playlist does not match the recorded order");
+                }
+                self.set_order(after);
+            }
+            PlaylistEdit::Clear { tracks, .. } => {
+                if self.tracks != tracks {
+                    bail!("Playlist does not match the recorded tracks");
+                }
+                self.clear_tracks();
+            }
+            PlaylistEdit::Restore {
+                tracks,
+                current_track_index,
+                played_index,
+            } => {
+                if !self.tracks.is_empty() {
+                    bail!("Playlist is not empty, cannot restore cleared tracks");
+                }
+                self.insert_tracks(0, tracks);
+                self.current_track_index = current_track_index;
+                self.played_index = played_index;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Get the current tracks and state as a GRPC [`PlaylistTracks`] object.
     ///
     /// # Errors
@@ -985,6 +2154,10 @@ impl Playlist {
             current_track_index: u64::try_from(self.get_current_track_index())
                 .context("current_track_index(usize) to u64")?,
             tracks,
+            optional_name: self
+                .name
+                .clone()
+                .map(player::playlist_tracks::OptionalName::Name),
         })
     }
 
@@ -1019,6 +2192,123 @@ impl Playlist {
         random_index
     }
 
+    /// Get a random index of a track that has not been played in the current cycle yet.
+    ///
+    /// Falls back to [`get_random_index`](Self::get_random_index) if all tracks have been played.
+    fn get_unplayed_random_index(&self) -> usize {
+        let candidates: Vec<usize> = self
+            .tracks
+            .iter()
+            .enumerate()
+            .filter(|(idx, track)| {
+                *idx != self.current_track_index
+                    && !self.shuffle_played.contains(&track.as_track_source())
+            })
+            .map(|(idx, _)| idx)
+            .collect();
+
+        candidates
+            .choose(&mut rand::rng())
+            .copied()
+            .unwrap_or_else(|| self.get_random_index())
+    }
+
+    /// Get the next track index in the current album, or the first track of a random other album.
+    ///
+    /// Albums are identified by album artist (or artist, if not set) and title, as different artists may have albums of the same title.
+    /// Tracks in a album are ordered by disc and track number, then by playlist order.
+    /// Tracks without a album are treated as their own album.
+    fn get_album_shuffle_index(&self) -> usize {
+        let album_of = |idx: usize| -> Option<(Option<&str>, &str)> {
+            let track = self.tracks.get(idx)?;
+            let track_data = track.as_track()?;
+
+            Some((
+                track_data.album_artist().or(track.artist()),
+                track_data.album()?,
+            ))
+        };
+        let order_of = |idx: usize| {
+            let track_data = self.tracks.get(idx).and_then(|track| track.as_track());
+            (
+                track_data.and_then(TrackData::disc_number),
+                track_data.and_then(TrackData::track_number),
+                idx,
+            )
+        };
+
+        if let Some(album) = album_of(self.current_track_index) {
+            let current_order = order_of(self.current_track_index);
+            let next_in_album = (0..self.len())
+                .filter(|idx| album_of(*idx) == Some(album) && order_of(*idx) > current_order)
+                .min_by_key(|idx| order_of(*idx));
+
+            if let Some(idx) = next_in_album {
+                return idx;
+            }
+        }
+
+        // collect the first track of each album, except the current album
+        let current_album = album_of(self.current_track_index);
+        let mut album_starts: HashMap<(Option<&str>, &str), usize> = HashMap::new();
+        let mut singles = Vec::new();
+        for idx in 0..self.len() {
+            match album_of(idx) {
+                Some(album) if current_album == Some(album) => (),
+                Some(album) => {
+                    let start = album_starts.entry(album).or_insert(idx);
+                    if order_of(idx) < order_of(*start) {
+                        *start = idx;
+                    }
+                }
+                None if idx != self.current_track_index => singles.push(idx),
+                None => (),
+            }
+        }
+        singles.extend(album_starts.into_values());
+
+        singles
+            .choose(&mut rand::rng())
+            .copied()
+            .unwrap_or_else(|| self.get_random_index())
+    }
+
+    /// Get a random index, favouring tracks with a high rating and a low play count.
+    ///
+    /// Tracks without a rating are treated as average, the play count is the one stored in the file plus the plays in this session.
+    fn get_weighted_random_index(&self) -> usize {
+        if self.len() <= 1 {
+            return 0;
+        }
+
+        let weights = self.tracks.iter().enumerate().map(|(idx, track)| {
+            if idx == self.current_track_index {
+                return 0.0;
+            }
+            let track_data = track.as_track();
+            let rating = track_data.and_then(TrackData::rating).unwrap_or(50);
+            let plays = track_data.and_then(TrackData::play_count).unwrap_or(0)
+                + self
+                    .play_counts
+                    .get(&track.as_track_source())
+                    .copied()
+                    .unwrap_or(0);
+
+            #[allow(clippy::cast_precision_loss)] // precision does not matter for weights
+            let plays = plays as f64;
+
+            (f64::from(rating) + 10.0) / (1.0 + plays)
+        });
+
+        match WeightedIndex::new(weights) {
+            Ok(dist) => dist.sample(&mut rand::rng()),
+            Err(err) => {
+                warn!("Could not build weighted index, using uniform random: {err}");
+                self.get_random_index()
+            }
+        }
+    }
+
     /// Remove all tracks from the playlist that dont exist on the disk.
     ///
     /// # Panics
@@ -1061,6 +2351,11 @@ impl Playlist {
                 Some(new_index) => self.current_track_index = new_index,
                 None => self.current_track_index = 0,
             }
+
+            // the recorded indexes are not valid anymore
+            if self.tracks.len() != len {
+                self.clear_edit_journal();
+            }
         }
     }
 
@@ -1099,17 +2394,164 @@ impl Playlist {
 
     #[must_use]
     pub fn next_track(&self) -> Option<&Track> {
+        if self.next_queued.is_some() {
+            return self.next_queued.as_ref();
+        }
         let index = self.next_track_index?;
         self.tracks.get(index)
     }
 
+    /// Set the next track index, this always resets the next track from the queue.
     pub fn set_next_track(&mut self, track_idx: Option<usize>) {
         self.next_track_index = track_idx;
+        self.next_queued = None;
     }
 
     #[must_use]
     pub fn has_next_track(&self) -> bool {
-        self.next_track_index.is_some()
+        self.next_track_index.is_some() || self.next_queued.is_some()
+    }
+
+    /// Get the current track, if it was taken from the "play next" queue.
+    #[must_use]
+    pub fn current_queued_track(&self) -> Option<&Track> {
+        self.current_track.as_ref()
+    }
+
+    /// Get all tracks in the "play next" queue, in the order they will be played.
+    #[must_use]
+    pub fn queue(&self) -> &VecDeque<Track> {
+        &self.queue
+    }
+
+    /// Add tracks to the end of the "play next" queue.
+    ///
+    /// # Errors
+    ///
+    /// - When invalid inputs are given (non-existing path, etc)
+    pub fn queue_add(&mut self, tracks: &PlaylistQueueTracks, db_pod: &DBPod) -> Result<()> {
+        let tracks = tracks
+            .tracks
+            .iter()
+            .map(|v| Self::track_from_source(v, db_pod))
+            .collect::<Result<Vec<_>>>()?;
+
+        self.queue.extend(tracks);
+        self.send_queue_changed();
+
+        Ok(())
+    }
+
+    /// Remove a single track from the "play next" queue.
+    ///
+    /// # Errors
+    ///
+    /// - if converting u64 to usize fails
+    /// - if the given info's track mismatches with the actual queue
+    pub fn queue_remove(&mut self, info: &PlaylistQueueRemoveTrack) -> Result<()> {
+        let at_index = usize::try_from(info.at_index).context("convert at_index(u64) to usize")?;
+
+        let Some(track_at_idx) = self.queue.get(at_index) else {
+            bail!("Index {at_index} is out of bound {}", self.queue.len())
+        };
+
+        Self::check_same_source(&info.id, track_at_idx.inner(), at_index)?;
+
+        self.queue.remove(at_index);
+        self.send_queue_changed();
+
+        Ok(())
+    }
+
+    /// Remove all tracks from the "play next" queue.
+    ///
+    /// A queued track that is already enqueued for gapless playback will still be played.
+    pub fn queue_clear(&mut self) {
+        if self.queue.is_empty() {
+            return;
+        }
+
+        self.queue.clear();
+        self.send_queue_changed();
+    }
+
+    /// Get the "play next" queue as track sources.
+    #[must_use]
+    pub fn queue_as_sources(&self) -> PlaylistQueueTracks {
+        PlaylistQueueTracks {
+            tracks: self.queue.iter().map(Track::as_track_source).collect(),
+        }
+    }
+
+    /// Send the whole new "play next" queue as a event
+    fn send_queue_changed(&self) {
+        self.send_stream_ev(UpdatePlaylistEvents::PlaylistQueueChanged(
+            PlaylistQueueChangedInfo {
+                queue: self.queue_as_sources(),
+            },
+        ));
+    }
+
+    /// Check that the track enqueued for gapless playback (if any) is the current track.
+    fn next_is_current_or_none(&self) -> bool {
+        match (&self.next_queued, &self.current_track) {
+            (Some(next), Some(current)) => next.as_track_source() == current.as_track_source(),
+            (Some(_), None) => false,
+            (None, Some(_)) => self.next_track_index.is_none(),
+            (None, None) => self
+                .next_track_index
+                .is_none_or(|idx| idx == self.current_track_index),
+        }
+    }
+
+    /// Get the current one-shot playback controls.
+    #[must_use]
+    pub fn playback_controls(&self) -> PlaybackControls {
+        PlaybackControls {
+            stop_after_current: self.stop_after_current,
+            repeat_count: self.repeat_count,
+        }
+    }
+
+    /// Toggle stopping playback once the current track has finished, also sends a event.
+    pub fn toggle_stop_after_current(&mut self) -> PlaybackControls {
+        self.set_stop_after_current(!self.stop_after_current);
+
+        self.playback_controls()
+    }
+
+    /// Set whether to stop playback once the current track has finished, also sends a event if it changed.
+    pub fn set_stop_after_current(&mut self, value: bool) {
+        if value == self.stop_after_current {
+            return;
+        }
+
+        self.stop_after_current = value;
+        self.send_playback_controls();
+    }
+
+    /// Get and reset the "stop after current" flag, sends a event if it was set.
+    pub fn take_stop_after_current(&mut self) -> bool {
+        let value = self.stop_after_current;
+        self.set_stop_after_current(false);
+
+        value
+    }
+
+    /// Set how many more times the current track will be repeated, also sends a event if it changed.
+    pub fn set_repeat_count(&mut self, count: u32) {
+        if count == self.repeat_count {
+            return;
+        }
+
+        self.repeat_count = count;
+        self.send_playback_controls();
+    }
+
+    fn send_playback_controls(&self) {
+        self.send_stream_ev(UpdatePlaylistEvents::PlaylistPlaybackControls(
+            self.playback_controls(),
+        ));
     }
 
     /// Send stream events with consistent error handling
@@ -1125,15 +2567,212 @@ impl Playlist {
     }
 }
 
-const PLAYLIST_SAVE_FILENAME: &str = "playlist.log";
+/// Name of the main playlist file, without extension, see [`playlist_state::FILE_EXTENSION`]
+const PLAYLIST_SAVE_FILENAME: &str = "playlist";
 
 fn get_playlist_path() -> Result<PathBuf> {
     let mut path = get_app_config_path()?;
-    path.push(PLAYLIST_SAVE_FILENAME);
+    path.push(format!(
+        "{PLAYLIST_SAVE_FILENAME}.{}",
+        playlist_state::FILE_EXTENSION
+    ));
+
+    Ok(path)
+}
+
+/// Directory in the config dir that contains all named playlists
+const NAMED_PLAYLISTS_DIR: &str = "playlists";
+/// Comment prefix in the old main playlist file that stores the current named playlist
+const LEGACY_NAME_PREFIX: &str = "#name:";
+/// Comment prefix in a old named playlist file that stores the position in the current track, in seconds
+const LEGACY_POSITION_PREFIX: &str = "#position:";
+
+fn get_named_playlists_dir() -> Result<PathBuf> {
+    let mut path = get_app_config_path()?;
+    path.push(NAMED_PLAYLISTS_DIR);
 
     Ok(path)
 }
 
+/// Check that `name` can be used as a playlist name.
+///
+/// It has to be usable as a single file name and not be hidden.
+///
+/// # Errors
+///
+/// If the name is empty, starts with a `.`, contains path separators or control characters
+pub fn check_playlist_name(name: &str) -> Result<()> {
+    if name.trim().is_empty() {
+        bail!("Playlist name cannot be empty");
+    }
+    if name.starts_with('.') {
+        bail!("Playlist name cannot start with a \".\"");
+    }
+    if name.contains(['/', '\\']) || name.chars().any(char::is_control) {
+        bail!("Playlist name cannot contain path separators or control characters");
+    }
+
+    Ok(())
+}
+
+/// Get the path for named playlist `name`.
+///
+/// # Errors
+///
+/// - When the name is not valid, see [`check_playlist_name`]
+/// - When the config path cannot be determined
+fn named_playlist_path(name: &str) -> Result<PathBuf> {
+    check_playlist_name(name)?;
+
+    let mut path = get_named_playlists_dir()?;
+    path.push(format!("{name}.{}", playlist_state::FILE_EXTENSION));
+
+    Ok(path)
+}
+
+/// Get the path of the existing file for named playlist `name`, which may still be in the old format.
+///
+/// # Errors
+///
+/// See [`named_playlist_path`]
+fn existing_named_playlist_path(name: &str) -> Result<Option<PathBuf>> {
+    let path = named_playlist_path(name)?;
+    if path.is_file() {
+        return Ok(Some(path));
+    }
+
+    let legacy_path = path.with_extension(playlist_state::LEGACY_FILE_EXTENSION);
+    if legacy_path.is_file() {
+        return Ok(Some(legacy_path));
+    }
+
+    Ok(None)
+}
+
+/// Create the state file entries for `tracks`, with `entry_ids` in the same order.
+fn state_entries<'a>(
+    tracks: impl IntoIterator<Item = &'a Track>,
+    entry_ids: &[u64],
+) -> Vec<PlaylistStateEntry> {
+    tracks
+        .into_iter()
+        .zip(entry_ids)
+        .map(|(track, id)| PlaylistStateEntry {
+            id: *id,
+            source: track.as_track_source().into(),
+            metadata: CachedMetadata::from_track(track),
+        })
+        .collect()
+}
+
+/// Convert a track's url into a [`PlaylistValue`], keeping it as-is if it is not a valid url.
+fn url_value(url: &str) -> PlaylistValue {
+    PlaylistValue::try_from_str(url).unwrap_or_else(|_| PlaylistValue::Path(url.into()))
+}
+
+/// Sort `tracks` stable by `sort`, tracks without the metadata are always sorted last.
+fn sort_tracks(tracks: Vec<Track>, sort: PlaylistSort) -> Vec<Track> {
+    let descending = sort.descending;
+
+    if sort.key == PlaylistSortKey::DateAdded {
+        // file times are not part of the track, so only read them once per track
+        let mut keyed: Vec<_> = tracks
+            .into_iter()
+            .map(|track| (date_added(&track), track))
+            .collect();
+        keyed.sort_by(|(a, _), (b, _)| compare_option(a.as_ref(), b.as_ref(), descending));
+
+        return keyed.into_iter().map(|(_, track)| track).collect();
+    }
+
+    let mut tracks = tracks;
+    tracks.sort_by(|a, b| compare_tracks(a, b, sort.key, descending));
+
+    tracks
+}
+
+/// Compare two tracks by the given `key`, see [`sort_tracks`].
+fn compare_tracks(a: &Track, b: &Track, key: PlaylistSortKey, descending: bool) -> Ordering {
+    fn album(track: &Track) -> Option<&str> {
+        track.as_track().and_then(TrackData::album)
+    }
+    let disc_track = |a: &Track, b: &Track| {
+        let a = a.as_track();
+        let b = b.as_track();
+        compare_option(
+            a.and_then(TrackData::disc_number).as_ref(),
+            b.and_then(TrackData::disc_number).as_ref(),
+            descending,
+        )
+        .then_with(|| {
+            compare_option(
+                a.and_then(TrackData::track_number).as_ref(),
+                b.and_then(TrackData::track_number).as_ref(),
+                descending,
+            )
+        })
+    };
+
+    match key {
+        PlaylistSortKey::Artist => compare_text(a.artist(), b.artist(), descending)
+            .then_with(|| compare_text(album(a), album(b), descending))
+            .then_with(|| disc_track(a, b)),
+        PlaylistSortKey::Album => {
+            compare_text(album(a), album(b), descending).then_with(|| disc_track(a, b))
+        }
+        PlaylistSortKey::DiscTrack => disc_track(a, b),
+        PlaylistSortKey::Title => compare_text(a.title(), b.title(), descending),
+        PlaylistSortKey::Duration => {
+            compare_option(a.duration().as_ref(), b.duration().as_ref(), descending)
+        }
+        PlaylistSortKey::Path => compare_option(
+            Some(&track_location(a)),
+            Some(&track_location(b)),
+            descending,
+        ),
+        PlaylistSortKey::DateAdded => {
+            compare_option(date_added(a).as_ref(), date_added(b).as_ref(), descending)
+        }
+    }
+}
+
+/// Compare two optional values, `None` is always sorted last regardless of `descending`.
+fn compare_option<T: Ord>(a: Option<&T>, b: Option<&T>, descending: bool) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) if descending => b.cmp(a),
+        (Some(a), Some(b)) => a.cmp(b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// Compare two optional texts case-insensitively, see [`compare_option`].
+fn compare_text(a: Option<&str>, b: Option<&str>, descending: bool) -> Ordering {
+    compare_option(
+        a.map(str::to_lowercase).as_ref(),
+        b.map(str::to_lowercase).as_ref(),
+        descending,
+    )
+}
+
+/// Get the path or url of the track
+fn track_location(track: &Track) -> Cow<'_, str> {
+    match track.inner() {
+        MediaTypes::Track(track_data) => track_data.path().to_string_lossy(),
+        MediaTypes::Radio(radio_track_data) => radio_track_data.url().into(),
+        MediaTypes::Podcast(podcast_track_data) => podcast_track_data.url().into(),
+    }
+}
+
+/// Get the time the file of the track was created, or modified if the platform does not support creation times.
+fn date_added(track: &Track) -> Option<SystemTime> {
+    let path = track.path()?;
+    let metadata = std::fs::metadata(path).ok()?;
+
+    metadata.created().or_else(|_| metadata.modified()).ok()
+}
+
 // NOTE: this is not "thiserror" due to custom "Display" impl (the "Option" handling)
 /// Error for when [`Playlist::add_track`] fails
 #[derive(Debug)]
@@ -1212,14 +2851,49 @@ impl Error for PlaylistAddErrorVec {}
 
 #[cfg(test)]
 mod tests {
-    use std::path::PathBuf;
+    use std::path::{Path, PathBuf};
+    use std::sync::Arc;
 
+    use parking_lot::RwLock;
     use termusiclib::{
-        player::playlist_helpers::PlaylistTrackSource,
-        track::{MediaTypes, PodcastTrackData, RadioTrackData, TrackData},
+        config::{v2::server::LoopMode, ServerOverlay},
+        player::playlist_helpers::{
+            PlaylistMoveSelection, PlaylistMoveTrack, PlaylistRemoveTrackIndexed,
+            PlaylistSelectedTrack, PlaylistSelection, PlaylistSort, PlaylistSortKey,
+            PlaylistSwapTrack, PlaylistTrackSource,
+        },
+        player::RunningStatus,
+        playlist::state::{
+            self as playlist_state, CachedMetadata, PlaylistState, PlaylistStateEntry,
+            PlaylistStateSource,
+        },
+        podcast::db::Database as DBPod,
+        track::{MediaTypes, PodcastTrackData, RadioTrackData, Track, TrackData, TrackMetadata},
     };
+    use tokio::sync::broadcast;
 
-    use super::Playlist;
+    use super::{check_playlist_name, Playlist, LEGACY_NAME_PREFIX};
+
+    /// Create a playlist with radio tracks `0..amount`, currently at the first track
+    fn playlist_with_tracks(amount: usize) -> Playlist {
+        let config = Arc::new(RwLock::new(ServerOverlay::default()));
+        let (stream_tx, _) = broadcast::channel(10);
+        let mut playlist = Playlist::new(&config, stream_tx);
+        playlist.set_loop_mode(LoopMode::Playlist);
+        playlist.tracks = (0..amount)
+            .map(|v| Track::new_radio(format!("http://track/{v}")))
+            .collect();
+
+        playlist
+    }
+
+    fn current_url(playlist: &Playlist) -> PlaylistTrackSource {
+        playlist.current_track().unwrap().as_track_source()
+    }
+
+    fn url(name: &str) -> PlaylistTrackSource {
+        PlaylistTrackSource::Url(format!("http://{name}"))
+    }
 
     #[test]
     fn should_pass_check_info() {
@@ -1262,4 +2936,641 @@ mod tests {
         )
         .unwrap_err();
     }
+
+    #[test]
+    fn should_check_playlist_names() {
+        check_playlist_name("Morning Mix").unwrap();
+        check_playlist_name("rock & roll (2024)").unwrap();
+
+        check_playlist_name("").unwrap_err();
+        check_playlist_name("   ").unwrap_err();
+        check_playlist_name(".hidden").unwrap_err();
+        check_playlist_name("../escape").unwrap_err();
+        check_playlist_name("some/dir").unwrap_err();
+        check_playlist_name("some\\dir").unwrap_err();
+        check_playlist_name("new\nline").unwrap_err();
+    }
+
+    #[test]
+    fn should_play_queue_before_playlist() {
+        let mut playlist = playlist_with_tracks(3);
+        playlist.queue.push_back(Track::new_radio("http://queue/0"));
+        playlist.queue.push_back(Track::new_radio("http://queue/1"));
+
+        playlist.next();
+        assert_eq!(current_url(&playlist), url("queue/0"));
+        assert_eq!(playlist.get_current_track_index(), 0);
+        playlist.next();
+        assert_eq!(current_url(&playlist), url("queue/1"));
+        playlist.next();
+        assert_eq!(current_url(&playlist), url("track/1"));
+        assert!(playlist.queue().is_empty());
+        // the playlist track before the queued tracks is only recorded as played once
+        assert_eq!(playlist.played_index, vec![0]);
+        assert_eq!(
+            playlist
+                .play_counts
+                .get(&playlist.tracks[0].as_track_source()),
+            Some(&1)
+        );
+
+        // going back from a queued track returns to the playlist track before it
+        playlist.queue.push_back(Track::new_radio("http://queue/2"));
+        playlist.next();
+        assert_eq!(current_url(&playlist), url("queue/2"));
+        playlist.previous();
+        assert_eq!(current_url(&playlist), url("track/1"));
+    }
+
+    #[test]
+    fn should_enqueue_queue_gapless() {
+        let mut playlist = playlist_with_tracks(3);
+        playlist.queue.push_back(Track::new_radio("http://queue/0"));
+
+        let next = playlist.fetch_next_track().unwrap().as_track_source();
+        assert_eq!(next, url("queue/0"));
+        assert!(playlist.has_next_track());
+
+        // a track queued in front while the other was already enqueued does not replace it
+        playlist
+            .queue
+            .push_front(Track::new_radio("http://queue/1"));
+        playlist.next();
+        assert_eq!(current_url(&playlist), url("queue/0"));
+        playlist.set_next_track(None);
+        assert!(!playlist.has_next_track());
+
+        playlist.next();
+        assert_eq!(current_url(&playlist), url("queue/1"));
+        assert!(playlist.queue().is_empty());
+
+        // with a empty queue, the playlist continues after the last playlist track
+        let next = playlist.fetch_next_track().unwrap().as_track_source();
+        assert_eq!(next, url("track/1"));
+    }
+
+    fn sources(playlist: &Playlist) -> Vec<PlaylistTrackSource> {
+        playlist.tracks.iter().map(Track::as_track_source).collect()
+    }
+
+    #[test]
+    fn should_undo_and_redo_edits() {
+        let mut playlist = playlist_with_tracks(4);
+        let original = sources(&playlist);
+        playlist.current_track_index = 3;
+        playlist.played_index = vec![0, 3];
+        playlist.next_track_index = Some(2);
+
+        playlist
+            .remove_tracks(&PlaylistRemoveTrackIndexed {
+                at_index: 1,
+                tracks: vec![url("track/1"), url("track/2")],
+            })
+            .unwrap();
+        assert_eq!(sources(&playlist), vec![url("track/0"), url("track/3")]);
+        assert_eq!(playlist.current_track_index, 1);
+        assert_eq!(playlist.played_index, vec![0, 1]);
+        assert_eq!(playlist.next_track_index, None);
+
+        playlist.undo().unwrap();
+        assert_eq!(sources(&playlist), original);
+        assert_eq!(playlist.current_track_index, 3);
+        assert_eq!(playlist.played_index, vec![0, 3]);
+        playlist.redo().unwrap();
+        assert_eq!(sources(&playlist), vec![url("track/0"), url("track/3")]);
+        assert_eq!(playlist.current_track_index, 1);
+        playlist.undo().unwrap();
+
+        playlist
+            .swap_tracks(&PlaylistSwapTrack {
+                index_a: 0,
+                index_b: 3,
+            })
+            .unwrap();
+        playlist.shuffle();
+        let shuffled = sources(&playlist);
+        let (current, played) = (playlist.current_track_index, playlist.played_index.clone());
+        playlist.clear();
+        assert!(playlist.tracks.is_empty());
+        assert!(playlist.played_index.is_empty());
+
+        playlist.undo().unwrap();
+        assert_eq!(sources(&playlist), shuffled);
+        assert_eq!(playlist.current_track_index, current);
+        assert_eq!(playlist.played_index, played);
+        playlist.undo().unwrap();
+        assert_eq!(sources(&playlist)[0], url("track/3"));
+        playlist.undo().unwrap();
+        assert_eq!(sources(&playlist), original);
+
+        // nothing left to undo
+        playlist.undo().unwrap();
+        assert_eq!(sources(&playlist), original);
+
+        // a new edit discards the redo journal
+        playlist.clear();
+        playlist.redo().unwrap();
+        assert!(playlist.tracks.is_empty());
+    }
+
+    #[test]
+    fn should_sort_and_deduplicate() {
+        let mut playlist = playlist_with_tracks(0);
+        playlist.tracks = ["b", "a", "c", "a", "b"]
+            .into_iter()
+            .map(|v| Track::new_radio(format!("http://{v}")))
+            .collect();
+        // "c"
+        playlist.current_track_index = 2;
+
+        playlist.deduplicate();
+        assert_eq!(sources(&playlist), vec![url("b"), url("a"), url("c")]);
+        assert_eq!(current_url(&playlist), url("c"));
+
+        playlist.sort(PlaylistSort {
+            key: PlaylistSortKey::Path,
+            descending: true,
+        });
+        assert_eq!(sources(&playlist), vec![url("c"), url("b"), url("a")]);
+        assert_eq!(current_url(&playlist), url("c"));
+
+        // radio tracks have no titles, so the order stays the same
+        playlist.sort(PlaylistSort {
+            key: PlaylistSortKey::Title,
+            descending: false,
+        });
+        assert_eq!(sources(&playlist), vec![url("c"), url("b"), url("a")]);
+
+        playlist.undo().unwrap();
+        playlist.undo().unwrap();
+        assert_eq!(
+            sources(&playlist),
+            vec![url("b"), url("a"), url("c"), url("a"), url("b")]
+        );
+    }
+
+    /// Select the tracks at `indexes` of [`playlist_with_tracks`]
+    fn selection(indexes: &[u64]) -> PlaylistSelection {
+        PlaylistSelection {
+            tracks: indexes
+                .iter()
+                .map(|index| PlaylistSelectedTrack {
+                    index: *index,
+                    id: url(&format!("track/{index}")),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn should_move_tracks_as_block() {
+        let mut playlist = playlist_with_tracks(6);
+        let original = sources(&playlist);
+        // "track/4"
+        playlist.current_track_index = 4;
+
+        // moving up
+        playlist
+            .move_tracks(&PlaylistMoveTrack {
+                from_index: 3,
+                count: 2,
+                to_index: 0,
+            })
+            .unwrap();
+        assert_eq!(
+            sources(&playlist),
+            vec![
+                url("track/3"),
+                url("track/4"),
+                url("track/0"),
+                url("track/1"),
+                url("track/2"),
+                url("track/5")
+            ]
+        );
+        assert_eq!(playlist.current_track_index, 1);
+        assert_eq!(current_url(&playlist), url("track/4"));
+        playlist.undo().unwrap();
+        assert_eq!(sources(&playlist), original);
+        assert_eq!(playlist.current_track_index, 4);
+
+        // moving down, past the current track
+        playlist
+            .move_tracks(&PlaylistMoveTrack {
+                from_index: 0,
+                count: 1,
+                to_index: 5,
+            })
+            .unwrap();
+        assert_eq!(
+            sources(&playlist),
+            vec![
+                url("track/1"),
+                url("track/2"),
+                url("track/3"),
+                url("track/4"),
+                url("track/5"),
+                url("track/0")
+            ]
+        );
+        assert_eq!(current_url(&playlist), url("track/4"));
+        playlist.undo().unwrap();
+        playlist.redo().unwrap();
+        assert_eq!(sources(&playlist)[5], url("track/0"));
+        playlist.undo().unwrap();
+
+        // the block has to fit at the target
+        assert!(playlist
+            .move_tracks(&PlaylistMoveTrack {
+                from_index: 0,
+                count: 2,
+                to_index: 5,
+            })
+            .is_err());
+        assert!(playlist
+            .move_tracks(&PlaylistMoveTrack {
+                from_index: 5,
+                count: 2,
+                to_index: 0,
+            })
+            .is_err());
+        assert_eq!(sources(&playlist), original);
+    }
+
+    #[test]
+    fn should_edit_selection_as_one() {
+        let mut playlist = playlist_with_tracks(5);
+        let original = sources(&playlist);
+        // "track/3"
+        playlist.current_track_index = 3;
+
+        playlist
+            .move_selection(&PlaylistMoveSelection {
+                selection: selection(&[3, 1]),
+                to_index: 0,
+            })
+            .unwrap();
+        assert_eq!(
+            sources(&playlist),
+            vec![
+                url("track/1"),
+                url("track/3"),
+                url("track/0"),
+                url("track/2"),
+                url("track/4")
+            ]
+        );
+        assert_eq!(current_url(&playlist), url("track/3"));
+        playlist.undo().unwrap();
+        assert_eq!(sources(&playlist), original);
+
+        // clamped to the end
+        playlist
+            .move_selection(&PlaylistMoveSelection {
+                selection: selection(&[0, 2]),
+                to_index: 99,
+            })
+            .unwrap();
+        assert_eq!(
+            sources(&playlist),
+            vec![
+                url("track/1"),
+                url("track/3"),
+                url("track/4"),
+                url("track/0"),
+                url("track/2")
+            ]
+        );
+        playlist.undo().unwrap();
+        assert_eq!(sources(&playlist), original);
+
+        // moving down and up at once
+        playlist
+            .move_selection(&PlaylistMoveSelection {
+                selection: selection(&[0, 4]),
+                to_index: 1,
+            })
+            .unwrap();
+        assert_eq!(
+            sources(&playlist),
+            vec![
+                url("track/1"),
+                url("track/0"),
+                url("track/4"),
+                url("track/2"),
+                url("track/3")
+            ]
+        );
+        playlist.undo().unwrap();
+        assert_eq!(sources(&playlist), original);
+
+        playlist.remove_selection(&selection(&[0, 2, 4])).unwrap();
+        assert_eq!(sources(&playlist), vec![url("track/1"), url("track/3")]);
+        assert_eq!(current_url(&playlist), url("track/3"));
+        playlist.undo().unwrap();
+        assert_eq!(sources(&playlist), original);
+
+        // a mismatching track fails the whole selection
+        let mut wrong = selection(&[0, 1]);
+        wrong.tracks[1].index = 2;
+        assert!(playlist.remove_selection(&wrong).is_err());
+        assert_eq!(sources(&playlist), original);
+    }
+
+    #[test]
+    fn should_keep_played_and_next_tracks_on_reorder() {
+        let mut playlist = playlist_with_tracks(5);
+        let played = |playlist: &Playlist| -> Vec<PlaylistTrackSource> {
+            playlist
+                .played_index
+                .iter()
+                .map(|index| playlist.tracks[*index].as_track_source())
+                .collect()
+        };
+        playlist.played_index = vec![0, 4];
+        playlist.next_track_index = Some(2);
+
+        playlist.sort(PlaylistSort {
+            key: PlaylistSortKey::Path,
+            descending: true,
+        });
+        assert_eq!(played(&playlist), vec![url("track/0"), url("track/4")]);
+        assert_eq!(playlist.next_track_index, Some(2));
+
+        playlist
+            .move_selection(&PlaylistMoveSelection {
+                selection: PlaylistSelection {
+                    tracks: vec![PlaylistSelectedTrack {
+                        index: 4,
+                        id: url("track/0"),
+                    }],
+                },
+                to_index: 0,
+            })
+            .unwrap();
+        assert_eq!(played(&playlist), vec![url("track/0"), url("track/4")]);
+        assert_eq!(
+            playlist.tracks[playlist.next_track_index.unwrap()].as_track_source(),
+            url("track/2")
+        );
+
+        // removed tracks are not kept
+        playlist
+            .remove_selection(&PlaylistSelection {
+                tracks: vec![PlaylistSelectedTrack {
+                    index: 1,
+                    id: url("track/4"),
+                }],
+            })
+            .unwrap();
+        assert_eq!(played(&playlist), vec![url("track/0")]);
+        assert_eq!(
+            playlist.tracks[playlist.next_track_index.unwrap()].as_track_source(),
+            url("track/2")
+        );
+    }
+
+    #[test]
+    fn should_play_each_track_once_per_no_repeat_cycle() {
+        let mut playlist = playlist_with_tracks(5);
+        playlist.set_loop_mode(LoopMode::ShuffleNoRepeat);
+
+        for _ in 0..2 {
+            let mut played = vec![playlist.get_current_track_index()];
+            for _ in 0..4 {
+                playlist.next();
+                played.push(playlist.get_current_track_index());
+            }
+            played.sort_unstable();
+            assert_eq!(played, vec![0, 1, 2, 3, 4]);
+
+            // start the next cycle
+            playlist.next();
+        }
+    }
+
+    #[test]
+    fn should_go_back_through_history_in_shuffle_modes() {
+        let mut playlist = playlist_with_tracks(5);
+        playlist.set_loop_mode(LoopMode::ShuffleNoRepeat);
+
+        let mut played = vec![playlist.get_current_track_index()];
+        for _ in 0..3 {
+            playlist.next();
+            played.push(playlist.get_current_track_index());
+        }
+        played.pop();
+        while let Some(index) = played.pop() {
+            playlist.previous();
+            assert_eq!(playlist.get_current_track_index(), index);
+        }
+
+        // without any history left, the current track stays
+        playlist.previous();
+        assert_eq!(playlist.get_current_track_index(), 0);
+    }
+
+    #[test]
+    fn should_shuffle_albums_by_artist_and_title() {
+        let track = |name: &str, artist: &str, album_artist: Option<&str>, track_number: u32| {
+            Track::from_path_with_metadata(
+                PathBuf::from(format!("/music/{name}.mp3")),
+                TrackMetadata {
+                    artist: Some(artist.to_string()),
+                    album_artist: album_artist.map(ToString::to_string),
+                    album: Some("Greatest Hits".to_string()),
+                    track_number: Some(track_number),
+                    ..Default::default()
+                },
+            )
+        };
+        let mut playlist = playlist_with_tracks(0);
+        playlist.set_loop_mode(LoopMode::ShuffleAlbums);
+        playlist.tracks = vec![
+            track("a1", "A", None, 1),
+            track("b1", "B", None, 1),
+            track("b2", "B", None, 2),
+            track("a2", "A feat. C", Some("A"), 2),
+        ];
+
+        // the album of "B" has the same title, but is a different album
+        playlist.next();
+        assert_eq!(playlist.get_current_track_index(), 3);
+        // the next album starts at its first track
+        playlist.next();
+        assert_eq!(playlist.get_current_track_index(), 1);
+        playlist.next();
+        assert_eq!(playlist.get_current_track_index(), 2);
+    }
+
+    #[test]
+    fn should_repeat_current_track() {
+        let mut playlist = playlist_with_tracks(3);
+        playlist.set_repeat_count(2);
+
+        playlist.next();
+        assert_eq!(playlist.get_current_track_index(), 0);
+        assert_eq!(playlist.playback_controls().repeat_count, 1);
+
+        // gapless enqueue should also repeat the current track
+        assert_eq!(
+            playlist.fetch_next_track().map(Track::as_track_source),
+            Some(url("track/0"))
+        );
+        playlist.next();
+        assert_eq!(playlist.get_current_track_index(), 0);
+        assert_eq!(playlist.playback_controls().repeat_count, 0);
+
+        playlist.set_next_track(None);
+        playlist.next();
+        assert_eq!(playlist.get_current_track_index(), 1);
+
+        // a different track was already enqueued, so the repeat is cancelled
+        assert_eq!(
+            playlist.fetch_next_track().map(Track::as_track_source),
+            Some(url("track/2"))
+        );
+        playlist.set_repeat_count(1);
+        playlist.next();
+        assert_eq!(playlist.get_current_track_index(), 2);
+        assert_eq!(playlist.playback_controls().repeat_count, 0);
+    }
+
+    #[test]
+    fn should_hold_until_started() {
+        let mut playlist = playlist_with_tracks(2);
+        playlist.set_status(RunningStatus::Running);
+
+        playlist.hold();
+        assert!(playlist.is_stopped());
+        assert!(playlist.is_held());
+
+        // a stop without holding does not release it
+        playlist.set_status(RunningStatus::Stopped);
+        assert!(playlist.is_held());
+
+        playlist.set_status(RunningStatus::Running);
+        assert!(!playlist.is_held());
+    }
+
+    #[test]
+    fn should_keep_entry_ids_stable() {
+        let mut playlist = playlist_with_tracks(3);
+        assert_eq!(playlist.assign_entry_ids(), vec![0, 1, 2]);
+        // saving again does not change anything
+        assert_eq!(playlist.assign_entry_ids(), vec![0, 1, 2]);
+
+        playlist.tracks.remove(0);
+        playlist
+            .tracks
+            .insert(1, Track::new_radio("http://track/new"));
+        playlist.tracks.swap(0, 2);
+        assert_eq!(playlist.assign_entry_ids(), vec![2, 3, 1]);
+
+        // ids loaded from a file are kept, new ones continue after the highest
+        playlist.set_entry_ids(&[7, 5, 6], 8);
+        playlist.tracks.push(Track::new_radio("http://track/1"));
+        assert_eq!(playlist.assign_entry_ids(), vec![7, 5, 6, 8]);
+    }
+
+    /// Create a new temporary directory with a empty podcast database
+    fn state_dir(name: &str) -> (PathBuf, DBPod) {
+        let dir =
+            std::env::temp_dir().join(format!("termusic-playlist-{name}-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let db_pod = DBPod::new(&dir).unwrap();
+
+        (dir, db_pod)
+    }
+
+    /// A state entry for `path` with the cached `title`
+    fn cached_entry(
+        id: u64,
+        path: &Path,
+        modified: Option<u64>,
+        title: &str,
+    ) -> PlaylistStateEntry {
+        PlaylistStateEntry {
+            id,
+            source: PlaylistStateSource::Path {
+                path: path.to_path_buf(),
+            },
+            metadata: Some(CachedMetadata {
+                modified,
+                title: Some(title.to_string()),
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn should_load_state_with_cached_metadata() {
+        let (dir, db_pod) = state_dir("cache");
+        // not a valid audio file, so reading the tags results in no title
+        let path = dir.join("track.mp3");
+        std::fs::write(&path, "").unwrap();
+        let modified = CachedMetadata::from_track(&Track::read_track_from_path(&path).unwrap())
+            .unwrap()
+            .modified;
+
+        let state = PlaylistState {
+            current_track_index: 99,
+            next_id: 0,
+            entries: vec![
+                cached_entry(3, &path, modified, "Cached"),
+                cached_entry(7, &path, modified.map(|v| v - 1000), "Stale"),
+                cached_entry(1, &dir.join("missing.mp3"), Some(1), "Missing"),
+                PlaylistStateEntry {
+                    id: 2,
+                    source: PlaylistStateSource::Podcast {
+                        url: "http://episode".to_string(),
+                    },
+                    metadata: None,
+                },
+            ],
+            ..Default::default()
+        };
+        let loaded = Playlist::load_state(state, &db_pod);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let titles: Vec<Option<&str>> = loaded.tracks.iter().map(Track::title).collect();
+        assert_eq!(titles, [Some("Cached"), None, Some("Missing"), None]);
+        // unknown podcast episodes are kept as radio
+        assert_eq!(loaded.tracks[3].as_track_source(), url("episode"));
+        assert_eq!(loaded.entry_ids, [3, 7, 1, 2]);
+        assert_eq!(loaded.next_entry_id, 8);
+        assert_eq!(loaded.current_track_index, 3);
+    }
+
+    #[test]
+    fn should_migrate_legacy_playlist() {
+        let (dir, db_pod) = state_dir("migrate");
+        let legacy_path = dir.join("playlist.log");
+        let path = dir.join("playlist.toml");
+        std::fs::write(
+            &legacy_path,
+            format!(
+                "1\n{LEGACY_NAME_PREFIX}Some Name\n# comment\nhttp://radio/0\nhttp://radio/1\n"
+            ),
+        )
+        .unwrap();
+
+        let loaded = Playlist::migrate_legacy(&legacy_path, &path, &db_pod).unwrap();
+        assert_eq!(loaded.name.as_deref(), Some("Some Name"));
+        assert_eq!(loaded.current_track_index, 1);
+        assert_eq!(loaded.entry_ids, [0, 1]);
+        assert_eq!(loaded.next_entry_id, 2);
+
+        assert!(!legacy_path.exists());
+        assert!(dir.join("playlist.log.bak").exists());
+        let state = playlist_state::decode(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(state.name.as_deref(), Some("Some Name"));
+        assert_eq!(state.current_track_index, 1);
+        assert_eq!(state.next_id, 2);
+        let sources: Vec<PlaylistTrackSource> =
+            state.entries.into_iter().map(|v| v.source.into()).collect();
+        assert_eq!(sources, [url("radio/0"), url("radio/1")]);
+    }
 }
@@ -102,6 +102,16 @@ pub enum Action {
         #[arg(value_name = "FILE")]
         file: PathBuf,
     },
+    /// Analyze the loudness (EBU R128) of all tracks and write ReplayGain tags.
+    #[command(name = "replaygain")]
+    ReplayGain {
+        /// Directories to analyze, default are the configured music directories.
+        #[arg(value_name = "DIR")]
+        dirs: Vec<PathBuf>,
+        /// Only analyze and print the results, dont write any tags.
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 const DEFAULT_LOGFILE_FILENAME: &str = "termusic-server.log";
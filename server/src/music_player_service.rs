@@ -1,16 +1,27 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use parking_lot::Mutex;
+use std::path::Path;
 use std::pin::Pin;
 use std::sync::Arc;
+use termusiclib::config::v2::server::LoopMode;
 use termusiclib::config::SharedServerSettings;
 use termusiclib::player::music_player_server::MusicPlayer;
 use termusiclib::player::playlist_helpers::{PlaylistPlaySpecific, PlaylistRemoveTrackType};
 use termusiclib::player::{
-    self, stream_updates, Empty, GaplessState, GetProgressResponse, PlayState, PlayerTime,
-    PlaylistLoopMode, PlaylistSwapTracks, PlaylistTracks, PlaylistTracksToAdd,
-    PlaylistTracksToRemove, SpeedReply, StreamUpdates, UpdateMissedEvents, VolumeReply,
+    self, named_playlists, stream_updates, AbLoopRange, Empty, GaplessState, GetProgressResponse,
+    NamedPlaylist, NamedPlaylistRename, NamedPlaylistSelection, NamedPlaylists, PitchState,
+    PlayState, PlayerTime, PlaylistLoopMode, PlaylistMoveSelection, PlaylistMoveTracks,
+    PlaylistPlaybackControls, PlaylistRepeatCount, PlaylistSaveToFile, PlaylistSavedToFile,
+    PlaylistSelection, PlaylistSort, PlaylistSwapTracks, PlaylistTracks, PlaylistTracksToAdd,
+    PlaylistTracksToRemove, QueueRemoveTrack, QueueTracks, SpeedReply, StreamUpdates,
+    UpdateMissedEvents, VolumeReply,
+};
+use termusiclib::playlist::resolve_export_path;
+use termusicplayback::playlist::check_playlist_name;
+use termusicplayback::{
+    ABLoopError, PlayerCmd, PlayerCmdCallback, PlayerCmdSender, Playlist, SharedPlaylist,
+    SpectrumTX, StreamTX,
 };
-use termusicplayback::{PlayerCmd, PlayerCmdCallback, PlayerCmdSender, SharedPlaylist, StreamTX};
 use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
 use tokio_stream::wrappers::BroadcastStream;
 use tokio_stream::{Stream, StreamExt};
@@ -22,6 +33,7 @@ use crate::PlayerStats;
 pub struct MusicPlayerService {
     cmd_tx: PlayerCmdSender,
     stream_tx: StreamTX,
+    spectrum_tx: SpectrumTX,
     config: SharedServerSettings,
     playlist: SharedPlaylist,
     pub(crate) player_stats: Arc<Mutex<PlayerStats>>,
@@ -31,6 +43,7 @@ impl MusicPlayerService {
     pub fn new(
         cmd_tx: PlayerCmdSender,
         stream_tx: StreamTX,
+        spectrum_tx: SpectrumTX,
         config: SharedServerSettings,
         playlist: SharedPlaylist,
     ) -> Self {
@@ -40,6 +53,7 @@ impl MusicPlayerService {
             cmd_tx,
             player_stats,
             stream_tx,
+            spectrum_tx,
             playlist,
             config,
         }
@@ -53,6 +67,20 @@ impl MusicPlayerService {
         }
     }
 
+    /// Convert the result of the last A-B loop change to a [`Status`]
+    #[expect(clippy::result_large_err)] // see "command_cb"
+    fn ab_loop_result(&self) -> Result<(), Status> {
+        match self.player_stats.lock().ab_loop_result {
+            Ok(()) => Ok(()),
+            Err(ABLoopError::OutOfRange) => Err(Status::failed_precondition(
+                "A-B loop does not start before the end of the current track",
+            )),
+            Err(ABLoopError::Unsupported) => Err(Status::unimplemented(
+                "A-B loops are not supported by the current backend",
+            )),
+        }
+    }
+
     #[expect(clippy::result_large_err)] // for now we dont care about that here, also see https://github.com/hyperium/tonic/issues/2253
     fn command_cb(&self, cmd: PlayerCmd) -> Result<PlayerCmdCallback, Status> {
         let rx = self.cmd_tx.send_cb(cmd.clone()).map_err(|err| {
@@ -81,6 +109,49 @@ impl MusicPlayer for MusicPlayerService {
 
         Ok(Response::new(reply))
     }
+    async fn set_loop_mode(
+        &self,
+        request: Request<PlaylistLoopMode>,
+    ) -> Result<Response<PlaylistLoopMode>, Status> {
+        let mode = request.into_inner().mode;
+        let mode = u8::try_from(mode)
+            .ok()
+            .and_then(LoopMode::tryfrom_discriminant)
+            .ok_or_else(|| Status::invalid_argument(format!("Unknown loop mode {mode}")))?;
+
+        let rx = self.command_cb(PlayerCmd::SetLoopMode(mode))?;
+        // wait until the event was processed
+        let _ = rx.await;
+        let config = self.config.read();
+
+        let reply = PlaylistLoopMode {
+            mode: u32::from(config.settings.player.loop_mode.discriminant()),
+        };
+
+        Ok(Response::new(reply))
+    }
+    async fn toggle_stop_after_current(
+        &self,
+        _request: Request<Empty>,
+    ) -> Result<Response<PlaylistPlaybackControls>, Status> {
+        let rx = self.command_cb(PlayerCmd::ToggleStopAfterCurrent)?;
+        // wait until the event was processed
+        let _ = rx.await;
+        let reply = self.playlist.read().playback_controls().into();
+
+        Ok(Response::new(reply))
+    }
+    async fn set_repeat_count(
+        &self,
+        request: Request<PlaylistRepeatCount>,
+    ) -> Result<Response<PlaylistPlaybackControls>, Status> {
+        let rx = self.command_cb(PlayerCmd::SetRepeatCount(request.into_inner().count))?;
+        // wait until the event was processed
+        let _ = rx.await;
+        let reply = self.playlist.read().playback_controls().into();
+
+        Ok(Response::new(reply))
+    }
     async fn get_progress(
         &self,
         _request: Request<Empty>,
@@ -176,6 +247,65 @@ impl MusicPlayer for MusicPlayerService {
         Ok(Response::new(reply))
     }
 
+    async fn pitch_down(&self, _request: Request<Empty>) -> Result<Response<PitchState>, Status> {
+        let rx = self.command_cb(PlayerCmd::PitchDown)?;
+        // wait until the event was processed
+        let _ = rx.await;
+        let s = self.player_stats.lock();
+        let reply = PitchState { cents: s.pitch };
+
+        Ok(Response::new(reply))
+    }
+
+    async fn pitch_up(&self, _request: Request<Empty>) -> Result<Response<PitchState>, Status> {
+        let rx = self.command_cb(PlayerCmd::PitchUp)?;
+        // wait until the event was processed
+        let _ = rx.await;
+        let s = self.player_stats.lock();
+        let reply = PitchState { cents: s.pitch };
+
+        Ok(Response::new(reply))
+    }
+
+    async fn pitch_fine_down(
+        &self,
+        _request: Request<Empty>,
+    ) -> Result<Response<PitchState>, Status> {
+        let rx = self.command_cb(PlayerCmd::PitchFineDown)?;
+        // wait until the event was processed
+        let _ = rx.await;
+        let s = self.player_stats.lock();
+        let reply = PitchState { cents: s.pitch };
+
+        Ok(Response::new(reply))
+    }
+
+    async fn pitch_fine_up(
+        &self,
+        _request: Request<Empty>,
+    ) -> Result<Response<PitchState>, Status> {
+        let rx = self.command_cb(PlayerCmd::PitchFineUp)?;
+        // wait until the event was processed
+        let _ = rx.await;
+        let s = self.player_stats.lock();
+        let reply = PitchState { cents: s.pitch };
+
+        Ok(Response::new(reply))
+    }
+
+    async fn set_pitch(
+        &self,
+        request: Request<PitchState>,
+    ) -> Result<Response<PitchState>, Status> {
+        let rx = self.command_cb(PlayerCmd::SetPitch(request.into_inner().cents))?;
+        // wait until the event was processed
+        let _ = rx.await;
+        let s = self.player_stats.lock();
+        let reply = PitchState { cents: s.pitch };
+
+        Ok(Response::new(reply))
+    }
+
     async fn toggle_gapless(
         &self,
         _request: Request<Empty>,
@@ -223,6 +353,31 @@ impl MusicPlayer for MusicPlayerService {
         Ok(Response::new(reply))
     }
 
+    async fn set_ab_loop(&self, request: Request<AbLoopRange>) -> Result<Response<Empty>, Status> {
+        let converted = request
+            .into_inner()
+            .try_into()
+            .map_err(|err: anyhow::Error| Status::from_error(err.into()))?;
+
+        let rx = self.command_cb(PlayerCmd::SetABLoop(converted))?;
+        // wait until the event was processed
+        let _ = rx.await;
+        self.ab_loop_result()?;
+        let reply = Empty {};
+
+        Ok(Response::new(reply))
+    }
+
+    async fn clear_ab_loop(&self, _: Request<Empty>) -> Result<Response<Empty>, Status> {
+        let rx = self.command_cb(PlayerCmd::ClearABLoop)?;
+        // wait until the event was processed
+        let _ = rx.await;
+        self.ab_loop_result()?;
+        let reply = Empty {};
+
+        Ok(Response::new(reply))
+    }
+
     type SubscribeServerUpdatesStream =
         Pin<Box<dyn Stream<Item = Result<termusiclib::player::StreamUpdates, Status>> + Send>>;
     async fn subscribe_server_updates(
@@ -249,6 +404,21 @@ impl MusicPlayer for MusicPlayerService {
         Ok(Response::new(Box::pin(receiver_stream)))
     }
 
+    type SubscribeSpectrumStream =
+        Pin<Box<dyn Stream<Item = Result<termusiclib::player::SpectrumUpdate, Status>> + Send>>;
+    async fn subscribe_spectrum(
+        &self,
+        _: Request<Empty>,
+    ) -> Result<Response<Self::SubscribeSpectrumStream>, Status> {
+        let rx = self.spectrum_tx.subscribe();
+
+        // map to the grpc types, lagged frames are simply skipped as only the most recent frames are of interest
+        #[expect(clippy::result_large_err)] // see "command_cb"
+        let receiver_stream =
+            BroadcastStream::new(rx).filter_map(|res| res.ok().map(|v| Ok(v.into())));
+        Ok(Response::new(Box::pin(receiver_stream)))
+    }
+
     async fn add_to_playlist(
         &self,
         request: Request<PlaylistTracksToAdd>,
@@ -304,6 +474,57 @@ impl MusicPlayer for MusicPlayerService {
         Ok(Response::new(reply))
     }
 
+    async fn move_tracks(
+        &self,
+        request: Request<PlaylistMoveTracks>,
+    ) -> Result<Response<Empty>, Status> {
+        let converted = request
+            .into_inner()
+            .try_into()
+            .map_err(|err: anyhow::Error| Status::from_error(err.into()))?;
+
+        let rx = self.command_cb(PlayerCmd::PlaylistMoveTracks(converted))?;
+        // wait until the event was processed
+        let _ = rx.await;
+        let reply = Empty {};
+
+        Ok(Response::new(reply))
+    }
+
+    async fn remove_selection_from_playlist(
+        &self,
+        request: Request<PlaylistSelection>,
+    ) -> Result<Response<Empty>, Status> {
+        let converted = request
+            .into_inner()
+            .try_into()
+            .map_err(|err: anyhow::Error| Status::from_error(err.into()))?;
+
+        let rx = self.command_cb(PlayerCmd::PlaylistRemoveSelection(converted))?;
+        // wait until the event was processed
+        let _ = rx.await;
+        let reply = Empty {};
+
+        Ok(Response::new(reply))
+    }
+
+    async fn move_selection(
+        &self,
+        request: Request<PlaylistMoveSelection>,
+    ) -> Result<Response<Empty>, Status> {
+        let converted = request
+            .into_inner()
+            .try_into()
+            .map_err(|err: anyhow::Error| Status::from_error(err.into()))?;
+
+        let rx = self.command_cb(PlayerCmd::PlaylistMoveSelection(converted))?;
+        // wait until the event was processed
+        let _ = rx.await;
+        let reply = Empty {};
+
+        Ok(Response::new(reply))
+    }
+
     async fn get_playlist(&self, _: Request<Empty>) -> Result<Response<PlaylistTracks>, Status> {
         let playlist = self.playlist.read();
         let reply = playlist.as_grpc_playlist_tracks().unwrap();
@@ -331,4 +552,234 @@ impl MusicPlayer for MusicPlayerService {
 
         Ok(Response::new(reply))
     }
+
+    async fn undo_playlist(&self, _: Request<Empty>) -> Result<Response<Empty>, Status> {
+        let rx = self.command_cb(PlayerCmd::PlaylistUndo)?;
+        // wait until the event was processed
+        let _ = rx.await;
+        let reply = Empty {};
+
+        Ok(Response::new(reply))
+    }
+
+    async fn redo_playlist(&self, _: Request<Empty>) -> Result<Response<Empty>, Status> {
+        let rx = self.command_cb(PlayerCmd::PlaylistRedo)?;
+        // wait until the event was processed
+        let _ = rx.await;
+        let reply = Empty {};
+
+        Ok(Response::new(reply))
+    }
+
+    async fn sort_playlist(
+        &self,
+        request: Request<PlaylistSort>,
+    ) -> Result<Response<Empty>, Status> {
+        let converted = request
+            .into_inner()
+            .try_into()
+            .map_err(|err: anyhow::Error| Status::from_error(err.into()))?;
+
+        let rx = self.command_cb(PlayerCmd::PlaylistSort(converted))?;
+        // wait until the event was processed
+        let _ = rx.await;
+        let reply = Empty {};
+
+        Ok(Response::new(reply))
+    }
+
+    async fn deduplicate_playlist(&self, _: Request<Empty>) -> Result<Response<Empty>, Status> {
+        let rx = self.command_cb(PlayerCmd::PlaylistDeduplicate)?;
+        // wait until the event was processed
+        let _ = rx.await;
+        let reply = Empty {};
+
+        Ok(Response::new(reply))
+    }
+
+    async fn save_playlist(
+        &self,
+        request: Request<PlaylistSaveToFile>,
+    ) -> Result<Response<PlaylistSavedToFile>, Status> {
+        let PlaylistSaveToFile { path, overwrite } = request.into_inner();
+
+        let music_dirs = self.config.read().settings.player.music_dirs.clone();
+        let path = resolve_export_path(Path::new(&path), &music_dirs)
+            .map_err(|err| Status::invalid_argument(format!("{err:#}")))?;
+        if !overwrite && path.exists() {
+            return Err(Status::already_exists(format!(
+                "\"{}\" already exists",
+                path.display()
+            )));
+        }
+
+        // this only reads the playlist, so it does not need to go through the player thread
+        let content = self
+            .playlist
+            .read()
+            .encode_to_file(&path)
+            .map_err(|err| Status::failed_precondition(format!("{err:#}")))?;
+        std::fs::write(&path, content)
+            .with_context(|| format!("write \"{}\"", path.display()))
+            .map_err(|err| Status::from_error(err.into()))?;
+
+        let reply = PlaylistSavedToFile {
+            path: path.to_string_lossy().to_string(),
+        };
+
+        Ok(Response::new(reply))
+    }
+
+    async fn list_playlists(&self, _: Request<Empty>) -> Result<Response<NamedPlaylists>, Status> {
+        let names = Playlist::list_named().map_err(|err| Status::from_error(err.into()))?;
+        let current = self.playlist.read().name().map(ToString::to_string);
+
+        let reply = NamedPlaylists {
+            names,
+            optional_current: current.map(named_playlists::OptionalCurrent::Current),
+        };
+
+        Ok(Response::new(reply))
+    }
+
+    async fn load_playlist(
+        &self,
+        request: Request<NamedPlaylist>,
+    ) -> Result<Response<Empty>, Status> {
+        let name = request.into_inner().name;
+
+        // check here, as errors in the player thread cannot be returned
+        let names = Playlist::list_named().map_err(|err| Status::from_error(err.into()))?;
+        if !names.contains(&name) {
+            return Err(Status::not_found(format!(
+                "Playlist \"{name}\" does not exist"
+            )));
+        }
+
+        let rx = self.command_cb(PlayerCmd::PlaylistLoadNamed(name))?;
+        // wait until the event was processed
+        let _ = rx.await;
+        let reply = Empty {};
+
+        Ok(Response::new(reply))
+    }
+
+    async fn save_playlist_as(
+        &self,
+        request: Request<NamedPlaylist>,
+    ) -> Result<Response<Empty>, Status> {
+        let name = request.into_inner().name;
+
+        // check here, as errors in the player thread cannot be returned
+        check_playlist_name(&name).map_err(|err| Status::invalid_argument(err.to_string()))?;
+
+        // done in the player thread as it needs the current position
+        let rx = self.command_cb(PlayerCmd::PlaylistSaveNamed(name))?;
+        // wait until the event was processed
+        let _ = rx.await;
+        let reply = Empty {};
+
+        Ok(Response::new(reply))
+    }
+
+    async fn save_selection_as(
+        &self,
+        request: Request<NamedPlaylistSelection>,
+    ) -> Result<Response<Empty>, Status> {
+        let NamedPlaylistSelection { name, selection } = request.into_inner();
+        let selection = selection
+            .ok_or_else(|| Status::invalid_argument("Missing selection"))?
+            .try_into()
+            .map_err(|err: anyhow::Error| Status::from_error(err.into()))?;
+
+        check_playlist_name(&name).map_err(|err| Status::invalid_argument(err.to_string()))?;
+
+        // this only reads the playlist, so it does not need to go through the player thread
+        self.playlist
+            .read()
+            .save_selection_named(&name, &selection)
+            .map_err(|err| Status::from_error(err.into()))?;
+
+        let reply = Empty {};
+
+        Ok(Response::new(reply))
+    }
+
+    async fn rename_playlist(
+        &self,
+        request: Request<NamedPlaylistRename>,
+    ) -> Result<Response<Empty>, Status> {
+        let NamedPlaylistRename { from, to } = request.into_inner();
+
+        // this only touches the stored files and the current name, so it does not need to go through the player thread
+        self.playlist
+            .write()
+            .rename_named(&from, &to)
+            .map_err(|err| Status::from_error(err.into()))?;
+
+        let reply = Empty {};
+
+        Ok(Response::new(reply))
+    }
+
+    async fn delete_playlist(
+        &self,
+        request: Request<NamedPlaylist>,
+    ) -> Result<Response<Empty>, Status> {
+        let name = request.into_inner().name;
+
+        // this only touches the stored files and the current name, so it does not need to go through the player thread
+        self.playlist
+            .write()
+            .delete_named(&name)
+            .map_err(|err| Status::from_error(err.into()))?;
+
+        let reply = Empty {};
+
+        Ok(Response::new(reply))
+    }
+
+    async fn queue_add(&self, request: Request<QueueTracks>) -> Result<Response<Empty>, Status> {
+        let converted = request
+            .into_inner()
+            .try_into()
+            .map_err(|err: anyhow::Error| Status::from_error(err.into()))?;
+        let rx = self.command_cb(PlayerCmd::QueueAdd(converted))?;
+        // wait until the event was processed
+        let _ = rx.await;
+        let reply = Empty {};
+
+        Ok(Response::new(reply))
+    }
+
+    async fn queue_remove(
+        &self,
+        request: Request<QueueRemoveTrack>,
+    ) -> Result<Response<Empty>, Status> {
+        let converted = request
+            .into_inner()
+            .try_into()
+            .map_err(|err: anyhow::Error| Status::from_error(err.into()))?;
+        let rx = self.command_cb(PlayerCmd::QueueRemove(converted))?;
+        // wait until the event was processed
+        let _ = rx.await;
+        let reply = Empty {};
+
+        Ok(Response::new(reply))
+    }
+
+    async fn queue_clear(&self, _: Request<Empty>) -> Result<Response<Empty>, Status> {
+        let rx = self.command_cb(PlayerCmd::QueueClear)?;
+        // wait until the event was processed
+        let _ = rx.await;
+        let reply = Empty {};
+
+        Ok(Response::new(reply))
+    }
+
+    async fn get_queue(&self, _: Request<Empty>) -> Result<Response<QueueTracks>, Status> {
+        let reply = self.playlist.read().queue_as_sources().into();
+
+        Ok(Response::new(reply))
+    }
 }
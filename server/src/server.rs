@@ -16,12 +16,16 @@ use termusiclib::config::v2::server::config_extra::ServerConfigVersionedDefaulte
 use termusiclib::config::v2::server::{ComProtocol, ScanDepth};
 use termusiclib::config::{new_shared_server_settings, ServerOverlay, SharedServerSettings};
 use termusiclib::player::music_player_server::MusicPlayerServer;
-use termusiclib::player::{GetProgressResponse, PlayerProgress, PlayerTime, RunningStatus};
-use termusiclib::track::MediaTypesSimple;
-use termusiclib::{podcast, utils};
+use termusiclib::player::playlist_helpers::PlaylistTrackSource;
+use termusiclib::player::{
+    GetProgressResponse, PlaybackControls, PlayerProgress, PlayerTime, RunningStatus,
+};
+use termusiclib::track::{MediaTypesSimple, Track};
+use termusiclib::{loudness, podcast, utils};
 use termusicplayback::{
-    Backend, BackendSelect, GeneralPlayer, PlayerCmd, PlayerCmdReciever, PlayerCmdSender,
-    PlayerErrorType, PlayerTrait, Playlist, SharedPlaylist, SpeedSigned, VolumeSigned,
+    ABLoopError, Backend, BackendSelect, GeneralPlayer, Pitch, PitchSigned, PlayerCmd,
+    PlayerCmdReciever, PlayerCmdSender, PlayerErrorType, PlayerTrait, Playlist, SharedPlaylist,
+    SpeedSigned, VolumeSigned,
 };
 use tokio::runtime::Handle;
 use tokio::select;
@@ -38,6 +42,9 @@ extern crate log;
 pub const MAX_DEPTH: usize = 4;
 pub const VOLUME_STEP: VolumeSigned = 5;
 pub const SPEED_STEP: SpeedSigned = 1;
+/// One semitone
+pub const PITCH_STEP: PitchSigned = 100;
+pub const PITCH_STEP_FINE: PitchSigned = 10;
 
 /// The Limit of continues errors before stopping playback and awaiting user input to start something specific again.
 // SAFETY: using "unsafe" here as "const unwrap" is MSRV 1.83, we are currently on 1.82
@@ -47,13 +54,21 @@ const BACKEND_ERROR_LIMIT: NonZeroUsize = unsafe { NonZeroUsize::new_unchecked(5
 #[derive(Debug, Clone, PartialEq)]
 struct PlayerStats {
     pub progress: PlayerProgress,
+    /// Time skipped as silence in the current track
+    pub silence_skipped: Option<Duration>,
     pub current_track_index: u64,
     pub status: u32,
     pub volume: u16,
     pub speed: i32,
+    pub pitch: i32,
     pub gapless: bool,
     pub current_track_updated: bool,
     pub radio_title: String,
+    /// The current track, if it was taken from the "play next" queue
+    pub queued_track: Option<PlaylistTrackSource>,
+    pub playback_controls: PlaybackControls,
+    /// The result of the last A-B loop change
+    pub ab_loop_result: Result<(), ABLoopError>,
 }
 
 impl PlayerStats {
@@ -63,13 +78,18 @@ impl PlayerStats {
                 position: None,
                 total_duration: None,
             },
+            silence_skipped: None,
             current_track_index: 0,
             status: 1,
             volume: 0,
             speed: 10,
+            pitch: 0,
             gapless: true,
             current_track_updated: false,
             radio_title: String::new(),
+            queued_track: None,
+            playback_controls: PlaybackControls::default(),
+            ab_loop_result: Ok(()),
         }
     }
 
@@ -83,6 +103,9 @@ impl PlayerStats {
             gapless: self.gapless,
             current_track_updated: self.current_track_updated,
             radio_title: self.radio_title.clone(),
+            silence_skipped: self.silence_skipped.map(Into::into),
+            queued_track: self.queued_track.clone().map(Into::into),
+            playback_controls: Some(self.playback_controls.into()),
         }
     }
 
@@ -131,6 +154,8 @@ async fn actual_main() -> Result<()> {
     let cmd_tx = PlayerCmdSender::new(cmd_tx);
     // Note that the channel size might quickly become too low if there is a massive delete (like removing the non-existent tracks from the playlist)
     let (stream_tx, _) = broadcast::channel(10);
+    // small channel size, as spectrum frames are only useful while they are current; slow clients will just skip frames
+    let (spectrum_tx, _) = broadcast::channel(4);
 
     let playlist =
         Playlist::new_shared(&config, stream_tx.clone()).context("Failed to load playlist")?;
@@ -138,6 +163,7 @@ async fn actual_main() -> Result<()> {
     let music_player_service: MusicPlayerService = MusicPlayerService::new(
         cmd_tx.clone(),
         stream_tx.clone(),
+        spectrum_tx.clone(),
         config.clone(),
         playlist.clone(),
     );
@@ -176,6 +202,7 @@ async fn actual_main() -> Result<()> {
                 config,
                 playerstats,
                 stream_tx,
+                spectrum_tx,
                 playlist,
             );
             let _ = player_handle_os_tx.send(res);
@@ -380,6 +407,7 @@ mod uds {
 }
 
 /// The main player loop where we handle all events
+#[allow(clippy::too_many_arguments)]
 fn player_loop(
     backend: BackendSelect,
     cmd_tx: PlayerCmdSender,
@@ -387,9 +415,11 @@ fn player_loop(
     config: SharedServerSettings,
     playerstats: Arc<Mutex<PlayerStats>>,
     stream_tx: termusicplayback::StreamTX,
+    spectrum_tx: termusicplayback::SpectrumTX,
     playlist: SharedPlaylist,
 ) -> Result<()> {
-    let mut player = GeneralPlayer::new_backend(backend, config, cmd_tx, stream_tx, playlist)?;
+    let mut player =
+        GeneralPlayer::new_backend(backend, config, cmd_tx, stream_tx, spectrum_tx, playlist)?;
 
     let mut had_enqueue_error = false;
 
@@ -399,8 +429,9 @@ fn player_loop(
             PlayerCmd::AboutToFinish => {
                 info!("about to finish signal received");
                 let playlist = player.playlist.read();
-                if !playlist.is_empty()
+                if (!playlist.is_empty() || !playlist.queue().is_empty())
                     && !playlist.has_next_track()
+                    && !playlist.playback_controls().stop_after_current
                     && player.config.read().settings.player.gapless
                 {
                     drop(playlist);
@@ -412,6 +443,7 @@ fn player_loop(
                 // to have a consistent last position
                 player.pause();
                 player.player_save_last_position();
+                save_current_named(&mut player);
                 if let Err(e) = player.playlist.write().save() {
                     error!("error when saving playlist: {e}");
                 };
@@ -429,6 +461,18 @@ fn player_loop(
                 player.config.write().settings.player.loop_mode =
                     player.playlist.write().cycle_loop_mode();
             }
+            PlayerCmd::ToggleStopAfterCurrent => {
+                let controls = player.playlist.write().toggle_stop_after_current();
+                info!("stop after current: {}", controls.stop_after_current);
+            }
+            PlayerCmd::SetRepeatCount(count) => {
+                info!("repeat current track {count} more times");
+                player.playlist.write().set_repeat_count(count);
+            }
+            PlayerCmd::SetLoopMode(mode) => {
+                player.playlist.write().set_loop_mode(mode);
+                player.config.write().settings.player.loop_mode = mode;
+            }
             PlayerCmd::Eos => {
                 info!("Eos received");
                 player_eos(&mut player, had_enqueue_error);
@@ -478,6 +522,8 @@ fn player_loop(
                 player.reset_errors();
                 info!("skip to next track.");
                 player.player_save_last_position();
+                // skipping the current track also means to not repeat it anymore
+                player.playlist.write().set_repeat_count(0);
                 player.next();
             }
             PlayerCmd::SpeedDown => {
@@ -495,6 +541,26 @@ fn player_loop(
                 let mut p_tick = playerstats.lock();
                 p_tick.speed = new_speed;
             }
+            PlayerCmd::PitchDown => {
+                let new_pitch = player.add_pitch(-PITCH_STEP);
+                player_pitch_changed(&player, &playerstats, new_pitch);
+            }
+            PlayerCmd::PitchUp => {
+                let new_pitch = player.add_pitch(PITCH_STEP);
+                player_pitch_changed(&player, &playerstats, new_pitch);
+            }
+            PlayerCmd::PitchFineDown => {
+                let new_pitch = player.add_pitch(-PITCH_STEP_FINE);
+                player_pitch_changed(&player, &playerstats, new_pitch);
+            }
+            PlayerCmd::PitchFineUp => {
+                let new_pitch = player.add_pitch(PITCH_STEP_FINE);
+                player_pitch_changed(&player, &playerstats, new_pitch);
+            }
+            PlayerCmd::SetPitch(pitch) => {
+                let new_pitch = player.set_pitch(pitch);
+                player_pitch_changed(&player, &playerstats, new_pitch);
+            }
             PlayerCmd::Tick => {
                 // info!("tick received");
                 player.mpris_handle_events();
@@ -503,7 +569,7 @@ fn player_loop(
                 p_tick.status = playlist.status().as_u32();
                 // branch to auto-start playing if status is "stopped"(not paused) and playlist is not empty anymore
                 if playlist.status() == RunningStatus::Stopped {
-                    if playlist.is_empty() {
+                    if playlist.is_empty() || playlist.is_held() {
                         continue;
                     }
                     debug!(
@@ -521,6 +587,7 @@ fn player_loop(
                 if let Some(progress) = player.get_progress() {
                     let pl_status = playlist.status();
                     p_tick.progress = progress;
+                    p_tick.silence_skipped = player.silence_skipped();
 
                     // the following function is "mut", which does not like having the immutable borrow to "playlist"
                     // so we have to unlock first then later re-acquire the handle for later parts
@@ -541,8 +608,11 @@ fn player_loop(
                     p_tick.current_track_index =
                         u64::try_from(playlist.get_current_track_index()).unwrap();
                     p_tick.current_track_updated = player.current_track_updated;
+                    p_tick.queued_track =
+                        playlist.current_queued_track().map(Track::as_track_source);
                     player.current_track_updated = false;
                 }
+                p_tick.playback_controls = playlist.playback_controls();
                 if let Some(track) = playlist.current_track() {
                     // if only one backend is enabled, rust will complain that it is the only thing that happens
                     #[allow(irrefutable_let_patterns)]
@@ -601,6 +671,14 @@ fn player_loop(
                 p_tick.volume = new_volume;
                 player.mpris_volume_update();
             }
+            PlayerCmd::SetABLoop(range) => {
+                info!("setting A-B loop to {range:?}");
+                playerstats.lock().ab_loop_result = player.set_ab_loop_checked(Some(range));
+            }
+            PlayerCmd::ClearABLoop => {
+                info!("clearing A-B loop");
+                playerstats.lock().ab_loop_result = player.set_ab_loop_checked(None);
+            }
             PlayerCmd::Pause => {
                 player.pause();
             }
@@ -621,12 +699,16 @@ fn player_loop(
                 player.next();
             }
             PlayerCmd::PlaylistAddTrack(info) => {
-                if let Err(err) = player.playlist.write().add_tracks(info, &player.db_podcast) {
+                if let Err(err) = player
+                    .playlist
+                    .write()
+                    .add_tracks(&info, &player.db_podcast)
+                {
                     error!("Error adding tracks: {err}");
                 }
             }
             PlayerCmd::PlaylistRemoveTrack(info) => {
-                if let Err(err) = player.playlist.write().remove_tracks(info) {
+                if let Err(err) = player.playlist.write().remove_tracks(&info) {
                     error!("Error removing tracks: {err}");
                 }
             }
@@ -639,12 +721,65 @@ fn player_loop(
                     error!("Error swapping tracks: {err}");
                 }
             }
+            PlayerCmd::PlaylistMoveTracks(info) => {
+                if let Err(err) = player.playlist.write().move_tracks(&info) {
+                    error!("Error moving tracks: {err}");
+                }
+            }
+            PlayerCmd::PlaylistRemoveSelection(selection) => {
+                if let Err(err) = player.playlist.write().remove_selection(&selection) {
+                    error!("Error removing selected tracks: {err}");
+                }
+            }
+            PlayerCmd::PlaylistMoveSelection(info) => {
+                if let Err(err) = player.playlist.write().move_selection(&info) {
+                    error!("Error moving selected tracks: {err}");
+                }
+            }
             PlayerCmd::PlaylistShuffle => {
                 player.playlist.write().shuffle();
             }
             PlayerCmd::PlaylistRemoveDeletedTracks => {
                 player.playlist.write().remove_deleted_items();
             }
+            PlayerCmd::PlaylistUndo => {
+                if let Err(err) = player.playlist.write().undo() {
+                    error!("Error undoing playlist edit: {err:#}");
+                }
+            }
+            PlayerCmd::PlaylistRedo => {
+                if let Err(err) = player.playlist.write().redo() {
+                    error!("Error redoing playlist edit: {err:#}");
+                }
+            }
+            PlayerCmd::PlaylistSort(sort) => {
+                player.playlist.write().sort(sort);
+            }
+            PlayerCmd::PlaylistDeduplicate => {
+                player.playlist.write().deduplicate();
+            }
+            PlayerCmd::PlaylistSaveNamed(name) => {
+                let position = player.position();
+                if let Err(err) = player.playlist.write().save_named(&name, position) {
+                    error!("Error saving playlist \"{name}\": {err:#}");
+                }
+            }
+            PlayerCmd::PlaylistLoadNamed(name) => {
+                player_load_named(&mut player, &name);
+            }
+            PlayerCmd::QueueAdd(info) => {
+                if let Err(err) = player.playlist.write().queue_add(&info, &player.db_podcast) {
+                    error!("Error adding tracks to the queue: {err}");
+                }
+            }
+            PlayerCmd::QueueRemove(info) => {
+                if let Err(err) = player.playlist.write().queue_remove(&info) {
+                    error!("Error removing track from the queue: {err}");
+                }
+            }
+            PlayerCmd::QueueClear => {
+                player.playlist.write().queue_clear();
+            }
         }
 
         cb.call();
@@ -653,11 +788,67 @@ fn player_loop(
     Ok(())
 }
 
+/// Store the `new_pitch` (in cents) the player has been set to in the config and the stats
+fn player_pitch_changed(
+    player: &GeneralPlayer,
+    playerstats: &Mutex<PlayerStats>,
+    new_pitch: Pitch,
+) {
+    info!("pitch is now {new_pitch} cents");
+    player.config.write().settings.player.pitch = new_pitch;
+    playerstats.lock().pitch = new_pitch;
+}
+
+/// [`PlayerCmd::PlaylistLoadNamed`] handler.
+///
+/// Stores the current named playlist (if any) with its position, then loads `name` and resumes it.
+fn player_load_named(player: &mut GeneralPlayer, name: &str) {
+    player.player_save_last_position();
+    save_current_named(player);
+
+    let mut playlist = player.playlist.write();
+    let position = match playlist.load_named(name) {
+        Ok(v) => v,
+        Err(err) => {
+            error!("Error loading playlist \"{name}\": {err:#}");
+            return;
+        }
+    };
+
+    if playlist.is_empty() {
+        drop(playlist);
+        player.stop();
+        return;
+    }
+
+    playlist.proceed_false();
+    drop(playlist);
+
+    player.reset_errors();
+    player.pending_position = position;
+    player.next();
+}
+
+/// Store the current named playlist (if any) with the current position, so that it can be resumed later.
+fn save_current_named(player: &mut GeneralPlayer) {
+    let position = player.position();
+    let mut playlist = player.playlist.write();
+    if let Some(current) = playlist.name().map(ToString::to_string) {
+        if let Err(err) = playlist.save_named(&current, position) {
+            error!("Error saving playlist \"{current}\": {err:#}");
+        }
+    }
+}
+
 /// Common [`PlayerCmd::Eos`] handler.
 ///
 /// Use `use_skip` to skip the next track instead of trying to play it.
 fn player_eos(player: &mut GeneralPlayer, use_skip: bool) {
     let mut playlist = player.playlist.write();
+    // the backend may report the end of the stopped track, which should not start the next one
+    if playlist.is_held() {
+        return;
+    }
     if playlist.is_empty() {
         drop(playlist);
         player.stop();
@@ -677,8 +868,16 @@ fn player_eos(player: &mut GeneralPlayer, use_skip: bool) {
         "current track index: {:?}",
         playlist.get_current_track_index()
     );
-    playlist.clear_current_track();
+    // a finished track from the queue is not cleared here, "Playlist::next" takes it (or keeps it when repeated)
+    // and knows to not mark the playlist track as played again
+    let stop_after_current = playlist.take_stop_after_current();
     drop(playlist);
+    // the next track is only started once playback is started again
+    if stop_after_current {
+        info!("Stopping playback after the current track as requested");
+        player.stop_held(true);
+        return;
+    }
     // skip the next one as it had already errored via enqueuement, no need to try again
     if use_skip {
         player.next();
@@ -765,7 +964,83 @@ async fn execute_action(action: cli::Action, config: &ServerOverlay) -> Result<(
                 utils::get_app_config_path().context("getting app-config-path")?;
             podcast::export_to_opml(&config_dir_path, &path).context("export opml")?;
         }
+        cli::Action::ReplayGain { dirs, dry_run } => {
+            let dirs = if dirs.is_empty() {
+                config.settings.player.music_dirs.clone()
+            } else {
+                dirs
+            };
+            let max_depth = config.get_library_scan_depth();
+
+            tokio::task::spawn_blocking(move || replaygain_action(&dirs, max_depth, dry_run))
+                .await??;
+        }
     };
 
     Ok(())
 }
+
+/// Analyze the loudness of all albums in `dirs` and write the ReplayGain tags, unless `dry_run` is set.
+fn replaygain_action(dirs: &[PathBuf], max_depth: ScanDepth, dry_run: bool) -> Result<()> {
+    let mut failed = 0usize;
+
+    for dir in dirs {
+        let dir = get_path(dir).context("replaygain cli dir-path")?;
+        println!("Scanning {}", dir.display());
+        let albums = loudness::find_albums(&dir, max_depth);
+
+        for (album_idx, group) in albums.iter().enumerate() {
+            println!(
+                "[{}/{}] {}",
+                album_idx + 1,
+                albums.len(),
+                group.album.as_deref().unwrap_or("<no album>")
+            );
+
+            let analysis = loudness::analyze_album(group, |progress| {
+                println!(
+                    "  ({}/{}) {}",
+                    progress.current + 1,
+                    progress.total,
+                    progress.path.display()
+                );
+            });
+
+            for (path, res) in &analysis.tracks {
+                match res {
+                    Ok(track) => println!(
+                        "  {:.2} LUFS, peak {:.6}: {}",
+                        track.integrated,
+                        track.true_peak,
+                        path.display()
+                    ),
+                    Err(err) => {
+                        failed += 1;
+                        eprintln!("  Failed to analyze {}: {err:#}", path.display());
+                    }
+                }
+            }
+            if let Some(album) = analysis.album {
+                println!(
+                    "  Album: {:.2} LUFS, peak {:.6}",
+                    album.integrated, album.true_peak
+                );
+            }
+
+            if dry_run {
+                continue;
+            }
+
+            for (path, err) in analysis.write_tags() {
+                failed += 1;
+                eprintln!("  Failed to write tags to {}: {err:#}", path.display());
+            }
+        }
+    }
+
+    if failed > 0 {
+        bail!("{failed} tracks failed to be analyzed or tagged");
+    }
+
+    Ok(())
+}
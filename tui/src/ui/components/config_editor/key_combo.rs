@@ -1163,6 +1163,7 @@ impl KEModifierSelect {
             IdKey::GlobalLyricAdjustForward => keys.lyric_keys.adjust_offset_forwards.mod_key(),
             IdKey::GlobalLyricAdjustBackward => keys.lyric_keys.adjust_offset_backwards.mod_key(),
             IdKey::GlobalLyricCycle => keys.lyric_keys.cycle_frames.mod_key(),
+            IdKey::GlobalLyricSaveOffset => keys.lyric_keys.save_offset.mod_key(),
             IdKey::GlobalPlayerToggleGapless => keys.player_keys.toggle_prefetch.mod_key(),
             IdKey::GlobalPlayerTogglePause => keys.player_keys.toggle_pause.mod_key(),
             IdKey::GlobalPlayerNext => keys.player_keys.next_track.mod_key(),
@@ -1194,6 +1195,18 @@ impl KEModifierSelect {
             IdKey::PlaylistSwapUp => keys.playlist_keys.swap_up.mod_key(),
             IdKey::PlaylistAddRandomAlbum => keys.playlist_keys.add_random_album.mod_key(),
             IdKey::PlaylistAddRandomTracks => keys.playlist_keys.add_random_songs.mod_key(),
+            IdKey::PlaylistNamedPlaylists => keys.playlist_keys.named_playlists.mod_key(),
+            IdKey::PlaylistQueueNext => keys.playlist_keys.queue_next.mod_key(),
+            IdKey::PlaylistShowQueue => keys.playlist_keys.show_queue.mod_key(),
+            IdKey::LibraryQueueNext => keys.library_keys.queue_next.mod_key(),
+            IdKey::PlaylistUndo => keys.playlist_keys.undo.mod_key(),
+            IdKey::PlaylistRedo => keys.playlist_keys.redo.mod_key(),
+            IdKey::PlaylistSort => keys.playlist_keys.sort.mod_key(),
+            IdKey::PlaylistMark => keys.playlist_keys.mark.mod_key(),
+            IdKey::PlaylistMarkRange => keys.playlist_keys.mark_range.mod_key(),
+            IdKey::PlaylistMoveTop => keys.playlist_keys.move_top.mod_key(),
+            IdKey::PlaylistMoveBottom => keys.playlist_keys.move_bottom.mod_key(),
+            IdKey::PlaylistSaveMarked => keys.playlist_keys.save_marked.mod_key(),
             IdKey::LibrarySwitchRoot => keys.library_keys.cycle_root.mod_key(),
             IdKey::LibraryAddRoot => keys.library_keys.add_root.mod_key(),
             IdKey::LibraryRemoveRoot => keys.library_keys.remove_root.mod_key(),
@@ -1205,6 +1218,13 @@ impl KEModifierSelect {
             IdKey::GlobalXywhZoomIn => keys.move_cover_art_keys.increase_size.mod_key(),
             IdKey::GlobalXywhZoomOut => keys.move_cover_art_keys.decrease_size.mod_key(),
             IdKey::GlobalXywhHide => keys.move_cover_art_keys.toggle_hide.mod_key(),
+            IdKey::GlobalPlayerABLoopMarkA => keys.player_keys.ab_loop_mark_a.mod_key(),
+            IdKey::GlobalPlayerABLoopMarkB => keys.player_keys.ab_loop_mark_b.mod_key(),
+            IdKey::GlobalPlayerABLoopClear => keys.player_keys.ab_loop_clear.mod_key(),
+            IdKey::GlobalPlayerPitchUp => keys.player_keys.pitch_up.mod_key(),
+            IdKey::GlobalPlayerPitchDown => keys.player_keys.pitch_down.mod_key(),
+            IdKey::GlobalPlayerPitchFineUp => keys.player_keys.pitch_fine_up.mod_key(),
+            IdKey::GlobalPlayerPitchFineDown => keys.player_keys.pitch_fine_down.mod_key(),
             IdKey::PodcastMarkPlayed => keys.podcast_keys.mark_played.mod_key(),
             IdKey::PodcastMarkAllPlayed => keys.podcast_keys.mark_all_played.mod_key(),
             IdKey::PodcastEpDownload => keys.podcast_keys.download_episode.mod_key(),
@@ -1928,6 +1948,35 @@ impl Component<Msg, UserEvent> for ConfigGlobalLyricCycle {
     }
 }
 
+#[derive(MockComponent)]
+pub struct ConfigGlobalLyricSaveOffset {
+    component: KEModifierSelect,
+}
+
+impl ConfigGlobalLyricSaveOffset {
+    pub fn new(config: SharedTuiSettings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Lyric Save Offset ",
+                IdKey::GlobalLyricSaveOffset,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::GlobalLyricSaveOffsetBlurDown,
+                )),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::GlobalLyricSaveOffsetBlurUp,
+                )),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, UserEvent> for ConfigGlobalLyricSaveOffset {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
 #[derive(MockComponent)]
 pub struct ConfigGlobalLayoutTreeview {
     component: KEModifierSelect,
@@ -2525,6 +2574,310 @@ impl Component<Msg, UserEvent> for ConfigPlaylistAddRandomTracks {
     }
 }
 
+#[derive(MockComponent)]
+pub struct ConfigPlaylistNamedPlaylists {
+    component: KEModifierSelect,
+}
+
+impl ConfigPlaylistNamedPlaylists {
+    pub fn new(config: SharedTuiSettings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Playlist Named Playlists ",
+                IdKey::PlaylistNamedPlaylists,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::PlaylistNamedPlaylistsBlurDown,
+                )),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::PlaylistNamedPlaylistsBlurUp,
+                )),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, UserEvent> for ConfigPlaylistNamedPlaylists {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigPlaylistQueueNext {
+    component: KEModifierSelect,
+}
+
+impl ConfigPlaylistQueueNext {
+    pub fn new(config: SharedTuiSettings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Playlist Queue Next ",
+                IdKey::PlaylistQueueNext,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PlaylistQueueNextBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PlaylistQueueNextBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, UserEvent> for ConfigPlaylistQueueNext {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigPlaylistShowQueue {
+    component: KEModifierSelect,
+}
+
+impl ConfigPlaylistShowQueue {
+    pub fn new(config: SharedTuiSettings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Playlist Show Queue ",
+                IdKey::PlaylistShowQueue,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PlaylistShowQueueBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PlaylistShowQueueBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, UserEvent> for ConfigPlaylistShowQueue {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigLibraryQueueNext {
+    component: KEModifierSelect,
+}
+
+impl ConfigLibraryQueueNext {
+    pub fn new(config: SharedTuiSettings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Library Queue Next ",
+                IdKey::LibraryQueueNext,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::LibraryQueueNextBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::LibraryQueueNextBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, UserEvent> for ConfigLibraryQueueNext {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigPlaylistUndo {
+    component: KEModifierSelect,
+}
+
+impl ConfigPlaylistUndo {
+    pub fn new(config: SharedTuiSettings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Playlist Undo ",
+                IdKey::PlaylistUndo,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PlaylistUndoBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PlaylistUndoBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, UserEvent> for ConfigPlaylistUndo {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigPlaylistRedo {
+    component: KEModifierSelect,
+}
+
+impl ConfigPlaylistRedo {
+    pub fn new(config: SharedTuiSettings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Playlist Redo ",
+                IdKey::PlaylistRedo,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PlaylistRedoBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PlaylistRedoBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, UserEvent> for ConfigPlaylistRedo {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigPlaylistSort {
+    component: KEModifierSelect,
+}
+
+impl ConfigPlaylistSort {
+    pub fn new(config: SharedTuiSettings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Playlist Sort ",
+                IdKey::PlaylistSort,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PlaylistSortBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PlaylistSortBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, UserEvent> for ConfigPlaylistSort {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigPlaylistMark {
+    component: KEModifierSelect,
+}
+
+impl ConfigPlaylistMark {
+    pub fn new(config: SharedTuiSettings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Playlist Mark ",
+                IdKey::PlaylistMark,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PlaylistMarkBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PlaylistMarkBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, UserEvent> for ConfigPlaylistMark {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigPlaylistMarkRange {
+    component: KEModifierSelect,
+}
+
+impl ConfigPlaylistMarkRange {
+    pub fn new(config: SharedTuiSettings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Playlist Mark Range ",
+                IdKey::PlaylistMarkRange,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PlaylistMarkRangeBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PlaylistMarkRangeBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, UserEvent> for ConfigPlaylistMarkRange {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigPlaylistMoveTop {
+    component: KEModifierSelect,
+}
+
+impl ConfigPlaylistMoveTop {
+    pub fn new(config: SharedTuiSettings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Playlist Move to Top ",
+                IdKey::PlaylistMoveTop,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PlaylistMoveTopBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PlaylistMoveTopBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, UserEvent> for ConfigPlaylistMoveTop {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigPlaylistMoveBottom {
+    component: KEModifierSelect,
+}
+
+impl ConfigPlaylistMoveBottom {
+    pub fn new(config: SharedTuiSettings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Playlist Move to Bottom ",
+                IdKey::PlaylistMoveBottom,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PlaylistMoveBottomBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PlaylistMoveBottomBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, UserEvent> for ConfigPlaylistMoveBottom {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigPlaylistSaveMarked {
+    component: KEModifierSelect,
+}
+
+impl ConfigPlaylistSaveMarked {
+    pub fn new(config: SharedTuiSettings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Playlist Save Marked ",
+                IdKey::PlaylistSaveMarked,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PlaylistSaveMarkedBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PlaylistSaveMarkedBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, UserEvent> for ConfigPlaylistSaveMarked {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
 #[derive(MockComponent)]
 pub struct ConfigLibrarySwitchRoot {
     component: KEModifierSelect,
@@ -2562,265 +2915,466 @@ impl ConfigLibraryAddRoot {
                 " Library Add Root ",
                 IdKey::LibraryAddRoot,
                 config,
-                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::LibraryAddRootBlurDown)),
-                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::LibraryAddRootBlurUp)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::LibraryAddRootBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::LibraryAddRootBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, UserEvent> for ConfigLibraryAddRoot {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigLibraryRemoveRoot {
+    component: KEModifierSelect,
+}
+
+impl ConfigLibraryRemoveRoot {
+    pub fn new(config: SharedTuiSettings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Library Remove Root ",
+                IdKey::LibraryRemoveRoot,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::LibraryRemoveRootBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::LibraryRemoveRootBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, UserEvent> for ConfigLibraryRemoveRoot {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigGlobalSavePlaylist {
+    component: KEModifierSelect,
+}
+
+impl ConfigGlobalSavePlaylist {
+    pub fn new(config: SharedTuiSettings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Global Save Playlist ",
+                IdKey::GlobalSavePlaylist,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalSavePlaylistBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalSavePlaylistBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, UserEvent> for ConfigGlobalSavePlaylist {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigGlobalLayoutPodcast {
+    component: KEModifierSelect,
+}
+
+impl ConfigGlobalLayoutPodcast {
+    pub fn new(config: SharedTuiSettings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Layout Podcast ",
+                IdKey::GlobalLayoutPodcast,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::GlobalLayoutPodcastBlurDown,
+                )),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalLayoutPodcastBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, UserEvent> for ConfigGlobalLayoutPodcast {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigGlobalXywhMoveLeft {
+    component: KEModifierSelect,
+}
+
+impl ConfigGlobalXywhMoveLeft {
+    pub fn new(config: SharedTuiSettings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Photo move left ",
+                IdKey::GlobalXywhMoveLeft,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalXywhMoveLeftBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalXywhMoveLeftBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, UserEvent> for ConfigGlobalXywhMoveLeft {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigGlobalXywhMoveRight {
+    component: KEModifierSelect,
+}
+
+impl ConfigGlobalXywhMoveRight {
+    pub fn new(config: SharedTuiSettings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Photo move right ",
+                IdKey::GlobalXywhMoveRight,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::GlobalXywhMoveRightBlurDown,
+                )),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalXywhMoveRightBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, UserEvent> for ConfigGlobalXywhMoveRight {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+#[derive(MockComponent)]
+pub struct ConfigGlobalXywhMoveUp {
+    component: KEModifierSelect,
+}
+
+impl ConfigGlobalXywhMoveUp {
+    pub fn new(config: SharedTuiSettings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Photo move up ",
+                IdKey::GlobalXywhMoveUp,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalXywhMoveUpBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalXywhMoveUpBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, UserEvent> for ConfigGlobalXywhMoveUp {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+#[derive(MockComponent)]
+pub struct ConfigGlobalXywhMoveDown {
+    component: KEModifierSelect,
+}
+
+impl ConfigGlobalXywhMoveDown {
+    pub fn new(config: SharedTuiSettings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Photo move down ",
+                IdKey::GlobalXywhMoveDown,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalXywhMoveDownBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalXywhMoveDownBlurUp)),
             ),
         }
     }
 }
 
-impl Component<Msg, UserEvent> for ConfigLibraryAddRoot {
+impl Component<Msg, UserEvent> for ConfigGlobalXywhMoveDown {
     fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
         self.component.on(ev)
     }
 }
 
 #[derive(MockComponent)]
-pub struct ConfigLibraryRemoveRoot {
+pub struct ConfigGlobalXywhZoomIn {
     component: KEModifierSelect,
 }
 
-impl ConfigLibraryRemoveRoot {
+impl ConfigGlobalXywhZoomIn {
     pub fn new(config: SharedTuiSettings) -> Self {
         Self {
             component: KEModifierSelect::new(
-                " Library Remove Root ",
-                IdKey::LibraryRemoveRoot,
+                " Photo zoom in ",
+                IdKey::GlobalXywhZoomIn,
                 config,
-                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::LibraryRemoveRootBlurDown)),
-                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::LibraryRemoveRootBlurUp)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalXywhZoomInBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalXywhZoomInBlurUp)),
             ),
         }
     }
 }
 
-impl Component<Msg, UserEvent> for ConfigLibraryRemoveRoot {
+impl Component<Msg, UserEvent> for ConfigGlobalXywhZoomIn {
     fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
         self.component.on(ev)
     }
 }
-
 #[derive(MockComponent)]
-pub struct ConfigGlobalSavePlaylist {
+pub struct ConfigGlobalXywhZoomOut {
     component: KEModifierSelect,
 }
 
-impl ConfigGlobalSavePlaylist {
+impl ConfigGlobalXywhZoomOut {
     pub fn new(config: SharedTuiSettings) -> Self {
         Self {
             component: KEModifierSelect::new(
-                " Global Save Playlist ",
-                IdKey::GlobalSavePlaylist,
+                " Photo zoom out ",
+                IdKey::GlobalXywhZoomOut,
                 config,
-                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalSavePlaylistBlurDown)),
-                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalSavePlaylistBlurUp)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalXywhZoomOutBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalXywhZoomOutBlurUp)),
             ),
         }
     }
 }
 
-impl Component<Msg, UserEvent> for ConfigGlobalSavePlaylist {
+impl Component<Msg, UserEvent> for ConfigGlobalXywhZoomOut {
     fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
         self.component.on(ev)
     }
 }
 
 #[derive(MockComponent)]
-pub struct ConfigGlobalLayoutPodcast {
+pub struct ConfigGlobalXywhHide {
     component: KEModifierSelect,
 }
 
-impl ConfigGlobalLayoutPodcast {
+impl ConfigGlobalXywhHide {
     pub fn new(config: SharedTuiSettings) -> Self {
         Self {
             component: KEModifierSelect::new(
-                " Layout Podcast ",
-                IdKey::GlobalLayoutPodcast,
+                " Photo hide ",
+                IdKey::GlobalXywhHide,
                 config,
-                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
-                    KFMsg::GlobalLayoutPodcastBlurDown,
-                )),
-                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalLayoutPodcastBlurUp)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalXywhHideBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalXywhHideBlurUp)),
             ),
         }
     }
 }
 
-impl Component<Msg, UserEvent> for ConfigGlobalLayoutPodcast {
+impl Component<Msg, UserEvent> for ConfigGlobalXywhHide {
     fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
         self.component.on(ev)
     }
 }
 
 #[derive(MockComponent)]
-pub struct ConfigGlobalXywhMoveLeft {
+pub struct ConfigGlobalPlayerABLoopMarkA {
     component: KEModifierSelect,
 }
 
-impl ConfigGlobalXywhMoveLeft {
+impl ConfigGlobalPlayerABLoopMarkA {
     pub fn new(config: SharedTuiSettings) -> Self {
         Self {
             component: KEModifierSelect::new(
-                " Photo move left ",
-                IdKey::GlobalXywhMoveLeft,
+                " A-B Loop Mark A ",
+                IdKey::GlobalPlayerABLoopMarkA,
                 config,
-                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalXywhMoveLeftBlurDown)),
-                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalXywhMoveLeftBlurUp)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::GlobalPlayerABLoopMarkABlurDown,
+                )),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::GlobalPlayerABLoopMarkABlurUp,
+                )),
             ),
         }
     }
 }
 
-impl Component<Msg, UserEvent> for ConfigGlobalXywhMoveLeft {
+impl Component<Msg, UserEvent> for ConfigGlobalPlayerABLoopMarkA {
     fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
         self.component.on(ev)
     }
 }
 
 #[derive(MockComponent)]
-pub struct ConfigGlobalXywhMoveRight {
+pub struct ConfigGlobalPlayerABLoopMarkB {
     component: KEModifierSelect,
 }
 
-impl ConfigGlobalXywhMoveRight {
+impl ConfigGlobalPlayerABLoopMarkB {
     pub fn new(config: SharedTuiSettings) -> Self {
         Self {
             component: KEModifierSelect::new(
-                " Photo move right ",
-                IdKey::GlobalXywhMoveRight,
+                " A-B Loop Mark B ",
+                IdKey::GlobalPlayerABLoopMarkB,
                 config,
                 Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
-                    KFMsg::GlobalXywhMoveRightBlurDown,
+                    KFMsg::GlobalPlayerABLoopMarkBBlurDown,
+                )),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::GlobalPlayerABLoopMarkBBlurUp,
                 )),
-                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalXywhMoveRightBlurUp)),
             ),
         }
     }
 }
 
-impl Component<Msg, UserEvent> for ConfigGlobalXywhMoveRight {
+impl Component<Msg, UserEvent> for ConfigGlobalPlayerABLoopMarkB {
     fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
         self.component.on(ev)
     }
 }
+
 #[derive(MockComponent)]
-pub struct ConfigGlobalXywhMoveUp {
+pub struct ConfigGlobalPlayerABLoopClear {
     component: KEModifierSelect,
 }
 
-impl ConfigGlobalXywhMoveUp {
+impl ConfigGlobalPlayerABLoopClear {
     pub fn new(config: SharedTuiSettings) -> Self {
         Self {
             component: KEModifierSelect::new(
-                " Photo move up ",
-                IdKey::GlobalXywhMoveUp,
+                " A-B Loop Clear ",
+                IdKey::GlobalPlayerABLoopClear,
                 config,
-                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalXywhMoveUpBlurDown)),
-                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalXywhMoveUpBlurUp)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::GlobalPlayerABLoopClearBlurDown,
+                )),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::GlobalPlayerABLoopClearBlurUp,
+                )),
             ),
         }
     }
 }
 
-impl Component<Msg, UserEvent> for ConfigGlobalXywhMoveUp {
+impl Component<Msg, UserEvent> for ConfigGlobalPlayerABLoopClear {
     fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
         self.component.on(ev)
     }
 }
+
 #[derive(MockComponent)]
-pub struct ConfigGlobalXywhMoveDown {
+pub struct ConfigGlobalPlayerPitchUp {
     component: KEModifierSelect,
 }
 
-impl ConfigGlobalXywhMoveDown {
+impl ConfigGlobalPlayerPitchUp {
     pub fn new(config: SharedTuiSettings) -> Self {
         Self {
             component: KEModifierSelect::new(
-                " Photo move down ",
-                IdKey::GlobalXywhMoveDown,
+                " Pitch Up ",
+                IdKey::GlobalPlayerPitchUp,
                 config,
-                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalXywhMoveDownBlurDown)),
-                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalXywhMoveDownBlurUp)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::GlobalPlayerPitchUpBlurDown,
+                )),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalPlayerPitchUpBlurUp)),
             ),
         }
     }
 }
 
-impl Component<Msg, UserEvent> for ConfigGlobalXywhMoveDown {
+impl Component<Msg, UserEvent> for ConfigGlobalPlayerPitchUp {
     fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
         self.component.on(ev)
     }
 }
 
 #[derive(MockComponent)]
-pub struct ConfigGlobalXywhZoomIn {
+pub struct ConfigGlobalPlayerPitchDown {
     component: KEModifierSelect,
 }
 
-impl ConfigGlobalXywhZoomIn {
+impl ConfigGlobalPlayerPitchDown {
     pub fn new(config: SharedTuiSettings) -> Self {
         Self {
             component: KEModifierSelect::new(
-                " Photo zoom in ",
-                IdKey::GlobalXywhZoomIn,
+                " Pitch Down ",
+                IdKey::GlobalPlayerPitchDown,
                 config,
-                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalXywhZoomInBlurDown)),
-                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalXywhZoomInBlurUp)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::GlobalPlayerPitchDownBlurDown,
+                )),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::GlobalPlayerPitchDownBlurUp,
+                )),
             ),
         }
     }
 }
 
-impl Component<Msg, UserEvent> for ConfigGlobalXywhZoomIn {
+impl Component<Msg, UserEvent> for ConfigGlobalPlayerPitchDown {
     fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
         self.component.on(ev)
     }
 }
+
 #[derive(MockComponent)]
-pub struct ConfigGlobalXywhZoomOut {
+pub struct ConfigGlobalPlayerPitchFineUp {
     component: KEModifierSelect,
 }
 
-impl ConfigGlobalXywhZoomOut {
+impl ConfigGlobalPlayerPitchFineUp {
     pub fn new(config: SharedTuiSettings) -> Self {
         Self {
             component: KEModifierSelect::new(
-                " Photo zoom out ",
-                IdKey::GlobalXywhZoomOut,
+                " Pitch Fine Up ",
+                IdKey::GlobalPlayerPitchFineUp,
                 config,
-                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalXywhZoomOutBlurDown)),
-                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalXywhZoomOutBlurUp)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::GlobalPlayerPitchFineUpBlurDown,
+                )),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::GlobalPlayerPitchFineUpBlurUp,
+                )),
             ),
         }
     }
 }
 
-impl Component<Msg, UserEvent> for ConfigGlobalXywhZoomOut {
+impl Component<Msg, UserEvent> for ConfigGlobalPlayerPitchFineUp {
     fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
         self.component.on(ev)
     }
 }
 
 #[derive(MockComponent)]
-pub struct ConfigGlobalXywhHide {
+pub struct ConfigGlobalPlayerPitchFineDown {
     component: KEModifierSelect,
 }
 
-impl ConfigGlobalXywhHide {
+impl ConfigGlobalPlayerPitchFineDown {
     pub fn new(config: SharedTuiSettings) -> Self {
         Self {
             component: KEModifierSelect::new(
-                " Photo hide ",
-                IdKey::GlobalXywhHide,
+                " Pitch Fine Down ",
+                IdKey::GlobalPlayerPitchFineDown,
                 config,
-                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalXywhHideBlurDown)),
-                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalXywhHideBlurUp)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::GlobalPlayerPitchFineDownBlurDown,
+                )),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::GlobalPlayerPitchFineDownBlurUp,
+                )),
             ),
         }
     }
 }
 
-impl Component<Msg, UserEvent> for ConfigGlobalXywhHide {
+impl Component<Msg, UserEvent> for ConfigGlobalPlayerPitchFineDown {
     fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
         self.component.on(ev)
     }
@@ -3259,6 +3813,43 @@ impl Model {
             Box::new(ConfigGlobalXywhHide::new(self.config_tui.clone())),
             Vec::new(),
         )?;
+        self.app.remount(
+            Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalPlayerABLoopMarkA)),
+            Box::new(ConfigGlobalPlayerABLoopMarkA::new(self.config_tui.clone())),
+            Vec::new(),
+        )?;
+        self.app.remount(
+            Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalPlayerABLoopMarkB)),
+            Box::new(ConfigGlobalPlayerABLoopMarkB::new(self.config_tui.clone())),
+            Vec::new(),
+        )?;
+        self.app.remount(
+            Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalPlayerABLoopClear)),
+            Box::new(ConfigGlobalPlayerABLoopClear::new(self.config_tui.clone())),
+            Vec::new(),
+        )?;
+        self.app.remount(
+            Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalPlayerPitchUp)),
+            Box::new(ConfigGlobalPlayerPitchUp::new(self.config_tui.clone())),
+            Vec::new(),
+        )?;
+        self.app.remount(
+            Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalPlayerPitchDown)),
+            Box::new(ConfigGlobalPlayerPitchDown::new(self.config_tui.clone())),
+            Vec::new(),
+        )?;
+        self.app.remount(
+            Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalPlayerPitchFineUp)),
+            Box::new(ConfigGlobalPlayerPitchFineUp::new(self.config_tui.clone())),
+            Vec::new(),
+        )?;
+        self.app.remount(
+            Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalPlayerPitchFineDown)),
+            Box::new(ConfigGlobalPlayerPitchFineDown::new(
+                self.config_tui.clone(),
+            )),
+            Vec::new(),
+        )?;
 
         Ok(())
     }
@@ -3323,6 +3914,7 @@ impl Model {
     }
 
     /// Mount / Remount the Config-Editor's Third Page, the Playlist key-combos
+    #[allow(clippy::too_many_lines)]
     fn remount_config_keys_playlist(&mut self) -> Result<()> {
         self.app.remount(
             Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistDelete)),
@@ -3376,6 +3968,84 @@ impl Model {
             Vec::new(),
         )?;
 
+        self.app.remount(
+            Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistNamedPlaylists)),
+            Box::new(ConfigPlaylistNamedPlaylists::new(self.config_tui.clone())),
+            Vec::new(),
+        )?;
+
+        self.app.remount(
+            Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistQueueNext)),
+            Box::new(ConfigPlaylistQueueNext::new(self.config_tui.clone())),
+            Vec::new(),
+        )?;
+
+        self.app.remount(
+            Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistShowQueue)),
+            Box::new(ConfigPlaylistShowQueue::new(self.config_tui.clone())),
+            Vec::new(),
+        )?;
+
+        self.app.remount(
+            Id::ConfigEditor(IdConfigEditor::Key(IdKey::LibraryQueueNext)),
+            Box::new(ConfigLibraryQueueNext::new(self.config_tui.clone())),
+            Vec::new(),
+        )?;
+
+        self.app.remount(
+            Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistUndo)),
+            Box::new(ConfigPlaylistUndo::new(self.config_tui.clone())),
+            Vec::new(),
+        )?;
+
+        self.app.remount(
+            Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistRedo)),
+            Box::new(ConfigPlaylistRedo::new(self.config_tui.clone())),
+            Vec::new(),
+        )?;
+
+        self.app.remount(
+            Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistSort)),
+            Box::new(ConfigPlaylistSort::new(self.config_tui.clone())),
+            Vec::new(),
+        )?;
+
+        self.app.remount(
+            Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistMark)),
+            Box::new(ConfigPlaylistMark::new(self.config_tui.clone())),
+            Vec::new(),
+        )?;
+
+        self.app.remount(
+            Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistMarkRange)),
+            Box::new(ConfigPlaylistMarkRange::new(self.config_tui.clone())),
+            Vec::new(),
+        )?;
+
+        self.app.remount(
+            Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistMoveTop)),
+            Box::new(ConfigPlaylistMoveTop::new(self.config_tui.clone())),
+            Vec::new(),
+        )?;
+
+        self.app.remount(
+            Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistMoveBottom)),
+            Box::new(ConfigPlaylistMoveBottom::new(self.config_tui.clone())),
+            Vec::new(),
+        )?;
+
+        self.app.remount(
+            Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistSaveMarked)),
+            Box::new(ConfigPlaylistSaveMarked::new(self.config_tui.clone())),
+            Vec::new(),
+        )?;
+
+        self.app.remount(
+            Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalLyricSaveOffset)),
+            Box::new(ConfigGlobalLyricSaveOffset::new(self.config_tui.clone())),
+            Vec::new(),
+        )?;
+
         Ok(())
     }
 
@@ -3459,6 +4129,7 @@ impl Model {
     }
 
     /// Unmount the Config-Editor's Third Page, the Global key-combos
+    #[allow(clippy::too_many_lines)]
     fn umount_config_keys_global(&mut self) -> Result<()> {
         // umount keys global
         self.app
@@ -3555,6 +4226,27 @@ impl Model {
         self.app.umount(&Id::ConfigEditor(IdConfigEditor::Key(
             IdKey::GlobalXywhHide,
         )))?;
+        self.app.umount(&Id::ConfigEditor(IdConfigEditor::Key(
+            IdKey::GlobalPlayerABLoopMarkA,
+        )))?;
+        self.app.umount(&Id::ConfigEditor(IdConfigEditor::Key(
+            IdKey::GlobalPlayerABLoopMarkB,
+        )))?;
+        self.app.umount(&Id::ConfigEditor(IdConfigEditor::Key(
+            IdKey::GlobalPlayerABLoopClear,
+        )))?;
+        self.app.umount(&Id::ConfigEditor(IdConfigEditor::Key(
+            IdKey::GlobalPlayerPitchUp,
+        )))?;
+        self.app.umount(&Id::ConfigEditor(IdConfigEditor::Key(
+            IdKey::GlobalPlayerPitchDown,
+        )))?;
+        self.app.umount(&Id::ConfigEditor(IdConfigEditor::Key(
+            IdKey::GlobalPlayerPitchFineUp,
+        )))?;
+        self.app.umount(&Id::ConfigEditor(IdConfigEditor::Key(
+            IdKey::GlobalPlayerPitchFineDown,
+        )))?;
 
         Ok(())
     }
@@ -3629,6 +4321,54 @@ impl Model {
             IdKey::PlaylistAddRandomTracks,
         )))?;
 
+        self.app.umount(&Id::ConfigEditor(IdConfigEditor::Key(
+            IdKey::PlaylistNamedPlaylists,
+        )))?;
+
+        self.app.umount(&Id::ConfigEditor(IdConfigEditor::Key(
+            IdKey::PlaylistQueueNext,
+        )))?;
+
+        self.app.umount(&Id::ConfigEditor(IdConfigEditor::Key(
+            IdKey::PlaylistShowQueue,
+        )))?;
+
+        self.app.umount(&Id::ConfigEditor(IdConfigEditor::Key(
+            IdKey::LibraryQueueNext,
+        )))?;
+
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistUndo)))?;
+
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistRedo)))?;
+
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistSort)))?;
+
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistMark)))?;
+
+        self.app.umount(&Id::ConfigEditor(IdConfigEditor::Key(
+            IdKey::PlaylistMarkRange,
+        )))?;
+
+        self.app.umount(&Id::ConfigEditor(IdConfigEditor::Key(
+            IdKey::PlaylistMoveTop,
+        )))?;
+
+        self.app.umount(&Id::ConfigEditor(IdConfigEditor::Key(
+            IdKey::PlaylistMoveBottom,
+        )))?;
+
+        self.app.umount(&Id::ConfigEditor(IdConfigEditor::Key(
+            IdKey::PlaylistSaveMarked,
+        )))?;
+
+        self.app.umount(&Id::ConfigEditor(IdConfigEditor::Key(
+            IdKey::GlobalLyricSaveOffset,
+        )))?;
+
         Ok(())
     }
 
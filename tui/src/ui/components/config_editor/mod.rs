@@ -57,6 +57,7 @@ impl CEHeader {
                     "Themes and Colors",
                     "Keys Global",
                     "Keys Other",
+                    "Keys Marking",
                 ])
                 .foreground(config.settings.theme.library_highlight())
                 .inactive(Style::default().fg(config.settings.theme.library_highlight()))
@@ -65,6 +66,7 @@ impl CEHeader {
                     ConfigEditorLayout::Color => 1,
                     ConfigEditorLayout::Key1 => 2,
                     ConfigEditorLayout::Key2 => 3,
+                    ConfigEditorLayout::Key3 => 4,
                 }),
         }
     }
@@ -433,7 +433,14 @@ impl Model {
     fn update_key_focus(&mut self, msg: KFMsg) {
         match msg {
             // Focus of key global page
-            KFMsg::GlobalXywhHideBlurDown | KFMsg::GlobalLeftBlurUp => {
+            KFMsg::GlobalPlayerABLoopClearBlurDown | KFMsg::GlobalQuitBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::GlobalPlayerPitchFineDown,
+                    )))
+                    .ok();
+            }
+            KFMsg::GlobalPlayerPitchFineDownBlurDown | KFMsg::GlobalLeftBlurUp => {
                 self.app
                     .active(&Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalQuit)))
                     .ok();
@@ -480,14 +487,21 @@ impl Model {
                     )))
                     .ok();
             }
-            KFMsg::GlobalPlayerTogglePauseBlurDown | KFMsg::GlobalPlayerPreviousBlurUp => {
+            KFMsg::GlobalPlayerTogglePauseBlurDown | KFMsg::GlobalPlayerPitchUpBlurUp => {
                 self.app
                     .active(&Id::ConfigEditor(IdConfigEditor::Key(
                         IdKey::GlobalPlayerNext,
                     )))
                     .ok();
             }
-            KFMsg::GlobalPlayerNextBlurDown | KFMsg::GlobalHelpBlurUp => {
+            KFMsg::GlobalPlayerNextBlurDown | KFMsg::GlobalPlayerPreviousBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::GlobalPlayerPitchUp,
+                    )))
+                    .ok();
+            }
+            KFMsg::GlobalPlayerPitchUpBlurDown | KFMsg::GlobalHelpBlurUp => {
                 self.app
                     .active(&Id::ConfigEditor(IdConfigEditor::Key(
                         IdKey::GlobalPlayerPrevious,
@@ -545,7 +559,7 @@ impl Model {
                     .ok();
             }
 
-            KFMsg::GlobalPlayerSpeedDownBlurDown | KFMsg::GlobalLyricAdjustBackwardBlurUp => {
+            KFMsg::GlobalPlayerSpeedDownBlurDown | KFMsg::GlobalPlayerPitchDownBlurUp => {
                 self.app
                     .active(&Id::ConfigEditor(IdConfigEditor::Key(
                         IdKey::GlobalLyricAdjustForward,
@@ -553,7 +567,15 @@ impl Model {
                     .ok();
             }
 
-            KFMsg::GlobalLyricAdjustForwardBlurDown | KFMsg::GlobalLyricCycleBlurUp => {
+            KFMsg::GlobalLyricAdjustForwardBlurDown | KFMsg::GlobalLyricAdjustBackwardBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::GlobalPlayerPitchDown,
+                    )))
+                    .ok();
+            }
+
+            KFMsg::GlobalPlayerPitchDownBlurDown | KFMsg::GlobalLyricCycleBlurUp => {
                 self.app
                     .active(&Id::ConfigEditor(IdConfigEditor::Key(
                         IdKey::GlobalLyricAdjustBackward,
@@ -614,14 +636,21 @@ impl Model {
                     )))
                     .ok();
             }
-            KFMsg::GlobalLayoutPodcastBlurDown | KFMsg::GlobalXywhMoveRightBlurUp => {
+            KFMsg::GlobalLayoutPodcastBlurDown | KFMsg::GlobalPlayerPitchFineUpBlurUp => {
                 self.app
                     .active(&Id::ConfigEditor(IdConfigEditor::Key(
                         IdKey::GlobalXywhMoveLeft,
                     )))
                     .ok();
             }
-            KFMsg::GlobalXywhMoveLeftBlurDown | KFMsg::GlobalXywhMoveUpBlurUp => {
+            KFMsg::GlobalXywhMoveLeftBlurDown | KFMsg::GlobalXywhMoveRightBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::GlobalPlayerPitchFineUp,
+                    )))
+                    .ok();
+            }
+            KFMsg::GlobalPlayerPitchFineUpBlurDown | KFMsg::GlobalXywhMoveUpBlurUp => {
                 self.app
                     .active(&Id::ConfigEditor(IdConfigEditor::Key(
                         IdKey::GlobalXywhMoveRight,
@@ -656,16 +685,37 @@ impl Model {
                     )))
                     .ok();
             }
-            KFMsg::GlobalXywhZoomOutBlurDown | KFMsg::GlobalQuitBlurUp => {
+            KFMsg::GlobalXywhZoomOutBlurDown | KFMsg::GlobalPlayerABLoopMarkABlurUp => {
                 self.app
                     .active(&Id::ConfigEditor(IdConfigEditor::Key(
                         IdKey::GlobalXywhHide,
                     )))
                     .ok();
             }
+            KFMsg::GlobalXywhHideBlurDown | KFMsg::GlobalPlayerABLoopMarkBBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::GlobalPlayerABLoopMarkA,
+                    )))
+                    .ok();
+            }
+            KFMsg::GlobalPlayerABLoopMarkABlurDown | KFMsg::GlobalPlayerABLoopClearBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::GlobalPlayerABLoopMarkB,
+                    )))
+                    .ok();
+            }
+            KFMsg::GlobalPlayerABLoopMarkBBlurDown | KFMsg::GlobalPlayerPitchFineDownBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::GlobalPlayerABLoopClear,
+                    )))
+                    .ok();
+            }
 
             // Focus of key 2 page
-            KFMsg::PodcastSearchAddFeedBlurDown | KFMsg::LibraryDeleteBlurUp => {
+            KFMsg::PlaylistRedoBlurDown | KFMsg::LibraryDeleteBlurUp => {
                 self.app
                     .active(&Id::ConfigEditor(IdConfigEditor::Key(
                         IdKey::LibraryTagEditor,
@@ -860,14 +910,19 @@ impl Model {
                     )))
                     .ok();
             }
-            KFMsg::PodcastEpDeleteFileBlurDown | KFMsg::PodcastDeleteAllFeedsBlurUp => {
+            KFMsg::PodcastEpDeleteFileBlurDown | KFMsg::PlaylistSortBlurUp => {
                 self.app
                     .active(&Id::ConfigEditor(IdConfigEditor::Key(
                         IdKey::PodcastDeleteFeed,
                     )))
                     .ok();
             }
-            KFMsg::PodcastDeleteFeedBlurDown | KFMsg::PodcastRefreshFeedBlurUp => {
+            KFMsg::PodcastDeleteFeedBlurDown | KFMsg::PodcastDeleteAllFeedsBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistSort)))
+                    .ok();
+            }
+            KFMsg::PlaylistSortBlurDown | KFMsg::PodcastRefreshFeedBlurUp => {
                 self.app
                     .active(&Id::ConfigEditor(IdConfigEditor::Key(
                         IdKey::PodcastDeleteAllFeeds,
@@ -888,13 +943,93 @@ impl Model {
                     )))
                     .ok();
             }
-            KFMsg::PodcastRefreshAllFeedsBlurDown | KFMsg::LibraryTagEditorBlurUp => {
+            KFMsg::PodcastRefreshAllFeedsBlurDown | KFMsg::PlaylistNamedPlaylistsBlurUp => {
                 self.app
                     .active(&Id::ConfigEditor(IdConfigEditor::Key(
                         IdKey::PodcastSearchAddFeed,
                     )))
                     .ok();
             }
+            KFMsg::PodcastSearchAddFeedBlurDown | KFMsg::PlaylistQueueNextBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::PlaylistNamedPlaylists,
+                    )))
+                    .ok();
+            }
+            KFMsg::PlaylistNamedPlaylistsBlurDown | KFMsg::PlaylistShowQueueBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::PlaylistQueueNext,
+                    )))
+                    .ok();
+            }
+            KFMsg::PlaylistQueueNextBlurDown | KFMsg::LibraryQueueNextBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::PlaylistShowQueue,
+                    )))
+                    .ok();
+            }
+            KFMsg::PlaylistShowQueueBlurDown | KFMsg::PlaylistUndoBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::LibraryQueueNext,
+                    )))
+                    .ok();
+            }
+            KFMsg::LibraryQueueNextBlurDown | KFMsg::PlaylistRedoBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistUndo)))
+                    .ok();
+            }
+            KFMsg::PlaylistUndoBlurDown | KFMsg::LibraryTagEditorBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistRedo)))
+                    .ok();
+            }
+
+            // Focus of key 3 page
+            KFMsg::GlobalLyricSaveOffsetBlurDown | KFMsg::PlaylistMarkRangeBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistMark)))
+                    .ok();
+            }
+            KFMsg::PlaylistMarkBlurDown | KFMsg::PlaylistMoveTopBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::PlaylistMarkRange,
+                    )))
+                    .ok();
+            }
+            KFMsg::PlaylistMarkRangeBlurDown | KFMsg::PlaylistMoveBottomBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::PlaylistMoveTop,
+                    )))
+                    .ok();
+            }
+            KFMsg::PlaylistMoveTopBlurDown | KFMsg::PlaylistSaveMarkedBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::PlaylistMoveBottom,
+                    )))
+                    .ok();
+            }
+            KFMsg::PlaylistMoveBottomBlurDown | KFMsg::GlobalLyricSaveOffsetBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::PlaylistSaveMarked,
+                    )))
+                    .ok();
+            }
+            KFMsg::PlaylistSaveMarkedBlurDown | KFMsg::PlaylistMarkBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::GlobalLyricSaveOffset,
+                    )))
+                    .ok();
+            }
         }
     }
 
@@ -928,6 +1063,7 @@ impl Model {
                 keys.lyric_keys.adjust_offset_backwards = binding;
             }
             IdKey::GlobalLyricCycle => keys.lyric_keys.cycle_frames = binding,
+            IdKey::GlobalLyricSaveOffset => keys.lyric_keys.save_offset = binding,
             IdKey::GlobalPlayerToggleGapless => {
                 keys.player_keys.toggle_prefetch = binding;
             }
@@ -971,6 +1107,18 @@ impl Model {
             IdKey::PlaylistAddRandomTracks => {
                 keys.playlist_keys.add_random_songs = binding;
             }
+            IdKey::PlaylistNamedPlaylists => keys.playlist_keys.named_playlists = binding,
+            IdKey::PlaylistQueueNext => keys.playlist_keys.queue_next = binding,
+            IdKey::PlaylistShowQueue => keys.playlist_keys.show_queue = binding,
+            IdKey::LibraryQueueNext => keys.library_keys.queue_next = binding,
+            IdKey::PlaylistUndo => keys.playlist_keys.undo = binding,
+            IdKey::PlaylistRedo => keys.playlist_keys.redo = binding,
+            IdKey::PlaylistSort => keys.playlist_keys.sort = binding,
+            IdKey::PlaylistMark => keys.playlist_keys.mark = binding,
+            IdKey::PlaylistMarkRange => keys.playlist_keys.mark_range = binding,
+            IdKey::PlaylistMoveTop => keys.playlist_keys.move_top = binding,
+            IdKey::PlaylistMoveBottom => keys.playlist_keys.move_bottom = binding,
+            IdKey::PlaylistSaveMarked => keys.playlist_keys.save_marked = binding,
             IdKey::LibrarySwitchRoot => keys.library_keys.cycle_root = binding,
             IdKey::LibraryAddRoot => keys.library_keys.add_root = binding,
             IdKey::LibraryRemoveRoot => keys.library_keys.remove_root = binding,
@@ -990,6 +1138,13 @@ impl Model {
                 keys.move_cover_art_keys.decrease_size = binding;
             }
             IdKey::GlobalXywhHide => keys.move_cover_art_keys.toggle_hide = binding,
+            IdKey::GlobalPlayerABLoopMarkA => keys.player_keys.ab_loop_mark_a = binding,
+            IdKey::GlobalPlayerABLoopMarkB => keys.player_keys.ab_loop_mark_b = binding,
+            IdKey::GlobalPlayerABLoopClear => keys.player_keys.ab_loop_clear = binding,
+            IdKey::GlobalPlayerPitchUp => keys.player_keys.pitch_up = binding,
+            IdKey::GlobalPlayerPitchDown => keys.player_keys.pitch_down = binding,
+            IdKey::GlobalPlayerPitchFineUp => keys.player_keys.pitch_fine_up = binding,
+            IdKey::GlobalPlayerPitchFineDown => keys.player_keys.pitch_fine_down = binding,
             IdKey::PodcastMarkPlayed => keys.podcast_keys.mark_played = binding,
             IdKey::PodcastMarkAllPlayed => {
                 keys.podcast_keys.mark_all_played = binding;
@@ -48,6 +48,7 @@ impl Model {
             ConfigEditorLayout::Color => self.view_config_editor_color(),
             ConfigEditorLayout::Key1 => self.view_config_editor_key1(),
             ConfigEditorLayout::Key2 => self.view_config_editor_key2(),
+            ConfigEditorLayout::Key3 => self.view_config_editor_key3(),
         }
     }
 
@@ -853,6 +854,48 @@ impl Model {
             Ok(State::One(_)) => 3,
             _ => 8,
         };
+        let select_global_player_pitch_up_len = match self.app.state(&Id::ConfigEditor(
+            IdConfigEditor::Key(IdKey::GlobalPlayerPitchUp),
+        )) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_global_player_pitch_down_len = match self.app.state(&Id::ConfigEditor(
+            IdConfigEditor::Key(IdKey::GlobalPlayerPitchDown),
+        )) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_global_player_pitch_fine_up_len = match self.app.state(&Id::ConfigEditor(
+            IdConfigEditor::Key(IdKey::GlobalPlayerPitchFineUp),
+        )) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_global_player_pitch_fine_down_len = match self.app.state(&Id::ConfigEditor(
+            IdConfigEditor::Key(IdKey::GlobalPlayerPitchFineDown),
+        )) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_global_player_ab_loop_mark_a = match self.app.state(&Id::ConfigEditor(
+            IdConfigEditor::Key(IdKey::GlobalPlayerABLoopMarkA),
+        )) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_global_player_ab_loop_mark_b = match self.app.state(&Id::ConfigEditor(
+            IdConfigEditor::Key(IdKey::GlobalPlayerABLoopMarkB),
+        )) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_global_player_ab_loop_clear = match self.app.state(&Id::ConfigEditor(
+            IdConfigEditor::Key(IdKey::GlobalPlayerABLoopClear),
+        )) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
 
         self.terminal
             .raw_mut()
@@ -882,6 +925,7 @@ impl Model {
                     Constraint::Length(select_global_goto_bottom_len),
                     Constraint::Length(select_global_player_toggle_pause_len),
                     Constraint::Length(select_global_player_next_len),
+                    Constraint::Length(select_global_player_pitch_up_len),
                     Constraint::Min(0),
                 ])
                 .split(chunks_middle[0]);
@@ -896,6 +940,7 @@ impl Model {
                     Constraint::Length(select_global_player_speed_up_len),
                     Constraint::Length(select_global_player_speed_down_len),
                     Constraint::Length(select_global_lyric_adjust_forward_len),
+                    Constraint::Length(select_global_player_pitch_down_len),
                     Constraint::Min(0),
                 ])
                 .split(chunks_middle[1]);
@@ -909,6 +954,7 @@ impl Model {
                     Constraint::Length(select_global_save_playlist),
                     Constraint::Length(select_global_layout_podcast),
                     Constraint::Length(select_global_xywh_move_left),
+                    Constraint::Length(select_global_player_pitch_fine_up_len),
                     Constraint::Min(0),
                 ])
                 .split(chunks_middle[2]);
@@ -920,9 +966,10 @@ impl Model {
                     Constraint::Length(select_global_xywh_zoom_in),
                     Constraint::Length(select_global_xywh_zoom_out),
                     Constraint::Length(select_global_xywh_hide),
-                    // Constraint::Length(select_global_xywh_hide),
-                    // Constraint::Length(select_global_xywh_hide),
-                    // Constraint::Length(select_global_xywh_hide),
+                    Constraint::Length(select_global_player_ab_loop_mark_a),
+                    Constraint::Length(select_global_player_ab_loop_mark_b),
+                    Constraint::Length(select_global_player_ab_loop_clear),
+                    Constraint::Length(select_global_player_pitch_fine_down_len),
                     Constraint::Min(0),
                 ])
                 .split(chunks_middle[3]);
@@ -1101,6 +1148,41 @@ impl Model {
                     f,
                     chunks_middle_column4[5],
                 );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalPlayerABLoopMarkA)),
+                    f,
+                    chunks_middle_column4[6],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalPlayerABLoopMarkB)),
+                    f,
+                    chunks_middle_column4[7],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalPlayerABLoopClear)),
+                    f,
+                    chunks_middle_column4[8],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalPlayerPitchUp)),
+                    f,
+                    chunks_middle_column1[9],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalPlayerPitchDown)),
+                    f,
+                    chunks_middle_column2[9],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalPlayerPitchFineUp)),
+                    f,
+                    chunks_middle_column3[9],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalPlayerPitchFineDown)),
+                    f,
+                    chunks_middle_column4[9],
+                );
                 Self::view_config_editor_commons(f, &mut self.app);
             })
             .expect("Expected to draw without error");
@@ -1319,6 +1401,58 @@ impl Model {
             _ => 8,
         };
 
+        let select_playlist_named_playlists_len = match self.app.state(&Id::ConfigEditor(
+            IdConfigEditor::Key(IdKey::PlaylistNamedPlaylists),
+        )) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+
+        let select_playlist_queue_next_len = match self.app.state(&Id::ConfigEditor(
+            IdConfigEditor::Key(IdKey::PlaylistQueueNext),
+        )) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+
+        let select_playlist_show_queue_len = match self.app.state(&Id::ConfigEditor(
+            IdConfigEditor::Key(IdKey::PlaylistShowQueue),
+        )) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+
+        let select_library_queue_next_len = match self.app.state(&Id::ConfigEditor(
+            IdConfigEditor::Key(IdKey::LibraryQueueNext),
+        )) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+
+        let select_playlist_undo_len = match self
+            .app
+            .state(&Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistUndo)))
+        {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+
+        let select_playlist_redo_len = match self
+            .app
+            .state(&Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistRedo)))
+        {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+
+        let select_playlist_sort_len = match self
+            .app
+            .state(&Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistSort)))
+        {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+
         self.terminal
             .raw_mut()
             .draw(|f| {
@@ -1374,6 +1508,7 @@ impl Model {
                     Constraint::Length(podcast_ep_download_len),
                     Constraint::Length(podcast_ep_delete_file_len),
                     Constraint::Length(podcast_delete_feed_len),
+                    Constraint::Length(select_playlist_sort_len),
                     Constraint::Min(0),
                 ])
                 .split(chunks_middle[2]);
@@ -1383,6 +1518,12 @@ impl Model {
                     Constraint::Length(podcast_refresh_feed_len),
                     Constraint::Length(podcast_refresh_all_feeds_len),
                     Constraint::Length(podcast_search_add_feed_len),
+                    Constraint::Length(select_playlist_named_playlists_len),
+                    Constraint::Length(select_playlist_queue_next_len),
+                    Constraint::Length(select_playlist_show_queue_len),
+                    Constraint::Length(select_library_queue_next_len),
+                    Constraint::Length(select_playlist_undo_len),
+                    Constraint::Length(select_playlist_redo_len),
                     // Constraint::Length(podcast_mark_played_len),
                     // Constraint::Length(podcast_mark_all_played_len),
                     // Constraint::Length(podcast_ep_download_len),
@@ -1537,6 +1678,11 @@ impl Model {
                     f,
                     chunks_middle_column3[8],
                 );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistSort)),
+                    f,
+                    chunks_middle_column3[9],
+                );
                 self.app.view(
                     &Id::ConfigEditor(IdConfigEditor::Key(IdKey::PodcastDeleteAllFeeds)),
                     f,
@@ -1557,6 +1703,150 @@ impl Model {
                     f,
                     chunks_middle_column4[3],
                 );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistNamedPlaylists)),
+                    f,
+                    chunks_middle_column4[4],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistQueueNext)),
+                    f,
+                    chunks_middle_column4[5],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistShowQueue)),
+                    f,
+                    chunks_middle_column4[6],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::LibraryQueueNext)),
+                    f,
+                    chunks_middle_column4[7],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistUndo)),
+                    f,
+                    chunks_middle_column4[8],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistRedo)),
+                    f,
+                    chunks_middle_column4[9],
+                );
+                Self::view_config_editor_commons(f, &mut self.app);
+            })
+            .expect("Expected to draw without error");
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn view_config_editor_key3(&mut self) {
+        let select_playlist_mark_len = match self
+            .app
+            .state(&Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistMark)))
+        {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_playlist_mark_range_len = match self.app.state(&Id::ConfigEditor(
+            IdConfigEditor::Key(IdKey::PlaylistMarkRange),
+        )) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_playlist_move_top_len = match self.app.state(&Id::ConfigEditor(
+            IdConfigEditor::Key(IdKey::PlaylistMoveTop),
+        )) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_playlist_move_bottom_len = match self.app.state(&Id::ConfigEditor(
+            IdConfigEditor::Key(IdKey::PlaylistMoveBottom),
+        )) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_playlist_save_marked_len = match self.app.state(&Id::ConfigEditor(
+            IdConfigEditor::Key(IdKey::PlaylistSaveMarked),
+        )) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_global_lyric_save_offset_len = match self.app.state(&Id::ConfigEditor(
+            IdConfigEditor::Key(IdKey::GlobalLyricSaveOffset),
+        )) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+
+        self.terminal
+            .raw_mut()
+            .draw(|f| {
+                let chunks_main = Layout::vertical([
+                    Constraint::Length(3),
+                    Constraint::Min(3),
+                    Constraint::Length(1),
+                ])
+                .split(f.area());
+
+                let chunks_middle = Layout::horizontal([
+                    Constraint::Ratio(1, 4),
+                    Constraint::Ratio(1, 4),
+                    Constraint::Ratio(1, 4),
+                    Constraint::Ratio(1, 4),
+                ])
+                .split(chunks_main[1]);
+
+                let chunks_middle_column1 = Layout::vertical([
+                    Constraint::Length(select_playlist_mark_len),
+                    Constraint::Length(select_playlist_mark_range_len),
+                    Constraint::Length(select_playlist_move_top_len),
+                    Constraint::Length(select_playlist_move_bottom_len),
+                    Constraint::Length(select_playlist_save_marked_len),
+                    Constraint::Min(0),
+                ])
+                .split(chunks_middle[0]);
+
+                let chunks_middle_column2 = Layout::vertical([
+                    Constraint::Length(select_global_lyric_save_offset_len),
+                    Constraint::Min(0),
+                ])
+                .split(chunks_middle[1]);
+
+                self.app
+                    .view(&Id::ConfigEditor(IdConfigEditor::Header), f, chunks_main[0]);
+                self.app
+                    .view(&Id::ConfigEditor(IdConfigEditor::Footer), f, chunks_main[2]);
+
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistMark)),
+                    f,
+                    chunks_middle_column1[0],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistMarkRange)),
+                    f,
+                    chunks_middle_column1[1],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistMoveTop)),
+                    f,
+                    chunks_middle_column1[2],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistMoveBottom)),
+                    f,
+                    chunks_middle_column1[3],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistSaveMarked)),
+                    f,
+                    chunks_middle_column1[4],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalLyricSaveOffset)),
+                    f,
+                    chunks_middle_column2[0],
+                );
                 Self::view_config_editor_commons(f, &mut self.app);
             })
             .expect("Expected to draw without error");
@@ -1594,6 +1884,7 @@ impl Model {
         self.playlist_reload();
         self.database_reload();
         self.progress_reload();
+        self.spectrum_reload();
         self.mount_label_help();
         self.lyric_reload();
 
@@ -1625,7 +1916,8 @@ impl Model {
 
             ConfigEditorLayout::Color => self.config_editor.layout = ConfigEditorLayout::Key1,
             ConfigEditorLayout::Key1 => self.config_editor.layout = ConfigEditorLayout::Key2,
-            ConfigEditorLayout::Key2 => self.config_editor.layout = ConfigEditorLayout::General,
+            ConfigEditorLayout::Key2 => self.config_editor.layout = ConfigEditorLayout::Key3,
+            ConfigEditorLayout::Key3 => self.config_editor.layout = ConfigEditorLayout::General,
         }
 
         assert!(self
@@ -1658,6 +1950,10 @@ impl Model {
                     IdKey::LibraryTagEditor,
                 )))
                 .ok(),
+            ConfigEditorLayout::Key3 => self
+                .app
+                .active(&Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistMark)))
+                .ok(),
         };
     }
 
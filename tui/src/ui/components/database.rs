@@ -376,6 +376,14 @@ impl Component<Msg, UserEvent> for DBListSearchResult {
                 Event::Keyboard(keyevent) if keyevent == keys.database_keys.add_all.get() => {
                     return Either::Right(Msg::DataBase(DBMsg::AddAllResultsConfirmShow));
                 }
+                Event::Keyboard(keyevent)
+                    if keyevent == keys.library_keys.open_tag_editor.get() =>
+                {
+                    if let State::One(StateValue::Usize(index)) = self.state() {
+                        return Either::Right(Msg::DataBase(DBMsg::OpenTagEditor(index)));
+                    }
+                    CmdResult::None
+                }
 
                 _ => CmdResult::None,
             };
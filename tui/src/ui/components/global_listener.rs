@@ -64,6 +64,18 @@ impl Component<Msg, UserEvent> for GlobalListener {
             Event::Keyboard(keyevent) if keyevent == keys.player_keys.speed_down.get() => {
                 Some(Msg::Player(PlayerMsg::SpeedDown))
             }
+            Event::Keyboard(keyevent) if keyevent == keys.player_keys.pitch_up.get() => {
+                Some(Msg::Player(PlayerMsg::PitchUp))
+            }
+            Event::Keyboard(keyevent) if keyevent == keys.player_keys.pitch_down.get() => {
+                Some(Msg::Player(PlayerMsg::PitchDown))
+            }
+            Event::Keyboard(keyevent) if keyevent == keys.player_keys.pitch_fine_up.get() => {
+                Some(Msg::Player(PlayerMsg::PitchFineUp))
+            }
+            Event::Keyboard(keyevent) if keyevent == keys.player_keys.pitch_fine_down.get() => {
+                Some(Msg::Player(PlayerMsg::PitchFineDown))
+            }
 
             Event::Keyboard(keyevent)
                 if keyevent == keys.lyric_keys.adjust_offset_forwards.get() =>
@@ -78,6 +90,9 @@ impl Component<Msg, UserEvent> for GlobalListener {
             Event::Keyboard(keyevent) if keyevent == keys.lyric_keys.cycle_frames.get() => {
                 Some(Msg::LyricCycle)
             }
+            Event::Keyboard(keyevent) if keyevent == keys.lyric_keys.save_offset.get() => {
+                Some(Msg::LyricOffsetConfirmShow)
+            }
 
             Event::Keyboard(keyevent) if keyevent == keys.select_view_keys.view_library.get() => {
                 Some(Msg::Layout(MainLayoutMsg::TreeView))
@@ -94,6 +109,15 @@ impl Component<Msg, UserEvent> for GlobalListener {
             Event::Keyboard(keyevent) if keyevent == keys.player_keys.toggle_prefetch.get() => {
                 Some(Msg::Player(PlayerMsg::ToggleGapless))
             }
+            Event::Keyboard(keyevent) if keyevent == keys.player_keys.ab_loop_mark_a.get() => {
+                Some(Msg::Player(PlayerMsg::ABLoopMarkA))
+            }
+            Event::Keyboard(keyevent) if keyevent == keys.player_keys.ab_loop_mark_b.get() => {
+                Some(Msg::Player(PlayerMsg::ABLoopMarkB))
+            }
+            Event::Keyboard(keyevent) if keyevent == keys.player_keys.ab_loop_clear.get() => {
+                Some(Msg::Player(PlayerMsg::ABLoopClear))
+            }
 
             Event::Keyboard(keyevent) if keyevent == keys.select_view_keys.open_config.get() => {
                 Some(Msg::ConfigEditor(ConfigEditorMsg::Open))
@@ -165,6 +189,22 @@ impl Model {
                 SubEventClause::Keyboard(keys.player_keys.speed_down.get()),
                 Self::no_popup_mounted_clause(),
             ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.player_keys.pitch_up.get()),
+                Self::no_popup_mounted_clause(),
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.player_keys.pitch_down.get()),
+                Self::no_popup_mounted_clause(),
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.player_keys.pitch_fine_up.get()),
+                Self::no_popup_mounted_clause(),
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.player_keys.pitch_fine_down.get()),
+                Self::no_popup_mounted_clause(),
+            ),
             Sub::new(
                 SubEventClause::Keyboard(keys.player_keys.volume_down.get()),
                 Self::no_popup_mounted_clause(),
@@ -205,6 +245,10 @@ impl Model {
                 SubEventClause::Keyboard(keys.lyric_keys.cycle_frames.get()),
                 Self::no_popup_mounted_clause(),
             ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.lyric_keys.save_offset.get()),
+                Self::no_popup_mounted_clause(),
+            ),
             Sub::new(
                 SubEventClause::Keyboard(keys.select_view_keys.view_library.get()),
                 Self::no_popup_mounted_clause(),
@@ -217,6 +261,18 @@ impl Model {
                 SubEventClause::Keyboard(keys.player_keys.toggle_prefetch.get()),
                 Self::no_popup_mounted_clause(),
             ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.player_keys.ab_loop_mark_a.get()),
+                Self::no_popup_mounted_clause(),
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.player_keys.ab_loop_mark_b.get()),
+                Self::no_popup_mounted_clause(),
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.player_keys.ab_loop_clear.get()),
+                Self::no_popup_mounted_clause(),
+            ),
             Sub::new(
                 SubEventClause::Keyboard(keys.select_view_keys.open_config.get()),
                 Self::no_popup_mounted_clause(),
@@ -273,7 +329,16 @@ impl Model {
             Box::new(SubClause::IsMounted(Id::FeedDeleteConfirmRadioPopup)),
             Box::new(SubClause::Or(
                 Box::new(SubClause::IsMounted(Id::FeedDeleteConfirmInputPopup)),
-                Box::new(SubClause::IsMounted(Id::PodcastSearchTablePopup)),
+                Box::new(SubClause::Or(
+                    Box::new(SubClause::IsMounted(Id::PodcastSearchTablePopup)),
+                    Box::new(SubClause::Or(
+                        Box::new(SubClause::IsMounted(Id::NamedPlaylistsPopup)),
+                        Box::new(SubClause::Or(
+                            Box::new(SubClause::IsMounted(Id::QueuePopup)),
+                            Box::new(SubClause::IsMounted(Id::PlaylistSortPopup)),
+                        )),
+                    )),
+                )),
             )),
         )))
     }
@@ -294,7 +359,10 @@ impl Model {
                                 Box::new(SubClause::IsMounted(Id::SavePlaylistConfirm)),
                                 Box::new(SubClause::Or(
                                     Box::new(SubClause::IsMounted(Id::PodcastAddPopup)),
-                                    Box::new(SubClause::IsMounted(Id::DatabaseAddConfirmPopup)),
+                                    Box::new(SubClause::Or(
+                                        Box::new(SubClause::IsMounted(Id::DatabaseAddConfirmPopup)),
+                                        Box::new(SubClause::IsMounted(Id::LyricOffsetConfirmPopup)),
+                                    )),
                                 )),
                             )),
                         )))),
@@ -1,24 +1,31 @@
 use std::sync::LazyLock;
 
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use regex::Regex;
 use termusiclib::config::SharedTuiSettings;
 use termusiclib::ids::Id;
 use termusiclib::library_db::const_unknown::{UNKNOWN_ARTIST, UNKNOWN_TITLE};
 use termusiclib::player::RunningStatus;
 use termusiclib::podcast::episode::Episode;
-use termusiclib::track::MediaTypes;
+use termusiclib::songtag::edit::edit_tag;
+use termusiclib::songtag::lrc::CaptionContext;
+use termusiclib::songtag::sidecar;
 use termusiclib::track::MediaTypesSimple;
+use termusiclib::track::{MediaTypes, Track};
 use termusiclib::types::{LyricMsg, Msg};
 use tui_realm_stdlib::Textarea;
-use tuirealm::command::{Cmd, Direction, Position};
+use tuirealm::command::{Cmd, CmdResult, Direction, Position};
 use tuirealm::event::{Key, KeyEvent, KeyModifiers};
 use tuirealm::props::{
-    Alignment, AttrValue, Attribute, BorderType, Borders, PropPayload, PropValue, TextSpan,
+    Alignment, AttrValue, Attribute, BorderType, Borders, Color, PropPayload, PropValue, TextSpan,
 };
-use tuirealm::{Component, Event, MockComponent, State, StateValue};
+use tuirealm::ratatui::layout::Rect;
+use tuirealm::ratatui::style::Style;
+use tuirealm::ratatui::text::{Line, Span};
+use tuirealm::ratatui::widgets::{Paragraph, Wrap};
+use tuirealm::{Component, Event, Frame, MockComponent, State, StateValue};
 
-use super::TETrack;
+use super::popups::{YNConfirm, YNConfirmStyle};
 use crate::ui::model::{ExtraLyricData, UserEvent};
 use crate::ui::{model::TermusicLayout, Model};
 
@@ -34,10 +41,11 @@ static RE_HTML_TAGS: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<[^<>]*>").
 static RE_MULT_LINE_BREAKS: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"((\r\n)|\r|\n){3,}").unwrap());
 
-#[derive(MockComponent)]
 pub struct Lyric {
     component: Textarea,
     config: SharedTuiSettings,
+    /// Styled lines shown centered instead of the text, set via a `Linked` payload of `Vec` payloads of [`TextSpan`]s
+    lines: Option<Vec<Line<'static>>>,
 }
 
 impl Lyric {
@@ -59,10 +67,182 @@ impl Lyric {
                 .text_rows(&[TextSpan::new(format!("{}.", RunningStatus::Stopped))])
         };
 
-        Self { component, config }
+        Self {
+            component,
+            config,
+            lines: None,
+        }
+    }
+}
+
+/// Component for a "Save lyric offset of +X ms? Y/N" popup
+#[derive(MockComponent)]
+pub struct LyricOffsetConfirm {
+    component: YNConfirm,
+}
+
+impl LyricOffsetConfirm {
+    pub fn new(config: SharedTuiSettings, shift: i64, to_sidecar: bool) -> Self {
+        let target = if to_sidecar {
+            "the sidecar file"
+        } else {
+            "the track"
+        };
+        let component = YNConfirm::new_with_cb(
+            config,
+            format!(" Save lyric offset of {shift:+} ms to {target}? "),
+            |config| YNConfirmStyle {
+                foreground_color: config.settings.theme.important_popup_foreground(),
+                background_color: config.settings.theme.important_popup_background(),
+                border_color: config.settings.theme.important_popup_border(),
+                title_alignment: Alignment::Left,
+            },
+        );
+
+        Self { component }
+    }
+}
+
+impl Component<Msg, UserEvent> for LyricOffsetConfirm {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        self.component.on(
+            ev,
+            Msg::LyricOffsetConfirmCloseOk,
+            Msg::LyricOffsetConfirmCloseCancel,
+        )
+    }
+}
+
+/// Convert a [`TextSpan`] to a ratatui [`Span`], with the same style.
+fn span_from_text_span(span: TextSpan) -> Span<'static> {
+    Span::styled(
+        span.content,
+        Style::default()
+            .fg(span.fg)
+            .bg(span.bg)
+            .add_modifier(span.modifiers),
+    )
+}
+
+impl MockComponent for Lyric {
+    fn view(&mut self, frame: &mut Frame<'_>, area: Rect) {
+        let Some(lines) = &self.lines else {
+            self.component.view(frame, area);
+            return;
+        };
+
+        let borders = self
+            .query(Attribute::Borders)
+            .map_or_else(Borders::default, AttrValue::unwrap_borders);
+        let title = self.query(Attribute::Title).map(AttrValue::unwrap_title);
+        let focus = self
+            .query(Attribute::Focus)
+            .is_some_and(AttrValue::unwrap_flag);
+        let background = self
+            .query(Attribute::Background)
+            .map_or(Color::Reset, AttrValue::unwrap_color);
+        let block = tui_realm_stdlib::utils::get_block(borders, title, focus, None);
+
+        // center the lines vertically, assuming they do not wrap
+        let inner_height = usize::from(block.inner(area).height);
+        let padding = inner_height.saturating_sub(lines.len()) / 2;
+        let text: Vec<Line<'_>> = std::iter::repeat_n(Line::default(), padding)
+            .chain(lines.iter().cloned())
+            .collect();
+
+        let paragraph = Paragraph::new(text)
+            .block(block)
+            .style(Style::default().bg(background))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+
+        frame.render_widget(paragraph, area);
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.component.query(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        match (attr, value) {
+            (Attribute::Text, AttrValue::Payload(PropPayload::Linked(lines))) => {
+                self.lines = Some(
+                    lines
+                        .into_iter()
+                        .map(|line| {
+                            let spans = match line {
+                                PropPayload::Vec(spans) => spans,
+                                _ => Vec::new(),
+                            };
+                            Line::from(
+                                spans
+                                    .into_iter()
+                                    .map(|v| span_from_text_span(v.unwrap_text_span()))
+                                    .collect::<Vec<_>>(),
+                            )
+                        })
+                        .collect(),
+                );
+            }
+            (attr, value) => {
+                if attr == Attribute::Text {
+                    self.lines = None;
+                }
+                self.component.attr(attr, value);
+            }
+        }
+    }
+
+    fn state(&self) -> State {
+        self.component.state()
+    }
+
+    fn perform(&mut self, cmd: Cmd) -> CmdResult {
+        self.component.perform(cmd)
     }
 }
 
+/// Build the lines for the lyric context view: the previous, current and next caption.
+///
+/// The current caption is bold, with the words already sung bold and the current word highlighted.
+fn lyric_context_lines(
+    context: &CaptionContext<'_>,
+    foreground: Color,
+    highlight: Color,
+) -> Vec<Vec<TextSpan>> {
+    let plain = |text: &str| vec![TextSpan::new(text).fg(foreground)];
+
+    let current = if let Some(word_index) = context.word {
+        context
+            .current
+            .words()
+            .iter()
+            .enumerate()
+            .map(|(index, word)| {
+                let span = TextSpan::new(word.text()).fg(foreground);
+                match index.cmp(&word_index) {
+                    std::cmp::Ordering::Less => span.bold(),
+                    std::cmp::Ordering::Equal => span.fg(highlight).bold().underlined(),
+                    std::cmp::Ordering::Greater => span,
+                }
+            })
+            .collect()
+    } else if context.current.words().is_empty() {
+        vec![TextSpan::new(context.current.text()).fg(foreground).bold()]
+    } else {
+        // no word has started yet
+        plain(context.current.text())
+    };
+
+    vec![
+        context.previous.map_or_else(Vec::new, |v| plain(v.text())),
+        Vec::new(),
+        current,
+        Vec::new(),
+        context.next.map_or_else(Vec::new, |v| plain(v.text())),
+    ]
+}
+
 impl Component<Msg, UserEvent> for Lyric {
     fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
         let config = self.config.clone();
@@ -268,8 +448,6 @@ impl Model {
                 return;
             }
 
-            let mut line = String::new();
-
             if self
                 .current_track_lyric
                 .as_ref()
@@ -281,11 +459,13 @@ impl Model {
                     return;
                 }
 
-                if let Ok(Some(data)) = track.get_lyrics() {
+                let sidecar_dirs = self.config_tui.read().settings.lyrics.sidecar_dirs.clone();
+                if let Ok(Some(data)) = track.get_lyrics(&sidecar_dirs) {
                     self.current_track_lyric = Some(ExtraLyricData {
                         for_track: track.as_track().unwrap().path().to_owned(),
                         data: (*data).clone(),
                         selected_idx: 0,
+                        offset_shift: 0,
                     });
                 } else {
                     self.lyric_set_lyric(NO_LYRICS);
@@ -297,19 +477,31 @@ impl Model {
 
             let extra = self.current_track_lyric.as_ref().unwrap();
 
-            let Some(parsed_lyrics) = &extra.data.parsed_lyrics else {
-                self.lyric_set_lyric(NO_LYRICS);
+            let context = extra
+                .data
+                .parsed_lyrics
+                .as_ref()
+                .and_then(|v| v.get_context(self.playback.current_track_pos()));
+            let Some(context) = context else {
+                // unsynced lyrics (like from a ".txt" sidecar file) are shown as they are
+                let text = extra
+                    .data
+                    .raw_lyrics
+                    .get(extra.selected_idx)
+                    .map(|v| v.text.trim().to_string())
+                    .filter(|v| !v.is_empty());
+                self.lyric_set_lyric(text.unwrap_or_else(|| NO_LYRICS.to_string()));
                 return;
             };
-
-            if parsed_lyrics.captions.is_empty() {
-                self.lyric_set_lyric(NO_LYRICS);
-                return;
-            }
-            if let Some(l) = parsed_lyrics.get_text(self.playback.current_track_pos()) {
-                line = l.to_string();
-            }
-            self.lyric_set_lyric(line);
+            let lines = {
+                let config = self.config_tui.read();
+                lyric_context_lines(
+                    &context,
+                    config.settings.theme.lyric_foreground(),
+                    config.settings.theme.fallback_highlight(),
+                )
+            };
+            self.lyric_set_lines(lines);
         }
     }
 
@@ -334,14 +526,33 @@ impl Model {
             .attr(
                 &Id::Lyric,
                 Attribute::Text,
-                AttrValue::Payload(PropPayload::Vec(vec![PropValue::TextSpan(TextSpan::from(
-                    &text,
-                ))])),
+                AttrValue::Payload(PropPayload::Vec(
+                    text.lines()
+                        .map(|line| PropValue::TextSpan(TextSpan::from(line)))
+                        .collect(),
+                )),
             )
             .ok();
         self.lyric_line = text;
     }
 
+    /// Show styled `lines` centered instead of plain text, like the context of synced lyrics.
+    fn lyric_set_lines(&mut self, lines: Vec<Vec<TextSpan>>) {
+        let lines = lines
+            .into_iter()
+            .map(|line| PropPayload::Vec(line.into_iter().map(PropValue::TextSpan).collect()))
+            .collect();
+        self.app
+            .attr(
+                &Id::Lyric,
+                Attribute::Text,
+                AttrValue::Payload(PropPayload::Linked(lines)),
+            )
+            .ok();
+        // the next text always needs to be set again
+        self.lyric_line.clear();
+    }
+
     pub fn lyric_cycle(&mut self) {
         if let Some(extra) = self.current_track_lyric.as_mut() {
             if let Some(f) = extra.cycle_lyric().ok().flatten() {
@@ -354,28 +565,119 @@ impl Model {
             }
         }
     }
+    /// Get the lyric data of the current track, if it is loaded for the current track.
+    fn lyric_current_extra(&mut self) -> Option<(&Track, &mut ExtraLyricData)> {
+        let track = self.playback.current_track()?;
+        let Some(track_data) = track.as_track() else {
+            debug!("Could not use lyric data because it is not a music track!");
+            return None;
+        };
+        let extra = self.current_track_lyric.as_mut()?;
+        if extra.for_track != track_data.path() {
+            debug!("Could not use lyric data because of mismatching extra data and current track!");
+            return None;
+        }
+
+        Some((track, extra))
+    }
+
+    /// Adjust the lyric delay at the current position by `offset`, in memory only.
+    ///
+    /// Use [`lyric_save_offset`](Self::lyric_save_offset) to persist it.
     pub fn lyric_adjust_delay(&mut self, offset: i64) {
         let time_pos = self.playback.current_track_pos();
-        if let Some(track) = self.playback.current_track() {
-            let Ok(mut te_track) = TETrack::try_from(track) else {
-                debug!("Could not adjust delay because it is not a music track!");
-                return;
-            };
-            if te_track
-                .lyric_set_with_extra(self.current_track_lyric.as_ref())
-                .is_none()
-            {
-                debug!(
-                    "Could not adjust delay because of mismatching extra data and current track!"
-                );
-                return;
-            }
-            te_track.lyric_adjust_delay(time_pos, offset);
-            if let Err(e) = te_track.save_tag() {
-                self.mount_error_popup(e.context("adjust lyric delay"));
-            }
-            self.current_track_lyric = Some(te_track.into_extra_lyric_data());
+        if let Some((_, extra)) = self.lyric_current_extra() {
+            extra.adjust_delay(time_pos, offset);
+        }
+    }
+
+    /// Mount the [`LyricOffsetConfirm`] popup, if there is a adjusted offset to save
+    pub fn mount_lyric_offset_confirm(&mut self) {
+        let Some((_, extra)) = self.lyric_current_extra() else {
+            return;
+        };
+        let shift = extra.offset_shift;
+        let to_sidecar = extra
+            .data
+            .raw_lyrics
+            .get(extra.selected_idx)
+            .is_some_and(|v| v.description == sidecar::SIDECAR_DESCRIPTION);
+        if shift == 0 {
+            self.update_show_message_timeout("Lyric offset", "No adjusted offset to save", None);
+            return;
         }
+
+        self.app
+            .remount(
+                Id::LyricOffsetConfirmPopup,
+                Box::new(LyricOffsetConfirm::new(
+                    self.config_tui.clone(),
+                    shift,
+                    to_sidecar,
+                )),
+                Vec::new(),
+            )
+            .unwrap();
+
+        self.app.active(&Id::LyricOffsetConfirmPopup).unwrap();
+    }
+
+    /// Unmount the [`LyricOffsetConfirm`] popup
+    pub fn umount_lyric_offset_confirm(&mut self) {
+        let _ = self.app.umount(&Id::LyricOffsetConfirmPopup);
+    }
+
+    /// Write the adjusted lyric of the current track back to where it was read from,
+    /// either the sidecar lyric file or the embedded lyric frame.
+    pub fn lyric_save_offset(&mut self) -> Result<()> {
+        let sidecar_dirs = self.config_tui.read().settings.lyrics.sidecar_dirs.clone();
+        let Some((track, extra)) = self.lyric_current_extra() else {
+            bail!("No lyrics loaded for the current track");
+        };
+        let (Some(lyric), Some(frame)) = (
+            extra.data.parsed_lyrics.as_ref(),
+            extra.data.raw_lyrics.get(extra.selected_idx),
+        ) else {
+            bail!("No synced lyric selected");
+        };
+        let text = lyric.as_lrc_text();
+
+        if frame.description == sidecar::SIDECAR_DESCRIPTION {
+            let path = sidecar::find_sidecar(
+                &extra.for_track,
+                track.artist(),
+                track.title(),
+                &sidecar_dirs,
+            )
+            .context("Sidecar lyric file not found")?;
+            std::fs::write(&path, &text)
+                .with_context(|| format!("writing sidecar lyrics \"{}\"", path.display()))?;
+        } else {
+            // only replace the lyric frame, as saving the whole track would drop tags the editor does not know
+            edit_tag(&extra.for_track, |tag| {
+                if !tag.replace_lyrics(frame, text.clone()) {
+                    bail!(
+                        "Lyric frame \"{}\" not found in the track",
+                        frame.description
+                    );
+                }
+                Ok(true)
+            })?;
+        }
+
+        if let Some(frame) = extra.data.raw_lyrics.get_mut(extra.selected_idx) {
+            frame.text = text;
+        }
+        let shift = std::mem::take(&mut extra.offset_shift);
+        Track::forget_cached_lyrics(&extra.for_track);
+
+        self.update_show_message_timeout(
+            "Lyric offset",
+            &format!("Saved offset of {shift:+} ms"),
+            None,
+        );
+
+        Ok(())
     }
 
     /// Update the Lyric Component's title.
@@ -33,6 +33,7 @@ mod playlist;
 mod podcast;
 mod popups;
 mod progress;
+mod spectrum;
 #[allow(
     clippy::match_bool,
     clippy::redundant_closure_for_method_calls,
@@ -54,5 +55,7 @@ pub use music_library::MusicLibrary;
 pub use playlist::Playlist;
 pub use podcast::{EpisodeList, FeedsList};
 pub use popups::general_search::{GSInputPopup, GSTablePopup, Source};
+pub use popups::NamedPlaylistInput;
 pub use progress::Progress;
+pub use spectrum::Spectrum;
 pub use tag_editor::*;
@@ -6,7 +6,7 @@ use termusiclib::config::v2::server::config_extra::ServerConfigVersionedDefaulte
 use termusiclib::config::v2::server::ScanDepth;
 use termusiclib::config::SharedTuiSettings;
 use termusiclib::ids::Id;
-use termusiclib::types::{GSMsg, LIMsg, Msg, PLMsg, RecVec, TEMsg, YSMsg};
+use termusiclib::types::{GSMsg, LIMsg, Msg, PLMsg, QueueMsg, RecVec, TEMsg, YSMsg};
 use tui_realm_treeview::{Node, Tree, TreeView, TREE_CMD_CLOSE, TREE_CMD_OPEN, TREE_INITIAL_NODE};
 use tuirealm::command::{Cmd, CmdResult, Direction, Position};
 use tuirealm::event::{Key, KeyEvent, KeyModifiers};
@@ -223,6 +223,10 @@ impl Component<Msg, UserEvent> for MusicLibrary {
                     current_node.to_string(),
                 )));
             }
+            Event::Keyboard(keyevent) if keyevent == keys.library_keys.queue_next.get() => {
+                let current_node = self.component.tree_state().selected().unwrap();
+                return Some(Msg::Queue(QueueMsg::Add(PathBuf::from(current_node))));
+            }
 
             _ => CmdResult::None,
         };
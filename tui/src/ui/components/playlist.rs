@@ -1,6 +1,5 @@
 use std::borrow::Cow;
-use std::ffi::OsString;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::{anyhow, bail, Context as _, Result};
 use rand::seq::IndexedRandom;
@@ -11,16 +10,16 @@ use termusiclib::library_db::const_unknown::{UNKNOWN_ALBUM, UNKNOWN_ARTIST};
 use termusiclib::library_db::SearchCriteria;
 use termusiclib::library_db::TrackDB;
 use termusiclib::player::playlist_helpers::{
-    PlaylistAddTrack, PlaylistPlaySpecific, PlaylistRemoveTrackIndexed, PlaylistSwapTrack,
-    PlaylistTrackSource,
+    moved_index, PlaylistAddTrack, PlaylistMoveSelection, PlaylistMoveTrack, PlaylistPlaySpecific,
+    PlaylistRemoveTrackIndexed, PlaylistSelectedTrack, PlaylistSelection, PlaylistTrackSource,
 };
 use termusiclib::player::{
-    PlaylistAddTrackInfo, PlaylistLoopModeInfo, PlaylistRemoveTrackInfo, PlaylistShuffledInfo,
-    PlaylistSwapInfo,
+    PlaylistAddTrackInfo, PlaylistLoadedInfo, PlaylistLoopModeInfo, PlaylistMoveInfo,
+    PlaylistRemoveTrackInfo, PlaylistShuffledInfo, PlaylistSwapInfo,
 };
 use termusiclib::track::Track;
 use termusiclib::track::{DurationFmtShort, PodcastTrackData};
-use termusiclib::types::{GSMsg, Msg, PLMsg};
+use termusiclib::types::{GSMsg, Msg, NamedPlaylistMsg, PLMsg, QueueMsg};
 use termusiclib::utils::{filetype_supported, get_parent_folder, is_playlist, playlist_get_vec};
 use tui_realm_stdlib::Table;
 use tuirealm::props::Borders;
@@ -42,6 +41,8 @@ use crate::ui::Model;
 pub struct Playlist {
     component: Table,
     config: SharedTuiSettings,
+    /// Whether a range is currently being marked, which follows the selection
+    marking_range: bool,
 }
 
 impl Playlist {
@@ -75,7 +76,19 @@ impl Playlist {
                 )
         };
 
-        Self { component, config }
+        Self {
+            component,
+            config,
+            marking_range: false,
+        }
+    }
+
+    /// Get the currently selected index, if any.
+    fn selected_index(&self) -> Option<usize> {
+        match self.component.state() {
+            State::One(StateValue::Usize(index)) => Some(index),
+            _ => None,
+        }
     }
 }
 
@@ -84,6 +97,7 @@ impl Component<Msg, UserEvent> for Playlist {
     fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
         let config = self.config.clone();
         let keys = &config.read().settings.keys;
+        let selected_before = self.selected_index();
         let cmd_result = match ev {
             Event::Keyboard(KeyEvent {
                 code: Key::Down,
@@ -189,8 +203,80 @@ impl Component<Msg, UserEvent> for Playlist {
             Event::Keyboard(key) if key == keys.playlist_keys.add_random_songs.get() => {
                 return Some(Msg::Playlist(PLMsg::AddRandomTracks));
             }
+            Event::Keyboard(key) if key == keys.playlist_keys.named_playlists.get() => {
+                return Some(Msg::NamedPlaylist(NamedPlaylistMsg::PopupShow));
+            }
+            Event::Keyboard(key) if key == keys.playlist_keys.queue_next.get() => {
+                if let State::One(StateValue::Usize(index)) = self.state() {
+                    return Some(Msg::Queue(QueueMsg::AddFromPlaylist(index)));
+                }
+                CmdResult::None
+            }
+            Event::Keyboard(key) if key == keys.playlist_keys.show_queue.get() => {
+                return Some(Msg::Queue(QueueMsg::PopupShow));
+            }
+            Event::Keyboard(key) if key == keys.playlist_keys.undo.get() => {
+                return Some(Msg::Playlist(PLMsg::Undo));
+            }
+            Event::Keyboard(key) if key == keys.playlist_keys.redo.get() => {
+                return Some(Msg::Playlist(PLMsg::Redo));
+            }
+            Event::Keyboard(key) if key == keys.playlist_keys.sort.get() => {
+                return Some(Msg::Playlist(PLMsg::SortPopupShow));
+            }
+            Event::Keyboard(key) if key == keys.playlist_keys.mark.get() => {
+                if let Some(index) = self.selected_index() {
+                    return Some(Msg::Playlist(PLMsg::Mark(index)));
+                }
+                CmdResult::None
+            }
+            Event::Keyboard(key) if key == keys.playlist_keys.mark_range.get() => {
+                if self.marking_range {
+                    self.marking_range = false;
+                    return Some(Msg::Playlist(PLMsg::MarkRangeEnd));
+                }
+                if let Some(index) = self.selected_index() {
+                    self.marking_range = true;
+                    return Some(Msg::Playlist(PLMsg::MarkRangeStart(index)));
+                }
+                CmdResult::None
+            }
+            Event::Keyboard(key) if key == keys.playlist_keys.move_top.get() => {
+                if let Some(index) = self.selected_index() {
+                    return Some(Msg::Playlist(PLMsg::MoveTop(index)));
+                }
+                CmdResult::None
+            }
+            Event::Keyboard(key) if key == keys.playlist_keys.move_bottom.get() => {
+                if let Some(index) = self.selected_index() {
+                    return Some(Msg::Playlist(PLMsg::MoveBottom(index)));
+                }
+                CmdResult::None
+            }
+            Event::Keyboard(key) if key == keys.playlist_keys.save_marked.get() => {
+                return Some(Msg::Playlist(PLMsg::SaveMarkedShow));
+            }
+            Event::Keyboard(key) if key == keys.library_keys.open_tag_editor.get() => {
+                if let Some(index) = self.selected_index() {
+                    return Some(Msg::Playlist(PLMsg::OpenTagEditor(index)));
+                }
+                CmdResult::None
+            }
+            Event::Keyboard(key) if key == keys.escape.get() => {
+                self.marking_range = false;
+                return Some(Msg::Playlist(PLMsg::MarkClear));
+            }
             _ => CmdResult::None,
         };
+        // extend the range being marked to the new selection
+        if self.marking_range {
+            if let Some(index) = self
+                .selected_index()
+                .filter(|v| Some(*v) != selected_before)
+            {
+                return Some(Msg::Playlist(PLMsg::MarkRangeUpdate(index)));
+            }
+        }
         match cmd_result {
             CmdResult::None => None,
             _ => Some(Msg::ForceRedraw),
@@ -208,6 +294,8 @@ impl Model {
                 Vec::new()
             )
             .is_ok());
+        // the new component does not know about a range being marked
+        self.playback.playlist.end_mark_range();
         self.playlist_switch_layout();
         self.playlist_sync();
     }
@@ -475,6 +563,28 @@ impl Model {
         Ok(())
     }
 
+    /// Handle when the playlist had moved some tracks, the selection stays on the same track
+    pub fn handle_playlist_move_tracks(&mut self, moved_tracks: &PlaylistMoveInfo) -> Result<()> {
+        let from_index = usize::try_from(moved_tracks.from_index)
+            .context("Failed to convert from_index to usize")?;
+        let count =
+            usize::try_from(moved_tracks.count).context("Failed to convert count to usize")?;
+        let to_index = usize::try_from(moved_tracks.to_index)
+            .context("Failed to convert to_index to usize")?;
+
+        self.playback
+            .playlist
+            .move_tracks(from_index, count, to_index)?;
+
+        let selected = self.playlist_get_selected_index();
+        self.playlist_sync();
+        if let Some(selected) = selected {
+            self.playlist_locate(moved_index(selected, from_index, count, to_index));
+        }
+
+        Ok(())
+    }
+
     /// Handle when the playlist has been shuffled and so has new order of tracks
     pub fn handle_playlist_shuffled(&mut self, shuffled: PlaylistShuffledInfo) -> Result<()> {
         let playlist_comp_selected_index = self.playlist_get_selected_index();
@@ -503,9 +613,64 @@ impl Model {
         Ok(())
     }
 
-    /// Handle setting the current track index in the TUI playlist and selecting the proper list item
+    /// Handle the playlist having been replaced by a named playlist on the server.
+    ///
+    /// # Errors
+    ///
+    /// see [`load_from_grpc`](crate::ui::model::Playback::load_from_grpc)
+    pub fn handle_playlist_loaded(&mut self, loaded: PlaylistLoadedInfo) -> Result<()> {
+        let current_track_index = usize::try_from(loaded.tracks.current_track_index)
+            .context("convert current_track_index(u64) to usize")?;
+
+        self.playback
+            .load_from_grpc(loaded.tracks, &self.podcast.db_podcast)?;
+        self.playlist_sync();
+
+        if !self.playback.playlist.is_empty() {
+            self.playlist_locate(current_track_index);
+        }
+
+        Ok(())
+    }
+
+    /// Handle a track change from the server, which may be a track from the "play next" queue instead of the playlist.
     ///
     /// Note: currently this function is called twice per track change, once for `UpdateEvents::TrackChanged` and once for `run_playback::GetProgress`
+    pub fn handle_current_track_changed(
+        &mut self,
+        current_track_index: usize,
+        queued_track: Option<PlaylistTrackSource>,
+    ) {
+        let Some(queued_track) = queued_track else {
+            self.handle_current_track_index(current_track_index, false);
+            return;
+        };
+
+        // the playlist stays at the last played playlist track, as that is where it will continue from
+        let _ = self
+            .playback
+            .playlist
+            .set_current_track_index(current_track_index);
+        if let Err(err) = self
+            .playback
+            .set_current_track_from_source(&queued_track, &self.podcast.db_podcast)
+        {
+            self.mount_error_popup(err.context("current track from queue"));
+            return;
+        }
+        self.playlist_sync();
+
+        self.update_layout_for_current_track();
+        self.player_update_current_track_after();
+
+        self.lyric_update_for_podcast_by_current_track();
+
+        if let Err(e) = self.podcast_mark_current_track_played() {
+            self.mount_error_popup(e.context("Marking podcast track as played"));
+        }
+    }
+
+    /// Handle setting the current track index in the TUI playlist and selecting the proper list item
     pub fn handle_current_track_index(&mut self, current_track_index: usize, force_relocate: bool) {
         let tui_old_current_index = self.playback.playlist.current_track_index();
         info!(
@@ -571,9 +736,13 @@ impl Model {
                         .current_track_symbol
                 );
             }
-            table
-                .add_col(TextSpan::new(duration_str.as_str()))
-                .add_col(TextSpan::new(title).bold());
+            let mut duration = TextSpan::new(duration_str.as_str());
+            let mut title = TextSpan::new(title).bold();
+            if self.playback.playlist.is_marked(idx) {
+                duration = duration.reversed();
+                title = title.reversed();
+            }
+            table.add_col(duration).add_col(title);
         }
         if self.playback.playlist.is_empty() {
             table.add_col(TextSpan::from("0"));
@@ -634,11 +803,19 @@ impl Model {
                 .into();
             }
 
-            table
-                .add_col(TextSpan::new(duration_str.as_str()))
-                .add_col(TextSpan::new(artist).fg(tuirealm::ratatui::style::Color::LightYellow))
-                .add_col(TextSpan::new(title).bold())
-                .add_col(TextSpan::new(album));
+            let mut cols = [
+                TextSpan::new(duration_str.as_str()),
+                TextSpan::new(artist).fg(tuirealm::ratatui::style::Color::LightYellow),
+                TextSpan::new(title).bold(),
+                TextSpan::new(album),
+            ];
+            // marked tracks are shown inverted, like a selection
+            if self.playback.playlist.is_marked(idx) {
+                cols = cols.map(TextSpan::reversed);
+            }
+            for col in cols {
+                table.add_col(col);
+            }
         }
         if self.playback.playlist.is_empty() {
             table.add_col(TextSpan::from("0"));
@@ -690,25 +867,26 @@ impl Model {
         self.command(TuiCmd::Playlist(PlaylistCmd::Shuffle));
     }
 
-    /// Send command to swap 2 indexes. Does nothing if either index is out-of-bounds.
+    /// Send command to move `count` tracks starting at `from_index` to `to_index`. Does nothing if either is out-of-bounds.
     ///
     /// # Panics
     ///
     /// if `usize` cannot be converted to `u64`
-    fn playlist_swap(&mut self, index_a: usize, index_b: usize) {
+    fn playlist_move_tracks(&mut self, from_index: usize, count: usize, to_index: usize) {
         let len = self.playback.playlist.tracks().len();
-        if index_a.max(index_b) >= len {
+        if from_index.max(to_index) + count > len {
             error!(
-                "Index out-of-bounds, not executing swap: {}",
-                index_a.max(index_b)
+                "Index out-of-bounds, not executing move: {}",
+                from_index.max(to_index) + count
             );
             return;
         }
 
-        self.command(TuiCmd::Playlist(PlaylistCmd::SwapTrack(
-            PlaylistSwapTrack {
-                index_a: u64::try_from(index_a).unwrap(),
-                index_b: u64::try_from(index_b).unwrap(),
+        self.command(TuiCmd::Playlist(PlaylistCmd::MoveTracks(
+            PlaylistMoveTrack {
+                from_index: u64::try_from(from_index).unwrap(),
+                count: u64::try_from(count).unwrap(),
+                to_index: u64::try_from(to_index).unwrap(),
             },
         )));
     }
@@ -720,7 +898,7 @@ impl Model {
         }
 
         // always guranteed to be above 0, no saturated necessary
-        self.playlist_swap(index, index - 1);
+        self.playlist_move_tracks(index, 1, index - 1);
     }
 
     /// Swap the given index downwards, does nothing if out-of-bounds.
@@ -729,7 +907,176 @@ impl Model {
             return;
         }
 
-        self.playlist_swap(index, index.saturating_add(1));
+        self.playlist_move_tracks(index, 1, index.saturating_add(1));
+    }
+
+    /// Get the selection of the tracks at `indexes`, ignoring indexes that are out-of-bounds.
+    ///
+    /// # Panics
+    ///
+    /// if `usize` cannot be converted to `u64`
+    fn playlist_selection(&self, indexes: &[usize]) -> PlaylistSelection {
+        let tracks = indexes
+            .iter()
+            .filter_map(|index| {
+                let track = self.playback.playlist.tracks().get(*index)?;
+
+                Some(PlaylistSelectedTrack {
+                    index: u64::try_from(*index).unwrap(),
+                    id: track.as_track_source(),
+                })
+            })
+            .collect();
+
+        PlaylistSelection { tracks }
+    }
+
+    /// Delete all marked tracks from the playlist at once.
+    pub fn playlist_delete_marked(&mut self) {
+        let marked = self.playback.playlist.marked();
+        if marked.is_empty() {
+            return;
+        }
+
+        let selection = self.playlist_selection(&marked);
+        // all marked tracks will be gone
+        self.playback.playlist.clear_marks();
+        self.playlist_sync();
+
+        self.command(TuiCmd::Playlist(PlaylistCmd::RemoveSelection(selection)));
+    }
+
+    /// Send command to move `indexes` as one block to `to_index`, keeping them marked at the new position if `keep_marks` is set.
+    ///
+    /// Does nothing if the tracks are already there.
+    ///
+    /// # Panics
+    ///
+    /// if `usize` cannot be converted to `u64`
+    fn playlist_move_selection(&mut self, indexes: &[usize], to_index: usize, keep_marks: bool) {
+        let already_there = indexes
+            .iter()
+            .enumerate()
+            .all(|(offset, index)| *index == to_index + offset);
+        if already_there {
+            return;
+        }
+
+        // tracks next to each other can be moved with a single event, which also moves the marks
+        if let (Some(first), Some(last)) = (indexes.first(), indexes.last()) {
+            if last - first + 1 == indexes.len() {
+                self.playlist_move_tracks(*first, indexes.len(), to_index);
+                return;
+            }
+        }
+
+        let selection = self.playlist_selection(indexes);
+        if keep_marks {
+            // the new tracks will come as a full refresh, which keeps the marks
+            self.playback
+                .playlist
+                .set_marks(to_index..to_index + indexes.len());
+            self.playlist_sync();
+        }
+
+        self.command(TuiCmd::Playlist(PlaylistCmd::MoveSelection(
+            PlaylistMoveSelection {
+                selection,
+                to_index: u64::try_from(to_index).unwrap(),
+            },
+        )));
+    }
+
+    /// Move the marked tracks, or the track at `index` if none are marked, as one block to `to_index`.
+    ///
+    /// `None` moves them to the bottom.
+    pub fn playlist_move_marked_to(&mut self, index: usize, to_index: Option<usize>) {
+        let len = self.playback.playlist.len();
+        let mut indexes = self.playback.playlist.marked();
+        let has_marks = !indexes.is_empty();
+        if !has_marks {
+            if index >= len {
+                return;
+            }
+            indexes.push(index);
+        }
+
+        let max_index = len - indexes.len();
+        let to_index = to_index.unwrap_or(max_index).min(max_index);
+
+        self.playlist_move_selection(&indexes, to_index, has_marks);
+    }
+
+    /// Move the marked tracks up by one as a block, or swap the track at `index` upwards if none are marked.
+    pub fn playlist_move_up(&mut self, index: usize) {
+        // the component already moved the selection, undo that so that the update can follow the selected track
+        self.playlist_locate(index);
+
+        let marked = self.playback.playlist.marked();
+        let Some(first) = marked.first().copied() else {
+            self.playlist_swap_up(index);
+            return;
+        };
+
+        if first == 0 {
+            return;
+        }
+
+        self.playlist_move_selection(&marked, first - 1, true);
+    }
+
+    /// Move the marked tracks down by one as a block, or swap the track at `index` downwards if none are marked.
+    pub fn playlist_move_down(&mut self, index: usize) {
+        // the component already moved the selection, undo that so that the update can follow the selected track
+        self.playlist_locate(index);
+
+        let marked = self.playback.playlist.marked();
+        let (Some(first), Some(last)) = (marked.first().copied(), marked.last().copied()) else {
+            self.playlist_swap_down(index);
+            return;
+        };
+
+        if last + 1 >= self.playback.playlist.len() {
+            return;
+        }
+
+        self.playlist_move_selection(&marked, first + 1, true);
+    }
+
+    /// Save the marked tracks as named playlist `name`.
+    pub fn playlist_save_marked(&mut self, name: String) {
+        let marked = self.playback.playlist.marked();
+        if marked.is_empty() {
+            return;
+        }
+
+        let selection = self.playlist_selection(&marked);
+        self.command(TuiCmd::Playlist(PlaylistCmd::SaveSelectionNamed(
+            name, selection,
+        )));
+    }
+
+    /// Open the tag editor for the marked tracks, or the track at `index` if none are marked.
+    ///
+    /// Only tracks which are local files are edited.
+    pub fn playlist_open_tag_editor(&mut self, index: usize) {
+        let mut indexes = self.playback.playlist.marked();
+        if indexes.is_empty() {
+            indexes.push(index);
+        }
+
+        let paths: Vec<PathBuf> = indexes
+            .into_iter()
+            .filter_map(|idx| self.playback.playlist.tracks().get(idx)?.path())
+            .filter(|v| v.exists())
+            .map(Path::to_path_buf)
+            .collect();
+        if paths.is_empty() {
+            self.mount_error_popup(anyhow!("No local tracks selected"));
+            return;
+        }
+
+        self.mount_tageditor_batch(&paths);
     }
 
     pub fn playlist_update_library_delete(&mut self) {
@@ -753,8 +1100,32 @@ impl Model {
             .playlist
             .use_loop_mode_symbol;
         let loop_mode = self.config_server.read().settings.player.loop_mode;
+        let name = self
+            .playback
+            .playlist
+            .name()
+            .map(|v| format!(": {v}"))
+            .unwrap_or_default();
+        let queued = match self.playback.queue().len() {
+            0 => String::new(),
+            len => format!(" | Queued: {len}"),
+        };
+        let controls = self.playback.playback_controls();
+        let repeat = match controls.repeat_count {
+            0 => String::new(),
+            count => format!(" | Repeat: {count}x"),
+        };
+        let stop = if controls.stop_after_current {
+            " | Stop after current"
+        } else {
+            ""
+        };
+        let marked = match self.playback.playlist.marked().len() {
+            0 => String::new(),
+            len => format!(" | Marked: {len}"),
+        };
         let title = format!(
-            "\u{2500} Playlist \u{2500}\u{2500}\u{2524} Total {} tracks | {} | Mode: {} \u{251c}\u{2500}",
+            "\u{2500} Playlist{name} \u{2500}\u{2500}\u{2524} Total {} tracks | {} | Mode: {}{queued}{repeat}{stop}{marked} \u{251c}\u{2500}",
             self.playback.playlist.len(),
             DurationFmtShort(duration),
             loop_mode.display(display_symbol),
@@ -860,36 +1231,33 @@ impl Model {
         result
     }
 
-    /// Save the current playlist as m3u with the given `filename`
-    pub fn playlist_save_m3u_before(&mut self, filename: &str) -> Result<()> {
+    /// Save the current playlist with the given `filename` in the directory of the selected library node.
+    ///
+    /// The server appends `.m3u` if `filename` does not have the extension of a writable format,
+    /// and asks for confirmation via [`SavePlaylistMsg::ConfirmCloseOk`](termusiclib::types::SavePlaylistMsg::ConfirmCloseOk) if the file exists.
+    pub fn playlist_save_file_before(&mut self, filename: &str) -> Result<()> {
         let current_node: String = match self.app.state(&Id::Library).ok().unwrap() {
             State::One(StateValue::String(id)) => id,
             _ => bail!("Invalid node selected in library"),
         };
 
-        let path_m3u = {
-            let mut parent_folder = get_parent_folder(Path::new(&current_node)).to_path_buf();
-            let mut filename = OsString::from(filename);
-            filename.push(".m3u");
-            parent_folder.push(filename);
-
-            parent_folder
-        };
+        let path = get_parent_folder(Path::new(&current_node)).join(filename);
 
-        if path_m3u.exists() {
-            self.mount_save_playlist_confirm(&path_m3u.to_string_lossy());
-            return Ok(());
-        }
-
-        self.playlist_save_m3u(&path_m3u)
+        self.playlist_save_file(&path, false)
     }
 
-    /// Save the current playlist as m3u in the given full path
-    pub fn playlist_save_m3u(&mut self, filename: &Path) -> Result<()> {
-        // TODO: move this to server?
-        self.playback.playlist.save_m3u(filename)?;
+    /// Save the current playlist in the given path on the server, the format is picked from the extension.
+    ///
+    /// The library is reloaded once the server is done.
+    pub fn playlist_save_file(&mut self, filename: &Path, overwrite: bool) -> Result<()> {
+        if self.playback.playlist.is_empty() {
+            bail!("Unable to save since the playlist is empty.");
+        }
 
-        self.library_reload_with_node_focus(Some(filename.to_string_lossy().to_string()));
+        self.command(TuiCmd::Playlist(PlaylistCmd::SaveToFile(
+            filename.to_path_buf(),
+            overwrite,
+        )));
 
         Ok(())
     }
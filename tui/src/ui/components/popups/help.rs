@@ -95,9 +95,28 @@ impl HelpPopup {
                         ]))
                         .add_col(Self::comment("Playback speed up/down 10 percent"))
                         .add_row()
+                        .add_col(Self::key(&[
+                            &keys.player_keys.pitch_up,
+                            &keys.player_keys.pitch_down,
+                        ]))
+                        .add_col(Self::comment("Pitch up/down 1 semitone"))
+                        .add_row()
+                        .add_col(Self::key(&[
+                            &keys.player_keys.pitch_fine_up,
+                            &keys.player_keys.pitch_fine_down,
+                        ]))
+                        .add_col(Self::comment("Pitch up/down 10 cents"))
+                        .add_row()
                         .add_col(Self::key(&[&keys.player_keys.toggle_prefetch]))
                         .add_col(Self::comment("Toggle gapless playback"))
                         .add_row()
+                        .add_col(Self::key(&[
+                            &keys.player_keys.ab_loop_mark_a,
+                            &keys.player_keys.ab_loop_mark_b,
+                            &keys.player_keys.ab_loop_clear,
+                        ]))
+                        .add_col(Self::comment("Mark A/Mark B and start/Clear A-B loop"))
+                        .add_row()
                         .add_col(Self::key(&[
                             &keys.lyric_keys.adjust_offset_forwards,
                             &keys.lyric_keys.adjust_offset_backwards,
@@ -107,6 +126,9 @@ impl HelpPopup {
                         .add_col(Self::key(&[&keys.lyric_keys.cycle_frames]))
                         .add_col(Self::comment("Switch lyrics if more than 1 available"))
                         .add_row()
+                        .add_col(Self::key(&[&keys.lyric_keys.save_offset]))
+                        .add_col(Self::comment("Save adjusted lyric offset"))
+                        .add_row()
                         .add_col(Self::key(&[
                             &keys.player_keys.next_track,
                             &keys.player_keys.previous_track,
@@ -173,7 +195,12 @@ impl HelpPopup {
                         .add_col(Self::comment("Search or download track from youtube"))
                         .add_row()
                         .add_col(Self::key(&[&keys.library_keys.open_tag_editor]))
-                        .add_col(Self::comment("Open tag editor for tag and lyric download"))
+                        .add_col(Self::comment(
+                            "Open tag editor for track or all tracks of folder",
+                        ))
+                        .add_row()
+                        .add_col(Self::key(&[&keys.library_keys.queue_next]))
+                        .add_col(Self::comment("Play selected file/directory next (queue)"))
                         .add_row()
                         .add_col(Self::key(&[
                             &keys.library_keys.yank,
@@ -231,6 +258,42 @@ impl HelpPopup {
                         ]))
                         .add_col(Self::comment("Select random tracks/albums to playlist"))
                         .add_row()
+                        .add_col(Self::key(&[&keys.playlist_keys.named_playlists]))
+                        .add_col(Self::comment("Load/save/rename/delete named playlists"))
+                        .add_row()
+                        .add_col(Self::key(&[&keys.playlist_keys.queue_next]))
+                        .add_col(Self::comment("Play selected track next (queue)"))
+                        .add_row()
+                        .add_col(Self::key(&[&keys.playlist_keys.show_queue]))
+                        .add_col(Self::comment("Show/edit the play next queue"))
+                        .add_row()
+                        .add_col(Self::key(&[
+                            &keys.playlist_keys.undo,
+                            &keys.playlist_keys.redo,
+                        ]))
+                        .add_col(Self::comment("Undo/redo playlist edit"))
+                        .add_row()
+                        .add_col(Self::key(&[&keys.playlist_keys.sort]))
+                        .add_col(Self::comment("Sort/deduplicate playlist"))
+                        .add_row()
+                        .add_col(Self::key(&[
+                            &keys.playlist_keys.mark,
+                            &keys.playlist_keys.mark_range,
+                        ]))
+                        .add_col(Self::comment("Mark track/range, Esc to clear marks"))
+                        .add_row()
+                        .add_col(Self::key(&[
+                            &keys.playlist_keys.move_top,
+                            &keys.playlist_keys.move_bottom,
+                        ]))
+                        .add_col(Self::comment("Move marked tracks to top/bottom"))
+                        .add_row()
+                        .add_col(Self::key(&[&keys.playlist_keys.save_marked]))
+                        .add_col(Self::comment("Save marked tracks as named playlist"))
+                        .add_row()
+                        .add_col(Self::key(&[&keys.library_keys.open_tag_editor]))
+                        .add_col(Self::comment("Open tag editor for marked/selected tracks"))
+                        .add_row()
                         .add_col(TextSpan::new("Database").bold().fg(Color::LightYellow))
                         .add_row()
                         .add_col(Self::key(&[
@@ -243,6 +306,9 @@ impl HelpPopup {
                         .add_col(Self::key(&[&keys.library_keys.search]))
                         .add_col(Self::comment("Search in database"))
                         .add_row()
+                        .add_col(Self::key(&[&keys.library_keys.open_tag_editor]))
+                        .add_col(Self::comment("Open tag editor for all tracks of result"))
+                        .add_row()
                         .add_col(TextSpan::new("Podcast").bold().fg(Color::LightYellow))
                         .add_row()
                         .add_col(Self::key(&[&keys.podcast_keys.search]))
@@ -6,7 +6,10 @@ pub mod general_search;
 mod help;
 mod message;
 mod mock_yn_confirm;
+mod named_playlists;
+mod playlist_sort;
 mod podcast;
+mod queue;
 mod quit;
 mod saveplaylist;
 pub mod youtube_search;
@@ -21,8 +24,17 @@ pub use help::HelpPopup;
 pub use message::MessagePopup;
 pub use mock_yn_confirm::{YNConfirm, YNConfirmStyle};
 #[allow(unused_imports)]
+pub use named_playlists::{
+    NamedPlaylistDeleteConfirmPopup, NamedPlaylistInput, NamedPlaylistInputPopup,
+    NamedPlaylistsTablePopup,
+};
+#[allow(unused_imports)]
+pub use playlist_sort::PlaylistSortPopup;
+#[allow(unused_imports)]
 pub use podcast::{FeedDeleteConfirmRadioPopup, PodcastAddPopup, PodcastSearchTablePopup};
 #[allow(unused_imports)]
+pub use queue::QueueTablePopup;
+#[allow(unused_imports)]
 pub use quit::QuitPopup;
 #[allow(unused_imports)]
 pub use saveplaylist::{SavePlaylistConfirmPopup, SavePlaylistPopup};
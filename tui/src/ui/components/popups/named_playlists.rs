@@ -0,0 +1,400 @@
+use termusiclib::config::{SharedTuiSettings, TuiOverlay};
+use termusiclib::ids::Id;
+use termusiclib::player::{named_playlists, NamedPlaylists};
+use termusiclib::types::{Msg, NamedPlaylistMsg};
+use tui_realm_stdlib::{Input, Table};
+use tuirealm::{
+    command::{Cmd, CmdResult, Direction, Position},
+    event::{Key, KeyEvent, KeyModifiers},
+    props::{Alignment, BorderType, Borders, InputType, TableBuilder, TextSpan},
+    Component, Event, MockComponent, State, StateValue,
+};
+
+use crate::ui::model::{Model, UserEvent};
+
+use super::{YNConfirm, YNConfirmStyle};
+
+#[derive(MockComponent)]
+pub struct NamedPlaylistsTablePopup {
+    component: Table,
+    config: SharedTuiSettings,
+}
+
+impl NamedPlaylistsTablePopup {
+    pub fn new(config: SharedTuiSettings) -> Self {
+        let component = {
+            let config = config.read();
+            Table::default()
+                .background(config.settings.theme.library_background())
+                .foreground(config.settings.theme.library_foreground())
+                .borders(
+                    Borders::default()
+                        .color(config.settings.theme.library_border())
+                        .modifiers(BorderType::Rounded),
+                )
+                .title(
+                    " Playlists: Enter to load, a: save current as, r: rename, d: delete ",
+                    Alignment::Left,
+                )
+                .scroll(true)
+                .highlighted_color(config.settings.theme.library_highlight())
+                .highlighted_str(&config.settings.theme.style.library.highlight_symbol)
+                .rewind(false)
+                .step(4)
+                .row_height(1)
+                .headers(&[" Name "])
+                .column_spacing(3)
+                .widths(&[100])
+                .table(
+                    TableBuilder::default()
+                        .add_col(TextSpan::from("Loading..."))
+                        .build(),
+                )
+        };
+
+        Self { component, config }
+    }
+
+    /// Get the currently selected index, if any.
+    fn selected(&self) -> Option<usize> {
+        if let State::One(StateValue::Usize(index)) = self.state() {
+            return Some(index);
+        }
+
+        None
+    }
+}
+
+impl Component<Msg, UserEvent> for NamedPlaylistsTablePopup {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        let config = self.config.clone();
+        let keys = &config.read().settings.keys;
+        let cmd_result = match ev {
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                return Some(Msg::NamedPlaylist(NamedPlaylistMsg::PopupCloseCancel))
+            }
+            Event::Keyboard(keyevent) if keyevent == keys.quit.get() => {
+                return Some(Msg::NamedPlaylist(NamedPlaylistMsg::PopupCloseCancel))
+            }
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => self.perform(Cmd::Move(Direction::Down)),
+
+            Event::Keyboard(keyevent) if keyevent == keys.navigation_keys.down.get() => {
+                self.perform(Cmd::Move(Direction::Down))
+            }
+
+            Event::Keyboard(keyevent) if keyevent == keys.navigation_keys.up.get() => {
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::PageDown,
+                ..
+            }) => self.perform(Cmd::Scroll(Direction::Down)),
+            Event::Keyboard(KeyEvent {
+                code: Key::PageUp, ..
+            }) => self.perform(Cmd::Scroll(Direction::Up)),
+            Event::Keyboard(keyevent) if keyevent == keys.navigation_keys.goto_top.get() => {
+                self.perform(Cmd::GoTo(Position::Begin))
+            }
+            Event::Keyboard(keyevent) if keyevent == keys.navigation_keys.goto_bottom.get() => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => {
+                if let Some(index) = self.selected() {
+                    return Some(Msg::NamedPlaylist(NamedPlaylistMsg::Load(index)));
+                }
+                CmdResult::None
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('a'),
+                modifiers: KeyModifiers::NONE,
+            }) => return Some(Msg::NamedPlaylist(NamedPlaylistMsg::SaveAsShow)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('r'),
+                modifiers: KeyModifiers::NONE,
+            }) => {
+                if let Some(index) = self.selected() {
+                    return Some(Msg::NamedPlaylist(NamedPlaylistMsg::RenameShow(index)));
+                }
+                CmdResult::None
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('d'),
+                modifiers: KeyModifiers::NONE,
+            }) => {
+                if let Some(index) = self.selected() {
+                    return Some(Msg::NamedPlaylist(NamedPlaylistMsg::DeleteShow(index)));
+                }
+                CmdResult::None
+            }
+            _ => CmdResult::None,
+        };
+        match cmd_result {
+            CmdResult::None => None,
+            _ => Some(Msg::ForceRedraw),
+        }
+    }
+}
+
+/// What a entered playlist name is used for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NamedPlaylistInput {
+    /// Save the current playlist as the name
+    SaveAs,
+    /// Save the marked tracks of the current playlist as the name
+    SaveMarkedAs,
+    /// Rename the given playlist to the name
+    Rename(String),
+}
+
+/// Input for a playlist name, see [`NamedPlaylistInput`].
+#[derive(MockComponent)]
+pub struct NamedPlaylistInputPopup {
+    component: Input,
+    input: NamedPlaylistInput,
+}
+
+impl NamedPlaylistInputPopup {
+    pub fn new(config: &TuiOverlay, input: NamedPlaylistInput) -> Self {
+        let settings = &config.settings;
+        let (title, value) = match &input {
+            NamedPlaylistInput::SaveAs => (" Save current Playlist as: (Enter to confirm) ", ""),
+            NamedPlaylistInput::SaveMarkedAs => (" Save marked Tracks as: (Enter to confirm) ", ""),
+            NamedPlaylistInput::Rename(from) => {
+                (" Rename Playlist to: (Enter to confirm) ", from.as_str())
+            }
+        };
+        Self {
+            component: Input::default()
+                .foreground(settings.theme.library_foreground())
+                .background(settings.theme.library_background())
+                .borders(
+                    Borders::default()
+                        .color(settings.theme.library_border())
+                        .modifiers(BorderType::Rounded),
+                )
+                .input_type(InputType::Text)
+                .value(value)
+                .title(title, Alignment::Left),
+            input,
+        }
+    }
+}
+
+impl Component<Msg, UserEvent> for NamedPlaylistInputPopup {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        let cmd_result = match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Left, ..
+            }) => self.perform(Cmd::Move(Direction::Left)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => self.perform(Cmd::Move(Direction::Right)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => self.perform(Cmd::GoTo(Position::Begin)),
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Delete, ..
+            }) => self.perform(Cmd::Cancel),
+            Event::Keyboard(KeyEvent {
+                code: Key::Backspace,
+                ..
+            }) => self.perform(Cmd::Delete),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char(ch),
+                modifiers: KeyModifiers::SHIFT | KeyModifiers::NONE,
+            }) => self.perform(Cmd::Type(ch)),
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                return Some(Msg::NamedPlaylist(NamedPlaylistMsg::InputCloseCancel));
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => match self.component.state() {
+                State::One(StateValue::String(input_string)) => {
+                    let msg = match &self.input {
+                        NamedPlaylistInput::SaveAs => NamedPlaylistMsg::SaveAs(input_string),
+                        NamedPlaylistInput::SaveMarkedAs => {
+                            NamedPlaylistMsg::SaveMarkedAs(input_string)
+                        }
+                        NamedPlaylistInput::Rename(from) => {
+                            NamedPlaylistMsg::Rename(from.clone(), input_string)
+                        }
+                    };
+                    return Some(Msg::NamedPlaylist(msg));
+                }
+                _ => CmdResult::None,
+            },
+            _ => CmdResult::None,
+        };
+        match cmd_result {
+            CmdResult::None => None,
+            _ => Some(Msg::ForceRedraw),
+        }
+    }
+}
+
+#[derive(MockComponent)]
+pub struct NamedPlaylistDeleteConfirmPopup {
+    component: YNConfirm,
+    name: String,
+}
+
+impl NamedPlaylistDeleteConfirmPopup {
+    pub fn new(config: SharedTuiSettings, name: &str) -> Self {
+        let component =
+            YNConfirm::new_with_cb(config, format!(" Delete playlist \"{name}\"? "), |config| {
+                YNConfirmStyle {
+                    foreground_color: config.settings.theme.important_popup_foreground(),
+                    background_color: config.settings.theme.important_popup_background(),
+                    border_color: config.settings.theme.important_popup_border(),
+                    title_alignment: Alignment::Center,
+                }
+            });
+
+        Self {
+            component,
+            name: name.to_string(),
+        }
+    }
+}
+
+impl Component<Msg, UserEvent> for NamedPlaylistDeleteConfirmPopup {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        self.component.on(
+            ev,
+            Msg::NamedPlaylist(NamedPlaylistMsg::DeleteCloseOk(self.name.clone())),
+            Msg::NamedPlaylist(NamedPlaylistMsg::DeleteCloseCancel),
+        )
+    }
+}
+
+impl Model {
+    pub fn mount_named_playlists(&mut self) {
+        assert!(self
+            .app
+            .remount(
+                Id::NamedPlaylistsPopup,
+                Box::new(NamedPlaylistsTablePopup::new(self.config_tui.clone())),
+                vec![]
+            )
+            .is_ok());
+        assert!(self.app.active(&Id::NamedPlaylistsPopup).is_ok());
+        if let Err(e) = self.update_photo() {
+            self.mount_error_popup(e.context("update_photo"));
+        }
+    }
+
+    pub fn umount_named_playlists(&mut self) {
+        self.umount_named_playlist_input();
+        self.umount_named_playlist_delete_confirm();
+        if self.app.mounted(&Id::NamedPlaylistsPopup) {
+            assert!(self.app.umount(&Id::NamedPlaylistsPopup).is_ok());
+        }
+        if let Err(e) = self.update_photo() {
+            self.mount_error_popup(e.context("update_photo"));
+        }
+    }
+
+    /// Apply the list of named playlists from the server, also updating the current playlist name.
+    pub fn handle_named_playlists(&mut self, list: NamedPlaylists) {
+        let current = list.optional_current.map(|v| {
+            let named_playlists::OptionalCurrent::Current(v) = v;
+            v
+        });
+        self.playback.playlist.set_name(current);
+        self.playback.set_named_playlists(list.names);
+
+        self.playlist_update_title();
+        self.update_named_playlists_table();
+    }
+
+    fn update_named_playlists_table(&mut self) {
+        if !self.app.mounted(&Id::NamedPlaylistsPopup) {
+            return;
+        }
+
+        let mut table: TableBuilder = TableBuilder::default();
+        let current = self.playback.playlist.name();
+        for (idx, name) in self.playback.named_playlists().iter().enumerate() {
+            if idx > 0 {
+                table.add_row();
+            }
+
+            if current == Some(name.as_str()) {
+                let symbol = &self
+                    .config_tui
+                    .read()
+                    .settings
+                    .theme
+                    .style
+                    .playlist
+                    .current_track_symbol;
+                table.add_col(TextSpan::new(format!("{symbol}{name}")).bold());
+            } else {
+                table.add_col(TextSpan::new(name));
+            }
+        }
+        if self.playback.named_playlists().is_empty() {
+            table.add_col(TextSpan::from(
+                "No saved playlists, press \"a\" to save the current one.",
+            ));
+        }
+        let table = table.build();
+
+        self.app
+            .attr(
+                &Id::NamedPlaylistsPopup,
+                tuirealm::Attribute::Content,
+                tuirealm::AttrValue::Table(table),
+            )
+            .ok();
+    }
+
+    pub fn mount_named_playlist_input(&mut self, input: NamedPlaylistInput) {
+        assert!(self
+            .app
+            .remount(
+                Id::NamedPlaylistInputPopup,
+                Box::new(NamedPlaylistInputPopup::new(&self.config_tui.read(), input)),
+                vec![]
+            )
+            .is_ok());
+        assert!(self.app.active(&Id::NamedPlaylistInputPopup).is_ok());
+    }
+
+    pub fn umount_named_playlist_input(&mut self) {
+        if self.app.mounted(&Id::NamedPlaylistInputPopup) {
+            assert!(self.app.umount(&Id::NamedPlaylistInputPopup).is_ok());
+        }
+    }
+
+    pub fn mount_named_playlist_delete_confirm(&mut self, name: &str) {
+        assert!(self
+            .app
+            .remount(
+                Id::NamedPlaylistDeleteConfirm,
+                Box::new(NamedPlaylistDeleteConfirmPopup::new(
+                    self.config_tui.clone(),
+                    name
+                )),
+                vec![]
+            )
+            .is_ok());
+        assert!(self.app.active(&Id::NamedPlaylistDeleteConfirm).is_ok());
+    }
+
+    pub fn umount_named_playlist_delete_confirm(&mut self) {
+        if self.app.mounted(&Id::NamedPlaylistDeleteConfirm) {
+            assert!(self.app.umount(&Id::NamedPlaylistDeleteConfirm).is_ok());
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use termusiclib::config::SharedTuiSettings;
+use termusiclib::ids::Id;
+use termusiclib::player::playlist_helpers::{PlaylistSort, PlaylistSortKey};
+use termusiclib::types::{Msg, PLMsg};
+use tui_realm_stdlib::Table;
+use tuirealm::{
+    command::{Cmd, CmdResult, Direction, Position},
+    event::{Key, KeyEvent, KeyModifiers},
+    props::{Alignment, BorderType, Borders, TableBuilder, TextSpan},
+    Component, Event, MockComponent, State, StateValue,
+};
+
+use crate::ui::model::{Model, UserEvent};
+
+/// The sort choices in the order they are displayed, the entry after these is "Remove duplicates"
+const SORT_CHOICES: &[(PlaylistSortKey, &str)] = &[
+    (PlaylistSortKey::Artist, "Artist"),
+    (PlaylistSortKey::Album, "Album"),
+    (PlaylistSortKey::DiscTrack, "Disc / Track number"),
+    (PlaylistSortKey::Title, "Title"),
+    (PlaylistSortKey::Duration, "Duration"),
+    (PlaylistSortKey::Path, "Path"),
+    (PlaylistSortKey::DateAdded, "Date added"),
+];
+
+#[derive(MockComponent)]
+pub struct PlaylistSortPopup {
+    component: Table,
+    config: SharedTuiSettings,
+}
+
+impl PlaylistSortPopup {
+    pub fn new(config: SharedTuiSettings) -> Self {
+        let component = {
+            let config = config.read();
+
+            let mut table = TableBuilder::default();
+            for (_, name) in SORT_CHOICES {
+                table.add_col(TextSpan::new(format!("Sort by {name}")));
+                table.add_row();
+            }
+            table.add_col(TextSpan::new("Remove duplicates"));
+
+            Table::default()
+                .background(config.settings.theme.playlist_background())
+                .foreground(config.settings.theme.playlist_foreground())
+                .borders(
+                    Borders::default()
+                        .color(config.settings.theme.playlist_border())
+                        .modifiers(BorderType::Rounded),
+                )
+                .title(
+                    " Enter: ascending, r: descending, Esc to close ",
+                    Alignment::Left,
+                )
+                .scroll(false)
+                .highlighted_color(config.settings.theme.playlist_highlight())
+                .highlighted_str(&config.settings.theme.style.playlist.highlight_symbol)
+                .rewind(true)
+                .step(4)
+                .row_height(1)
+                .widths(&[100])
+                .table(table.build())
+        };
+
+        Self { component, config }
+    }
+
+    /// Get the message for the currently selected entry.
+    fn submit(&self, descending: bool) -> Option<Msg> {
+        let State::One(StateValue::Usize(index)) = self.state() else {
+            return None;
+        };
+
+        let msg = match SORT_CHOICES.get(index) {
+            Some((key, _)) => PLMsg::Sort(PlaylistSort {
+                key: *key,
+                descending,
+            }),
+            None => PLMsg::Deduplicate,
+        };
+
+        Some(Msg::Playlist(msg))
+    }
+}
+
+impl Component<Msg, UserEvent> for PlaylistSortPopup {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        let config = self.config.clone();
+        let keys = &config.read().settings.keys;
+        let cmd_result = match ev {
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                return Some(Msg::Playlist(PLMsg::SortPopupCloseCancel))
+            }
+            Event::Keyboard(keyevent) if keyevent == keys.quit.get() => {
+                return Some(Msg::Playlist(PLMsg::SortPopupCloseCancel))
+            }
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => self.perform(Cmd::Move(Direction::Down)),
+            Event::Keyboard(keyevent) if keyevent == keys.navigation_keys.down.get() => {
+                self.perform(Cmd::Move(Direction::Down))
+            }
+            Event::Keyboard(keyevent) if keyevent == keys.navigation_keys.up.get() => {
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(keyevent) if keyevent == keys.navigation_keys.goto_top.get() => {
+                self.perform(Cmd::GoTo(Position::Begin))
+            }
+            Event::Keyboard(keyevent) if keyevent == keys.navigation_keys.goto_bottom.get() => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter,
+                modifiers: KeyModifiers::NONE,
+            }) => return self.submit(false),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('r'),
+                modifiers: KeyModifiers::NONE,
+            }) => return self.submit(true),
+            _ => CmdResult::None,
+        };
+        match cmd_result {
+            CmdResult::None => None,
+            _ => Some(Msg::ForceRedraw),
+        }
+    }
+}
+
+impl Model {
+    pub fn mount_playlist_sort(&mut self) {
+        assert!(self
+            .app
+            .remount(
+                Id::PlaylistSortPopup,
+                Box::new(PlaylistSortPopup::new(self.config_tui.clone())),
+                vec![]
+            )
+            .is_ok());
+        assert!(self.app.active(&Id::PlaylistSortPopup).is_ok());
+        if let Err(e) = self.update_photo() {
+            self.mount_error_popup(e.context("update_photo"));
+        }
+    }
+
+    pub fn umount_playlist_sort(&mut self) {
+        if self.app.mounted(&Id::PlaylistSortPopup) {
+            assert!(self.app.umount(&Id::PlaylistSortPopup).is_ok());
+        }
+        if let Err(e) = self.update_photo() {
+            self.mount_error_popup(e.context("update_photo"));
+        }
+    }
+}
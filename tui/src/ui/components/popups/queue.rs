@@ -0,0 +1,265 @@
+use std::path::Path;
+
+use anyhow::Result;
+use termusiclib::config::SharedTuiSettings;
+use termusiclib::ids::Id;
+use termusiclib::player::playlist_helpers::{
+    PlaylistQueueRemoveTrack, PlaylistQueueTracks, PlaylistTrackSource,
+};
+use termusiclib::types::{Msg, QueueMsg};
+use tui_realm_stdlib::Table;
+use tuirealm::{
+    command::{Cmd, CmdResult, Direction, Position},
+    event::{Key, KeyEvent, KeyModifiers},
+    props::{Alignment, BorderType, Borders, TableBuilder, TextSpan},
+    Component, Event, MockComponent, State, StateValue,
+};
+
+use crate::ui::model::{Model, UserEvent};
+use crate::ui::tui_cmd::{PlaylistCmd, TuiCmd};
+
+#[derive(MockComponent)]
+pub struct QueueTablePopup {
+    component: Table,
+    config: SharedTuiSettings,
+}
+
+impl QueueTablePopup {
+    pub fn new(config: SharedTuiSettings) -> Self {
+        let component = {
+            let config = config.read();
+            Table::default()
+                .background(config.settings.theme.playlist_background())
+                .foreground(config.settings.theme.playlist_foreground())
+                .borders(
+                    Borders::default()
+                        .color(config.settings.theme.playlist_border())
+                        .modifiers(BorderType::Rounded),
+                )
+                .title(
+                    " Play next queue: d: remove, D: clear, Esc to close ",
+                    Alignment::Left,
+                )
+                .scroll(true)
+                .highlighted_color(config.settings.theme.playlist_highlight())
+                .highlighted_str(&config.settings.theme.style.playlist.highlight_symbol)
+                .rewind(false)
+                .step(4)
+                .row_height(1)
+                .headers(&[" Duration ", " Artist ", " Title "])
+                .column_spacing(2)
+                .widths(&[12, 28, 60])
+                .table(
+                    TableBuilder::default()
+                        .add_col(TextSpan::from("Loading..."))
+                        .build(),
+                )
+        };
+
+        Self { component, config }
+    }
+
+    /// Get the currently selected index, if any.
+    fn selected(&self) -> Option<usize> {
+        if let State::One(StateValue::Usize(index)) = self.state() {
+            return Some(index);
+        }
+
+        None
+    }
+}
+
+impl Component<Msg, UserEvent> for QueueTablePopup {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        let config = self.config.clone();
+        let keys = &config.read().settings.keys;
+        let cmd_result = match ev {
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                return Some(Msg::Queue(QueueMsg::PopupCloseCancel))
+            }
+            Event::Keyboard(keyevent) if keyevent == keys.quit.get() => {
+                return Some(Msg::Queue(QueueMsg::PopupCloseCancel))
+            }
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => self.perform(Cmd::Move(Direction::Down)),
+
+            Event::Keyboard(keyevent) if keyevent == keys.navigation_keys.down.get() => {
+                self.perform(Cmd::Move(Direction::Down))
+            }
+
+            Event::Keyboard(keyevent) if keyevent == keys.navigation_keys.up.get() => {
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::PageDown,
+                ..
+            }) => self.perform(Cmd::Scroll(Direction::Down)),
+            Event::Keyboard(KeyEvent {
+                code: Key::PageUp, ..
+            }) => self.perform(Cmd::Scroll(Direction::Up)),
+            Event::Keyboard(keyevent) if keyevent == keys.navigation_keys.goto_top.get() => {
+                self.perform(Cmd::GoTo(Position::Begin))
+            }
+            Event::Keyboard(keyevent) if keyevent == keys.navigation_keys.goto_bottom.get() => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('d'),
+                modifiers: KeyModifiers::NONE,
+            }) => {
+                if let Some(index) = self.selected() {
+                    return Some(Msg::Queue(QueueMsg::Delete(index)));
+                }
+                CmdResult::None
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('D'),
+                modifiers: KeyModifiers::SHIFT,
+            }) => return Some(Msg::Queue(QueueMsg::DeleteAll)),
+            _ => CmdResult::None,
+        };
+        match cmd_result {
+            CmdResult::None => None,
+            _ => Some(Msg::ForceRedraw),
+        }
+    }
+}
+
+impl Model {
+    pub fn mount_queue(&mut self) {
+        assert!(self
+            .app
+            .remount(
+                Id::QueuePopup,
+                Box::new(QueueTablePopup::new(self.config_tui.clone())),
+                vec![]
+            )
+            .is_ok());
+        assert!(self.app.active(&Id::QueuePopup).is_ok());
+        self.update_queue_table();
+        if let Err(e) = self.update_photo() {
+            self.mount_error_popup(e.context("update_photo"));
+        }
+    }
+
+    pub fn umount_queue(&mut self) {
+        if self.app.mounted(&Id::QueuePopup) {
+            assert!(self.app.umount(&Id::QueuePopup).is_ok());
+        }
+        if let Err(e) = self.update_photo() {
+            self.mount_error_popup(e.context("update_photo"));
+        }
+    }
+
+    /// Apply the new "play next" queue from the server.
+    ///
+    /// # Errors
+    ///
+    /// see [`set_queue_from_grpc`](crate::ui::model::Playback::set_queue_from_grpc)
+    pub fn handle_queue_changed(&mut self, queue: &PlaylistQueueTracks) -> Result<()> {
+        self.playback
+            .set_queue_from_grpc(queue, &self.podcast.db_podcast)?;
+
+        self.playlist_update_title();
+        self.update_queue_table();
+
+        Ok(())
+    }
+
+    fn update_queue_table(&mut self) {
+        if !self.app.mounted(&Id::QueuePopup) {
+            return;
+        }
+
+        let mut table: TableBuilder = TableBuilder::default();
+        for (idx, track) in self.playback.queue().iter().enumerate() {
+            if idx > 0 {
+                table.add_row();
+            }
+
+            let duration_str = if let Some(dur) = track.duration_str_short() {
+                format!("[{dur:^7.7}]")
+            } else {
+                "[--:--]".to_string()
+            };
+
+            table
+                .add_col(TextSpan::new(duration_str))
+                .add_col(TextSpan::new(track.artist().unwrap_or_default()))
+                .add_col(TextSpan::new(
+                    track.title().map_or_else(|| track.id_str(), Into::into),
+                ));
+        }
+        if self.playback.queue().is_empty() {
+            table.add_col(TextSpan::from("Empty queue"));
+        }
+        let table = table.build();
+
+        self.app
+            .attr(
+                &Id::QueuePopup,
+                tuirealm::Attribute::Content,
+                tuirealm::AttrValue::Table(table),
+            )
+            .ok();
+    }
+
+    /// Add a file or all tracks directly in a directory to the "play next" queue.
+    pub fn queue_add(&mut self, path: &Path) {
+        if !path.exists() {
+            return;
+        }
+
+        let tracks = if path.is_dir() {
+            Self::library_dir_children(path)
+                .into_iter()
+                .map(PlaylistTrackSource::Path)
+                .collect()
+        } else {
+            vec![PlaylistTrackSource::Path(
+                path.to_string_lossy().to_string(),
+            )]
+        };
+
+        self.command(TuiCmd::Playlist(PlaylistCmd::QueueAdd(
+            PlaylistQueueTracks { tracks },
+        )));
+    }
+
+    /// Add the playlist track at `index` to the "play next" queue.
+    pub fn queue_add_from_playlist(&mut self, index: usize) {
+        let Some(track) = self.playback.playlist.tracks().get(index) else {
+            error!("Track {index} not in playlist!");
+            return;
+        };
+
+        self.command(TuiCmd::Playlist(PlaylistCmd::QueueAdd(
+            PlaylistQueueTracks {
+                tracks: vec![track.as_track_source()],
+            },
+        )));
+    }
+
+    /// Remove the track at `index` from the "play next" queue.
+    pub fn queue_delete(&mut self, index: usize) {
+        let Some(track) = self.playback.queue().get(index) else {
+            error!("Track {index} not in queue!");
+            return;
+        };
+
+        self.command(TuiCmd::Playlist(PlaylistCmd::QueueRemove(
+            PlaylistQueueRemoveTrack {
+                at_index: u64::try_from(index).unwrap(),
+                id: track.as_track_source(),
+            },
+        )));
+    }
+
+    pub fn queue_clear(&mut self) {
+        self.command(TuiCmd::Playlist(PlaylistCmd::QueueClear));
+    }
+}
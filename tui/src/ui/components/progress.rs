@@ -1,9 +1,11 @@
+use std::fmt::Write as _;
 use std::time::Duration;
 
 use termusiclib::config::TuiOverlay;
 use termusiclib::ids::Id;
 use termusiclib::track::DurationFmtShort;
 use termusiclib::track::MediaTypesSimple;
+use termusiclib::track::Track;
 use termusiclib::types::Msg;
 use tui_realm_stdlib::ProgressBar;
 use tuirealm::props::{Alignment, BorderType, Borders, PropPayload, PropValue};
@@ -39,6 +41,22 @@ impl Progress {
     }
 }
 
+/// Display a pitch in cents as semitones and cents, like `+1st +30ct`
+struct PitchFmt(i32);
+
+impl std::fmt::Display for PitchFmt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let semitones = self.0 / 100;
+        let cents = self.0 % 100;
+
+        match (semitones, cents) {
+            (0, cents) => write!(f, "{cents:+}ct"),
+            (semitones, 0) => write!(f, "{semitones:+}st"),
+            (semitones, cents) => write!(f, "{semitones:+}st {cents:+}ct"),
+        }
+    }
+}
+
 impl Component<Msg, UserEvent> for Progress {
     fn on(&mut self, _ev: Event<UserEvent>) -> Option<Msg> {
         None
@@ -66,25 +84,33 @@ impl Model {
         } else {
             "False"
         };
+        let pitch = config_server.settings.player.pitch;
+        let pitch = if pitch == 0 {
+            String::new()
+        } else {
+            format!("| Pitch: {} ", PitchFmt(pitch))
+        };
         let mut progress_title = String::new();
         if let Some(track) = self.playback.current_track() {
             match track.media_type() {
                 MediaTypesSimple::Music | MediaTypesSimple::LiveRadio => {
                     progress_title = format!(
-                        " Status: {} | Volume: {} | Speed: {:^.1} | Gapless: {} ",
+                        " Status: {} | Volume: {} | Speed: {:^.1} {}| Gapless: {} ",
                         self.playback.status(),
                         config_server.settings.player.volume,
                         config_server.settings.player.speed as f32 / 10.0,
+                        pitch,
                         gapless,
                     );
                 }
                 MediaTypesSimple::Podcast => {
                     progress_title = format!(
-                        " Status: {} {:^.20} | Volume: {} | Speed: {:^.1} | Gapless: {} ",
+                        " Status: {} {:^.20} | Volume: {} | Speed: {:^.1} {}| Gapless: {} ",
                         self.playback.status(),
                         track.title().unwrap_or("Unknown title"),
                         config_server.settings.player.volume,
                         config_server.settings.player.speed as f32 / 10.0,
+                        pitch,
                         gapless,
                     );
                 }
@@ -131,6 +157,24 @@ impl Model {
         new_prog.clamp(0.0, 1.0)
     }
 
+    /// Update the progress text with the current A-B loop state, without waiting for the next progress update.
+    pub fn progress_update_ab_loop(&mut self) {
+        let total_duration = self
+            .playback
+            .current_track()
+            .and_then(Track::duration)
+            .unwrap_or_default();
+        let progress = self
+            .app
+            .query(&Id::Progress, Attribute::Value)
+            .ok()
+            .flatten()
+            .map_or(0.0, |v| v.unwrap_payload().unwrap_one().unwrap_f64());
+
+        self.progress_set(progress, total_duration);
+        self.force_redraw();
+    }
+
     fn progress_set(&mut self, progress: f64, total_duration: Duration) {
         self.app
             .attr(
@@ -140,17 +184,44 @@ impl Model {
             )
             .ok();
 
+        let mut text = format!(
+            "{}    -    {}",
+            DurationFmtShort(self.playback.current_track_pos()),
+            DurationFmtShort(total_duration),
+        );
+
+        if let Some(range) = self.playback.ab_loop() {
+            let _ = write!(
+                text,
+                "    [A-B: {} - {}]",
+                DurationFmtShort(range.start),
+                DurationFmtShort(range.end)
+            );
+        } else if let Some(mark_a) = self.playback.ab_loop_mark_a() {
+            let _ = write!(text, "    [A: {} - ?]", DurationFmtShort(mark_a));
+        }
+
+        if let Some(skipped) = self.playback.silence_skipped() {
+            let _ = write!(text, "    [Silence skipped: {}]", DurationFmtShort(skipped));
+        }
+
         self.app
-            .attr(
-                &Id::Progress,
-                Attribute::Text,
-                AttrValue::String(format!(
-                    "{}    -    {}",
-                    DurationFmtShort(self.playback.current_track_pos()),
-                    DurationFmtShort(total_duration),
-                )),
-            )
+            .attr(&Id::Progress, Attribute::Text, AttrValue::String(text))
             .ok();
         // self.force_redraw();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::PitchFmt;
+
+    #[test]
+    fn should_format_pitch() {
+        assert_eq!(PitchFmt(0).to_string(), "+0ct");
+        assert_eq!(PitchFmt(30).to_string(), "+30ct");
+        assert_eq!(PitchFmt(200).to_string(), "+2st");
+        assert_eq!(PitchFmt(130).to_string(), "+1st +30ct");
+        assert_eq!(PitchFmt(-130).to_string(), "-1st -30ct");
+    }
+}
@@ -0,0 +1,173 @@
+//! SPDX-License-Identifier: MIT
+
+use termusiclib::config::TuiOverlay;
+use termusiclib::ids::Id;
+use termusiclib::types::Msg;
+use tuirealm::command::{Cmd, CmdResult};
+use tuirealm::props::{AttrValue, Attribute, Borders, Color, PropPayload, PropValue, Props};
+use tuirealm::ratatui::layout::Rect;
+use tuirealm::ratatui::style::Style;
+use tuirealm::ratatui::widgets::{Bar, BarChart, BarGroup, Block, BorderType};
+use tuirealm::{Component, Event, Frame, MockComponent, State};
+
+use crate::ui::model::UserEvent;
+use crate::ui::Model;
+
+/// The maximal value a bar can have, bands are scaled to this
+const BAR_MAX: u64 = 100;
+
+/// Bar-graph of the current spectrum data
+pub struct Spectrum {
+    props: Props,
+    /// The current band magnitudes, from lowest to highest frequency, in `0.0..=1.0`
+    bands: Vec<f32>,
+    foreground: Color,
+    background: Color,
+    border: Color,
+}
+
+impl Spectrum {
+    pub fn new(config: &TuiOverlay) -> Self {
+        Self {
+            props: Props::default(),
+            bands: Vec::new(),
+            foreground: config.settings.theme.progress_foreground(),
+            background: config.settings.theme.progress_background(),
+            border: config.settings.theme.progress_border(),
+        }
+    }
+}
+
+/// Reduce `bands` to at most `max` bars, by taking the maximum of the grouped bands.
+fn fit_bands(bands: &[f32], max: usize) -> Vec<f32> {
+    if max == 0 {
+        return Vec::new();
+    }
+    if bands.len() <= max {
+        return bands.to_vec();
+    }
+
+    (0..max)
+        .map(|i| {
+            let start = i * bands.len() / max;
+            let end = ((i + 1) * bands.len() / max).max(start + 1);
+            bands[start..end].iter().copied().fold(0.0, f32::max)
+        })
+        .collect()
+}
+
+impl MockComponent for Spectrum {
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    fn view(&mut self, frame: &mut Frame<'_>, area: Rect) {
+        let block = Block::default()
+            .borders(Borders::default().sides)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(self.border))
+            .style(Style::default().bg(self.background));
+        let inner = block.inner(area);
+
+        // each bar has a gap of 1 to the next, so at most half the width can be bars
+        let bars = fit_bands(&self.bands, usize::from(inner.width).div_ceil(2));
+        let bar_width = if bars.is_empty() {
+            1
+        } else {
+            let bars_len = u16::try_from(bars.len()).unwrap_or(u16::MAX);
+            (inner.width.saturating_sub(bars_len - 1) / bars_len).max(1)
+        };
+
+        let data: Vec<Bar<'_>> = bars
+            .iter()
+            .map(|v| {
+                Bar::default()
+                    .value((v.clamp(0.0, 1.0) * BAR_MAX as f32) as u64)
+                    .text_value(String::new())
+            })
+            .collect();
+
+        let chart = BarChart::default()
+            .block(block)
+            .data(BarGroup::default().bars(&data))
+            .max(BAR_MAX)
+            .bar_width(bar_width)
+            .bar_gap(1)
+            .bar_style(Style::default().fg(self.foreground));
+
+        frame.render_widget(chart, area);
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.props.get(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        match (attr, value) {
+            (Attribute::Value, AttrValue::Payload(PropPayload::Vec(values))) => {
+                self.bands = values.into_iter().map(PropValue::unwrap_f32).collect();
+            }
+            (attr, value) => self.props.set(attr, value),
+        }
+    }
+
+    fn state(&self) -> State {
+        State::None
+    }
+
+    fn perform(&mut self, _cmd: Cmd) -> CmdResult {
+        CmdResult::None
+    }
+}
+
+impl Component<Msg, UserEvent> for Spectrum {
+    fn on(&mut self, _ev: Event<UserEvent>) -> Option<Msg> {
+        None
+    }
+}
+
+impl Model {
+    /// Set new spectrum `bands` to be displayed on the next draw.
+    pub fn spectrum_update(&mut self, bands: Vec<f32>) {
+        if self.config_tui.read().settings.spectrum.hidden {
+            return;
+        }
+
+        self.app
+            .attr(
+                &Id::Spectrum,
+                Attribute::Value,
+                AttrValue::Payload(PropPayload::Vec(
+                    bands.into_iter().map(PropValue::F32).collect(),
+                )),
+            )
+            .ok();
+        self.redraw = true;
+    }
+
+    pub fn spectrum_reload(&mut self) {
+        assert!(self
+            .app
+            .remount(
+                Id::Spectrum,
+                Box::new(Spectrum::new(&self.config_tui.read())),
+                Vec::new()
+            )
+            .is_ok());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fit_bands;
+
+    #[test]
+    fn should_fit_bands() {
+        let bands = [0.1, 0.5, 0.2, 0.9, 0.3, 0.0];
+        assert_eq!(fit_bands(&bands, 10), bands.to_vec());
+        assert_eq!(fit_bands(&bands, 3), vec![0.5, 0.9, 0.3]);
+        assert_eq!(fit_bands(&bands, 1), vec![0.9]);
+        assert!(fit_bands(&bands, 0).is_empty());
+    }
+}
@@ -23,10 +23,18 @@
  */
 
 /// -- modules
+mod te_batch;
 mod te_counter_delete_lyric;
+mod te_cover;
 mod te_footer;
 mod te_input;
+mod te_keys;
+mod te_loudness;
+mod te_lyric_sync;
+mod te_musicbrainz;
+mod te_pattern;
 mod te_select_lyric;
+mod te_sidecar;
 mod te_table_lyric_options;
 mod te_textarea_lyric;
 mod te_track;
@@ -34,8 +42,12 @@ mod update;
 mod view;
 
 // -- exports
+pub use te_batch::BatchEdit;
 pub use te_counter_delete_lyric::TECounterDelete;
 pub use te_input::*;
+pub use te_lyric_sync::LyricSync;
+pub use te_musicbrainz::MusicBrainzLookup;
+pub use te_pattern::PatternPreview;
 pub use te_select_lyric::TESelectLyric;
 pub use te_table_lyric_options::TETableLyricOptions;
 pub use te_textarea_lyric::TETextareaLyric;
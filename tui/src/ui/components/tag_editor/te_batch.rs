@@ -0,0 +1,386 @@
+use std::path::{Path, PathBuf};
+
+use termusiclib::config::SharedTuiSettings;
+use termusiclib::ids::{Id, IdTagEditor};
+use termusiclib::songtag::batch::{self, BatchChange, BatchField, BatchFile, FieldEdit};
+use termusiclib::types::{BatchTagMsg, Msg, TEMsg, TFMsg};
+use tui_realm_stdlib::Table;
+use tuirealm::command::CmdResult;
+use tuirealm::event::{Key, KeyEvent, KeyModifiers};
+use tuirealm::props::{TableBuilder, TextSpan};
+use tuirealm::{AttrValue, Attribute, Component, Event, MockComponent, State, StateValue};
+
+use crate::ui::model::UserEvent;
+use crate::ui::Model;
+
+use super::te_keys::action_key_msg;
+use super::te_musicbrainz::{lookup_table, perform_navigation};
+
+/// State of editing the tags of many files at once
+#[derive(Debug, Default)]
+pub struct BatchEdit {
+    files: Vec<BatchFile>,
+    /// The changes shown in the preview, if open
+    changes: Vec<BatchChange>,
+}
+
+impl BatchEdit {
+    /// Read the tags of all `paths`, files which cannot be read are skipped
+    pub fn read(paths: &[PathBuf]) -> Self {
+        let files = paths
+            .iter()
+            .filter_map(|path| {
+                BatchFile::read(path)
+                    .inspect_err(|err| warn!("Failed to read \"{}\": {err:#}", path.display()))
+                    .ok()
+            })
+            .collect();
+
+        Self {
+            files,
+            changes: Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn paths(&self) -> Vec<PathBuf> {
+        self.files.iter().map(|v| v.path.clone()).collect()
+    }
+
+    pub fn files(&self) -> &[BatchFile] {
+        &self.files
+    }
+}
+
+pub(super) fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|v| v.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Build the content of a table with the columns File, Field, Current and New for `changes`
+pub(super) fn changes_table(changes: &[BatchChange]) -> tuirealm::props::Table {
+    let mut table = TableBuilder::default();
+    let mut last_path = None;
+    for (idx, change) in changes.iter().enumerate() {
+        if idx > 0 {
+            table.add_row();
+        }
+        // only name the file once, for readability
+        let file = if last_path == Some(&change.path) {
+            String::new()
+        } else {
+            file_name(&change.path)
+        };
+        last_path = Some(&change.path);
+
+        table
+            .add_col(TextSpan::new(file).bold())
+            .add_col(TextSpan::new(change.field.name()))
+            .add_col(TextSpan::new(change.current.as_deref().unwrap_or("-")))
+            .add_col(match &change.new {
+                Some(new) => TextSpan::new(new).fg(tuirealm::ratatui::style::Color::LightYellow),
+                None => TextSpan::new("removed").italic(),
+            });
+    }
+    if changes.is_empty() {
+        table.add_col(TextSpan::from("Nothing would change."));
+    }
+
+    table.build()
+}
+
+/// The files being edited with their current values, in place of the lyric section
+#[derive(MockComponent)]
+pub struct BatchFilesTable {
+    component: Table,
+    config: SharedTuiSettings,
+}
+
+impl BatchFilesTable {
+    pub fn new(config: SharedTuiSettings, count: usize) -> Self {
+        let component = lookup_table(
+            &config,
+            &format!(" Editing {count} files "),
+            &[" File ", " Artist ", " Title ", " Album ", " Genre "],
+            &[28, 18, 22, 18, 14],
+        );
+
+        Self { component, config }
+    }
+}
+
+impl Component<Msg, UserEvent> for BatchFilesTable {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        let config = self.config.clone();
+        let keys = &config.read().settings.keys;
+        if let Some(msg) = action_key_msg(&keys.tag_editor_keys, &ev) {
+            return Some(msg);
+        }
+        let cmd_result = match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Tab,
+                modifiers: KeyModifiers::NONE,
+            }) => return Some(Msg::TagEditor(TEMsg::TEFocus(TFMsg::BatchFilesBlurDown))),
+            Event::Keyboard(KeyEvent {
+                code: Key::BackTab,
+                modifiers: KeyModifiers::SHIFT,
+            }) => return Some(Msg::TagEditor(TEMsg::TEFocus(TFMsg::BatchFilesBlurUp))),
+            Event::Keyboard(keyevent) if keyevent == keys.config_keys.save.get() => {
+                return Some(Msg::TagEditor(TEMsg::TEBatch(BatchTagMsg::Preview)))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => return Some(Msg::TagEditor(TEMsg::TEBatch(BatchTagMsg::Preview))),
+            Event::Keyboard(keyevent) if keyevent == keys.escape.get() => {
+                return Some(Msg::TagEditor(TEMsg::TagEditorClose))
+            }
+            ev => perform_navigation(&mut self.component, &config, &ev),
+        };
+        match cmd_result {
+            CmdResult::None => None,
+            _ => Some(Msg::ForceRedraw),
+        }
+    }
+}
+
+/// The changes the field values would make to every file
+#[derive(MockComponent)]
+pub struct BatchPreviewTable {
+    component: Table,
+    config: SharedTuiSettings,
+}
+
+impl BatchPreviewTable {
+    pub fn new(config: SharedTuiSettings) -> Self {
+        let component = lookup_table(
+            &config,
+            " Changes: Enter to write to all files, Esc to go back ",
+            &[" File ", " Field ", " Current ", " New "],
+            &[30, 12, 29, 29],
+        );
+
+        Self { component, config }
+    }
+}
+
+impl Component<Msg, UserEvent> for BatchPreviewTable {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        let config = self.config.clone();
+        let cmd_result = match ev {
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                return Some(Msg::TagEditor(TEMsg::TEBatch(BatchTagMsg::PreviewClose)))
+            }
+            Event::Keyboard(keyevent) if keyevent == config.read().settings.keys.quit.get() => {
+                return Some(Msg::TagEditor(TEMsg::TEBatch(BatchTagMsg::PreviewClose)))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => return Some(Msg::TagEditor(TEMsg::TEBatch(BatchTagMsg::Write))),
+            ev => perform_navigation(&mut self.component, &config, &ev),
+        };
+        match cmd_result {
+            CmdResult::None => None,
+            _ => Some(Msg::ForceRedraw),
+        }
+    }
+}
+
+impl Model {
+    /// Handle [`BatchTagMsg`] events
+    pub fn te_update_batch(&mut self, msg: BatchTagMsg) {
+        match msg {
+            BatchTagMsg::Preview => self.te_batch_preview(),
+            BatchTagMsg::PreviewClose => self.umount_batch_preview(),
+            BatchTagMsg::Write => self.te_batch_write(),
+            BatchTagMsg::Done(written, failed) => self.te_batch_done(written, failed),
+        }
+    }
+
+    /// Filter the tag editor events which only work on a single file, when editing many files.
+    ///
+    /// Returns the event if it should be handled as usual.
+    pub fn te_batch_filter(&mut self, msg: TEMsg) -> Option<TEMsg> {
+        if self.te_batch.is_none() {
+            return Some(msg);
+        }
+
+        match msg {
+            TEMsg::TagEditorRun(_)
+            | TEMsg::TagEditorRunBatch(_)
+            | TEMsg::TagEditorClose
+            | TEMsg::TEFocus(_)
+            | TEMsg::TEBatch(_)
+            | TEMsg::TEPattern(_)
+            | TEMsg::TECoverArt(_) => Some(msg),
+            TEMsg::TERename | TEMsg::TESearch => {
+                self.te_batch_preview();
+                None
+            }
+            _ => {
+                self.show_message_timeout_label_help(
+                    " Not available when editing multiple files ",
+                    None,
+                    None,
+                    None,
+                );
+                None
+            }
+        }
+    }
+
+    /// Show the values of all files and the values they have in common in the inputs
+    pub fn init_by_batch(&mut self) {
+        let Some(edit) = &self.te_batch else {
+            return;
+        };
+
+        for (field, id) in BatchField::ALL.into_iter().zip([
+            IdTagEditor::InputArtist,
+            IdTagEditor::InputTitle,
+            IdTagEditor::InputAlbum,
+            IdTagEditor::InputGenre,
+        ]) {
+            self.app
+                .attr(
+                    &Id::TagEditor(id),
+                    Attribute::Value,
+                    AttrValue::String(batch::common_value(&edit.files, field)),
+                )
+                .ok();
+        }
+
+        let mut table = TableBuilder::default();
+        for (idx, file) in edit.files.iter().enumerate() {
+            if idx > 0 {
+                table.add_row();
+            }
+            table.add_col(TextSpan::new(file_name(&file.path)).bold());
+            for field in BatchField::ALL {
+                table.add_col(TextSpan::new(file.get(field).unwrap_or("-")));
+            }
+        }
+
+        self.app
+            .attr(
+                &Id::TagEditor(IdTagEditor::BatchFiles),
+                Attribute::Content,
+                AttrValue::Table(table.build()),
+            )
+            .ok();
+    }
+
+    /// Show the changes the current input values would make to all files
+    fn te_batch_preview(&mut self) {
+        let input = |id: IdTagEditor| match self.app.state(&Id::TagEditor(id)) {
+            Ok(State::One(StateValue::String(v))) => FieldEdit::from_input(&v),
+            _ => FieldEdit::Keep,
+        };
+        let edits = [
+            (BatchField::Artist, input(IdTagEditor::InputArtist)),
+            (BatchField::Title, input(IdTagEditor::InputTitle)),
+            (BatchField::Album, input(IdTagEditor::InputAlbum)),
+            (BatchField::Genre, input(IdTagEditor::InputGenre)),
+        ];
+        let Some(edit) = self.te_batch.as_mut() else {
+            return;
+        };
+
+        edit.changes = batch::preview(&edit.files, &edits);
+        let table = changes_table(&edit.changes);
+
+        assert!(self
+            .app
+            .remount(
+                Id::TagEditor(IdTagEditor::BatchPreview),
+                Box::new(BatchPreviewTable::new(self.config_tui.clone())),
+                vec![]
+            )
+            .is_ok());
+        self.app
+            .attr(
+                &Id::TagEditor(IdTagEditor::BatchPreview),
+                Attribute::Content,
+                AttrValue::Table(table),
+            )
+            .ok();
+        assert!(self
+            .app
+            .active(&Id::TagEditor(IdTagEditor::BatchPreview))
+            .is_ok());
+    }
+
+    /// Write the previewed changes to all files.
+    ///
+    /// This runs on a separate thread, reporting the result via [`BatchTagMsg::Done`].
+    fn te_batch_write(&mut self) {
+        let Some(edit) = self.te_batch.as_mut() else {
+            return;
+        };
+        let changes = std::mem::take(&mut edit.changes);
+        self.umount_batch_preview();
+        if changes.is_empty() {
+            return;
+        }
+
+        let tx = self.tx_to_main.clone();
+        self.show_message_timeout_label_help(" Writing tags... ", None, None, None);
+
+        std::thread::spawn(move || {
+            let mut files: Vec<&Path> = changes.iter().map(|v| v.path.as_path()).collect();
+            files.dedup();
+            let failed = batch::write_changes(&changes);
+            for (path, err) in &failed {
+                warn!("Failed to write tags to \"{}\": {err:#}", path.display());
+            }
+
+            let _ = tx.send(Msg::TagEditor(TEMsg::TEBatch(BatchTagMsg::Done(
+                files.len() - failed.len(),
+                failed.len(),
+            ))));
+        });
+    }
+
+    /// Show the result of [`Model::te_batch_write`] and re-read all files, which have likely changed.
+    fn te_batch_done(&mut self, written: usize, failed: usize) {
+        let Some(edit) = &self.te_batch else {
+            return;
+        };
+        self.te_batch = Some(BatchEdit::read(&edit.paths()));
+        self.init_by_batch();
+
+        let msg = if failed == 0 {
+            format!(" Tags written to {written} files. ")
+        } else {
+            format!(" Tags written to {written} files, {failed} failed, see log for details. ")
+        };
+        self.show_message_timeout_label_help(msg, None, None, None);
+    }
+
+    fn umount_batch_preview(&mut self) {
+        if self.app.mounted(&Id::TagEditor(IdTagEditor::BatchPreview)) {
+            assert!(self
+                .app
+                .umount(&Id::TagEditor(IdTagEditor::BatchPreview))
+                .is_ok());
+            self.app
+                .active(&Id::TagEditor(IdTagEditor::InputArtist))
+                .ok();
+        }
+    }
+
+    /// Close the batch editing state and its components, if open
+    pub fn umount_batch(&mut self) {
+        self.te_batch = None;
+        self.umount_batch_preview();
+        if self.app.mounted(&Id::TagEditor(IdTagEditor::BatchFiles)) {
+            assert!(self
+                .app
+                .umount(&Id::TagEditor(IdTagEditor::BatchFiles))
+                .is_ok());
+        }
+    }
+}
@@ -33,6 +33,7 @@ use tuirealm::{
     AttrValue, Attribute, Component, Event, Frame, MockComponent, Props, State, StateValue,
 };
 
+use super::te_keys::action_key_msg;
 use crate::ui::model::{Model, UserEvent};
 
 /// ## Counter
@@ -243,6 +244,9 @@ impl TECounterDelete {
 impl Component<Msg, UserEvent> for TECounterDelete {
     fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
         let keys = &self.config.read().settings.keys;
+        if let Some(msg) = action_key_msg(&keys.tag_editor_keys, &ev) {
+            return Some(msg);
+        }
         // Get command
         let _cmd = match ev {
             Event::Keyboard(keyevent) if keyevent == keys.config_keys.save.get() => {
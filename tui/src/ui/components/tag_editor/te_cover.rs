@@ -0,0 +1,428 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use lofty::picture::Picture;
+use termusiclib::config::SharedTuiSettings;
+use termusiclib::ids::{Id, IdTagEditor};
+use termusiclib::songtag::cover::{self, CoverKind};
+use termusiclib::track::Track;
+use termusiclib::types::{CoverArtMsg, Msg, TEMsg};
+use tui_realm_stdlib::{Input, Table};
+use tuirealm::command::{Cmd, CmdResult, Direction, Position};
+use tuirealm::event::{Key, KeyEvent, KeyModifiers};
+use tuirealm::props::{Alignment, BorderType, Borders, InputType, TableBuilder, TextSpan};
+use tuirealm::{AttrValue, Attribute, Component, Event, MockComponent, State, StateValue};
+
+use crate::ui::model::UserEvent;
+use crate::ui::Model;
+
+use super::te_musicbrainz::{lookup_table, perform_navigation};
+
+/// The cover art actions, in the order they are listed
+const ACTIONS: [(&str, CoverArtMsg); 8] = [
+    (
+        "Embed image file as front cover",
+        CoverArtMsg::Choose(CoverKind::Front),
+    ),
+    (
+        "Embed image file as back cover",
+        CoverArtMsg::Choose(CoverKind::Back),
+    ),
+    (
+        "Extract front cover to file",
+        CoverArtMsg::Extract(CoverKind::Front),
+    ),
+    (
+        "Extract back cover to file",
+        CoverArtMsg::Extract(CoverKind::Back),
+    ),
+    (
+        "Remove front cover",
+        CoverArtMsg::Remove(Some(CoverKind::Front)),
+    ),
+    (
+        "Remove back cover",
+        CoverArtMsg::Remove(Some(CoverKind::Back)),
+    ),
+    ("Remove all pictures", CoverArtMsg::Remove(None)),
+    ("Shrink oversized pictures", CoverArtMsg::Shrink),
+];
+
+/// The cover art actions with the current pictures
+#[derive(MockComponent)]
+pub struct CoverArtTable {
+    component: Table,
+    config: SharedTuiSettings,
+}
+
+impl CoverArtTable {
+    pub fn new(config: SharedTuiSettings) -> Self {
+        let component = lookup_table(
+            &config,
+            " Cover art: Enter to run, Esc to close ",
+            &[" Action ", " Current "],
+            &[45, 55],
+        );
+
+        Self { component, config }
+    }
+}
+
+impl Component<Msg, UserEvent> for CoverArtTable {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        let config = self.config.clone();
+        let cmd_result = match ev {
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                return Some(Msg::TagEditor(TEMsg::TECoverArt(CoverArtMsg::Close)))
+            }
+            Event::Keyboard(keyevent) if keyevent == config.read().settings.keys.quit.get() => {
+                return Some(Msg::TagEditor(TEMsg::TECoverArt(CoverArtMsg::Close)))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => {
+                if let State::One(StateValue::Usize(index)) = self.state() {
+                    if let Some((_, msg)) = ACTIONS.get(index) {
+                        return Some(Msg::TagEditor(TEMsg::TECoverArt(msg.clone())));
+                    }
+                }
+                CmdResult::None
+            }
+            ev => perform_navigation(&mut self.component, &config, &ev),
+        };
+        match cmd_result {
+            CmdResult::None => None,
+            _ => Some(Msg::ForceRedraw),
+        }
+    }
+}
+
+/// Input for the image file to embed
+#[derive(MockComponent)]
+pub struct CoverFileInput {
+    component: Input,
+    kind: CoverKind,
+}
+
+impl CoverFileInput {
+    pub fn new(config: &SharedTuiSettings, kind: CoverKind, value: &str) -> Self {
+        let config = config.read();
+        let component = Input::default()
+            .foreground(config.settings.theme.library_foreground())
+            .background(config.settings.theme.library_background())
+            .borders(
+                Borders::default()
+                    .color(config.settings.theme.library_border())
+                    .modifiers(BorderType::Rounded),
+            )
+            .input_type(InputType::Text)
+            .title(
+                format!(
+                    " Image file to embed as {} cover: (large images are shrunk; Enter to confirm) ",
+                    kind.name()
+                ),
+                Alignment::Left,
+            )
+            .value(value);
+
+        Self { component, kind }
+    }
+}
+
+impl Component<Msg, UserEvent> for CoverFileInput {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        let cmd_result = match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Left, ..
+            }) => self.perform(Cmd::Move(Direction::Left)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => self.perform(Cmd::Move(Direction::Right)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => self.perform(Cmd::GoTo(Position::Begin)),
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Delete, ..
+            }) => self.perform(Cmd::Cancel),
+            Event::Keyboard(KeyEvent {
+                code: Key::Backspace,
+                ..
+            }) => self.perform(Cmd::Delete),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char(ch),
+                modifiers: KeyModifiers::SHIFT | KeyModifiers::NONE,
+            }) => self.perform(Cmd::Type(ch)),
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                return Some(Msg::TagEditor(TEMsg::TECoverArt(CoverArtMsg::ChooseClose)));
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => match self.component.state() {
+                State::One(StateValue::String(input)) => {
+                    return Some(Msg::TagEditor(TEMsg::TECoverArt(CoverArtMsg::Embed(
+                        self.kind, input,
+                    ))))
+                }
+                _ => CmdResult::None,
+            },
+            _ => CmdResult::None,
+        };
+        match cmd_result {
+            CmdResult::None => None,
+            _ => Some(Msg::ForceRedraw),
+        }
+    }
+}
+
+impl Model {
+    /// Handle [`CoverArtMsg`] events
+    pub fn te_update_cover(&mut self, msg: CoverArtMsg) {
+        match msg {
+            CoverArtMsg::Open => self.te_cover_open(),
+            CoverArtMsg::Close => self.umount_cover(),
+            CoverArtMsg::Choose(kind) => self.te_cover_choose(kind),
+            CoverArtMsg::ChooseClose => {
+                self.umount_cover_file();
+                self.app.active(&Id::TagEditor(IdTagEditor::CoverArt)).ok();
+            }
+            CoverArtMsg::Embed(kind, path) => self.te_cover_embed(kind, Path::new(&path)),
+            CoverArtMsg::Extract(kind) => self.te_cover_extract(kind),
+            CoverArtMsg::Remove(kind) => {
+                let what = kind.map_or_else(
+                    || "Pictures removed".to_string(),
+                    |kind| format!("The {} cover removed", kind.name()),
+                );
+                self.te_cover_apply(what, move |path| Ok(cover::remove(path, kind)? > 0));
+            }
+            CoverArtMsg::Shrink => {
+                self.te_cover_apply("Pictures shrunk".to_string(), |path| {
+                    Ok(cover::shrink(path)? > 0)
+                });
+            }
+            CoverArtMsg::Done(what, paths, changed, failed) => {
+                self.te_cover_done(&what, &paths, changed, failed);
+            }
+        }
+    }
+
+    /// Show the cover art actions with the pictures of the edited files
+    fn te_cover_open(&mut self) {
+        let pictures: Vec<Vec<Picture>> = self
+            .te_edited_paths()
+            .iter()
+            .map(|path| {
+                cover::read_pictures(path)
+                    .inspect_err(|err| warn!("Failed to read \"{}\": {err:#}", path.display()))
+                    .unwrap_or_default()
+            })
+            .collect();
+        let current = |kind: CoverKind| match pictures.as_slice() {
+            [pictures] => {
+                cover::find(pictures, kind).map_or_else(|| "none".to_string(), cover::describe)
+            }
+            files => format!(
+                "in {} of {} files",
+                files
+                    .iter()
+                    .filter(|v| cover::find(v, kind).is_some())
+                    .count(),
+                files.len()
+            ),
+        };
+
+        let mut table = TableBuilder::default();
+        for (idx, (name, msg)) in ACTIONS.iter().enumerate() {
+            if idx > 0 {
+                table.add_row();
+            }
+            let info = match msg {
+                CoverArtMsg::Choose(kind) => current(*kind),
+                CoverArtMsg::Remove(None) => {
+                    format!("{} pictures", pictures.iter().map(Vec::len).sum::<usize>())
+                }
+                CoverArtMsg::Shrink => format!(
+                    "larger than {}px or {} MiB",
+                    cover::MAX_DIMENSION,
+                    cover::MAX_BYTES / 1024 / 1024
+                ),
+                _ => String::new(),
+            };
+
+            table
+                .add_col(TextSpan::new(*name).bold())
+                .add_col(TextSpan::new(info));
+        }
+
+        assert!(self
+            .app
+            .remount(
+                Id::TagEditor(IdTagEditor::CoverArt),
+                Box::new(CoverArtTable::new(self.config_tui.clone())),
+                vec![]
+            )
+            .is_ok());
+        self.app
+            .attr(
+                &Id::TagEditor(IdTagEditor::CoverArt),
+                Attribute::Content,
+                AttrValue::Table(table.build()),
+            )
+            .ok();
+        assert!(self
+            .app
+            .active(&Id::TagEditor(IdTagEditor::CoverArt))
+            .is_ok());
+    }
+
+    /// Ask for the image file to embed, starting with a image from the folder like `cover.jpg`
+    fn te_cover_choose(&mut self, kind: CoverKind) {
+        let Some(track_path) = self.te_edited_paths().into_iter().next() else {
+            return;
+        };
+        let value = match cover::folder_image(&track_path, kind) {
+            Ok(Some(path)) => path.to_string_lossy().to_string(),
+            _ => track_path
+                .parent()
+                .map(|v| format!("{}/", v.display()))
+                .unwrap_or_default(),
+        };
+
+        assert!(self
+            .app
+            .remount(
+                Id::TagEditor(IdTagEditor::CoverFile),
+                Box::new(CoverFileInput::new(&self.config_tui, kind, &value)),
+                vec![]
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .active(&Id::TagEditor(IdTagEditor::CoverFile))
+            .is_ok());
+    }
+
+    /// Embed the image file at `image_path` as cover `kind` in all edited files
+    fn te_cover_embed(&mut self, kind: CoverKind, image_path: &Path) {
+        let picture = match cover::read_image(image_path, kind) {
+            Ok(v) => v,
+            Err(err) => {
+                self.mount_error_popup(err.context("read cover image"));
+                return;
+            }
+        };
+
+        self.te_cover_apply(format!("The {} cover embedded", kind.name()), move |path| {
+            cover::embed(path, kind, &picture)?;
+            Ok(true)
+        });
+    }
+
+    /// Write the cover `kind` of the first edited file having it to a image file next to it
+    fn te_cover_extract(&mut self, kind: CoverKind) {
+        self.umount_cover();
+
+        let path = self
+            .te_edited_paths()
+            .into_iter()
+            .find(|path| cover::read_pictures(path).is_ok_and(|v| cover::find(&v, kind).is_some()));
+        let Some(path) = path else {
+            self.mount_error_popup(anyhow::anyhow!("There is no {} cover", kind.name()));
+            return;
+        };
+
+        match cover::extract(&path, kind) {
+            Ok(target) => self.show_message_timeout_label_help(
+                format!(" Cover written to \"{}\". ", target.display()),
+                None,
+                None,
+                None,
+            ),
+            Err(err) => self.mount_error_popup(err.context("extract cover")),
+        }
+    }
+
+    /// Run `action` on every edited file, returning if it changed the file.
+    ///
+    /// This runs on a separate thread, reporting the result via [`CoverArtMsg::Done`].
+    fn te_cover_apply<F>(&mut self, what: String, action: F)
+    where
+        F: Fn(&Path) -> Result<bool> + Send + 'static,
+    {
+        self.umount_cover();
+
+        let paths = self.te_edited_paths();
+        let tx = self.tx_to_main.clone();
+        self.show_message_timeout_label_help(" Changing pictures... ", None, None, None);
+
+        std::thread::spawn(move || {
+            let mut changed = 0;
+            let mut failed = 0;
+            for path in &paths {
+                match action(path) {
+                    Ok(true) => changed += 1,
+                    Ok(false) => (),
+                    Err(err) => {
+                        warn!(
+                            "Failed to change pictures of \"{}\": {err:#}",
+                            path.display()
+                        );
+                        failed += 1;
+                    }
+                }
+            }
+
+            let _ = tx.send(Msg::TagEditor(TEMsg::TECoverArt(CoverArtMsg::Done(
+                what, paths, changed, failed,
+            ))));
+        });
+    }
+
+    /// Show the result of [`Model::te_cover_apply`] and re-read the changed files.
+    fn te_cover_done(&mut self, what: &str, paths: &[PathBuf], changed: usize, failed: usize) {
+        for path in paths {
+            Track::forget_cached_picture(path);
+        }
+
+        // the tag editor may have been closed or changed files in the meantime
+        let edited = self.te_edited_paths();
+        if paths.iter().any(|path| edited.contains(path)) {
+            self.te_reload_edited(&edited);
+        }
+        if let Err(err) = self.update_photo() {
+            self.mount_error_popup(err.context("update_photo"));
+        }
+
+        let msg = if failed == 0 {
+            format!(" {what} for {changed} files. ")
+        } else {
+            format!(" {what} for {changed} files, {failed} failed, see log for details. ")
+        };
+        self.show_message_timeout_label_help(msg, None, None, None);
+    }
+
+    fn umount_cover_file(&mut self) {
+        if self.app.mounted(&Id::TagEditor(IdTagEditor::CoverFile)) {
+            assert!(self
+                .app
+                .umount(&Id::TagEditor(IdTagEditor::CoverFile))
+                .is_ok());
+        }
+    }
+
+    /// Close the cover art popups, if open
+    pub fn umount_cover(&mut self) {
+        self.umount_cover_file();
+        if self.app.mounted(&Id::TagEditor(IdTagEditor::CoverArt)) {
+            assert!(self
+                .app
+                .umount(&Id::TagEditor(IdTagEditor::CoverArt))
+                .is_ok());
+            self.app
+                .active(&Id::TagEditor(IdTagEditor::InputArtist))
+                .ok();
+        }
+    }
+}
@@ -38,6 +38,94 @@ impl TEFooter {
                     ))
                     .bold()
                     .fg(config.settings.theme.library_highlight()),
+                    TextSpan::new(" ReplayGain album: ")
+                        .fg(config.settings.theme.library_foreground()),
+                    TextSpan::new(format!(
+                        "<{}>",
+                        config.settings.keys.tag_editor_keys.analyze_loudness
+                    ))
+                    .bold()
+                    .fg(config.settings.theme.library_highlight()),
+                    TextSpan::new(" Lyric file embed/export: ")
+                        .fg(config.settings.theme.library_foreground()),
+                    TextSpan::new(format!(
+                        "<{}/{}>",
+                        config.settings.keys.tag_editor_keys.embed_sidecar,
+                        config.settings.keys.tag_editor_keys.export_sidecar
+                    ))
+                    .bold()
+                    .fg(config.settings.theme.library_highlight()),
+                    TextSpan::new(" Sync lyrics: ").fg(config.settings.theme.library_foreground()),
+                    TextSpan::new(format!(
+                        "<{}>",
+                        config.settings.keys.tag_editor_keys.sync_lyrics
+                    ))
+                    .bold()
+                    .fg(config.settings.theme.library_highlight()),
+                    TextSpan::new(" MusicBrainz album: ")
+                        .fg(config.settings.theme.library_foreground()),
+                    TextSpan::new(format!(
+                        "<{}>",
+                        config.settings.keys.tag_editor_keys.musicbrainz_lookup
+                    ))
+                    .bold()
+                    .fg(config.settings.theme.library_highlight()),
+                    TextSpan::new(" Move by pattern / tags from name: ")
+                        .fg(config.settings.theme.library_foreground()),
+                    TextSpan::new(format!(
+                        "<{}/{}>",
+                        config.settings.keys.tag_editor_keys.rename_by_pattern,
+                        config.settings.keys.tag_editor_keys.tags_from_filename
+                    ))
+                    .bold()
+                    .fg(config.settings.theme.library_highlight()),
+                    TextSpan::new(" Cover art: ").fg(config.settings.theme.library_foreground()),
+                    TextSpan::new(format!(
+                        "<{}>",
+                        config.settings.keys.tag_editor_keys.cover_art
+                    ))
+                    .bold()
+                    .fg(config.settings.theme.library_highlight()),
+                ],
+            )),
+        }
+    }
+
+    /// The footer when editing many files at once, where only the common fields can be changed
+    pub fn new_batch(config: &TuiOverlay) -> Self {
+        Self {
+            component: (LabelSpan::new(
+                config,
+                &[
+                    TextSpan::new(" Preview changes: ")
+                        .fg(config.settings.theme.library_foreground()),
+                    TextSpan::new(format!("<ENTER/{}>", config.settings.keys.config_keys.save))
+                        .bold()
+                        .fg(config.settings.theme.library_highlight()),
+                    TextSpan::new(" Exit: ").fg(config.settings.theme.library_foreground()),
+                    TextSpan::new(format!("<{}>", config.settings.keys.escape))
+                        .bold()
+                        .fg(config.settings.theme.library_highlight()),
+                    TextSpan::new(" Change field: ").fg(config.settings.theme.library_foreground()),
+                    TextSpan::new("<Tab/ShiftTab>")
+                        .bold()
+                        .fg(config.settings.theme.library_highlight()),
+                    TextSpan::new(" Move by pattern / tags from name: ")
+                        .fg(config.settings.theme.library_foreground()),
+                    TextSpan::new(format!(
+                        "<{}/{}>",
+                        config.settings.keys.tag_editor_keys.rename_by_pattern,
+                        config.settings.keys.tag_editor_keys.tags_from_filename
+                    ))
+                    .bold()
+                    .fg(config.settings.theme.library_highlight()),
+                    TextSpan::new(" Cover art: ").fg(config.settings.theme.library_foreground()),
+                    TextSpan::new(format!(
+                        "<{}>",
+                        config.settings.keys.tag_editor_keys.cover_art
+                    ))
+                    .bold()
+                    .fg(config.settings.theme.library_highlight()),
                 ],
             )),
         }
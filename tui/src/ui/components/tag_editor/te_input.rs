@@ -29,6 +29,7 @@ use tuirealm::event::{Key, KeyEvent, KeyModifiers};
 use tuirealm::props::{Alignment, BorderType, Borders, InputType};
 use tuirealm::{Component, Event, MockComponent};
 
+use super::te_keys::action_key_msg;
 use crate::ui::model::UserEvent;
 
 /// Common Field Properties and event handling
@@ -63,6 +64,9 @@ impl EditField {
     pub fn on(&mut self, ev: Event<UserEvent>, on_key_down: Msg, on_key_up: Msg) -> Option<Msg> {
         let config = self.config.clone();
         let keys = &config.read().settings.keys;
+        if let Some(msg) = action_key_msg(&keys.tag_editor_keys, &ev) {
+            return Some(msg);
+        }
         match ev {
             // Global Hotkeys
             Event::Keyboard(keyevent) if keyevent == keys.config_keys.save.get() => {
@@ -0,0 +1,36 @@
+use termusiclib::config::v2::tui::keys::KeysTagEditor;
+use termusiclib::types::{CoverArtMsg, LyricSyncMsg, Msg, MusicBrainzMsg, PatternMsg, TEMsg};
+use tuirealm::Event;
+
+use crate::ui::model::UserEvent;
+
+/// Get the message for the tag editor action keys (like analyzing the loudness), which are the same in all tag editor widgets.
+///
+/// Returns [`None`] if `ev` is not one of those keys.
+pub(super) fn action_key_msg(keys: &KeysTagEditor, ev: &Event<UserEvent>) -> Option<Msg> {
+    let Event::Keyboard(keyevent) = ev else {
+        return None;
+    };
+
+    let msg = if *keyevent == keys.analyze_loudness.get() {
+        TEMsg::TEAnalyzeLoudness
+    } else if *keyevent == keys.embed_sidecar.get() {
+        TEMsg::TEEmbedSidecar
+    } else if *keyevent == keys.export_sidecar.get() {
+        TEMsg::TEExportSidecar
+    } else if *keyevent == keys.sync_lyrics.get() {
+        TEMsg::TELyricSync(LyricSyncMsg::Start)
+    } else if *keyevent == keys.musicbrainz_lookup.get() {
+        TEMsg::TEMusicBrainz(MusicBrainzMsg::Search)
+    } else if *keyevent == keys.rename_by_pattern.get() {
+        TEMsg::TEPattern(PatternMsg::Rename)
+    } else if *keyevent == keys.tags_from_filename.get() {
+        TEMsg::TEPattern(PatternMsg::TagsFromFilename)
+    } else if *keyevent == keys.cover_art.get() {
+        TEMsg::TECoverArt(CoverArtMsg::Open)
+    } else {
+        return None;
+    };
+
+    Some(Msg::TagEditor(msg))
+}
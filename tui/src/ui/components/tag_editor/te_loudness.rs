@@ -0,0 +1,61 @@
+use termusiclib::loudness::{self, AlbumGroup};
+use termusiclib::types::{Msg, TEMsg};
+
+use crate::ui::Model;
+
+impl Model {
+    /// Analyze the loudness of the album the current tag editor track belongs to, and write the ReplayGain tags.
+    ///
+    /// This runs on a separate thread, reporting progress via [`TEMsg::TELoudnessProgress`] and [`TEMsg::TELoudnessDone`].
+    pub fn te_analyze_loudness(&mut self) {
+        let Some(song) = &self.tageditor_song else {
+            return;
+        };
+        let path = song.path().to_path_buf();
+        let tx = self.tx_to_main.clone();
+
+        self.show_message_timeout_label_help(" Analyzing loudness... ", None, None, None);
+
+        std::thread::spawn(move || {
+            let group = loudness::find_album_of(&path).unwrap_or_else(|| AlbumGroup {
+                album: None,
+                files: vec![path],
+            });
+
+            let analysis = loudness::analyze_album(&group, |progress| {
+                let _ = tx.send(Msg::TagEditor(TEMsg::TELoudnessProgress(
+                    progress.current + 1,
+                    progress.total,
+                )));
+            });
+
+            let mut failed = 0;
+            for (path, res) in &analysis.tracks {
+                if let Err(err) = res {
+                    failed += 1;
+                    warn!("Failed to analyze \"{}\": {err:#}", path.display());
+                }
+            }
+            for (path, err) in analysis.write_tags() {
+                failed += 1;
+                warn!("Failed to write tags to \"{}\": {err:#}", path.display());
+            }
+
+            let _ = tx.send(Msg::TagEditor(TEMsg::TELoudnessDone(
+                analysis.tracks.len().saturating_sub(failed),
+                failed,
+            )));
+        });
+    }
+
+    /// Show the result of [`Model::te_analyze_loudness`].
+    pub fn te_loudness_done(&mut self, tagged: usize, failed: usize) {
+        let msg = if failed == 0 {
+            format!(" ReplayGain tags written for {tagged} tracks. ")
+        } else {
+            format!(" ReplayGain tags written for {tagged} tracks, {failed} failed, see log for details. ")
+        };
+
+        self.show_message_timeout_label_help(msg, None, None, None);
+    }
+}
@@ -0,0 +1,406 @@
+use std::collections::VecDeque;
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use termusiclib::ids::{Id, IdTagEditor};
+use termusiclib::songtag::lrc::{Caption, Lyric};
+use termusiclib::types::LyricSyncMsg;
+use tuirealm::props::{Alignment, AttrValue, Attribute, PropPayload, PropValue, TextSpan};
+
+use crate::ui::tui_cmd::TuiCmd;
+use crate::ui::Model;
+
+/// Attribute of [`TETextareaLyric`](super::TETextareaLyric) to enable the keys of the sync editor
+pub const ATTR_LYRIC_SYNC: &str = "lyric-sync";
+
+/// Milliseconds a line is moved by one nudge
+pub const NUDGE_STEP: i64 = 100;
+
+/// Rows to show before the highlighted row
+const CONTEXT_ROWS: usize = 3;
+
+/// State of the tap-to-sync lyric editor, which stamps lines one after another with the playback position.
+#[derive(Debug, Clone)]
+pub struct LyricSync {
+    /// The lines stamped so far
+    lyric: Lyric,
+    /// The lines still to be stamped, in order
+    remaining: VecDeque<String>,
+    /// Highlight the line at the playback position instead of the next line to stamp
+    preview: bool,
+    /// The row highlighted when last shown, to only update the preview on change
+    shown_highlight: Option<usize>,
+}
+
+impl LyricSync {
+    /// Create a new state with the lines of `text`, existing timestamps and LRC metadata are removed.
+    pub fn new(text: &str) -> Self {
+        Self {
+            lyric: Lyric {
+                offset: 0,
+                captions: Vec::new(),
+            },
+            remaining: text.lines().filter_map(plain_line).collect(),
+            preview: false,
+            shown_highlight: None,
+        }
+    }
+
+    /// Get the count of all lines, stamped or not.
+    pub fn len(&self) -> usize {
+        self.lyric.captions.len() + self.remaining.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Stamp the next line with `position`, returns `false` if all lines are stamped already.
+    pub fn stamp(&mut self, position: Duration) -> bool {
+        let Some(text) = self.remaining.pop_front() else {
+            return false;
+        };
+        let timestamp = i64::try_from(position.as_millis()).unwrap_or(i64::MAX);
+        self.lyric.captions.push(Caption::new(timestamp, text));
+        self.lyric.captions.sort_by_key(Caption::timestamp);
+
+        true
+    }
+
+    /// Remove the timestamp of the last stamped line, making it the next line to stamp again.
+    pub fn undo(&mut self) {
+        if let Some(caption) = self.lyric.captions.pop() {
+            self.remaining.push_front(caption.text().to_string());
+        }
+    }
+
+    /// Move the last stamped line by `offset` milliseconds.
+    pub fn nudge(&mut self, offset: i64) {
+        if let Some(index) = self.lyric.captions.len().checked_sub(1) {
+            self.lyric.adjust_caption(index, offset);
+        }
+    }
+
+    pub fn toggle_preview(&mut self) {
+        self.preview = !self.preview;
+    }
+
+    /// Get the row to highlight: the line at `position` in preview, otherwise the next line to stamp.
+    fn highlight(&self, position: Duration) -> Option<usize> {
+        if self.preview {
+            let time = i64::try_from(position.as_millis()).unwrap_or(i64::MAX);
+            self.lyric.get_index(time)
+        } else {
+            (!self.remaining.is_empty()).then_some(self.lyric.captions.len())
+        }
+    }
+
+    /// Get all lines as rows, the stamped ones with their timestamp.
+    fn rows(&self) -> impl Iterator<Item = String> + '_ {
+        let stamped = self
+            .lyric
+            .captions
+            .iter()
+            .map(|v| format!("[{}] {}", time_label(v.timestamp()), v.text()));
+        let remaining = self.remaining.iter().map(|v| format!("[--:--.--] {v}"));
+
+        stamped.chain(remaining)
+    }
+
+    /// Format the stamped lines as LRC, followed by the lines not stamped yet without timestamp.
+    pub fn as_lrc_text(&self) -> String {
+        let mut text = self.lyric.as_lrc_text();
+        for line in &self.remaining {
+            text.push_str(line);
+            text.push('\n');
+        }
+
+        text
+    }
+}
+
+/// Get the text of a lyric line without LRC timestamps, or `None` for empty lines and LRC metadata like `[ar:Artist]`.
+fn plain_line(line: &str) -> Option<String> {
+    let line = line.trim();
+    if line.is_empty() {
+        return None;
+    }
+
+    if let Some(caption) = Lyric::from_str(line)
+        .ok()
+        .and_then(|v| v.captions.into_iter().next())
+    {
+        return (!caption.text().is_empty()).then(|| caption.text().to_string());
+    }
+
+    let is_metadata = line
+        .strip_prefix('[')
+        .and_then(|v| v.strip_suffix(']'))
+        .and_then(|v| v.split_once(':'))
+        .is_some_and(|(tag, _)| !tag.is_empty() && tag.chars().all(|c| c.is_ascii_alphabetic()));
+    if is_metadata {
+        return None;
+    }
+
+    Some(line.to_string())
+}
+
+/// Format milliseconds as `mm:ss.xx`
+fn time_label(timestamp: i64) -> String {
+    let timestamp = timestamp.max(0);
+    format!(
+        "{:02}:{:02}.{:02}",
+        timestamp / 60_000,
+        timestamp / 1000 % 60,
+        timestamp % 1000 / 10
+    )
+}
+
+impl Model {
+    /// Handle all tap-to-sync lyric editor messages.
+    pub fn te_update_lyric_sync(&mut self, msg: LyricSyncMsg) {
+        let res = match msg {
+            LyricSyncMsg::Start => self.te_lyric_sync_start(),
+            LyricSyncMsg::Stamp => {
+                let position = self.playback.current_track_pos_estimate();
+                if let Some(sync) = self.te_lyric_sync.as_mut() {
+                    if !sync.stamp(position) {
+                        self.show_message_timeout_label_help(
+                            " All lines are stamped, save them or undo the last one. ",
+                            None,
+                            None,
+                            None,
+                        );
+                    }
+                }
+                Ok(())
+            }
+            LyricSyncMsg::Undo => {
+                if let Some(sync) = self.te_lyric_sync.as_mut() {
+                    sync.undo();
+                }
+                Ok(())
+            }
+            LyricSyncMsg::Nudge(offset) => {
+                if let Some(sync) = self.te_lyric_sync.as_mut() {
+                    sync.nudge(offset);
+                }
+                Ok(())
+            }
+            LyricSyncMsg::TogglePreview => {
+                if let Some(sync) = self.te_lyric_sync.as_mut() {
+                    sync.toggle_preview();
+                }
+                Ok(())
+            }
+            LyricSyncMsg::Save => self.te_lyric_sync_save(),
+            LyricSyncMsg::Cancel => self.te_lyric_sync_stop(),
+        };
+
+        if let Err(err) = res {
+            self.mount_error_popup(err.context("lyric sync"));
+            return;
+        }
+
+        self.te_lyric_sync_view();
+    }
+
+    /// Start the tap-to-sync lyric editor with the selected lyric frame, and play the track if it is not the current one.
+    fn te_lyric_sync_start(&mut self) -> Result<()> {
+        let Some(song) = &self.tageditor_song else {
+            return Ok(());
+        };
+        let Some(frame) = song.lyric_selected() else {
+            bail!("No lyrics to sync, add plain text lyrics first");
+        };
+        let sync = LyricSync::new(&frame.text);
+        if sync.is_empty() {
+            bail!("The selected lyrics have no lines to sync");
+        }
+
+        let path = song.path().to_path_buf();
+        let is_current = self
+            .playback
+            .current_track()
+            .and_then(|v| v.path())
+            .is_some_and(|v| v == path);
+        if !is_current {
+            if !self.playback.queue().is_empty() {
+                bail!("The \"play next\" queue is not empty, clear it or play the track first");
+            }
+            self.queue_add(&path);
+            self.command(TuiCmd::SkipNext);
+        }
+
+        self.te_lyric_sync = Some(sync);
+        self.app.attr(
+            &Id::TagEditor(IdTagEditor::TextareaLyric),
+            Attribute::Custom(ATTR_LYRIC_SYNC),
+            AttrValue::Flag(true),
+        )?;
+        self.app
+            .active(&Id::TagEditor(IdTagEditor::TextareaLyric))?;
+
+        Ok(())
+    }
+
+    /// Write the synced lyrics into the selected lyric frame and save the tags, like [`te_rename_song_by_tag`](Self::te_rename_song_by_tag).
+    fn te_lyric_sync_save(&mut self) -> Result<()> {
+        let (Some(sync), Some(song)) = (&self.te_lyric_sync, self.tageditor_song.as_mut()) else {
+            return Ok(());
+        };
+
+        let text = sync.as_lrc_text();
+        let lang = song
+            .lyric_selected()
+            .map_or_else(|| "eng".to_string(), |v| v.lang.clone());
+        song.set_parsed_lyrics(Lyric::from_str(&text).ok());
+        song.set_lyric(text, lang, None::<String>);
+
+        // save first, stopping re-initializes the fields from the track
+        self.te_rename_song_by_tag()?;
+        self.te_lyric_sync_stop()?;
+
+        self.show_message_timeout_label_help(" Saved synced lyrics. ", None, None, None);
+
+        Ok(())
+    }
+
+    /// Stop the tap-to-sync lyric editor without saving and show the lyric frame again.
+    fn te_lyric_sync_stop(&mut self) -> Result<()> {
+        self.te_lyric_sync = None;
+        self.app.attr(
+            &Id::TagEditor(IdTagEditor::TextareaLyric),
+            Attribute::Custom(ATTR_LYRIC_SYNC),
+            AttrValue::Flag(false),
+        )?;
+
+        if let Some(song) = self.tageditor_song.clone() {
+            self.init_by_song(song)?;
+        }
+
+        Ok(())
+    }
+
+    /// Update the highlighted line of the sync editor preview, if it changed.
+    pub fn te_lyric_sync_update(&mut self) {
+        let position = self.playback.current_track_pos_estimate();
+        if self
+            .te_lyric_sync
+            .as_ref()
+            .is_some_and(|v| v.preview && v.highlight(position) != v.shown_highlight)
+        {
+            self.te_lyric_sync_view();
+            self.force_redraw();
+        }
+    }
+
+    /// Show the state of the sync editor in the lyric textarea.
+    fn te_lyric_sync_view(&mut self) {
+        let position = self.playback.current_track_pos_estimate();
+        let Some(sync) = self.te_lyric_sync.as_mut() else {
+            return;
+        };
+
+        let highlight = sync.highlight(position);
+        sync.shown_highlight = highlight;
+
+        let skip = highlight.map_or(0, |v| v.saturating_sub(CONTEXT_ROWS));
+        let rows: Vec<PropValue> = sync
+            .rows()
+            .enumerate()
+            .skip(skip)
+            .map(|(idx, row)| {
+                if Some(idx) == highlight {
+                    TextSpan::new(format!("> {row}")).bold()
+                } else {
+                    TextSpan::new(format!("  {row}"))
+                }
+            })
+            .map(PropValue::TextSpan)
+            .collect();
+
+        let mode = if sync.preview { "Preview" } else { "Sync" };
+        let title = format!(
+            " {mode} {}/{}: <ENTER> stamp, <BACKSPACE> undo, <LEFT>/<RIGHT> nudge, <p> preview ",
+            sync.lyric.captions.len(),
+            sync.len()
+        );
+
+        self.app
+            .attr(
+                &Id::TagEditor(IdTagEditor::TextareaLyric),
+                Attribute::Title,
+                AttrValue::Title((title, Alignment::Left)),
+            )
+            .ok();
+        self.app
+            .attr(
+                &Id::TagEditor(IdTagEditor::TextareaLyric),
+                Attribute::Text,
+                AttrValue::Payload(PropPayload::Vec(rows)),
+            )
+            .ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use pretty_assertions::assert_eq;
+
+    use super::{plain_line, LyricSync};
+
+    #[test]
+    fn should_strip_timestamps_and_metadata() {
+        assert_eq!(plain_line("  Some line "), Some("Some line".to_string()));
+        assert_eq!(
+            plain_line("[00:12.00]Timed line"),
+            Some("Timed line".to_string())
+        );
+        assert_eq!(
+            plain_line("[00:12.00]<00:12.00>Timed <00:12.50>words"),
+            Some("Timed words".to_string())
+        );
+        assert_eq!(plain_line("[Chorus]"), Some("[Chorus]".to_string()));
+        assert_eq!(plain_line("[ar:Artist]"), None);
+        assert_eq!(plain_line("[00:12.00]"), None);
+        assert_eq!(plain_line(""), None);
+    }
+
+    #[test]
+    fn should_stamp_undo_and_nudge() {
+        let mut sync = LyricSync::new("First\n\nSecond\nThird");
+        assert_eq!(sync.len(), 3);
+
+        assert!(sync.stamp(Duration::from_secs(1)));
+        assert!(sync.stamp(Duration::from_secs(5)));
+        sync.nudge(-200);
+        assert_eq!(
+            sync.as_lrc_text(),
+            "[00:01.00]First\n[00:04.80]Second\nThird\n"
+        );
+
+        sync.undo();
+        assert!(sync.stamp(Duration::from_secs(6)));
+        assert!(sync.stamp(Duration::from_secs(9)));
+        assert!(!sync.stamp(Duration::from_secs(12)));
+        assert_eq!(
+            sync.as_lrc_text(),
+            "[00:01.00]First\n[00:06.00]Second\n[00:09.00]Third\n"
+        );
+    }
+
+    #[test]
+    fn should_highlight_next_or_current_line() {
+        let mut sync = LyricSync::new("First\nSecond");
+        sync.stamp(Duration::from_secs(1));
+
+        assert_eq!(sync.highlight(Duration::ZERO), Some(1));
+
+        sync.toggle_preview();
+        assert_eq!(sync.highlight(Duration::from_millis(1500)), Some(0));
+    }
+}
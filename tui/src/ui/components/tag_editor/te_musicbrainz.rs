@@ -0,0 +1,521 @@
+use std::path::{Path, PathBuf};
+
+use termusiclib::config::SharedTuiSettings;
+use termusiclib::ids::{Id, IdTagEditor};
+use termusiclib::loudness;
+use termusiclib::songtag::musicbrainz::{
+    self, Api, FileChange, Release, ReleaseCandidate, ReleaseQuery,
+};
+use termusiclib::types::{Msg, MusicBrainzMsg, TEMsg};
+use tokio::runtime::Handle;
+use tui_realm_stdlib::Table;
+use tuirealm::command::{Cmd, CmdResult, Direction, Position};
+use tuirealm::event::{Key, KeyEvent};
+use tuirealm::props::{Alignment, BorderType, Borders, TableBuilder, TextSpan};
+use tuirealm::{Component, Event, MockComponent, State, StateValue};
+
+use crate::ui::model::UserEvent;
+use crate::ui::Model;
+
+use super::TETrack;
+
+/// Maximal number of candidates to show
+const CANDIDATES_LIMIT: u32 = 15;
+
+/// State of the MusicBrainz release lookup
+#[derive(Debug, Default)]
+pub struct MusicBrainzLookup {
+    candidates: Vec<ReleaseCandidate>,
+    /// The selected release and the changes to the files of the album
+    release: Option<(Release, Vec<FileChange>)>,
+}
+
+/// Handle the navigation keys common to the lookup tables
+pub(super) fn perform_navigation(
+    component: &mut Table,
+    config: &SharedTuiSettings,
+    ev: &Event<UserEvent>,
+) -> CmdResult {
+    let keys = &config.read().settings.keys;
+    match ev {
+        Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+            component.perform(Cmd::Move(Direction::Up))
+        }
+        Event::Keyboard(KeyEvent {
+            code: Key::Down, ..
+        }) => component.perform(Cmd::Move(Direction::Down)),
+        Event::Keyboard(keyevent) if *keyevent == keys.navigation_keys.down.get() => {
+            component.perform(Cmd::Move(Direction::Down))
+        }
+        Event::Keyboard(keyevent) if *keyevent == keys.navigation_keys.up.get() => {
+            component.perform(Cmd::Move(Direction::Up))
+        }
+        Event::Keyboard(KeyEvent {
+            code: Key::PageDown,
+            ..
+        }) => component.perform(Cmd::Scroll(Direction::Down)),
+        Event::Keyboard(KeyEvent {
+            code: Key::PageUp, ..
+        }) => component.perform(Cmd::Scroll(Direction::Up)),
+        Event::Keyboard(keyevent) if *keyevent == keys.navigation_keys.goto_top.get() => {
+            component.perform(Cmd::GoTo(Position::Begin))
+        }
+        Event::Keyboard(keyevent) if *keyevent == keys.navigation_keys.goto_bottom.get() => {
+            component.perform(Cmd::GoTo(Position::End))
+        }
+        _ => CmdResult::None,
+    }
+}
+
+/// Create a table in the style of the tag editor
+pub(super) fn lookup_table(
+    config: &SharedTuiSettings,
+    title: &str,
+    headers: &[&str],
+    widths: &[u16],
+) -> Table {
+    let config = config.read();
+    Table::default()
+        .background(config.settings.theme.library_background())
+        .foreground(config.settings.theme.library_foreground())
+        .borders(
+            Borders::default()
+                .color(config.settings.theme.library_border())
+                .modifiers(BorderType::Rounded),
+        )
+        .title(title, Alignment::Left)
+        .scroll(true)
+        .highlighted_color(config.settings.theme.library_highlight())
+        .highlighted_str(&config.settings.theme.style.library.highlight_symbol)
+        .rewind(false)
+        .step(4)
+        .row_height(1)
+        .headers(headers)
+        .column_spacing(2)
+        .widths(widths)
+        .table(
+            TableBuilder::default()
+                .add_col(TextSpan::from("Loading..."))
+                .build(),
+        )
+}
+
+/// The releases found for the album, to choose one to apply
+#[derive(MockComponent)]
+pub struct MBCandidatesTable {
+    component: Table,
+    config: SharedTuiSettings,
+}
+
+impl MBCandidatesTable {
+    pub fn new(config: SharedTuiSettings) -> Self {
+        let component = lookup_table(
+            &config,
+            " MusicBrainz releases: Enter to show changes, Esc to close ",
+            &[
+                " Title ",
+                " Artist ",
+                " Date ",
+                " Country ",
+                " Tracks ",
+                " Score ",
+            ],
+            &[32, 28, 12, 8, 8, 8],
+        );
+
+        Self { component, config }
+    }
+}
+
+impl Component<Msg, UserEvent> for MBCandidatesTable {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        let config = self.config.clone();
+        let cmd_result = match ev {
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                return Some(Msg::TagEditor(TEMsg::TEMusicBrainz(MusicBrainzMsg::Close)))
+            }
+            Event::Keyboard(keyevent) if keyevent == config.read().settings.keys.quit.get() => {
+                return Some(Msg::TagEditor(TEMsg::TEMusicBrainz(MusicBrainzMsg::Close)))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => {
+                if let State::One(StateValue::Usize(index)) = self.state() {
+                    return Some(Msg::TagEditor(TEMsg::TEMusicBrainz(
+                        MusicBrainzMsg::Select(index),
+                    )));
+                }
+                CmdResult::None
+            }
+            ev => perform_navigation(&mut self.component, &config, &ev),
+        };
+        match cmd_result {
+            CmdResult::None => None,
+            _ => Some(Msg::ForceRedraw),
+        }
+    }
+}
+
+/// The changes a release would make to every file of the album
+#[derive(MockComponent)]
+pub struct MBChangesTable {
+    component: Table,
+    config: SharedTuiSettings,
+}
+
+impl MBChangesTable {
+    pub fn new(config: SharedTuiSettings, release: &Release) -> Self {
+        let component = lookup_table(
+            &config,
+            &format!(
+                " Changes by \"{}\" of {}: Enter to write to all files, Esc to go back ",
+                release.title, release.artist
+            ),
+            &[" File ", " Field ", " Current ", " New "],
+            &[30, 18, 26, 26],
+        );
+
+        Self { component, config }
+    }
+}
+
+impl Component<Msg, UserEvent> for MBChangesTable {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        let config = self.config.clone();
+        let cmd_result = match ev {
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                return Some(Msg::TagEditor(TEMsg::TEMusicBrainz(MusicBrainzMsg::Back)))
+            }
+            Event::Keyboard(keyevent) if keyevent == config.read().settings.keys.quit.get() => {
+                return Some(Msg::TagEditor(TEMsg::TEMusicBrainz(MusicBrainzMsg::Back)))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => return Some(Msg::TagEditor(TEMsg::TEMusicBrainz(MusicBrainzMsg::Apply))),
+            ev => perform_navigation(&mut self.component, &config, &ev),
+        };
+        match cmd_result {
+            CmdResult::None => None,
+            _ => Some(Msg::ForceRedraw),
+        }
+    }
+}
+
+/// Get all files of the album the track at `path` belongs to, see [`loudness::find_album_of`]
+fn album_files(path: &Path) -> Vec<PathBuf> {
+    loudness::find_album_of(path).map_or_else(|| vec![path.to_path_buf()], |v| v.files)
+}
+
+/// Read the tags of all `files` and match them to `release`, files which cannot be read are skipped
+fn plan_changes(release: &Release, files: &[PathBuf]) -> Vec<FileChange> {
+    let mut changes: Vec<FileChange> = files
+        .iter()
+        .filter_map(|path| {
+            FileChange::read(path)
+                .inspect_err(|err| warn!("Failed to read \"{}\": {err:#}", path.display()))
+                .ok()
+        })
+        .collect();
+    musicbrainz::match_release(release, &mut changes);
+
+    changes
+}
+
+impl Model {
+    /// Handle [`MusicBrainzMsg`] events
+    pub fn te_update_musicbrainz(&mut self, msg: MusicBrainzMsg) {
+        match msg {
+            MusicBrainzMsg::Search => self.te_musicbrainz_search(),
+            MusicBrainzMsg::SearchSuccess(list) => self.te_musicbrainz_candidates(list),
+            MusicBrainzMsg::Select(index) => self.te_musicbrainz_select(index),
+            MusicBrainzMsg::ReleaseLoaded(release, changes) => {
+                self.te_musicbrainz_changes(*release, changes);
+            }
+            MusicBrainzMsg::Fail(err) => {
+                self.umount_musicbrainz();
+                self.mount_error_popup(anyhow::anyhow!(err).context("MusicBrainz lookup"));
+            }
+            MusicBrainzMsg::Apply => self.te_musicbrainz_apply(),
+            MusicBrainzMsg::Done(written, failed) => self.te_musicbrainz_done(written, failed),
+            MusicBrainzMsg::Back => {
+                if let Some(lookup) = self.te_musicbrainz.as_mut() {
+                    lookup.release = None;
+                }
+                self.umount_musicbrainz_changes();
+            }
+            MusicBrainzMsg::Close => self.umount_musicbrainz(),
+        }
+    }
+
+    /// Search MusicBrainz for releases of the album of the current tag editor track, by artist, album and track count.
+    fn te_musicbrainz_search(&mut self) {
+        let Some(song) = &self.tageditor_song else {
+            return;
+        };
+        let path = song.path().to_path_buf();
+
+        let input = |id: IdTagEditor| match self.app.state(&Id::TagEditor(id)) {
+            Ok(State::One(StateValue::String(v))) => Some(v),
+            _ => None,
+        };
+        let mut query = ReleaseQuery {
+            artist: input(IdTagEditor::InputArtist),
+            album: input(IdTagEditor::InputAlbum),
+            track_count: None,
+        };
+
+        self.te_musicbrainz = Some(MusicBrainzLookup::default());
+        self.mount_musicbrainz_candidates();
+
+        let tx = self.tx_to_main.clone();
+        let base_url = self.config_tui.read().settings.musicbrainz.base_url.clone();
+
+        Handle::current().spawn(async move {
+            let files = tokio::task::spawn_blocking(move || album_files(&path))
+                .await
+                .unwrap_or_default();
+            query.track_count = Some(files.len()).filter(|v| *v > 0);
+
+            let msg = match Api::new(&base_url)
+                .search_releases(&query, CANDIDATES_LIMIT)
+                .await
+            {
+                Ok(list) => MusicBrainzMsg::SearchSuccess(list),
+                Err(err) => MusicBrainzMsg::Fail(format!("{err:#}")),
+            };
+            let _ = tx.send(Msg::TagEditor(TEMsg::TEMusicBrainz(msg)));
+        });
+    }
+
+    /// Show the found candidates
+    fn te_musicbrainz_candidates(&mut self, list: Vec<ReleaseCandidate>) {
+        let Some(lookup) = self.te_musicbrainz.as_mut() else {
+            return;
+        };
+
+        let mut table = TableBuilder::default();
+        for (idx, candidate) in list.iter().enumerate() {
+            if idx > 0 {
+                table.add_row();
+            }
+            table
+                .add_col(TextSpan::new(&candidate.title).bold())
+                .add_col(TextSpan::new(&candidate.artist))
+                .add_col(TextSpan::new(candidate.date.as_deref().unwrap_or("-")))
+                .add_col(TextSpan::new(candidate.country.as_deref().unwrap_or("-")))
+                .add_col(TextSpan::new(candidate.track_count.to_string()))
+                .add_col(TextSpan::new(candidate.score.to_string()));
+        }
+        if list.is_empty() {
+            table.add_col(TextSpan::from("No releases found."));
+        }
+        lookup.candidates = list;
+
+        self.app
+            .attr(
+                &Id::TagEditor(IdTagEditor::MusicBrainzCandidates),
+                tuirealm::Attribute::Content,
+                tuirealm::AttrValue::Table(table.build()),
+            )
+            .ok();
+    }
+
+    /// Fetch the candidate at `index` and match it to the files of the album
+    fn te_musicbrainz_select(&mut self, index: usize) {
+        let (Some(song), Some(lookup)) = (&self.tageditor_song, &self.te_musicbrainz) else {
+            return;
+        };
+        let Some(candidate) = lookup.candidates.get(index) else {
+            return;
+        };
+        let id = candidate.id.clone();
+        let path = song.path().to_path_buf();
+
+        self.show_message_timeout_label_help(" Fetching release... ", None, None, None);
+
+        let tx = self.tx_to_main.clone();
+        let base_url = self.config_tui.read().settings.musicbrainz.base_url.clone();
+
+        Handle::current().spawn(async move {
+            let msg = match Api::new(&base_url).get_release(&id).await {
+                Ok(release) => tokio::task::spawn_blocking(move || {
+                    let changes = plan_changes(&release, &album_files(&path));
+                    MusicBrainzMsg::ReleaseLoaded(Box::new(release), changes)
+                })
+                .await
+                .unwrap_or_else(|err| MusicBrainzMsg::Fail(err.to_string())),
+                Err(err) => MusicBrainzMsg::Fail(format!("{err:#}")),
+            };
+            let _ = tx.send(Msg::TagEditor(TEMsg::TEMusicBrainz(msg)));
+        });
+    }
+
+    /// Show the changes `release` would make to the files of the album
+    fn te_musicbrainz_changes(&mut self, release: Release, changes: Vec<FileChange>) {
+        let Some(lookup) = self.te_musicbrainz.as_mut() else {
+            return;
+        };
+
+        let mut table = TableBuilder::default();
+        let mut first = true;
+        for change in &changes {
+            let name = change
+                .path
+                .file_name()
+                .map(|v| v.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let diff = change.diff();
+
+            if !first {
+                table.add_row();
+            }
+            first = false;
+
+            if change.new.is_none() {
+                table
+                    .add_col(TextSpan::new(&name).bold())
+                    .add_col(TextSpan::new("no matching track").italic())
+                    .add_col(TextSpan::new(""))
+                    .add_col(TextSpan::new(""));
+                continue;
+            }
+            if diff.is_empty() {
+                table
+                    .add_col(TextSpan::new(&name).bold())
+                    .add_col(TextSpan::new("unchanged").italic())
+                    .add_col(TextSpan::new(""))
+                    .add_col(TextSpan::new(""));
+                continue;
+            }
+            for (idx, field) in diff.iter().enumerate() {
+                if idx > 0 {
+                    table.add_row();
+                }
+                // only name the file once, for readability
+                let file = if idx == 0 { name.as_str() } else { "" };
+                table
+                    .add_col(TextSpan::new(file).bold())
+                    .add_col(TextSpan::new(field.field))
+                    .add_col(TextSpan::new(field.current.as_deref().unwrap_or("-")))
+                    .add_col(
+                        TextSpan::new(&field.new).fg(tuirealm::ratatui::style::Color::LightYellow),
+                    );
+            }
+        }
+        if changes.is_empty() {
+            table.add_col(TextSpan::from("No files to tag."));
+        }
+
+        assert!(self
+            .app
+            .remount(
+                Id::TagEditor(IdTagEditor::MusicBrainzChanges),
+                Box::new(MBChangesTable::new(self.config_tui.clone(), &release)),
+                vec![]
+            )
+            .is_ok());
+        self.app
+            .attr(
+                &Id::TagEditor(IdTagEditor::MusicBrainzChanges),
+                tuirealm::Attribute::Content,
+                tuirealm::AttrValue::Table(table.build()),
+            )
+            .ok();
+        assert!(self
+            .app
+            .active(&Id::TagEditor(IdTagEditor::MusicBrainzChanges))
+            .is_ok());
+
+        lookup.release = Some((release, changes));
+    }
+
+    /// Write the shown changes to the files of the album.
+    ///
+    /// This runs on a separate thread, reporting the result via [`MusicBrainzMsg::Done`].
+    fn te_musicbrainz_apply(&mut self) {
+        let Some((_, changes)) = self.te_musicbrainz.take().and_then(|v| v.release) else {
+            return;
+        };
+        self.umount_musicbrainz();
+
+        let tx = self.tx_to_main.clone();
+        self.show_message_timeout_label_help(" Writing tags... ", None, None, None);
+
+        std::thread::spawn(move || {
+            let changes: Vec<FileChange> =
+                changes.into_iter().filter(|v| v.new.is_some()).collect();
+            let failed = musicbrainz::write_all(&changes);
+            for (path, err) in &failed {
+                warn!("Failed to write tags to \"{}\": {err:#}", path.display());
+            }
+
+            let _ = tx.send(Msg::TagEditor(TEMsg::TEMusicBrainz(MusicBrainzMsg::Done(
+                changes.len() - failed.len(),
+                failed.len(),
+            ))));
+        });
+    }
+
+    /// Show the result of [`Model::te_musicbrainz_apply`] and reload the current track, which has likely changed.
+    fn te_musicbrainz_done(&mut self, written: usize, failed: usize) {
+        if let Some(path) = self.tageditor_song.as_ref().map(|v| v.path().to_path_buf()) {
+            match TETrack::read_metadata_from_file(path) {
+                Ok(song) => {
+                    if let Err(err) = self.init_by_song(song) {
+                        self.mount_error_popup(err.context("reload track"));
+                    }
+                }
+                Err(err) => self.mount_error_popup(err.context("reload track")),
+            }
+        }
+
+        let msg = if failed == 0 {
+            format!(" MusicBrainz tags written for {written} tracks. ")
+        } else {
+            format!(" MusicBrainz tags written for {written} tracks, {failed} failed, see log for details. ")
+        };
+        self.show_message_timeout_label_help(msg, None, None, None);
+    }
+
+    fn mount_musicbrainz_candidates(&mut self) {
+        assert!(self
+            .app
+            .remount(
+                Id::TagEditor(IdTagEditor::MusicBrainzCandidates),
+                Box::new(MBCandidatesTable::new(self.config_tui.clone())),
+                vec![]
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .active(&Id::TagEditor(IdTagEditor::MusicBrainzCandidates))
+            .is_ok());
+    }
+
+    fn umount_musicbrainz_changes(&mut self) {
+        if self
+            .app
+            .mounted(&Id::TagEditor(IdTagEditor::MusicBrainzChanges))
+        {
+            assert!(self
+                .app
+                .umount(&Id::TagEditor(IdTagEditor::MusicBrainzChanges))
+                .is_ok());
+        }
+    }
+
+    /// Close all popups of the lookup, if open
+    pub fn umount_musicbrainz(&mut self) {
+        self.te_musicbrainz = None;
+        self.umount_musicbrainz_changes();
+        if self
+            .app
+            .mounted(&Id::TagEditor(IdTagEditor::MusicBrainzCandidates))
+        {
+            assert!(self
+                .app
+                .umount(&Id::TagEditor(IdTagEditor::MusicBrainzCandidates))
+                .is_ok());
+        }
+    }
+}
@@ -0,0 +1,401 @@
+use std::path::{Path, PathBuf};
+
+use termusiclib::config::SharedTuiSettings;
+use termusiclib::ids::{Id, IdTagEditor};
+use termusiclib::songtag::batch::{self, BatchChange, FieldEdit};
+use termusiclib::songtag::pattern::{self, FileMove, MoveStatus, Pattern};
+use termusiclib::types::{Msg, PatternMsg, TEMsg};
+use tui_realm_stdlib::Table;
+use tuirealm::command::CmdResult;
+use tuirealm::event::{Key, KeyEvent};
+use tuirealm::props::{TableBuilder, TextSpan};
+use tuirealm::ratatui::style::Color;
+use tuirealm::{AttrValue, Attribute, Component, Event, MockComponent};
+
+use crate::ui::model::UserEvent;
+use crate::ui::Model;
+
+use super::te_batch::{changes_table, file_name};
+use super::te_musicbrainz::{lookup_table, perform_navigation};
+use super::BatchEdit;
+
+/// The changes a file name pattern would make, shown in the preview
+#[derive(Debug)]
+pub enum PatternPreview {
+    Rename(Vec<FileMove>),
+    Tags(Vec<BatchChange>),
+}
+
+/// The moves or tag changes of a pattern for every file
+#[derive(MockComponent)]
+pub struct PatternPreviewTable {
+    component: Table,
+    config: SharedTuiSettings,
+}
+
+impl PatternPreviewTable {
+    pub fn new(config: SharedTuiSettings, title: &str, headers: &[&str], widths: &[u16]) -> Self {
+        let component = lookup_table(&config, title, headers, widths);
+
+        Self { component, config }
+    }
+}
+
+impl Component<Msg, UserEvent> for PatternPreviewTable {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        let config = self.config.clone();
+        let cmd_result = match ev {
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                return Some(Msg::TagEditor(TEMsg::TEPattern(PatternMsg::Close)))
+            }
+            Event::Keyboard(keyevent) if keyevent == config.read().settings.keys.quit.get() => {
+                return Some(Msg::TagEditor(TEMsg::TEPattern(PatternMsg::Close)))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => return Some(Msg::TagEditor(TEMsg::TEPattern(PatternMsg::Apply))),
+            ev => perform_navigation(&mut self.component, &config, &ev),
+        };
+        match cmd_result {
+            CmdResult::None => None,
+            _ => Some(Msg::ForceRedraw),
+        }
+    }
+}
+
+impl Model {
+    /// Handle [`PatternMsg`] events
+    pub fn te_update_pattern(&mut self, msg: PatternMsg) {
+        match msg {
+            PatternMsg::Rename => self.te_pattern_rename(),
+            PatternMsg::TagsFromFilename => self.te_pattern_tags(),
+            PatternMsg::Apply => self.te_pattern_apply(),
+            PatternMsg::Close => self.umount_pattern(),
+            PatternMsg::RenameDone(moved, failed, removed_dirs) => {
+                self.te_pattern_rename_done(&moved, failed, removed_dirs);
+            }
+            PatternMsg::TagsDone(written, failed) => self.te_pattern_tags_done(written, failed),
+        }
+    }
+
+    /// The files being edited: all files when editing many, otherwise the current track
+    pub(super) fn te_edited_paths(&self) -> Vec<PathBuf> {
+        match &self.te_batch {
+            Some(edit) => edit.paths(),
+            None => self
+                .tageditor_song
+                .iter()
+                .map(|v| v.path().to_path_buf())
+                .collect(),
+        }
+    }
+
+    /// The music directory containing `path`, or its parent if it is in none of them
+    fn te_pattern_base(&self, path: &Path) -> PathBuf {
+        let config_server = self.config_server.read();
+        config_server
+            .settings
+            .player
+            .music_dirs
+            .iter()
+            .map(|dir| shellexpand::path::tilde(dir).into_owned())
+            .filter(|dir| path.starts_with(dir))
+            .max_by_key(|dir| dir.components().count())
+            .or_else(|| path.parent().map(Path::to_path_buf))
+            .unwrap_or_default()
+    }
+
+    /// Parse the pattern from the config, showing a error if it is invalid
+    fn te_pattern_parse(&mut self, rename: bool) -> Option<Pattern> {
+        let value = {
+            let config = self.config_tui.read();
+            let patterns = &config.settings.file_patterns;
+            if rename {
+                patterns.rename.clone()
+            } else {
+                patterns.parse.clone()
+            }
+        };
+
+        match Pattern::parse(&value) {
+            Ok(v) => Some(v),
+            Err(err) => {
+                self.mount_error_popup(err.context(format!("file pattern \"{value}\"")));
+                None
+            }
+        }
+    }
+
+    /// Show where the rename pattern would move all files to
+    fn te_pattern_rename(&mut self) {
+        let Some(pattern) = self.te_pattern_parse(true) else {
+            return;
+        };
+        let edit = BatchEdit::read(&self.te_edited_paths());
+        let pairs: Vec<(PathBuf, PathBuf)> = edit
+            .files()
+            .iter()
+            .map(|file| {
+                let base = self.te_pattern_base(&file.path);
+                (file.path.clone(), pattern.target(&base, file))
+            })
+            .collect();
+        let moves = pattern::plan_moves(pairs);
+
+        let mut table = TableBuilder::default();
+        for (idx, file_move) in moves.iter().enumerate() {
+            if idx > 0 {
+                table.add_row();
+            }
+            let base = self.te_pattern_base(&file_move.from);
+            let to = file_move.to.strip_prefix(&base).unwrap_or(&file_move.to);
+            let status = TextSpan::new(file_move.status.name());
+
+            table
+                .add_col(TextSpan::new(file_name(&file_move.from)).bold())
+                .add_col(TextSpan::new(to.to_string_lossy()))
+                .add_col(match file_move.status {
+                    MoveStatus::Move => status.fg(Color::LightYellow),
+                    MoveStatus::Unchanged => status.italic(),
+                    MoveStatus::TargetExists | MoveStatus::Duplicate => status.fg(Color::LightRed),
+                });
+        }
+        if moves.is_empty() {
+            table.add_col(TextSpan::from("No files to move."));
+        }
+
+        self.te_pattern = Some(PatternPreview::Rename(moves));
+        self.mount_pattern_preview(
+            PatternPreviewTable::new(
+                self.config_tui.clone(),
+                " Move files: Enter to move, conflicts are skipped, Esc to go back ",
+                &[" File ", " New path ", " Status "],
+                &[30, 55, 15],
+            ),
+            table.build(),
+        );
+    }
+
+    /// Show the tags the parse pattern would read from the paths of all files
+    fn te_pattern_tags(&mut self) {
+        let Some(pattern) = self.te_pattern_parse(false) else {
+            return;
+        };
+        let edit = BatchEdit::read(&self.te_edited_paths());
+
+        let mut changes = Vec::new();
+        let mut unmatched = 0;
+        for file in edit.files() {
+            let Some(values) = pattern.extract(&file.path) else {
+                unmatched += 1;
+                continue;
+            };
+            let edits: Vec<_> = values
+                .into_iter()
+                .map(|(field, value)| (field, FieldEdit::Set(value)))
+                .collect();
+            changes.extend(batch::preview(std::slice::from_ref(file), &edits));
+        }
+
+        let title = if unmatched == 0 {
+            " Tags from file names: Enter to write, Esc to go back ".to_string()
+        } else {
+            format!(
+                " Tags from file names, {unmatched} not matching the pattern: Enter to write, Esc to go back "
+            )
+        };
+        let table = changes_table(&changes);
+
+        self.te_pattern = Some(PatternPreview::Tags(changes));
+        self.mount_pattern_preview(
+            PatternPreviewTable::new(
+                self.config_tui.clone(),
+                &title,
+                &[" File ", " Field ", " Current ", " New "],
+                &[30, 12, 29, 29],
+            ),
+            table,
+        );
+    }
+
+    /// Move the files or write the tags shown in the preview.
+    ///
+    /// This runs on a separate thread, reporting the result via [`PatternMsg::RenameDone`] or [`PatternMsg::TagsDone`].
+    fn te_pattern_apply(&mut self) {
+        let Some(preview) = self.te_pattern.take() else {
+            return;
+        };
+        self.umount_pattern();
+        let tx = self.tx_to_main.clone();
+
+        match preview {
+            PatternPreview::Rename(plan) => {
+                if !plan.iter().any(|v| v.status == MoveStatus::Move) {
+                    return;
+                }
+                self.show_message_timeout_label_help(" Moving files... ", None, None, None);
+                let remove_empty_dirs = self
+                    .config_tui
+                    .read()
+                    .settings
+                    .file_patterns
+                    .remove_empty_dirs;
+
+                std::thread::spawn(move || {
+                    let result = pattern::apply_moves(&plan, remove_empty_dirs);
+                    for (path, err) in &result.failed {
+                        warn!("Failed to move \"{}\": {err:#}", path.display());
+                    }
+                    for dir in &result.removed_dirs {
+                        info!("Removed empty directory \"{}\"", dir.display());
+                    }
+
+                    let _ = tx.send(Msg::TagEditor(TEMsg::TEPattern(PatternMsg::RenameDone(
+                        result.moved,
+                        result.failed.len(),
+                        result.removed_dirs.len(),
+                    ))));
+                });
+            }
+            PatternPreview::Tags(changes) => {
+                if changes.is_empty() {
+                    return;
+                }
+                self.show_message_timeout_label_help(" Writing tags... ", None, None, None);
+
+                std::thread::spawn(move || {
+                    let mut files: Vec<&Path> = changes.iter().map(|v| v.path.as_path()).collect();
+                    files.dedup();
+                    let failed = batch::write_changes(&changes);
+                    for (path, err) in &failed {
+                        warn!("Failed to write tags to \"{}\": {err:#}", path.display());
+                    }
+
+                    let _ = tx.send(Msg::TagEditor(TEMsg::TEPattern(PatternMsg::TagsDone(
+                        files.len() - failed.len(),
+                        failed.len(),
+                    ))));
+                });
+            }
+        }
+    }
+
+    /// Update the moved files in the database and show the files at their new paths.
+    ///
+    /// The loaded playlist and saved playlist files are not updated and still refer to the old paths,
+    /// the message tells how many tracks of the loaded playlist are affected.
+    fn te_pattern_rename_done(
+        &mut self,
+        moved: &[(PathBuf, PathBuf)],
+        failed: usize,
+        removed_dirs: usize,
+    ) {
+        let paths: Vec<PathBuf> = self
+            .te_edited_paths()
+            .into_iter()
+            .map(|path| {
+                moved
+                    .iter()
+                    .find(|(from, _)| *from == path)
+                    .map_or(path, |(_, to)| to.clone())
+            })
+            .collect();
+        self.te_reload_edited(&paths);
+
+        if let Err(err) = self.db.move_records(moved) {
+            self.mount_error_popup(
+                anyhow::Error::new(err).context("update moved tracks in database"),
+            );
+        }
+
+        let in_playlist = self
+            .playback
+            .playlist
+            .tracks()
+            .iter()
+            .filter_map(|track| track.as_track())
+            .filter(|track| moved.iter().any(|(from, _)| from == track.path()))
+            .count();
+
+        let mut parts = vec![format!("Moved {} files", moved.len())];
+        if failed > 0 {
+            parts.push(format!("{failed} failed, see log for details"));
+        }
+        if removed_dirs > 0 {
+            parts.push(format!("removed {removed_dirs} empty directories"));
+        }
+        if in_playlist > 0 {
+            parts.push(format!(
+                "{in_playlist} playlist tracks still use the old paths"
+            ));
+        }
+        let msg = format!(" {}. ", parts.join(", "));
+        self.show_message_timeout_label_help(msg, None, None, None);
+    }
+
+    /// Show the result of writing the tags and re-read all files
+    fn te_pattern_tags_done(&mut self, written: usize, failed: usize) {
+        let paths = self.te_edited_paths();
+        self.te_reload_edited(&paths);
+
+        let msg = if failed == 0 {
+            format!(" Tags written to {written} files. ")
+        } else {
+            format!(" Tags written to {written} files, {failed} failed, see log for details. ")
+        };
+        self.show_message_timeout_label_help(msg, None, None, None);
+    }
+
+    /// Open the tag editor again for `paths`, as the files or their tags have changed
+    pub(super) fn te_reload_edited(&mut self, paths: &[PathBuf]) {
+        if self.te_batch.is_some() {
+            self.umount_batch();
+            self.mount_tageditor_batch(paths);
+        } else if let Some(path) = paths.first() {
+            self.mount_tageditor(&path.to_string_lossy());
+        }
+    }
+
+    fn mount_pattern_preview(
+        &mut self,
+        component: PatternPreviewTable,
+        table: tuirealm::props::Table,
+    ) {
+        assert!(self
+            .app
+            .remount(
+                Id::TagEditor(IdTagEditor::PatternPreview),
+                Box::new(component),
+                vec![]
+            )
+            .is_ok());
+        self.app
+            .attr(
+                &Id::TagEditor(IdTagEditor::PatternPreview),
+                Attribute::Content,
+                AttrValue::Table(table),
+            )
+            .ok();
+        assert!(self
+            .app
+            .active(&Id::TagEditor(IdTagEditor::PatternPreview))
+            .is_ok());
+    }
+
+    /// Close the preview, if open
+    pub fn umount_pattern(&mut self) {
+        self.te_pattern = None;
+        if self
+            .app
+            .mounted(&Id::TagEditor(IdTagEditor::PatternPreview))
+        {
+            assert!(self
+                .app
+                .umount(&Id::TagEditor(IdTagEditor::PatternPreview))
+                .is_ok());
+            self.app
+                .active(&Id::TagEditor(IdTagEditor::InputArtist))
+                .ok();
+        }
+    }
+}
@@ -29,6 +29,7 @@ use tuirealm::event::{Key, KeyEvent, KeyModifiers};
 use tuirealm::props::{Alignment, BorderType, Borders};
 use tuirealm::{Component, Event, MockComponent, State, StateValue};
 
+use super::te_keys::action_key_msg;
 use crate::ui::model::UserEvent;
 
 #[derive(MockComponent)]
@@ -63,6 +64,9 @@ impl Component<Msg, UserEvent> for TESelectLyric {
     fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
         let config = self.config.clone();
         let keys = &config.read().settings.keys;
+        if let Some(msg) = action_key_msg(&keys.tag_editor_keys, &ev) {
+            return Some(msg);
+        }
         let cmd_result = match ev {
             Event::Keyboard(keyevent) if keyevent == keys.config_keys.save.get() => {
                 return Some(Msg::TagEditor(TEMsg::TERename))
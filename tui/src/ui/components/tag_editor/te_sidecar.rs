@@ -0,0 +1,83 @@
+use std::str::FromStr;
+
+use anyhow::{bail, Context, Result};
+use termusiclib::songtag::lrc::Lyric;
+use termusiclib::songtag::sidecar;
+
+use crate::ui::Model;
+
+impl Model {
+    /// Add the sidecar lyric file of the current tag editor track as a new lyric frame, which is written on the next save.
+    ///
+    /// # Errors
+    ///
+    /// - if no sidecar lyric file could be found in the configured `lyrics.sidecar_dirs`
+    /// - if reading the sidecar lyric file fails
+    pub fn te_embed_sidecar(&mut self) -> Result<()> {
+        let Some(mut song) = self.tageditor_song.clone() else {
+            return Ok(());
+        };
+
+        let sidecar_dirs = self.config_tui.read().settings.lyrics.sidecar_dirs.clone();
+        let Some(path) =
+            sidecar::find_sidecar(song.path(), song.artist(), song.title(), &sidecar_dirs)
+        else {
+            bail!(
+                "No sidecar lyric file found for \"{}\"",
+                song.path().display()
+            );
+        };
+
+        let frame = sidecar::read_sidecar(&path)?;
+        song.add_lyric(frame);
+        self.init_by_song(song)?;
+
+        self.show_message_timeout_label_help(
+            format!(
+                " Added lyrics from \"{}\", save to embed them. ",
+                path.display()
+            ),
+            None,
+            None,
+            None,
+        );
+
+        Ok(())
+    }
+
+    /// Write the selected lyric frame of the current tag editor track to a sidecar file next to the track.
+    ///
+    /// The file is `<basename>.lrc` for synced lyrics and `<basename>.txt` otherwise.
+    ///
+    /// # Errors
+    ///
+    /// - if there is no lyric frame selected
+    /// - if the sidecar file already exists
+    /// - if writing the file fails
+    pub fn te_export_sidecar(&mut self) -> Result<()> {
+        let Some(song) = &self.tageditor_song else {
+            return Ok(());
+        };
+        let Some(lyric) = song.lyric_selected() else {
+            bail!("No lyrics to export");
+        };
+
+        let synced = Lyric::from_str(&lyric.text).is_ok_and(|v| !v.captions.is_empty());
+        let path = sidecar::sidecar_export_path(song.path(), synced);
+        if path.exists() {
+            bail!("\"{}\" already exists", path.display());
+        }
+
+        std::fs::write(&path, &lyric.text)
+            .with_context(|| format!("writing \"{}\"", path.display()))?;
+
+        self.show_message_timeout_label_help(
+            format!(" Exported lyrics to \"{}\". ", path.display()),
+            None,
+            None,
+            None,
+        );
+
+        Ok(())
+    }
+}
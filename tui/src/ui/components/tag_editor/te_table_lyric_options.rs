@@ -1,3 +1,4 @@
+use super::te_keys::action_key_msg;
 use crate::ui::model::UserEvent;
 use crate::ui::Model;
 use anyhow::{anyhow, Context, Result};
@@ -81,6 +82,9 @@ impl Component<Msg, UserEvent> for TETableLyricOptions {
     fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
         let config = self.config.clone();
         let keys = &config.read().settings.keys;
+        if let Some(msg) = action_key_msg(&keys.tag_editor_keys, &ev) {
+            return Some(msg);
+        }
         let cmd_result = match ev {
             Event::Keyboard(KeyEvent { code: Key::Tab, .. }) => {
                 return Some(Msg::TagEditor(TEMsg::TEFocus(
@@ -262,9 +266,10 @@ impl Model {
 
         let songtag_tx = self.tx_to_main.clone();
         let tracker_handle = self.download_tracker.clone();
+        let providers = self.config_tui.read().settings.lyrics.providers.clone();
 
         handle.spawn(async move {
-            search(&search_str, songtag_tx).await;
+            search(&search_str, &providers, songtag_tx).await;
             tracker_handle.decrease_one(&search_str);
         });
     }
@@ -23,13 +23,15 @@
  */
 
 use termusiclib::config::SharedTuiSettings;
-use termusiclib::types::{Msg, TEMsg, TFMsg};
+use termusiclib::types::{LyricSyncMsg, Msg, PlayerMsg, TEMsg, TFMsg};
 use tui_realm_stdlib::Textarea;
 use tuirealm::command::{Cmd, Direction, Position};
 use tuirealm::event::{Key, KeyEvent, KeyModifiers};
-use tuirealm::props::{Alignment, BorderType, Borders, TextSpan};
+use tuirealm::props::{Alignment, AttrValue, Attribute, BorderType, Borders, TextSpan};
 use tuirealm::{Component, Event, MockComponent};
 
+use super::te_keys::action_key_msg;
+use super::te_lyric_sync::{ATTR_LYRIC_SYNC, NUDGE_STEP};
 use crate::ui::model::UserEvent;
 
 #[derive(MockComponent)]
@@ -57,12 +59,66 @@ impl TETextareaLyric {
         };
         Self { component, config }
     }
+
+    /// Whether the tap-to-sync lyric editor is active
+    fn is_syncing(&self) -> bool {
+        self.query(Attribute::Custom(ATTR_LYRIC_SYNC))
+            .is_some_and(AttrValue::unwrap_flag)
+    }
+
+    /// Handle the keys of the tap-to-sync lyric editor, which take precedence while it is active.
+    fn on_sync(&self, ev: &Event<UserEvent>) -> Option<Msg> {
+        let keys = &self.config.read().settings.keys;
+        let msg = match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => LyricSyncMsg::Stamp,
+            Event::Keyboard(KeyEvent {
+                code: Key::Backspace,
+                ..
+            }) => LyricSyncMsg::Undo,
+            Event::Keyboard(KeyEvent {
+                code: Key::Left, ..
+            }) => LyricSyncMsg::Nudge(-NUDGE_STEP),
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => LyricSyncMsg::Nudge(NUDGE_STEP),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('p'),
+                modifiers: KeyModifiers::NONE,
+            }) => LyricSyncMsg::TogglePreview,
+            Event::Keyboard(k) if *k == keys.config_keys.save.get() => LyricSyncMsg::Save,
+            Event::Keyboard(k) if *k == keys.escape.get() => LyricSyncMsg::Cancel,
+            // the global player keys are not active in the tag editor
+            Event::Keyboard(k) if *k == keys.player_keys.toggle_pause.get() => {
+                return Some(Msg::Player(PlayerMsg::TogglePause));
+            }
+            Event::Keyboard(k) if *k == keys.player_keys.seek_forward.get() => {
+                return Some(Msg::Player(PlayerMsg::SeekForward));
+            }
+            Event::Keyboard(k) if *k == keys.player_keys.seek_backward.get() => {
+                return Some(Msg::Player(PlayerMsg::SeekBackward));
+            }
+            _ => return None,
+        };
+
+        Some(Msg::TagEditor(TEMsg::TELyricSync(msg)))
+    }
 }
 
 impl Component<Msg, UserEvent> for TETextareaLyric {
     fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        if self.is_syncing() {
+            if let Some(msg) = self.on_sync(&ev) {
+                return Some(msg);
+            }
+        }
+
         let config = self.config.clone();
         let keys = &config.read().settings.keys;
+        if let Some(msg) = action_key_msg(&keys.tag_editor_keys, &ev) {
+            return Some(msg);
+        }
         let _cmd_result = match ev {
             Event::Keyboard(keyevent) if keyevent == keys.config_keys.save.get() => {
                 return Some(Msg::TagEditor(TEMsg::TERename))
@@ -2,7 +2,6 @@ use std::{
     borrow::Cow,
     path::{Path, PathBuf},
     str::FromStr,
-    time::Duration,
 };
 
 use anyhow::{bail, Result};
@@ -15,12 +14,14 @@ use lofty::{
     tag::{Accessor, ItemKey, ItemValue, Tag, TagExt, TagItem},
 };
 use termusiclib::{
-    songtag::lrc::Lyric,
-    track::{parse_metadata_from_file, LyricData, MetadataOptions, Track},
+    songtag::{
+        cover::{self, CoverKind},
+        lrc::Lyric,
+        sylt,
+    },
+    track::{parse_metadata_from_file, MetadataOptions, Track},
 };
 
-use crate::ui::model::ExtraLyricData;
-
 /// Track data for the Tag-Editor with helper functions
 #[derive(Debug, Clone, PartialEq)]
 pub struct TETrack {
@@ -31,7 +32,8 @@ pub struct TETrack {
     album: Option<String>,
     genre: Option<String>,
 
-    picture: Option<Picture>,
+    /// All embedded pictures, to keep the back cover and others when saving
+    pictures: Vec<Picture>,
 
     lyric_selected_idx: usize,
     lyric_frames: Vec<Lyrics>,
@@ -59,7 +61,7 @@ impl TryFrom<&Track> for TETrack {
             album: track_data.album().map(|v| v.to_string()),
             // TODO: init genre
             genre: None,
-            picture: None,
+            pictures: Vec::new(),
             lyric_selected_idx: 0,
             lyric_frames: Vec::new(),
             lyric_parsed: None,
@@ -111,8 +113,16 @@ impl TETrack {
         self.genre = Some(value.into());
     }
 
+    /// Set the picture, replacing the one of the same cover kind
     pub fn set_picture(&mut self, value: Picture) {
-        self.picture = Some(value);
+        let kind = [CoverKind::Front, CoverKind::Back]
+            .into_iter()
+            .find(|kind| kind.matches(value.pic_type()));
+        self.pictures.retain(|v| match kind {
+            Some(kind) => !kind.matches(v.pic_type()),
+            None => v.pic_type() != value.pic_type(),
+        });
+        self.pictures.push(value);
     }
 
     /// Set the current selected lyric with the given data, or add one with the given data if there is none.
@@ -141,6 +151,13 @@ impl TETrack {
         }
     }
 
+    /// Add a new lyric frame and select it.
+    pub fn add_lyric(&mut self, frame: Lyrics) {
+        self.lyric_parsed = Lyric::from_str(&frame.text).ok();
+        self.lyric_frames.push(frame);
+        self.lyric_selected_idx = self.lyric_frames.len() - 1;
+    }
+
     pub fn set_lyric_selected_index(&mut self, index: usize) {
         self.lyric_selected_idx = index;
     }
@@ -168,57 +185,6 @@ impl TETrack {
         self.lyric_parsed = parsed;
     }
 
-    /// Apply Lyric data from [`ExtraLyricData`]. But only if it is `Some` and for the same track-path.
-    pub fn lyric_set_with_extra(&mut self, extra_lyric: Option<&ExtraLyricData>) -> Option<()> {
-        let extra_lyric = extra_lyric?;
-        // dont apply lyric data for a different track
-        if extra_lyric.for_track != self.path {
-            return None;
-        }
-
-        self.lyric_selected_idx = extra_lyric.selected_idx;
-        self.lyric_frames.clone_from(&extra_lyric.data.raw_lyrics);
-        self.lyric_parsed
-            .clone_from(&extra_lyric.data.parsed_lyrics);
-
-        Some(())
-    }
-
-    /// Adjust the lyric delay at `time_pos` by `offset`.
-    ///
-    /// See [`Lyric::adjust_offset`].
-    pub fn lyric_adjust_delay(&mut self, time_pos: Duration, offset: i64) {
-        if let Some(lyric) = self.lyric_parsed.as_mut() {
-            lyric.adjust_offset(time_pos, offset);
-            let raw = self.lyric_frames.get(self.lyric_selected_idx);
-            let lang = raw.map_or_else(|| "eng".to_string(), |v| v.lang.clone());
-            let description = raw.map_or_else(
-                || "Adjusted".to_string(),
-                |v| {
-                    if v.description.starts_with("Adjusted") {
-                        v.description.clone()
-                    } else {
-                        format!("Adjusted {}", v.description)
-                    }
-                },
-            );
-            let content = lyric.as_lrc_text();
-            self.set_lyric(content, lang, Some(description));
-        }
-    }
-
-    /// Convert the current instance to only [`ExtraLyricData`].
-    pub fn into_extra_lyric_data(self) -> ExtraLyricData {
-        ExtraLyricData {
-            for_track: self.path,
-            data: LyricData {
-                raw_lyrics: self.lyric_frames,
-                parsed_lyrics: self.lyric_parsed,
-            },
-            selected_idx: self.lyric_selected_idx,
-        }
-    }
-
     /// Save the current tag data to the given path.
     pub fn save_tag(&mut self) -> Result<()> {
         match self.file_type {
@@ -234,8 +200,8 @@ impl TETrack {
         let mut tag = Tag::new(tag_type);
         self.set_data_on_tag(&mut tag);
 
-        if let Some(picture) = self.picture.clone() {
-            tag.push_picture(picture);
+        for picture in &self.pictures {
+            tag.push_picture(picture.clone());
         }
 
         if !self.lyric_frames.is_empty() {
@@ -266,8 +232,8 @@ impl TETrack {
         let mut tag = Id3v2Tag::default();
         self.set_data_on_tag(&mut tag);
 
-        if let Some(picture) = self.picture.clone() {
-            tag.insert_picture(picture);
+        for picture in &self.pictures {
+            tag.insert_picture(picture.clone());
         }
 
         if !self.lyric_frames.is_empty() {
@@ -285,6 +251,10 @@ impl TETrack {
                     lyric.text.clone(),
                 ));
                 tag.insert(frame);
+
+                if let Some(frame) = sylt::sylt_frame(lyric) {
+                    tag.insert(frame);
+                }
             }
         }
 
@@ -326,7 +296,6 @@ impl TETrack {
                 artist: true,
                 title: true,
                 genre: true,
-                cover: true,
                 lyrics: true,
                 ..Default::default()
             },
@@ -342,13 +311,16 @@ impl TETrack {
             .first()
             .and_then(|v| Lyric::from_str(&v.text).ok());
 
+        // all pictures, not only the cover from the metadata
+        let pictures = cover::read_pictures(&path)?;
+
         let res = Self {
             path,
             artist: metadata.artist,
             title: metadata.title,
             album: metadata.album,
             genre: metadata.genre,
-            picture: metadata.cover,
+            pictures,
             lyric_selected_idx: 0,
             lyric_frames,
             lyric_parsed,
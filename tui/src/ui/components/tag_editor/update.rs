@@ -27,14 +27,22 @@ use termusiclib::types::{TEMsg, TFMsg};
 
 impl Model {
     pub fn update_tageditor(&mut self, msg: TEMsg) {
+        let Some(msg) = self.te_batch_filter(msg) else {
+            return;
+        };
         match msg {
             TEMsg::TagEditorRun(node_id) => {
                 self.mount_tageditor(&node_id);
             }
+            TEMsg::TagEditorRunBatch(paths) => {
+                self.mount_tageditor_batch(&paths);
+            }
             TEMsg::TagEditorClose => {
                 if let Some(s) = self.tageditor_song.clone() {
                     // TODO: this should be re-done and take actual track ids themself, or at least verified to use the same functions to result in the same id
                     self.library_reload_with_node_focus(Some(s.path_as_id_str().to_string()));
+                } else if self.te_batch.is_some() {
+                    self.library_reload_with_node_focus(None);
                 }
                 self.umount_tageditor();
             }
@@ -70,12 +78,48 @@ impl Model {
             TEMsg::TEFocus(m) => self.update_tag_editor_focus(m),
 
             TEMsg::TESearchLyricResult(m) => self.te_update_lyric_results(m),
+
+            TEMsg::TEAnalyzeLoudness => self.te_analyze_loudness(),
+            TEMsg::TELoudnessProgress(current, total) => {
+                self.show_message_timeout_label_help(
+                    format!(" Analyzing loudness: {current} of {total} tracks... "),
+                    None,
+                    None,
+                    None,
+                );
+            }
+            TEMsg::TELoudnessDone(tagged, failed) => self.te_loudness_done(tagged, failed),
+            TEMsg::TEEmbedSidecar => {
+                if let Err(e) = self.te_embed_sidecar() {
+                    self.mount_error_popup(e.context("embed sidecar lyrics"));
+                }
+            }
+            TEMsg::TEExportSidecar => {
+                if let Err(e) = self.te_export_sidecar() {
+                    self.mount_error_popup(e.context("export sidecar lyrics"));
+                }
+            }
+            TEMsg::TELyricSync(m) => self.te_update_lyric_sync(m),
+            TEMsg::TEMusicBrainz(m) => self.te_update_musicbrainz(m),
+            TEMsg::TEBatch(m) => self.te_update_batch(m),
+            TEMsg::TEPattern(m) => self.te_update_pattern(m),
+            TEMsg::TECoverArt(m) => self.te_update_cover(m),
         }
     }
 
     fn update_tag_editor_focus(&mut self, msg: TFMsg) {
+        // when editing many files, the list of files takes the place of the lyric section
+        if self.te_batch.is_some()
+            && matches!(msg, TFMsg::InputGenreBlurDown | TFMsg::InputArtistBlurUp)
+        {
+            self.app
+                .active(&Id::TagEditor(IdTagEditor::BatchFiles))
+                .ok();
+            return;
+        }
+
         match msg {
-            TFMsg::TextareaLyricBlurDown | TFMsg::InputTitleBlurUp => {
+            TFMsg::TextareaLyricBlurDown | TFMsg::InputTitleBlurUp | TFMsg::BatchFilesBlurDown => {
                 self.app
                     .active(&Id::TagEditor(IdTagEditor::InputArtist))
                     .ok();
@@ -90,7 +134,9 @@ impl Model {
                     .active(&Id::TagEditor(IdTagEditor::InputAlbum))
                     .ok();
             }
-            TFMsg::InputAlbumBlurDown | TFMsg::TableLyricOptionsBlurUp => {
+            TFMsg::InputAlbumBlurDown
+            | TFMsg::TableLyricOptionsBlurUp
+            | TFMsg::BatchFilesBlurUp => {
                 self.app
                     .active(&Id::TagEditor(IdTagEditor::InputGenre))
                     .ok();
@@ -27,16 +27,20 @@ use crate::ui::components::{
     TESelectLyric, TETableLyricOptions, TETextareaLyric,
 };
 use crate::ui::model::Model;
-use crate::ui::utils::{draw_area_in_absolute, draw_area_top_right_absolute};
+use crate::ui::utils::{
+    draw_area_in_absolute, draw_area_in_relative, draw_area_top_right_absolute,
+};
 use anyhow::Result;
 use std::borrow::Cow;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use termusiclib::ids::{Id, IdTagEditor};
+use termusiclib::utils::filetype_supported;
 use tuirealm::props::{Alignment, AttrValue, Attribute, PropPayload, PropValue, TextSpan};
 use tuirealm::ratatui::layout::{Constraint, Layout};
 use tuirealm::ratatui::widgets::Clear;
 use tuirealm::State;
 
+use super::te_batch::{BatchEdit, BatchFilesTable};
 use super::TETrack;
 
 impl Model {
@@ -112,27 +116,76 @@ impl Model {
                         .view(&Id::TagEditor(IdTagEditor::InputAlbum), f, chunks_row2[0]);
                     self.app
                         .view(&Id::TagEditor(IdTagEditor::InputGenre), f, chunks_row2[1]);
-                    self.app.view(
-                        &Id::TagEditor(IdTagEditor::TableLyricOptions),
-                        f,
-                        chunks_row4[0],
-                    );
-                    self.app.view(
-                        &Id::TagEditor(IdTagEditor::SelectLyric),
-                        f,
-                        chunks_row4_right_top[0],
-                    );
-                    self.app.view(
-                        &Id::TagEditor(IdTagEditor::CounterDelete),
-                        f,
-                        chunks_row4_right_top[1],
-                    );
-                    self.app.view(
-                        &Id::TagEditor(IdTagEditor::TextareaLyric),
-                        f,
-                        chunks_row4_right[1],
-                    );
+                    if self.app.mounted(&Id::TagEditor(IdTagEditor::BatchFiles)) {
+                        self.app
+                            .view(&Id::TagEditor(IdTagEditor::BatchFiles), f, chunks_main[3]);
+                    } else {
+                        self.app.view(
+                            &Id::TagEditor(IdTagEditor::TableLyricOptions),
+                            f,
+                            chunks_row4[0],
+                        );
+                        self.app.view(
+                            &Id::TagEditor(IdTagEditor::SelectLyric),
+                            f,
+                            chunks_row4_right_top[0],
+                        );
+                        self.app.view(
+                            &Id::TagEditor(IdTagEditor::CounterDelete),
+                            f,
+                            chunks_row4_right_top[1],
+                        );
+                        self.app.view(
+                            &Id::TagEditor(IdTagEditor::TextareaLyric),
+                            f,
+                            chunks_row4_right[1],
+                        );
+                    }
 
+                    if self.app.mounted(&Id::TagEditor(IdTagEditor::BatchPreview)) {
+                        let popup = draw_area_in_relative(f.area(), 80, 70);
+                        f.render_widget(Clear, popup);
+                        self.app
+                            .view(&Id::TagEditor(IdTagEditor::BatchPreview), f, popup);
+                    }
+                    if self
+                        .app
+                        .mounted(&Id::TagEditor(IdTagEditor::PatternPreview))
+                    {
+                        let popup = draw_area_in_relative(f.area(), 80, 70);
+                        f.render_widget(Clear, popup);
+                        self.app
+                            .view(&Id::TagEditor(IdTagEditor::PatternPreview), f, popup);
+                    }
+                    if self.app.mounted(&Id::TagEditor(IdTagEditor::CoverArt)) {
+                        let popup = draw_area_in_relative(f.area(), 70, 40);
+                        f.render_widget(Clear, popup);
+                        self.app
+                            .view(&Id::TagEditor(IdTagEditor::CoverArt), f, popup);
+                    }
+                    if self.app.mounted(&Id::TagEditor(IdTagEditor::CoverFile)) {
+                        let popup = draw_area_in_absolute(f.area(), 100, 3);
+                        f.render_widget(Clear, popup);
+                        self.app
+                            .view(&Id::TagEditor(IdTagEditor::CoverFile), f, popup);
+                    }
+                    if self
+                        .app
+                        .mounted(&Id::TagEditor(IdTagEditor::MusicBrainzChanges))
+                    {
+                        let popup = draw_area_in_relative(f.area(), 80, 70);
+                        f.render_widget(Clear, popup);
+                        self.app
+                            .view(&Id::TagEditor(IdTagEditor::MusicBrainzChanges), f, popup);
+                    } else if self
+                        .app
+                        .mounted(&Id::TagEditor(IdTagEditor::MusicBrainzCandidates))
+                    {
+                        let popup = draw_area_in_relative(f.area(), 80, 60);
+                        f.render_widget(Clear, popup);
+                        self.app
+                            .view(&Id::TagEditor(IdTagEditor::MusicBrainzCandidates), f, popup);
+                    }
                     if self.app.mounted(&Id::MessagePopup) {
                         let popup = draw_area_top_right_absolute(f.area(), 25, 4);
                         f.render_widget(Clear, popup);
@@ -208,10 +261,24 @@ impl Model {
     }
 
     /// Mount the tageditor with the selected node id as the path.
+    ///
+    /// For a directory, all supported files directly in it are edited at once.
     pub fn mount_tageditor(&mut self, node_id: &str) {
         let node_path: &Path = Path::new(node_id);
         if node_path.is_dir() {
-            self.mount_error_popup(anyhow::anyhow!("{node_path:?} directory doesn't have tag!"));
+            let files: Vec<PathBuf> = Self::library_dir_children(node_path)
+                .into_iter()
+                .map(PathBuf::from)
+                .filter(|v| filetype_supported(v))
+                .collect();
+            if files.is_empty() {
+                self.mount_error_popup(anyhow::anyhow!(
+                    "{node_path:?} directory doesn't have any tracks!"
+                ));
+                return;
+            }
+
+            self.mount_tageditor_batch(&files);
             return;
         }
 
@@ -237,6 +304,59 @@ impl Model {
         }
     }
 
+    /// Mount the tageditor for all `paths` at once, or like [`Model::mount_tageditor`] if there is only one.
+    pub fn mount_tageditor_batch(&mut self, paths: &[PathBuf]) {
+        if let [path] = paths {
+            self.mount_tageditor(&path.to_string_lossy());
+            return;
+        }
+
+        let edit = BatchEdit::read(paths);
+        if edit.len() < 2 {
+            if let Some(path) = edit.paths().first() {
+                self.mount_tageditor(&path.to_string_lossy());
+            } else {
+                self.mount_error_popup(anyhow::anyhow!("None of the files could be read"));
+            }
+            return;
+        }
+
+        self.remount_tageditor().unwrap();
+        self.app
+            .remount(
+                Id::Label,
+                Box::new(TEFooter::new_batch(&self.config_tui.read())),
+                Vec::new(),
+            )
+            .unwrap();
+        self.app
+            .remount(
+                Id::TagEditor(IdTagEditor::LabelHint),
+                Box::new(LabelGeneric::new(
+                    &self.config_tui.read(),
+                    "Fields showing <keep> differ and are left as-is, empty fields are removed. Press <ENTER> to preview:",
+                )),
+                Vec::new(),
+            )
+            .unwrap();
+        self.app
+            .remount(
+                Id::TagEditor(IdTagEditor::BatchFiles),
+                Box::new(BatchFilesTable::new(self.config_tui.clone(), edit.len())),
+                Vec::new(),
+            )
+            .unwrap();
+
+        self.app
+            .active(&Id::TagEditor(IdTagEditor::InputArtist))
+            .ok();
+
+        self.tageditor_song = None;
+        self.te_batch = Some(edit);
+        self.init_by_song_no_lyric();
+        self.init_by_batch();
+    }
+
     /// Unmount the Tag Editor
     fn umount_tageditor_inner(&mut self) -> Result<()> {
         self.app.umount(&Id::TagEditor(IdTagEditor::LabelHint))?;
@@ -256,6 +376,11 @@ impl Model {
     }
 
     pub fn umount_tageditor(&mut self) {
+        self.te_lyric_sync = None;
+        self.umount_musicbrainz();
+        self.umount_batch();
+        self.umount_pattern();
+        self.umount_cover();
         self.mount_label_help();
         self.umount_tageditor_inner().unwrap();
         if let Err(e) = self.update_photo() {
@@ -32,13 +32,16 @@ use anyhow::Result;
 use futures_util::FutureExt;
 use model::{Model, TermusicLayout};
 use music_player_client::Playback;
+use std::path::Path;
 use std::time::Duration;
 use sysinfo::Pid;
 use sysinfo::System;
 use termusiclib::player::music_player_client::MusicPlayerClient;
-use termusiclib::player::playlist_helpers::PlaylistRemoveTrackType;
+use termusiclib::player::playlist_helpers::{PlaylistRemoveTrackType, PlaylistTrackSource};
 use termusiclib::player::PlayerProgress;
 use termusiclib::player::RunningStatus;
+use termusiclib::player::SpectrumData;
+use termusiclib::player::SpectrumUpdate;
 use termusiclib::player::StreamUpdates;
 use termusiclib::player::UpdateEvents;
 use termusiclib::player::UpdatePlaylistEvents;
@@ -101,6 +104,7 @@ impl UI {
     /// This function does NOT handle initializing and finializing the terminal.
     async fn run_inner(&mut self) -> Result<()> {
         let mut stream_updates = self.playback.subscribe_to_stream_updates().await?;
+        let mut spectrum_updates = None;
 
         self.load_playlist().await?;
 
@@ -111,9 +115,20 @@ impl UI {
             if self.model.layout != TermusicLayout::Podcast {
                 self.model.lyric_update();
             }
+            self.model.te_lyric_sync_update();
             if let Err(err) = self.handle_stream_events(&mut stream_updates) {
                 self.model.mount_error_popup(err);
             }
+            // only subscribe while actually shown, to not have the server do unnecessary work;
+            // checked every loop as the config editor can change it
+            if self.model.config_tui.read().settings.spectrum.hidden {
+                spectrum_updates = None;
+            } else if spectrum_updates.is_none() {
+                spectrum_updates = Some(self.playback.subscribe_to_spectrum().await?);
+            }
+            if let Some(spectrum_updates) = &mut spectrum_updates {
+                self.handle_spectrum_events(spectrum_updates);
+            }
             if progress_interval == 0 {
                 self.model.run();
             }
@@ -227,6 +242,7 @@ impl UI {
     }
 
     /// Execute a TUI-Server Request from the channel.
+    #[allow(clippy::too_many_lines)]
     async fn run_playback(&mut self) -> Result<()> {
         if let Ok(cmd) = self.cmd_rx.try_recv() {
             match cmd {
@@ -243,19 +259,32 @@ impl UI {
                 TuiCmd::GetProgress => {
                     let response = self.playback.get_progress().await?;
                     let pprogress: PlayerProgress = response.progress.unwrap_or_default().into();
+                    self.model
+                        .playback
+                        .set_silence_skipped(response.silence_skipped.map(Into::into));
                     self.model.progress_update(
                         pprogress.position,
                         pprogress.total_duration.unwrap_or_default(),
                     );
                     if response.current_track_updated {
-                        self.model.handle_current_track_index(
+                        let queued_track = response
+                            .queued_track
+                            .map(PlaylistTrackSource::try_from)
+                            .transpose()?;
+                        self.model.handle_current_track_changed(
                             usize::try_from(response.current_track_index).unwrap(),
-                            false,
+                            queued_track,
                         );
                     }
 
                     self.model.lyric_update_for_radio(response.radio_title);
 
+                    if let Some(controls) = response.playback_controls {
+                        if self.model.playback.set_playback_controls(controls.into()) {
+                            self.model.playlist_update_title();
+                        }
+                    }
+
                     self.handle_status(RunningStatus::from_u32(response.status));
                 }
 
@@ -290,6 +319,26 @@ impl UI {
                         self.playback.speed_up().await?;
                     self.model.progress_update_title();
                 }
+                TuiCmd::PitchUp => {
+                    self.model.config_server.write().settings.player.pitch =
+                        self.playback.pitch_up().await?;
+                    self.model.progress_update_title();
+                }
+                TuiCmd::PitchDown => {
+                    self.model.config_server.write().settings.player.pitch =
+                        self.playback.pitch_down().await?;
+                    self.model.progress_update_title();
+                }
+                TuiCmd::PitchFineUp => {
+                    self.model.config_server.write().settings.player.pitch =
+                        self.playback.pitch_fine_up().await?;
+                    self.model.progress_update_title();
+                }
+                TuiCmd::PitchFineDown => {
+                    self.model.config_server.write().settings.player.pitch =
+                        self.playback.pitch_fine_down().await?;
+                    self.model.progress_update_title();
+                }
                 TuiCmd::ToggleGapless => {
                     self.model.config_server.write().settings.player.gapless =
                         self.playback.toggle_gapless().await?;
@@ -305,6 +354,8 @@ impl UI {
                     self.model.config_server.write().settings.player.volume = volume;
                     self.model.progress_update_title();
                 }
+                TuiCmd::SetABLoop(range) => self.playback.set_ab_loop(range).await?,
+                TuiCmd::ClearABLoop => self.playback.clear_ab_loop().await?,
                 TuiCmd::Playlist(ev) => self.run_playback_playlist(ev).await?,
             }
         }
@@ -332,8 +383,14 @@ impl UI {
                     .remove_from_playlist(PlaylistRemoveTrackType::Clear)
                     .await?;
             }
-            PlaylistCmd::SwapTrack(info) => {
-                self.playback.swap_tracks(info).await?;
+            PlaylistCmd::MoveTracks(info) => {
+                self.playback.move_tracks(info).await?;
+            }
+            PlaylistCmd::RemoveSelection(selection) => {
+                self.playback.remove_selection(selection).await?;
+            }
+            PlaylistCmd::MoveSelection(info) => {
+                self.playback.move_selection(info).await?;
             }
             PlaylistCmd::Shuffle => {
                 self.playback.shuffle_playlist().await?;
@@ -344,6 +401,57 @@ impl UI {
             PlaylistCmd::RemoveDeletedItems => {
                 self.playback.remove_deleted_tracks().await?;
             }
+            PlaylistCmd::Undo => {
+                self.playback.undo_playlist().await?;
+            }
+            PlaylistCmd::Redo => {
+                self.playback.redo_playlist().await?;
+            }
+            PlaylistCmd::Sort(sort) => {
+                self.playback.sort_playlist(sort).await?;
+            }
+            PlaylistCmd::Deduplicate => {
+                self.playback.deduplicate_playlist().await?;
+            }
+            PlaylistCmd::SaveToFile(path, overwrite) => {
+                self.save_playlist_file(&path, overwrite).await;
+            }
+            PlaylistCmd::ListNamed => match self.playback.list_playlists().await {
+                Ok(list) => self.model.handle_named_playlists(list),
+                Err(err) => self.model.mount_error_popup(err.context("list playlists")),
+            },
+            PlaylistCmd::LoadNamed(name) => {
+                // the new tracks will be sent as a stream event
+                if let Err(err) = self.playback.load_playlist(name).await {
+                    self.model.mount_error_popup(err.context("load playlist"));
+                }
+            }
+            PlaylistCmd::SaveNamed(name) => {
+                let res = self.playback.save_playlist_as(name).await;
+                self.after_named_playlist_change(res.context("save playlist"));
+            }
+            PlaylistCmd::SaveSelectionNamed(name, selection) => {
+                let res = self.playback.save_selection_as(name, selection).await;
+                self.after_named_playlist_change(res.context("save marked tracks as playlist"));
+            }
+            PlaylistCmd::RenameNamed(from, to) => {
+                let res = self.playback.rename_playlist(from, to).await;
+                self.after_named_playlist_change(res.context("rename playlist"));
+            }
+            PlaylistCmd::DeleteNamed(name) => {
+                let res = self.playback.delete_playlist(name).await;
+                self.after_named_playlist_change(res.context("delete playlist"));
+            }
+            PlaylistCmd::QueueAdd(tracks) => {
+                // the new queue will be sent as a stream event
+                self.playback.queue_add(tracks).await?;
+            }
+            PlaylistCmd::QueueRemove(info) => {
+                self.playback.queue_remove(info).await?;
+            }
+            PlaylistCmd::QueueClear => {
+                self.playback.queue_clear().await?;
+            }
             PlaylistCmd::SelfReloadPlaylist => {
                 self.load_playlist().await?;
             }
@@ -386,6 +494,9 @@ impl UI {
                 UpdateEvents::SpeedChanged { speed } => {
                     self.model.config_server.write().settings.player.speed = speed;
                 }
+                UpdateEvents::PitchChanged { pitch } => {
+                    self.model.config_server.write().settings.player.pitch = pitch;
+                }
                 UpdateEvents::PlayStateChanged { playing } => {
                     self.model
                         .playback
@@ -401,9 +512,9 @@ impl UI {
                     }
 
                     if track_changed_info.current_track_updated {
-                        self.model.handle_current_track_index(
+                        self.model.handle_current_track_changed(
                             usize::try_from(track_changed_info.current_track_index).unwrap(),
-                            false,
+                            track_changed_info.queued_track,
                         );
                     }
 
@@ -415,12 +526,40 @@ impl UI {
                     self.model.config_server.write().settings.player.gapless = gapless;
                 }
                 UpdateEvents::PlaylistChanged(ev) => self.handle_playlist_events(ev)?,
+                UpdateEvents::ABLoopChanged { range } => {
+                    self.model.playback.set_ab_loop(range);
+                    self.model.progress_update_ab_loop();
+                }
             }
         }
 
         Ok(())
     }
 
+    /// Handle Spectrum updates from the provided stream.
+    ///
+    /// - Does not wait until the next event (non-blocking).
+    /// - Only applies the latest available frame, older ones are discarded.
+    fn handle_spectrum_events(
+        &mut self,
+        stream: &mut (impl Stream<Item = Result<SpectrumUpdate, anyhow::Error>> + std::marker::Unpin),
+    ) {
+        let mut latest = None;
+        while let Some(ev) = stream.next().now_or_never().flatten() {
+            match ev {
+                Ok(ev) => latest = Some(ev),
+                Err(err) => {
+                    warn!("Spectrum stream error: {err:#}");
+                    break;
+                }
+            }
+        }
+
+        if let Some(ev) = latest {
+            self.model.spectrum_update(SpectrumData::from(ev).bands);
+        }
+    }
+
     /// Handle Playlist Update Events, separately from [`Self::handle_stream_events`] to lessen clutter.
     fn handle_playlist_events(&mut self, ev: UpdatePlaylistEvents) -> Result<()> {
         match ev {
@@ -439,14 +578,57 @@ impl UI {
             UpdatePlaylistEvents::PlaylistSwapTracks(swapped_tracks) => {
                 self.model.handle_playlist_swap_tracks(&swapped_tracks)?;
             }
+            UpdatePlaylistEvents::PlaylistMoveTracks(moved_tracks) => {
+                self.model.handle_playlist_move_tracks(&moved_tracks)?;
+            }
             UpdatePlaylistEvents::PlaylistShuffled(shuffled) => {
                 self.model.handle_playlist_shuffled(shuffled)?;
             }
+            UpdatePlaylistEvents::PlaylistLoaded(loaded) => {
+                self.model.handle_playlist_loaded(loaded)?;
+            }
+            UpdatePlaylistEvents::PlaylistQueueChanged(changed) => {
+                self.model.handle_queue_changed(&changed.queue)?;
+            }
+            UpdatePlaylistEvents::PlaylistPlaybackControls(controls) => {
+                self.model.playback.set_playback_controls(controls);
+                self.model.playlist_update_title();
+            }
         }
 
         Ok(())
     }
 
+    /// Show the error of a named playlist change, if any, and re-request the list as it may have changed.
+    ///
+    /// A failed change should not take down the TUI.
+    /// Export the playlist to `path`, asking for confirmation if it exists and `overwrite` is not set.
+    async fn save_playlist_file(&mut self, path: &Path, overwrite: bool) {
+        // a failed save should not take down the TUI
+        match self.playback.save_playlist(path, overwrite).await {
+            Ok(saved) => self
+                .model
+                .library_reload_with_node_focus(Some(saved.to_string_lossy().to_string())),
+            Err(err)
+                if err
+                    .downcast_ref::<tonic::Status>()
+                    .is_some_and(|v| v.code() == tonic::Code::AlreadyExists) =>
+            {
+                self.model
+                    .mount_save_playlist_confirm(&path.to_string_lossy());
+            }
+            Err(err) => self.model.mount_error_popup(err.context("save playlist")),
+        }
+    }
+
+    fn after_named_playlist_change(&mut self, res: Result<()>) {
+        if let Err(err) = res {
+            self.model.mount_error_popup(err);
+        }
+
+        self.model.command(TuiCmd::Playlist(PlaylistCmd::ListNamed));
+    }
+
     /// Load the playlist from the server
     async fn load_playlist(&mut self) -> Result<()> {
         info!("Requesting Playlist from server");
@@ -461,6 +643,9 @@ impl UI {
         self.model
             .handle_current_track_index(usize::try_from(current_track_index).unwrap(), true);
 
+        let queue = self.playback.get_queue().await?;
+        self.model.handle_queue_changed(&queue)?;
+
         Ok(())
     }
 }
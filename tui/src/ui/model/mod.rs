@@ -13,8 +13,10 @@ use termusiclib::config::{ServerOverlay, SharedServerSettings, SharedTuiSettings
 use termusiclib::ids::Id;
 use termusiclib::library_db::TrackDB;
 use termusiclib::library_db::{DataBase, SearchCriteria};
-use termusiclib::player::playlist_helpers::PlaylistTrackSource;
-use termusiclib::player::{PlaylistTracks, RunningStatus};
+use termusiclib::player::playlist_helpers::{PlaylistQueueTracks, PlaylistTrackSource};
+use termusiclib::player::{
+    playlist_tracks, ABLoopRange, PlaybackControls, PlaylistTracks, RunningStatus,
+};
 use termusiclib::podcast::{db::Database as DBPod, Podcast, PodcastFeed};
 use termusiclib::songtag::lrc::Lyric;
 use termusiclib::songtag::SongTag;
@@ -29,7 +31,7 @@ use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
 use tui_realm_treeview::Tree;
 use tuirealm::terminal::{CrosstermTerminalAdapter, TerminalBridge};
 
-use super::components::TETrack;
+use super::components::{BatchEdit, LyricSync, MusicBrainzLookup, PatternPreview, TETrack};
 use super::tui_cmd::TuiCmd;
 use crate::ui::Application;
 use crate::CombinedSettings;
@@ -56,6 +58,7 @@ pub enum ConfigEditorLayout {
     Color,
     Key1,
     Key2,
+    Key3,
 }
 
 /// All data specific to the Music Library Widget / View
@@ -129,6 +132,20 @@ pub struct Playback {
     /// The current track, if there is one. Does not need to be in the playlist.
     current_track: Option<Track>,
     current_track_pos: Duration,
+    /// When `current_track_pos` was last set
+    current_track_pos_at: Instant,
+    /// The A-B loop currently active on the server, if any
+    ab_loop: Option<ABLoopRange>,
+    /// The position marked as "A", waiting for "B" to be marked to start a A-B loop
+    ab_loop_mark_a: Option<Duration>,
+    /// The time skipped as silence in the current track, if enabled for the track
+    silence_skipped: Option<Duration>,
+    /// The names of all named playlists on the server, as last requested
+    named_playlists: Vec<String>,
+    /// The "play next" queue of the server
+    queue: Vec<Track>,
+    /// The one-shot playback controls of the server, like "stop after current"
+    controls: PlaybackControls,
 }
 
 impl Playback {
@@ -138,6 +155,13 @@ impl Playback {
             status: RunningStatus::default(),
             current_track: None,
             current_track_pos: Duration::ZERO,
+            current_track_pos_at: Instant::now(),
+            ab_loop: None,
+            ab_loop_mark_a: None,
+            silence_skipped: None,
+            named_playlists: Vec::new(),
+            queue: Vec::new(),
+            controls: PlaybackControls::default(),
         }
     }
 
@@ -191,6 +215,110 @@ impl Playback {
 
     pub fn set_current_track_pos(&mut self, pos: Duration) {
         self.current_track_pos = pos;
+        self.current_track_pos_at = Instant::now();
+    }
+
+    /// Estimate the position of the current track, from the last reported position and the time since (assuming normal speed).
+    ///
+    /// The position is only reported periodically, so this is more precise for actions that need the exact time.
+    #[must_use]
+    pub fn current_track_pos_estimate(&self) -> Duration {
+        if self.status == RunningStatus::Running {
+            self.current_track_pos + self.current_track_pos_at.elapsed()
+        } else {
+            self.current_track_pos
+        }
+    }
+
+    #[must_use]
+    pub fn ab_loop(&self) -> Option<ABLoopRange> {
+        self.ab_loop
+    }
+
+    /// Set the A-B loop as reported by the server, this also resets the pending "A" mark
+    pub fn set_ab_loop(&mut self, range: Option<ABLoopRange>) {
+        self.ab_loop = range;
+        self.ab_loop_mark_a = None;
+    }
+
+    #[must_use]
+    pub fn ab_loop_mark_a(&self) -> Option<Duration> {
+        self.ab_loop_mark_a
+    }
+
+    pub fn set_ab_loop_mark_a(&mut self, pos: Option<Duration>) {
+        self.ab_loop_mark_a = pos;
+    }
+
+    #[must_use]
+    pub fn silence_skipped(&self) -> Option<Duration> {
+        self.silence_skipped
+    }
+
+    pub fn set_silence_skipped(&mut self, skipped: Option<Duration>) {
+        self.silence_skipped = skipped;
+    }
+
+    #[must_use]
+    pub fn named_playlists(&self) -> &[String] {
+        &self.named_playlists
+    }
+
+    pub fn set_named_playlists(&mut self, names: Vec<String>) {
+        self.named_playlists = names;
+    }
+
+    #[must_use]
+    pub fn playback_controls(&self) -> PlaybackControls {
+        self.controls
+    }
+
+    /// Set the playback controls as reported by the server, returns `true` if they changed.
+    pub fn set_playback_controls(&mut self, controls: PlaybackControls) -> bool {
+        let changed = self.controls != controls;
+        self.controls = controls;
+
+        changed
+    }
+
+    #[must_use]
+    pub fn queue(&self) -> &[Track] {
+        &self.queue
+    }
+
+    /// Set the "play next" queue from a GRPC response / event.
+    ///
+    /// # Errors
+    ///
+    /// - When invalid inputs are given (non-existing path, etc)
+    pub fn set_queue_from_grpc(
+        &mut self,
+        queue: &PlaylistQueueTracks,
+        podcast_db: &DBPod,
+    ) -> Result<()> {
+        self.queue = queue
+            .tracks
+            .iter()
+            .map(|v| playlist::TUIPlaylist::track_from_source(v, podcast_db))
+            .collect::<Result<_>>()?;
+
+        Ok(())
+    }
+
+    /// Set the current track from a source that is not in the playlist, like from the "play next" queue.
+    ///
+    /// # Errors
+    ///
+    /// - When invalid inputs are given (non-existing path, etc)
+    pub fn set_current_track_from_source(
+        &mut self,
+        source: &PlaylistTrackSource,
+        podcast_db: &DBPod,
+    ) -> Result<()> {
+        let track = playlist::TUIPlaylist::track_from_source(source, podcast_db)?;
+        self.set_current_track(Some(track));
+
+        Ok(())
     }
 
     /// Load Tracks from a GRPC response.
@@ -237,6 +365,10 @@ impl Playback {
         }
 
         self.playlist.set_tracks(playlist_items);
+        self.playlist.set_name(info.optional_name.map(|v| {
+            let playlist_tracks::OptionalName::Name(v) = v;
+            v
+        }));
 
         // the old server playlist implementation will send `current_track_index: 0`, even if there are not tracks
         // but the new TUI implementation function "set_current_track_index" will refuse to set anything if the index is out-of-bounds
@@ -255,6 +387,8 @@ pub struct ExtraLyricData {
     pub for_track: PathBuf,
     pub data: LyricData,
     pub selected_idx: usize,
+    /// The total milliseconds the selected lyric was shifted by since it was loaded or last saved
+    pub offset_shift: i64,
 }
 
 impl ExtraLyricData {
@@ -277,9 +411,21 @@ impl ExtraLyricData {
 
         let raw_lyric = self.data.raw_lyrics.get(self.selected_idx);
         self.data.parsed_lyrics = raw_lyric.and_then(|v| Lyric::from_str(&v.text).ok());
+        // unsaved adjustments are discarded with the re-parse
+        self.offset_shift = 0;
 
         Ok(raw_lyric)
     }
+
+    /// Adjust the parsed lyric at `time_pos` by `offset` (milliseconds), without saving it.
+    ///
+    /// See [`Lyric::adjust_offset`].
+    pub fn adjust_delay(&mut self, time_pos: Duration, offset: i64) {
+        if let Some(lyric) = self.data.parsed_lyrics.as_mut() {
+            lyric.adjust_offset(time_pos, offset);
+            self.offset_shift += offset;
+        }
+    }
 }
 
 pub type TxToMain = UnboundedSender<Msg>;
@@ -309,6 +455,14 @@ pub struct Model {
     pub config_editor: ConfigEditorData,
 
     pub tageditor_song: Option<TETrack>,
+    /// The state of the tap-to-sync lyric editor, if active
+    pub te_lyric_sync: Option<LyricSync>,
+    /// The state of the `MusicBrainz` release lookup, if active
+    pub te_musicbrainz: Option<MusicBrainzLookup>,
+    /// The state of editing many files at once, if the tag editor was opened for more than one file
+    pub te_batch: Option<BatchEdit>,
+    /// The moves or tag changes of a file name pattern, if the preview is open
+    pub te_pattern: Option<PatternPreview>,
     pub lyric_line: String,
     pub current_track_lyric: Option<ExtraLyricData>,
     pub playback: Playback,
@@ -430,6 +584,10 @@ impl Model {
             config_server,
             config_tui,
             tageditor_song: None,
+            te_lyric_sync: None,
+            te_musicbrainz: None,
+            te_batch: None,
+            te_pattern: None,
             lyric_line: String::new(),
 
             library: MusicLibraryData {
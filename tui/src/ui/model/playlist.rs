@@ -1,13 +1,14 @@
-use std::fmt::Write as _;
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap};
 use std::path::Path;
 
 use anyhow::{bail, Context, Result};
-use pathdiff::diff_paths;
-use termusiclib::player::playlist_helpers::{PlaylistAddTrack, PlaylistTrackSource};
+use termusiclib::player::playlist_helpers::{
+    move_range, moved_index, PlaylistAddTrack, PlaylistTrackSource,
+};
 use termusiclib::player::PlaylistRemoveTrackInfo;
 use termusiclib::podcast::db::Database as DBPod;
 use termusiclib::track::MediaTypes;
-use termusiclib::utils::get_parent_folder;
 use termusiclib::{config::v2::server::LoopMode, track::Track};
 
 /// A Playlist with all the tracks and options
@@ -17,6 +18,12 @@ pub struct TUIPlaylist {
     /// Index into `tracks`, if set
     current_track_idx: Option<usize>,
     loop_mode: LoopMode,
+    /// The name of the named playlist loaded on the server, if any
+    name: Option<String>,
+    /// Indexes of the tracks marked for batched edits
+    marked: BTreeSet<usize>,
+    /// The range that is currently being marked, as `(start, end)`
+    mark_range: Option<(usize, usize)>,
 }
 
 impl TUIPlaylist {
@@ -35,25 +42,119 @@ impl TUIPlaylist {
         &self.tracks
     }
 
+    #[must_use]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Set the name of the named playlist loaded on the server.
+    pub fn set_name(&mut self, name: Option<String>) {
+        self.name = name;
+    }
+
     /// Clear the current Playlist's contents.
     pub fn clear(&mut self) {
         self.tracks.clear();
         self.current_track_idx.take();
+        self.clear_marks();
+    }
+
+    /// Mark the track at `index`, or unmark it if it already is.
+    pub fn toggle_mark(&mut self, index: usize) {
+        if index >= self.tracks.len() {
+            return;
+        }
+
+        if !self.marked.remove(&index) {
+            self.marked.insert(index);
+        }
+    }
+
+    /// Start marking a range of tracks at `index`.
+    pub fn start_mark_range(&mut self, index: usize) {
+        self.end_mark_range();
+        self.mark_range = Some((index, index));
+    }
+
+    /// Move the end of the range that is currently being marked to `index`, does nothing if no range is being marked.
+    pub fn update_mark_range(&mut self, index: usize) {
+        if let Some((_, end)) = &mut self.mark_range {
+            *end = index;
+        }
+    }
+
+    /// Stop marking a range, keeping the tracks in it marked.
+    pub fn end_mark_range(&mut self) {
+        if let Some((start, end)) = self.mark_range.take() {
+            self.marked.extend(start.min(end)..=start.max(end));
+        }
+    }
+
+    /// Unmark all tracks.
+    pub fn clear_marks(&mut self) {
+        self.marked.clear();
+        self.mark_range.take();
+    }
+
+    /// Replace all marks with `indexes`.
+    pub fn set_marks(&mut self, indexes: impl IntoIterator<Item = usize>) {
+        self.clear_marks();
+        self.marked.extend(indexes);
+    }
+
+    /// Check whether the track at `index` is marked, either directly or by the range being marked.
+    #[must_use]
+    pub fn is_marked(&self, index: usize) -> bool {
+        self.marked.contains(&index)
+            || self
+                .mark_range
+                .is_some_and(|(start, end)| (start.min(end)..=start.max(end)).contains(&index))
+    }
+
+    /// Get the indexes of all marked tracks, sorted.
+    #[must_use]
+    pub fn marked(&self) -> Vec<usize> {
+        (0..self.tracks.len())
+            .filter(|index| self.is_marked(*index))
+            .collect()
+    }
+
+    /// Shift the marks for `amount` tracks having been inserted at `at_index`.
+    fn shift_marks_insert(&mut self, at_index: usize, amount: usize) {
+        self.end_mark_range();
+        self.marked = self
+            .marked
+            .iter()
+            .map(|index| {
+                if *index >= at_index {
+                    index + amount
+                } else {
+                    *index
+                }
+            })
+            .collect();
+    }
+
+    /// Shift the marks for the track at `at_index` having been removed.
+    fn shift_marks_remove(&mut self, at_index: usize) {
+        self.end_mark_range();
+        self.marked = self
+            .marked
+            .iter()
+            .filter_map(|index| match index.cmp(&at_index) {
+                Ordering::Less => Some(*index),
+                Ordering::Equal => None,
+                Ordering::Greater => Some(index - 1),
+            })
+            .collect();
     }
 
     // TODO: make this explicit with the server instead of saying "cycle"
     /// Cycle through the loop modes and return the new mode.
     ///
-    /// order:
-    /// [Random](LoopMode::Random) -> [Playlist](LoopMode::Playlist)
-    /// [Playlist](LoopMode::Playlist) -> [Single](LoopMode::Single)
-    /// [Single](LoopMode::Single) -> [Random](LoopMode::Random)
+    /// For the order see [`LoopMode::cycle`].
     pub fn cycle_loop_mode(&mut self) -> LoopMode {
-        let new_mode = match self.loop_mode {
-            LoopMode::Random => LoopMode::Playlist,
-            LoopMode::Playlist => LoopMode::Single,
-            LoopMode::Single => LoopMode::Random,
-        };
+        let new_mode = self.loop_mode.cycle();
 
         self.set_loop_mode(new_mode);
 
@@ -78,6 +179,32 @@ impl TUIPlaylist {
 
         self.tracks.swap(index_a, index_b);
 
+        self.end_mark_range();
+        let marked_a = self.marked.remove(&index_a);
+        let marked_b = self.marked.remove(&index_b);
+        if marked_a {
+            self.marked.insert(index_b);
+        }
+        if marked_b {
+            self.marked.insert(index_a);
+        }
+
+        Ok(())
+    }
+
+    /// Move `count` tracks starting at `from_index` as one block, so that the first of them is at `to_index` afterwards.
+    ///
+    /// # Errors
+    ///
+    /// - if the tracks to move or the target are out-of-bounds
+    pub fn move_tracks(&mut self, from_index: usize, count: usize, to_index: usize) -> Result<()> {
+        move_range(&mut self.tracks, from_index, count, to_index)?;
+
+        let moved = |index| moved_index(index, from_index, count, to_index);
+        self.current_track_idx = self.current_track_idx.map(moved);
+        self.end_mark_range();
+        self.marked = self.marked.iter().copied().map(moved).collect();
+
         Ok(())
     }
 
@@ -92,6 +219,7 @@ impl TUIPlaylist {
         }
 
         self.tracks.remove(index);
+        self.shift_marks_remove(index);
 
         Ok(())
     }
@@ -139,6 +267,7 @@ impl TUIPlaylist {
 
             return Ok(());
         }
+        self.shift_marks_insert(at_index, tracks.tracks.len());
         let mut at_index = at_index;
         // insert tracks at position
         for track_location in tracks.tracks {
@@ -155,6 +284,21 @@ impl TUIPlaylist {
         Ok(())
     }
 
+    /// Create a Track from any given source
+    ///
+    /// # Errors
+    ///
+    /// - When invalid inputs are given (non-existing path, etc)
+    pub fn track_from_source(source: &PlaylistTrackSource, db_pod: &DBPod) -> Result<Track> {
+        let track = match source {
+            PlaylistTrackSource::Path(path) => Self::track_from_path(path)?,
+            PlaylistTrackSource::Url(uri) => Self::track_from_uri(uri),
+            PlaylistTrackSource::PodcastUrl(uri) => Self::track_from_podcasturi(uri, db_pod)?,
+        };
+
+        Ok(track)
+    }
+
     /// Create a Track from a given Path
     fn track_from_path(path_str: &str) -> Result<Track> {
         let path = Path::new(path_str);
@@ -221,53 +365,33 @@ impl TUIPlaylist {
     }
 
     /// Completely overwrite the tracks in this playlist.
+    ///
+    /// Marks are kept on the same tracks, wherever they are now, as for example sorting replaces all tracks.
+    /// If a marked track is in the playlist multiple times, the first occurrences are marked.
     pub fn set_tracks(&mut self, tracks: Vec<Track>) {
+        self.end_mark_range();
+        let mut marked_sources: HashMap<PlaylistTrackSource, usize> = HashMap::new();
+        for track in self
+            .marked
+            .iter()
+            .filter_map(|index| self.tracks.get(*index))
+        {
+            *marked_sources.entry(track.as_track_source()).or_default() += 1;
+        }
+
         self.tracks = tracks;
         // remove the current index, as it is unknown if the data is the same
         self.current_track_idx.take();
-    }
 
-    // TODO: move "save_m3u" to server-side
-    /// Export the current playlist to a `.m3u` playlist file.
-    ///
-    /// # Errors
-    ///
-    /// Error could happen when writing file to local disk.
-    pub fn save_m3u(&self, filename: &Path) -> Result<()> {
-        if self.tracks.is_empty() {
-            bail!("Unable to save since the playlist is empty.");
-        }
-
-        let parent_folder = get_parent_folder(filename);
-
-        let m3u = self.get_m3u_file(&parent_folder);
-
-        std::fs::write(filename, m3u)?;
-        Ok(())
-    }
-
-    /// Generate the m3u's file content.
-    ///
-    /// All Paths are relative to the `parent_folder` directory.
-    fn get_m3u_file(&self, parent_folder: &Path) -> String {
-        let mut m3u = String::from("#EXTM3U\n");
-        for track in &self.tracks {
-            let file = match track.inner() {
-                MediaTypes::Track(track_data) => {
-                    let path_relative = diff_paths(track_data.path(), parent_folder);
-
-                    path_relative.map_or_else(
-                        || track_data.path().to_string_lossy(),
-                        |v| v.to_string_lossy().to_string().into(),
-                    )
+        self.marked.clear();
+        for (index, track) in self.tracks.iter().enumerate() {
+            if let Some(count) = marked_sources.get_mut(&track.as_track_source()) {
+                if *count > 0 {
+                    *count -= 1;
+                    self.marked.insert(index);
                 }
-                MediaTypes::Radio(radio_track_data) => radio_track_data.url().into(),
-                MediaTypes::Podcast(podcast_track_data) => podcast_track_data.url().into(),
-            };
-
-            let _ = writeln!(m3u, "{file}");
+            }
         }
-        m3u
     }
 
     /// Check that the given `info` track source matches the given `track_inner` types.
@@ -316,3 +440,28 @@ impl TUIPlaylist {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use termusiclib::track::Track;
+
+    use super::TUIPlaylist;
+
+    fn radio(name: &str) -> Track {
+        Track::new_radio(format!("http://{name}"))
+    }
+
+    #[test]
+    fn should_keep_marks_on_the_same_tracks_on_refresh() {
+        let mut playlist = TUIPlaylist::default();
+        playlist.set_tracks(vec![radio("a"), radio("b"), radio("c"), radio("b")]);
+        playlist.set_marks([1, 2]);
+
+        playlist.set_tracks(vec![radio("c"), radio("b"), radio("a"), radio("b")]);
+        assert_eq!(playlist.marked(), vec![0, 1]);
+
+        // marked tracks which are gone are unmarked
+        playlist.set_tracks(vec![radio("a")]);
+        assert!(playlist.marked().is_empty());
+    }
+}
@@ -5,17 +5,19 @@ use std::time::Duration;
 
 use anyhow::anyhow;
 use termusiclib::ids::{Id, IdTagEditor};
+use termusiclib::player::ABLoopRange;
 use termusiclib::track::MediaTypesSimple;
 use termusiclib::types::{
-    DBMsg, DLMsg, GSMsg, LIMsg, LyricMsg, MainLayoutMsg, Msg, PCMsg, PLMsg, PlayerMsg,
-    SavePlaylistMsg, XYWHMsg, YSMsg,
+    DBMsg, DLMsg, GSMsg, LIMsg, LyricMsg, MainLayoutMsg, Msg, NamedPlaylistMsg, PCMsg, PLMsg,
+    PlayerMsg, QueueMsg, SavePlaylistMsg, XYWHMsg, YSMsg,
 };
 use tokio::runtime::Handle;
 use tokio::time::sleep;
 use tuirealm::props::{AttrValue, Attribute};
 use tuirealm::Update;
 
-use crate::ui::tui_cmd::TuiCmd;
+use crate::ui::components::NamedPlaylistInput;
+use crate::ui::tui_cmd::{PlaylistCmd, TuiCmd};
 use crate::ui::{model::TermusicLayout, Model};
 
 impl Update<Msg> for Model {
@@ -93,6 +95,21 @@ impl Update<Msg> for Model {
                 self.lyric_adjust_delay(offset);
                 None
             }
+            Msg::LyricOffsetConfirmShow => {
+                self.mount_lyric_offset_confirm();
+                None
+            }
+            Msg::LyricOffsetConfirmCloseOk => {
+                self.umount_lyric_offset_confirm();
+                if let Err(e) = self.lyric_save_offset() {
+                    self.mount_error_popup(e.context("save lyric offset"));
+                }
+                None
+            }
+            Msg::LyricOffsetConfirmCloseCancel => {
+                self.umount_lyric_offset_confirm();
+                None
+            }
             Msg::TagEditor(m) => {
                 self.update_tageditor(m);
                 None
@@ -106,6 +123,8 @@ impl Update<Msg> for Model {
             Msg::Layout(msg) => self.update_layout(msg),
 
             Msg::SavePlaylist(msg) => self.update_save_playlist(msg),
+            Msg::NamedPlaylist(msg) => self.update_named_playlist(msg),
+            Msg::Queue(msg) => self.update_queue(msg),
 
             Msg::Podcast(m) => self.update_podcast(m),
             Msg::LyricMessage(m) => self.update_lyric_textarea(m),
@@ -354,6 +373,7 @@ impl Model {
     }
 
     /// Handle Player related messages & events
+    #[allow(clippy::too_many_lines)]
     fn update_player(&mut self, msg: PlayerMsg) -> Option<Msg> {
         match msg {
             PlayerMsg::TogglePause => {
@@ -389,6 +409,18 @@ impl Model {
             PlayerMsg::SpeedDown => {
                 self.command(TuiCmd::SpeedDown);
             }
+            PlayerMsg::PitchUp => {
+                self.command(TuiCmd::PitchUp);
+            }
+            PlayerMsg::PitchDown => {
+                self.command(TuiCmd::PitchDown);
+            }
+            PlayerMsg::PitchFineUp => {
+                self.command(TuiCmd::PitchFineUp);
+            }
+            PlayerMsg::PitchFineDown => {
+                self.command(TuiCmd::PitchFineDown);
+            }
             PlayerMsg::VolumeUp => {
                 self.command(TuiCmd::VolumeUp);
             }
@@ -398,6 +430,51 @@ impl Model {
             PlayerMsg::ToggleGapless => {
                 self.command(TuiCmd::ToggleGapless);
             }
+            PlayerMsg::ABLoopMarkA => {
+                if self.is_radio() {
+                    self.show_message_timeout_label_help(
+                        "A-B loop is not available for live radio",
+                        None,
+                        None,
+                        None,
+                    );
+                    return None;
+                }
+                let pos = self.playback.current_track_pos();
+                self.playback.set_ab_loop_mark_a(Some(pos));
+                self.progress_update_ab_loop();
+            }
+            PlayerMsg::ABLoopMarkB => {
+                if self.is_radio() {
+                    self.show_message_timeout_label_help(
+                        "A-B loop is not available for live radio",
+                        None,
+                        None,
+                        None,
+                    );
+                    return None;
+                }
+                // without a "A" mark, loop from the start of the track
+                let start = self.playback.ab_loop_mark_a().unwrap_or_default();
+                let end = self.playback.current_track_pos();
+                let Some(range) = ABLoopRange::new(start, end) else {
+                    self.show_message_timeout_label_help(
+                        "A-B loop: \"B\" needs to be after \"A\"",
+                        None,
+                        None,
+                        None,
+                    );
+                    return None;
+                };
+                self.command(TuiCmd::SetABLoop(range));
+            }
+            PlayerMsg::ABLoopClear => {
+                self.playback.set_ab_loop_mark_a(None);
+                if self.playback.ab_loop().is_some() {
+                    self.command(TuiCmd::ClearABLoop);
+                }
+                self.progress_update_ab_loop();
+            }
         }
 
         None
@@ -540,6 +617,17 @@ impl Model {
                     }
                 }
             }
+            DBMsg::OpenTagEditor(index) => {
+                if let Some(result) = self.dw.search_results.get(index).cloned() {
+                    if let Some(result) =
+                        self.database_get_tracks_by_criteria(self.dw.criteria, &result)
+                    {
+                        let paths: Vec<PathBuf> =
+                            result.iter().map(|v| PathBuf::from(&v.file)).collect();
+                        self.mount_tageditor_batch(&paths);
+                    }
+                }
+            }
             DBMsg::AddAllResultsToPlaylist => {
                 self.database_add_all_results();
             }
@@ -791,6 +879,7 @@ impl Model {
         }
         None
     }
+    #[allow(clippy::too_many_lines)]
     fn update_playlist(&mut self, msg: &PLMsg) {
         match msg {
             PLMsg::Add(current_node) => {
@@ -799,7 +888,47 @@ impl Model {
                 }
             }
             PLMsg::Delete(index) => {
-                self.playlist_delete_item(*index);
+                if self.playback.playlist.marked().is_empty() {
+                    self.playlist_delete_item(*index);
+                } else {
+                    self.playlist_delete_marked();
+                }
+            }
+            PLMsg::MoveTop(index) => {
+                self.playlist_move_marked_to(*index, Some(0));
+            }
+            PLMsg::MoveBottom(index) => {
+                self.playlist_move_marked_to(*index, None);
+            }
+            PLMsg::Mark(index) => {
+                self.playback.playlist.toggle_mark(*index);
+                self.playlist_sync();
+            }
+            PLMsg::MarkRangeStart(index) => {
+                self.playback.playlist.start_mark_range(*index);
+                self.playlist_sync();
+            }
+            PLMsg::MarkRangeUpdate(index) => {
+                self.playback.playlist.update_mark_range(*index);
+                self.playlist_sync();
+            }
+            PLMsg::MarkRangeEnd => {
+                self.playback.playlist.end_mark_range();
+                self.playlist_sync();
+            }
+            PLMsg::MarkClear => {
+                self.playback.playlist.clear_marks();
+                self.playlist_sync();
+            }
+            PLMsg::SaveMarkedShow => {
+                if self.playback.playlist.marked().is_empty() {
+                    self.mount_error_popup(anyhow!("No tracks are marked"));
+                } else {
+                    self.mount_named_playlist_input(NamedPlaylistInput::SaveMarkedAs);
+                }
+            }
+            PLMsg::OpenTagEditor(index) => {
+                self.playlist_open_tag_editor(*index);
             }
             PLMsg::DeleteAll => {
                 self.playlist_clear();
@@ -807,6 +936,22 @@ impl Model {
             PLMsg::Shuffle => {
                 self.playlist_shuffle();
             }
+            PLMsg::Undo => {
+                self.command(TuiCmd::Playlist(PlaylistCmd::Undo));
+            }
+            PLMsg::Redo => {
+                self.command(TuiCmd::Playlist(PlaylistCmd::Redo));
+            }
+            PLMsg::SortPopupShow => self.mount_playlist_sort(),
+            PLMsg::SortPopupCloseCancel => self.umount_playlist_sort(),
+            PLMsg::Sort(sort) => {
+                self.umount_playlist_sort();
+                self.command(TuiCmd::Playlist(PlaylistCmd::Sort(*sort)));
+            }
+            PLMsg::Deduplicate => {
+                self.umount_playlist_sort();
+                self.command(TuiCmd::Playlist(PlaylistCmd::Deduplicate));
+            }
             PLMsg::PlaySelected(index) => {
                 self.playlist_play_selected(*index);
             }
@@ -829,10 +974,10 @@ impl Model {
                 self.player_previous();
             }
             PLMsg::SwapDown(index) => {
-                self.playlist_swap_down(*index);
+                self.playlist_move_down(*index);
             }
             PLMsg::SwapUp(index) => {
-                self.playlist_swap_up(*index);
+                self.playlist_move_up(*index);
             }
             PLMsg::AddRandomAlbum => {
                 self.playlist_add_random_album();
@@ -999,7 +1144,7 @@ impl Model {
             }
             SavePlaylistMsg::PopupCloseOk(filename) => {
                 self.umount_save_playlist();
-                if let Err(e) = self.playlist_save_m3u_before(&filename) {
+                if let Err(e) = self.playlist_save_file_before(&filename) {
                     self.mount_error_popup(e.context("save m3u playlist before"));
                 }
             }
@@ -1012,13 +1157,80 @@ impl Model {
                 self.umount_save_playlist_confirm();
             }
             SavePlaylistMsg::ConfirmCloseOk(filename) => {
-                if let Err(e) = self.playlist_save_m3u(Path::new(&filename)) {
+                if let Err(e) = self.playlist_save_file(Path::new(&filename), true) {
                     self.mount_error_popup(e.context("save m3u playlist"));
                 }
                 self.umount_save_playlist_confirm();
             }
         }
 
+        None
+    }
+    fn update_named_playlist(&mut self, msg: NamedPlaylistMsg) -> Option<Msg> {
+        match msg {
+            NamedPlaylistMsg::PopupShow => {
+                self.mount_named_playlists();
+                self.command(TuiCmd::Playlist(PlaylistCmd::ListNamed));
+            }
+            NamedPlaylistMsg::PopupCloseCancel => {
+                self.umount_named_playlists();
+            }
+            NamedPlaylistMsg::Load(index) => {
+                if let Some(name) = self.playback.named_playlists().get(index).cloned() {
+                    self.umount_named_playlists();
+                    self.command(TuiCmd::Playlist(PlaylistCmd::LoadNamed(name)));
+                }
+            }
+            NamedPlaylistMsg::SaveAsShow => {
+                self.mount_named_playlist_input(NamedPlaylistInput::SaveAs);
+            }
+            NamedPlaylistMsg::RenameShow(index) => {
+                if let Some(name) = self.playback.named_playlists().get(index).cloned() {
+                    self.mount_named_playlist_input(NamedPlaylistInput::Rename(name));
+                }
+            }
+            NamedPlaylistMsg::InputCloseCancel => {
+                self.umount_named_playlist_input();
+            }
+            NamedPlaylistMsg::SaveAs(name) => {
+                self.umount_named_playlist_input();
+                self.command(TuiCmd::Playlist(PlaylistCmd::SaveNamed(name)));
+            }
+            NamedPlaylistMsg::SaveMarkedAs(name) => {
+                self.umount_named_playlist_input();
+                self.playlist_save_marked(name);
+            }
+            NamedPlaylistMsg::Rename(from, to) => {
+                self.umount_named_playlist_input();
+                self.command(TuiCmd::Playlist(PlaylistCmd::RenameNamed(from, to)));
+            }
+            NamedPlaylistMsg::DeleteShow(index) => {
+                if let Some(name) = self.playback.named_playlists().get(index).cloned() {
+                    self.mount_named_playlist_delete_confirm(&name);
+                }
+            }
+            NamedPlaylistMsg::DeleteCloseCancel => {
+                self.umount_named_playlist_delete_confirm();
+            }
+            NamedPlaylistMsg::DeleteCloseOk(name) => {
+                self.umount_named_playlist_delete_confirm();
+                self.command(TuiCmd::Playlist(PlaylistCmd::DeleteNamed(name)));
+            }
+        }
+
+        None
+    }
+
+    fn update_queue(&mut self, msg: QueueMsg) -> Option<Msg> {
+        match msg {
+            QueueMsg::Add(path) => self.queue_add(&path),
+            QueueMsg::AddFromPlaylist(index) => self.queue_add_from_playlist(index),
+            QueueMsg::PopupShow => self.mount_queue(),
+            QueueMsg::PopupCloseCancel => self.umount_queue(),
+            QueueMsg::Delete(index) => self.queue_delete(index),
+            QueueMsg::DeleteAll => self.queue_clear(),
+        }
+
         None
     }
 }
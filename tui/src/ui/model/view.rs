@@ -4,8 +4,10 @@ use std::path::Path;
 use std::time::{Duration, Instant};
 
 use anyhow::{bail, Result};
+use termusiclib::config::v2::tui::SpectrumPosition;
 use termusiclib::config::SharedTuiSettings;
 use termusiclib::ids::{Id, IdConfigEditor, IdTagEditor};
+use termusiclib::playlist::PlaylistFormat;
 use termusiclib::types::{DBMsg, Msg, PCMsg};
 use termusiclib::utils::get_parent_folder;
 use tui_realm_treeview::Tree;
@@ -18,11 +20,11 @@ use tuirealm::{Frame, State, StateValue};
 use crate::ui::components::{
     DBListCriteria, DBListSearchResult, DBListSearchTracks, DownloadSpinner, EpisodeList,
     FeedsList, Footer, GSInputPopup, GSTablePopup, GlobalListener, LabelSpan, Lyric, MusicLibrary,
-    Playlist, Progress, Source,
+    Playlist, Progress, Source, Spectrum,
 };
 use crate::ui::model::{Model, TermusicLayout, UserEvent};
 use crate::ui::utils::{
-    draw_area_in_absolute, draw_area_in_relative, draw_area_top_right_absolute,
+    draw_area_aligned, draw_area_in_absolute, draw_area_in_relative, draw_area_top_right_absolute,
 };
 use crate::ui::Application;
 
@@ -106,6 +108,11 @@ impl Model {
             Vec::new(),
         )?;
         app.mount(Id::Lyric, Box::new(Lyric::new(config.clone())), Vec::new())?;
+        app.mount(
+            Id::Spectrum,
+            Box::new(Spectrum::new(&config.read())),
+            Vec::new(),
+        )?;
 
         app.mount(
             Id::Podcast,
@@ -186,7 +193,12 @@ impl Model {
                 self.app.view(&Id::Lyric, f, right_lyric);
                 self.app.view(&Id::Progress, f, progress);
 
-                Self::view_layout_commons(f, &mut self.app, self.download_tracker.visible());
+                Self::view_layout_commons(
+                    f,
+                    &mut self.app,
+                    &self.config_tui.read().settings.spectrum,
+                    self.download_tracker.visible(),
+                );
             })
             .expect("Expected to draw without error");
     }
@@ -225,7 +237,12 @@ impl Model {
                 self.app.view(&Id::Progress, f, right_progress);
                 self.app.view(&Id::Lyric, f, right_lyric);
 
-                Self::view_layout_commons(f, &mut self.app, self.download_tracker.visible());
+                Self::view_layout_commons(
+                    f,
+                    &mut self.app,
+                    &self.config_tui.read().settings.spectrum,
+                    self.download_tracker.visible(),
+                );
             })
             .expect("Expected to draw without error");
     }
@@ -252,7 +269,12 @@ impl Model {
                 self.app.view(&Id::Progress, f, right_progress);
                 self.app.view(&Id::Lyric, f, right_lyric);
 
-                Self::view_layout_commons(f, &mut self.app, self.download_tracker.visible());
+                Self::view_layout_commons(
+                    f,
+                    &mut self.app,
+                    &self.config_tui.read().settings.spectrum,
+                    self.download_tracker.visible(),
+                );
             })
             .expect("Expected to draw without error");
     }
@@ -282,6 +304,7 @@ impl Model {
     }
 
     /// Draw any popup.
+    #[allow(clippy::too_many_lines)]
     fn view_popups(f: &mut Frame<'_>, app: &mut Application<Id, Msg, UserEvent>) {
         if app.mounted(&Id::QuitPopup) {
             let popup = draw_area_in_absolute(f.area(), 30, 3);
@@ -329,6 +352,27 @@ impl Model {
             let popup = draw_area_in_relative(f.area(), 65, 68);
             f.render_widget(Clear, popup);
             app.view(&Id::PodcastSearchTablePopup, f, popup);
+        } else if app.mounted(&Id::NamedPlaylistsPopup) {
+            let popup = draw_area_in_relative(f.area(), 50, 60);
+            f.render_widget(Clear, popup);
+            app.view(&Id::NamedPlaylistsPopup, f, popup);
+            if app.mounted(&Id::NamedPlaylistInputPopup) {
+                let popup = draw_area_in_absolute(f.area(), 60, 3);
+                f.render_widget(Clear, popup);
+                app.view(&Id::NamedPlaylistInputPopup, f, popup);
+            } else if app.mounted(&Id::NamedPlaylistDeleteConfirm) {
+                let popup = draw_area_in_absolute(f.area(), 50, 3);
+                f.render_widget(Clear, popup);
+                app.view(&Id::NamedPlaylistDeleteConfirm, f, popup);
+            }
+        } else if app.mounted(&Id::QueuePopup) {
+            let popup = draw_area_in_relative(f.area(), 65, 60);
+            f.render_widget(Clear, popup);
+            app.view(&Id::QueuePopup, f, popup);
+        } else if app.mounted(&Id::PlaylistSortPopup) {
+            let popup = draw_area_in_absolute(f.area(), 50, 10);
+            f.render_widget(Clear, popup);
+            app.view(&Id::PlaylistSortPopup, f, popup);
         } else if app.mounted(&Id::SavePlaylistPopup) {
             let popup = draw_area_in_absolute(f.area(), 76, 6);
             f.render_widget(Clear, popup);
@@ -348,6 +392,10 @@ impl Model {
             let popup = draw_area_in_absolute(f.area(), 60, 3);
             f.render_widget(Clear, popup);
             app.view(&Id::DatabaseAddConfirmPopup, f, popup);
+        } else if app.mounted(&Id::LyricOffsetConfirmPopup) {
+            let popup = draw_area_in_absolute(f.area(), 60, 3);
+            f.render_widget(Clear, popup);
+            app.view(&Id::LyricOffsetConfirmPopup, f, popup);
         }
         if app.mounted(&Id::MessagePopup) {
             let popup = draw_area_top_right_absolute(f.area(), 25, 4);
@@ -361,12 +409,32 @@ impl Model {
         }
     }
 
+    /// Draw the spectrum visualizer on top of the current layout, if enabled.
+    fn view_spectrum(
+        f: &mut Frame<'_>,
+        app: &mut Application<Id, Msg, UserEvent>,
+        config: &SpectrumPosition,
+    ) {
+        if config.hidden {
+            return;
+        }
+
+        let [content, _bottom_label] =
+            Layout::vertical([Constraint::Min(2), Constraint::Length(1)]).areas(f.area());
+        // 3 rows minimum for the borders and 1 row of bars
+        let area = draw_area_aligned(content, config.align, config.size_scale, 10, 3);
+        f.render_widget(Clear, area);
+        app.view(&Id::Spectrum, f, area);
+    }
+
     /// Draw common things, like the bottom label and popups.
     fn view_layout_commons(
         f: &mut Frame<'_>,
         app: &mut Application<Id, Msg, UserEvent>,
+        spectrum: &SpectrumPosition,
         downloading_visible: bool,
     ) {
+        Self::view_spectrum(f, app, spectrum);
         Self::view_common_footer(f, app, downloading_visible);
 
         Self::view_popups(f, app);
@@ -439,6 +507,13 @@ impl Model {
         // push extra "/" as "Path::to_string()" does not end with a "/"
         path_string.push('/');
 
+        // the server does the same, see "resolve_export_path"
+        let extension = if PlaylistFormat::from_path(Path::new(filename)).is_some() {
+            String::new()
+        } else {
+            format!(".{}", PlaylistFormat::M3U.extension())
+        };
+
         let config = self.config_tui.read();
 
         self.app
@@ -454,7 +529,7 @@ impl Model {
                             .fg(config.settings.theme.fallback_foreground())
                             .bold(),
                         TextSpan::new(filename).fg(Color::Cyan).bold(),
-                        TextSpan::new(".m3u")
+                        TextSpan::new(extension)
                             .fg(config.settings.theme.fallback_foreground())
                             .bold(),
                     ],
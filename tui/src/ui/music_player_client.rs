@@ -1,12 +1,18 @@
+use std::path::{Path, PathBuf};
+
 use anyhow::{Context, Result};
 use termusiclib::config::v2::server::LoopMode;
 use termusiclib::player::music_player_client::MusicPlayerClient;
 use termusiclib::player::playlist_helpers::{
-    PlaylistAddTrack, PlaylistPlaySpecific, PlaylistRemoveTrackType, PlaylistSwapTrack,
+    PlaylistAddTrack, PlaylistMoveSelection, PlaylistMoveTrack, PlaylistPlaySpecific,
+    PlaylistQueueRemoveTrack, PlaylistQueueTracks, PlaylistRemoveTrackType, PlaylistSelection,
+    PlaylistSort,
 };
 use termusiclib::player::{
-    Empty, GetProgressResponse, PlayerProgress, PlaylistSwapTracks, PlaylistTracks,
-    PlaylistTracksToAdd, PlaylistTracksToRemove, RunningStatus,
+    ABLoopRange, Empty, GetProgressResponse, NamedPlaylist, NamedPlaylistRename,
+    NamedPlaylistSelection, NamedPlaylists, PlayerProgress, PlaylistMoveTracks, PlaylistSaveToFile,
+    PlaylistTracks, PlaylistTracksToAdd, PlaylistTracksToRemove, QueueRemoveTrack, QueueTracks,
+    RunningStatus,
 };
 use tokio_stream::{Stream, StreamExt as _};
 use tonic::transport::Channel;
@@ -93,6 +99,38 @@ impl Playback {
         Ok(response.speed)
     }
 
+    pub async fn pitch_up(&mut self) -> Result<i32> {
+        let request = tonic::Request::new(Empty {});
+        let response = self.client.pitch_up(request).await?;
+        let response = response.into_inner();
+        info!("Got response from server: {response:?}");
+        Ok(response.cents)
+    }
+
+    pub async fn pitch_down(&mut self) -> Result<i32> {
+        let request = tonic::Request::new(Empty {});
+        let response = self.client.pitch_down(request).await?;
+        let response = response.into_inner();
+        info!("Got response from server: {response:?}");
+        Ok(response.cents)
+    }
+
+    pub async fn pitch_fine_up(&mut self) -> Result<i32> {
+        let request = tonic::Request::new(Empty {});
+        let response = self.client.pitch_fine_up(request).await?;
+        let response = response.into_inner();
+        info!("Got response from server: {response:?}");
+        Ok(response.cents)
+    }
+
+    pub async fn pitch_fine_down(&mut self) -> Result<i32> {
+        let request = tonic::Request::new(Empty {});
+        let response = self.client.pitch_fine_down(request).await?;
+        let response = response.into_inner();
+        info!("Got response from server: {response:?}");
+        Ok(response.cents)
+    }
+
     pub async fn toggle_gapless(&mut self) -> Result<bool> {
         let request = tonic::Request::new(Empty {});
         let response = self.client.toggle_gapless(request).await?;
@@ -117,6 +155,22 @@ impl Playback {
         Ok(response.into())
     }
 
+    pub async fn set_ab_loop(&mut self, range: ABLoopRange) -> Result<()> {
+        let request = tonic::Request::new(range.into());
+        let response = self.client.set_ab_loop(request).await?;
+        let response = response.into_inner();
+        info!("Got response from server: {response:?}");
+        Ok(())
+    }
+
+    pub async fn clear_ab_loop(&mut self) -> Result<()> {
+        let request = tonic::Request::new(Empty {});
+        let response = self.client.clear_ab_loop(request).await?;
+        let response = response.into_inner();
+        info!("Got response from server: {response:?}");
+        Ok(())
+    }
+
     pub async fn reload_config(&mut self) -> Result<()> {
         let request = tonic::Request::new(Empty {});
         let response = self.client.reload_config(request).await?;
@@ -151,6 +205,16 @@ impl Playback {
         Ok(response)
     }
 
+    pub async fn subscribe_to_spectrum(
+        &mut self,
+    ) -> Result<impl Stream<Item = Result<termusiclib::player::SpectrumUpdate>>> {
+        let request = tonic::Request::new(Empty {});
+        let response = self.client.subscribe_spectrum(request).await?;
+        let response = response.into_inner().map(|res| res.map_err(Into::into));
+        info!("Got response from server: {response:?}");
+        Ok(response)
+    }
+
     pub async fn add_to_playlist(&mut self, info: PlaylistAddTrack) -> Result<()> {
         let request = tonic::Request::new(PlaylistTracksToAdd::from(info));
         let response = self.client.add_to_playlist(request).await?;
@@ -167,14 +231,129 @@ impl Playback {
         Ok(())
     }
 
-    pub async fn swap_tracks(&mut self, info: PlaylistSwapTrack) -> Result<()> {
-        let request = tonic::Request::new(PlaylistSwapTracks::from(info));
-        let response = self.client.swap_tracks(request).await?;
+    pub async fn move_tracks(&mut self, info: PlaylistMoveTrack) -> Result<()> {
+        let request = tonic::Request::new(PlaylistMoveTracks::from(info));
+        let response = self.client.move_tracks(request).await?;
+        info!("Got response from server: {response:?}");
+
+        Ok(())
+    }
+
+    pub async fn remove_selection(&mut self, selection: PlaylistSelection) -> Result<()> {
+        let request = tonic::Request::new(selection.into());
+        let response = self.client.remove_selection_from_playlist(request).await?;
+        info!("Got response from server: {response:?}");
+
+        Ok(())
+    }
+
+    pub async fn move_selection(&mut self, info: PlaylistMoveSelection) -> Result<()> {
+        let request = tonic::Request::new(info.into());
+        let response = self.client.move_selection(request).await?;
+        info!("Got response from server: {response:?}");
+
+        Ok(())
+    }
+
+    /// Export the playlist to `path` on the server, returning the resolved path of the written file.
+    pub async fn save_playlist(&mut self, path: &Path, overwrite: bool) -> Result<PathBuf> {
+        let request = tonic::Request::new(PlaylistSaveToFile {
+            path: path.to_string_lossy().to_string(),
+            overwrite,
+        });
+        let response = self.client.save_playlist(request).await?;
+        info!("Got response from server: {response:?}");
+
+        Ok(PathBuf::from(response.into_inner().path))
+    }
+
+    pub async fn list_playlists(&mut self) -> Result<NamedPlaylists> {
+        let request = tonic::Request::new(Empty {});
+        let response = self.client.list_playlists(request).await?;
+        info!("Got response from server: {response:?}");
+
+        Ok(response.into_inner())
+    }
+
+    pub async fn load_playlist(&mut self, name: String) -> Result<()> {
+        let request = tonic::Request::new(NamedPlaylist { name });
+        let response = self.client.load_playlist(request).await?;
+        info!("Got response from server: {response:?}");
+
+        Ok(())
+    }
+
+    pub async fn save_playlist_as(&mut self, name: String) -> Result<()> {
+        let request = tonic::Request::new(NamedPlaylist { name });
+        let response = self.client.save_playlist_as(request).await?;
         info!("Got response from server: {response:?}");
 
         Ok(())
     }
 
+    pub async fn save_selection_as(
+        &mut self,
+        name: String,
+        selection: PlaylistSelection,
+    ) -> Result<()> {
+        let request = tonic::Request::new(NamedPlaylistSelection {
+            name,
+            selection: Some(selection.into()),
+        });
+        let response = self.client.save_selection_as(request).await?;
+        info!("Got response from server: {response:?}");
+
+        Ok(())
+    }
+
+    pub async fn rename_playlist(&mut self, from: String, to: String) -> Result<()> {
+        let request = tonic::Request::new(NamedPlaylistRename { from, to });
+        let response = self.client.rename_playlist(request).await?;
+        info!("Got response from server: {response:?}");
+
+        Ok(())
+    }
+
+    pub async fn delete_playlist(&mut self, name: String) -> Result<()> {
+        let request = tonic::Request::new(NamedPlaylist { name });
+        let response = self.client.delete_playlist(request).await?;
+        info!("Got response from server: {response:?}");
+
+        Ok(())
+    }
+
+    pub async fn queue_add(&mut self, tracks: PlaylistQueueTracks) -> Result<()> {
+        let request = tonic::Request::new(QueueTracks::from(tracks));
+        let response = self.client.queue_add(request).await?;
+        info!("Got response from server: {response:?}");
+
+        Ok(())
+    }
+
+    pub async fn queue_remove(&mut self, info: PlaylistQueueRemoveTrack) -> Result<()> {
+        let request = tonic::Request::new(QueueRemoveTrack::from(info));
+        let response = self.client.queue_remove(request).await?;
+        info!("Got response from server: {response:?}");
+
+        Ok(())
+    }
+
+    pub async fn queue_clear(&mut self) -> Result<()> {
+        let request = tonic::Request::new(Empty {});
+        let response = self.client.queue_clear(request).await?;
+        info!("Got response from server: {response:?}");
+
+        Ok(())
+    }
+
+    pub async fn get_queue(&mut self) -> Result<PlaylistQueueTracks> {
+        let request = tonic::Request::new(Empty {});
+        let response = self.client.get_queue(request).await?;
+        info!("Got response from server: {response:?}");
+
+        PlaylistQueueTracks::try_from(response.into_inner())
+    }
+
     pub async fn get_playlist(&mut self) -> Result<PlaylistTracks> {
         let request = tonic::Request::new(Empty {});
         let response = self.client.get_playlist(request).await?;
@@ -199,4 +378,36 @@ impl Playback {
 
         Ok(())
     }
+
+    pub async fn undo_playlist(&mut self) -> Result<()> {
+        let request = tonic::Request::new(Empty {});
+        let response = self.client.undo_playlist(request).await?;
+        info!("Got response from server: {response:?}");
+
+        Ok(())
+    }
+
+    pub async fn redo_playlist(&mut self) -> Result<()> {
+        let request = tonic::Request::new(Empty {});
+        let response = self.client.redo_playlist(request).await?;
+        info!("Got response from server: {response:?}");
+
+        Ok(())
+    }
+
+    pub async fn sort_playlist(&mut self, sort: PlaylistSort) -> Result<()> {
+        let request = tonic::Request::new(sort.into());
+        let response = self.client.sort_playlist(request).await?;
+        info!("Got response from server: {response:?}");
+
+        Ok(())
+    }
+
+    pub async fn deduplicate_playlist(&mut self) -> Result<()> {
+        let request = tonic::Request::new(Empty {});
+        let response = self.client.deduplicate_playlist(request).await?;
+        info!("Got response from server: {response:?}");
+
+        Ok(())
+    }
 }
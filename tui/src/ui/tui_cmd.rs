@@ -1,6 +1,11 @@
+use std::path::PathBuf;
+
 use termusiclib::player::playlist_helpers::{
-    PlaylistAddTrack, PlaylistPlaySpecific, PlaylistRemoveTrackIndexed, PlaylistSwapTrack,
+    PlaylistAddTrack, PlaylistMoveSelection, PlaylistMoveTrack, PlaylistPlaySpecific,
+    PlaylistQueueRemoveTrack, PlaylistQueueTracks, PlaylistRemoveTrackIndexed, PlaylistSelection,
+    PlaylistSort,
 };
+use termusiclib::player::ABLoopRange;
 
 #[allow(clippy::doc_link_with_quotes)]
 /// Enum for Commands to send to the [`MusicPlayerClient` "Actor"](crate::ui::music_player_client).
@@ -16,10 +21,16 @@ pub enum TuiCmd {
     VolumeDown,
     SpeedUp,
     SpeedDown,
+    PitchUp,
+    PitchDown,
+    PitchFineUp,
+    PitchFineDown,
     SkipNext,
     SkipPrevious,
     ToggleGapless,
     CycleLoop,
+    SetABLoop(ABLoopRange),
+    ClearABLoop,
 
     GetProgress,
     ReloadConfig,
@@ -34,9 +45,44 @@ pub enum PlaylistCmd {
     AddTrack(PlaylistAddTrack),
     RemoveTrack(PlaylistRemoveTrackIndexed),
     Clear,
-    SwapTrack(PlaylistSwapTrack),
+    /// Move some tracks next to each other as one block
+    MoveTracks(PlaylistMoveTrack),
+    /// Remove the marked tracks
+    RemoveSelection(PlaylistSelection),
+    /// Move the marked tracks as one block
+    MoveSelection(PlaylistMoveSelection),
     Shuffle,
     RemoveDeletedItems,
+    /// Undo the last edit to the playlist
+    Undo,
+    /// Redo the last undone edit to the playlist
+    Redo,
+    /// Sort the playlist by some metadata
+    Sort(PlaylistSort),
+    /// Remove repeated tracks from the playlist
+    Deduplicate,
+    /// Export the playlist to the given file, the format is picked from the extension
+    ///
+    /// `(Path, Overwrite)`
+    SaveToFile(PathBuf, bool),
+    /// Request the names of all named playlists
+    ListNamed,
+    /// Replace the playlist with the given named playlist
+    LoadNamed(String),
+    /// Store the playlist as the given named playlist
+    SaveNamed(String),
+    /// Store the marked tracks as the given named playlist
+    SaveSelectionNamed(String, PlaylistSelection),
+    /// Rename named playlist `(from, to)`
+    RenameNamed(String, String),
+    /// Delete the given named playlist
+    DeleteNamed(String),
+    /// Add tracks to the end of the "play next" queue
+    QueueAdd(PlaylistQueueTracks),
+    /// Remove a track from the "play next" queue
+    QueueRemove(PlaylistQueueRemoveTrack),
+    /// Clear the "play next" queue
+    QueueClear,
 
     /// Re-Request the playlist tracks and state
     #[allow(dead_code)] // replace with "expect" on 1.81 upgrade
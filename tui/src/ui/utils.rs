@@ -1,3 +1,4 @@
+use termusiclib::config::v2::tui::Alignment;
 use tuirealm::ratatui::layout::{Constraint, Layout, Rect};
 
 // /// Get block
@@ -60,6 +61,36 @@ pub fn draw_area_top_right_absolute(parent: Rect, width: u16, height: u16) -> Re
     .split(new_area[1])[1]
 }
 
+/// Draw an area of `scale` percent of the parent's width and height, placed in the corner given by `align`.
+///
+/// The area will be at least `min_width`x`min_height`, as long as the parent is big enough.
+pub fn draw_area_aligned(
+    parent: Rect,
+    align: Alignment,
+    scale: u8,
+    min_width: u16,
+    min_height: u16,
+) -> Rect {
+    let scale = u32::from(scale.min(100));
+    let scaled = |size: u16, min: u16| {
+        let size_scaled = u16::try_from(u32::from(size) * scale / 100).unwrap_or(size);
+        size_scaled.max(min).min(size)
+    };
+    let width = scaled(parent.width, min_width);
+    let height = scaled(parent.height, min_height);
+
+    let x = match align {
+        Alignment::TopLeft | Alignment::BottomLeft => parent.x,
+        Alignment::TopRight | Alignment::BottomRight => parent.x + parent.width - width,
+    };
+    let y = match align {
+        Alignment::TopLeft | Alignment::TopRight => parent.y,
+        Alignment::BottomLeft | Alignment::BottomRight => parent.y + parent.height - height,
+    };
+
+    Rect::new(x, y, width, height)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -71,4 +102,17 @@ mod tests {
         let child: Rect = draw_area_in_relative(area, 75, 30);
         assert_eq!(child, Rect::new(123, 179, 768, 154));
     }
+
+    #[test]
+    fn test_utils_ui_draw_area_aligned() {
+        let area: Rect = Rect::new(0, 0, 200, 50);
+        let child = draw_area_aligned(area, Alignment::TopRight, 20, 10, 5);
+        assert_eq!(child, Rect::new(160, 0, 40, 10));
+        let child = draw_area_aligned(area, Alignment::BottomLeft, 20, 10, 5);
+        assert_eq!(child, Rect::new(0, 40, 40, 10));
+
+        // minimal size, but not bigger than the parent
+        let child = draw_area_aligned(area, Alignment::BottomRight, 1, 10, 60);
+        assert_eq!(child, Rect::new(190, 0, 10, 50));
+    }
 }